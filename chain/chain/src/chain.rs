@@ -43,7 +43,7 @@ use std::fmt::{Debug, Formatter};
 use std::sync::Arc;
 use std::time::Instant;
 use tracing::{debug, debug_span, error, info, warn, Span};
-use unc_chain_configs::{MutableConfigValue, ReshardingConfig, ReshardingHandle};
+use unc_chain_configs::{GCHoldHandle, MutableConfigValue, ReshardingConfig, ReshardingHandle};
 #[cfg(feature = "new_epoch_sync")]
 use unc_chain_primitives::error::epoch_sync::EpochSyncInfoError;
 use unc_chain_primitives::error::{BlockKnownError, Error, LogTransientStorageError};
@@ -271,6 +271,10 @@ pub struct Chain {
     // A handle that allows the main process to interrupt resharding if needed.
     // This typically happens when the main process is interrupted.
     pub resharding_handle: ReshardingHandle,
+
+    // A handle that allows an external consumer, such as the indexer, to keep
+    // garbage collection from removing blocks it hasn't processed yet.
+    pub gc_hold_handle: GCHoldHandle,
 }
 
 impl Drop for Chain {
@@ -369,6 +373,7 @@ impl Chain {
                 "resharding_config",
             ),
             resharding_handle: ReshardingHandle::new(),
+            gc_hold_handle: GCHoldHandle::new(),
         })
     }
 
@@ -549,6 +554,7 @@ impl Chain {
             snapshot_callbacks,
             resharding_config: chain_config.resharding_config,
             resharding_handle: ReshardingHandle::new(),
+            gc_hold_handle: GCHoldHandle::new(),
         })
     }
 
@@ -5,13 +5,15 @@ use std::{fmt, io};
 use unc_chain_configs::GCConfig;
 use unc_chain_primitives::Error;
 use unc_epoch_manager::EpochManagerAdapter;
-use unc_primitives::block::Block;
+use unc_primitives::block::{Block, Tip};
 use unc_primitives::hash::CryptoHash;
 use unc_primitives::shard_layout::get_block_shard_uid;
 use unc_primitives::state_sync::{StateHeaderKey, StatePartKey};
-use unc_primitives::types::{BlockHeight, BlockHeightDelta, EpochId, NumBlocks, ShardId};
+use unc_primitives::types::{
+    AccountId, BlockHeight, BlockHeightDelta, EpochId, NumBlocks, ShardId,
+};
 use unc_primitives::utils::{get_block_shard_id, get_outcome_id_block_hash, index_to_bytes};
-use unc_store::flat::store_helper;
+use unc_store::flat::{store_helper, FlatStorageStatus};
 use unc_store::{DBCol, KeyForStateChanges, ShardTries, ShardUId};
 
 use crate::types::RuntimeAdapter;
@@ -110,15 +112,40 @@ impl Chain {
     //    and the Trie is updated with having only Genesis data.
     // 4. State Sync Clearing happens in `reset_data_pre_state_sync()`.
     //
-    pub fn clear_data(&mut self, tries: ShardTries, gc_config: &GCConfig) -> Result<(), Error> {
+    // Untracked Shards Clearing:
+    // 1. A node that doesn't track all shards only needs the state of the shards it tracks or
+    //    validates (`me`) in the epochs that are not garbage collected yet and the next epoch.
+    // 2. When the last block of an epoch is collected on the Canonical Chain, the trie and flat
+    //    state of all the other shards are removed. They are state synced again if the node
+    //    starts caring about them in a later epoch.
+    //
+    pub fn clear_data(
+        &mut self,
+        tries: ShardTries,
+        gc_config: &GCConfig,
+        me: Option<&AccountId>,
+    ) -> Result<(), Error> {
         let _span = tracing::debug_span!(target: "garbage_collection", "clear_data").entered();
 
         let head = self.chain_store().head()?;
         let tail = self.chain_store().tail()?;
-        let gc_stop_height = self.runtime_adapter.get_gc_stop_height(&head.last_block_hash);
+        let mut gc_stop_height = self.runtime_adapter.get_gc_stop_height(&head.last_block_hash);
         if gc_stop_height > head.height {
             return Err(Error::GCError("gc_stop_height cannot be larger than head.height".into()));
         }
+        if let Some(hold_height) = self.gc_hold_handle.get() {
+            // Keep the blocks an external consumer hasn't processed yet, up to a limit.
+            let min_stop_height = gc_stop_height.saturating_sub(gc_config.gc_hold_max_blocks);
+            if hold_height < min_stop_height {
+                tracing::warn!(
+                    target: "garbage_collection",
+                    hold_height,
+                    min_stop_height,
+                    "GC hold exceeds gc_hold_max_blocks, collecting the held blocks"
+                );
+            }
+            gc_stop_height = gc_stop_height.min(hold_height.max(min_stop_height));
+        }
         let prev_epoch_id = self.get_block_header(&head.prev_block_hash)?.epoch_id().clone();
         let epoch_change = prev_epoch_id != head.epoch_id;
         let mut fork_tail = self.chain_store().fork_tail()?;
@@ -162,6 +189,10 @@ impl Chain {
                 .flatten()
                 .cloned()
                 .collect::<Vec<_>>();
+            let untracked_shard_uids = match blocks_current_height.first() {
+                Some(block_hash) => self.get_untracked_shard_uids(me, block_hash, &head)?,
+                None => vec![],
+            };
             let epoch_manager = self.epoch_manager.clone();
             let runtime = self.runtime_adapter.clone();
             let mut chain_store_update = self.mut_chain_store().store_update();
@@ -183,6 +214,11 @@ impl Chain {
                         epoch_manager.as_ref(),
                         *block_hash,
                     )?;
+                    chain_store_update.clear_untracked_shards_data(
+                        runtime.as_ref(),
+                        *block_hash,
+                        &untracked_shard_uids,
+                    )?;
                     gc_blocks_remaining -= 1;
                 } else {
                     return Err(Error::GCError(
@@ -196,6 +232,61 @@ impl Chain {
         Ok(())
     }
 
+    /// Returns the shards whose state can be removed once `block_hash` is garbage collected.
+    ///
+    /// That is only the case if `block_hash` is the last block of its epoch, the node doesn't
+    /// track all shards and the shard layout didn't change since (resharding cleans up the old
+    /// shards by itself). A shard is returned if the node cares about it neither in any epoch
+    /// between `block_hash` and `head` nor in the epoch after `head`.
+    fn get_untracked_shard_uids(
+        &self,
+        me: Option<&AccountId>,
+        block_hash: &CryptoHash,
+        head: &Tip,
+    ) -> Result<Vec<ShardUId>, Error> {
+        if self.shard_tracker.tracks_all_shards()
+            || !self.epoch_manager.is_last_block_in_finished_epoch(block_hash)?
+        {
+            return Ok(vec![]);
+        }
+        let block_header = self.get_block_header(block_hash)?;
+        let shard_layout = self.epoch_manager.get_shard_layout(block_header.epoch_id())?;
+        if shard_layout != self.epoch_manager.get_shard_layout(&head.epoch_id)? {
+            return Ok(vec![]);
+        }
+
+        // Collect the previous block of the first block of every epoch that is still kept, the
+        // shards the node cares about in those epochs are decided by these blocks.
+        let mut parent_hashes = vec![head.last_block_hash];
+        let mut current_hash = head.last_block_hash;
+        loop {
+            let epoch_first_block =
+                *self.epoch_manager.get_block_info(&current_hash)?.epoch_first_block();
+            if epoch_first_block == CryptoHash::default() {
+                break;
+            }
+            let prev_hash = *self.get_block_header(&epoch_first_block)?.prev_hash();
+            parent_hashes.push(prev_hash);
+            if &prev_hash == block_hash
+                || self.get_block_header(&prev_hash)?.height() <= block_header.height()
+            {
+                break;
+            }
+            current_hash = prev_hash;
+        }
+
+        Ok(shard_layout
+            .shard_uids()
+            .filter(|shard_uid| {
+                let shard_id = shard_uid.shard_id as ShardId;
+                !self.shard_tracker.will_care_about_shard(me, &head.last_block_hash, shard_id, true)
+                    && parent_hashes.iter().all(|parent_hash| {
+                        !self.shard_tracker.care_about_shard(me, parent_hash, shard_id, true)
+                    })
+            })
+            .collect())
+    }
+
     /// Garbage collect data which archival node doesn’t need to keep.
     ///
     /// Normally, archival nodes keep all the data from the genesis block and
@@ -515,6 +606,40 @@ impl<'a> ChainStoreUpdate<'a> {
         Ok(())
     }
 
+    /// Removes the trie and flat state of the shards the node stopped caring about, see
+    /// `Chain::get_untracked_shard_uids`.
+    fn clear_untracked_shards_data(
+        &mut self,
+        runtime: &dyn RuntimeAdapter,
+        block_hash: CryptoHash,
+        shard_uids: &[ShardUId],
+    ) -> Result<(), Error> {
+        if shard_uids.is_empty() {
+            return Ok(());
+        }
+        let mut store_update = self.store().store_update();
+        for &shard_uid in shard_uids {
+            tracing::debug!(target: "garbage_collection", ?block_hash, ?shard_uid, "GC untracked shard");
+            runtime.get_tries().delete_trie_for_shard(shard_uid, &mut store_update);
+            if !runtime
+                .get_flat_storage_manager()
+                .remove_flat_storage_for_shard(shard_uid, &mut store_update)?
+            {
+                // The flat storage isn't loaded if the node already stopped caring about the
+                // shard before a restart, its data still has to go.
+                store_helper::remove_all_flat_state_values(&mut store_update, shard_uid);
+                store_helper::remove_all_deltas(&mut store_update, shard_uid);
+                store_helper::set_flat_storage_status(
+                    &mut store_update,
+                    shard_uid,
+                    FlatStorageStatus::Empty,
+                );
+            }
+        }
+        self.merge(store_update);
+        Ok(())
+    }
+
     // Clearing block data of `block_hash`, if on a fork.
     // Clearing block data of `block_hash.prev`, if on the Canonical Chain.
     pub fn clear_block_data(
@@ -978,6 +1103,7 @@ impl<'a> ChainStoreUpdate<'a> {
             | DBCol::BlockHeight  // block sync needs it + genesis should be accessible
             | DBCol::_Peers
             | DBCol::RecentOutboundConnections
+            | DBCol::BannedPeers
             | DBCol::BlockMerkleTree
             | DBCol::AccountAnnouncements
             | DBCol::EpochLightClientBlocks
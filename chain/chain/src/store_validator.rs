@@ -6,7 +6,7 @@ use borsh::BorshDeserialize;
 use enum_map::Enum;
 use strum::IntoEnumIterator;
 use tracing::warn;
-use unc_epoch_manager::shard_tracker::ShardTracker;
+use unc_epoch_manager::shard_tracker::{ShardTracker, TrackedConfig};
 use unc_epoch_manager::EpochManagerAdapter;
 
 use unc_chain_configs::GenesisConfig;
@@ -397,7 +397,7 @@ mod tests {
         let store = create_test_store();
         let chain_genesis = ChainGenesis::test();
         let epoch_manager = MockEpochManager::new(store.clone(), chain_genesis.epoch_length);
-        let shard_tracker = ShardTracker::new_empty(epoch_manager.clone());
+        let shard_tracker = ShardTracker::new(TrackedConfig::AllShards, epoch_manager.clone());
         let runtime = KeyValueRuntime::new(store.clone(), epoch_manager.as_ref());
         let mut genesis = GenesisConfig::default();
         genesis.genesis_height = 0;
@@ -18,7 +18,7 @@ use crate::DoomslugThresholdMode;
 use crate::{BlockProcessingArtifact, Provenance};
 use tracing::debug;
 use unc_chain_primitives::Error;
-use unc_epoch_manager::shard_tracker::ShardTracker;
+use unc_epoch_manager::shard_tracker::{ShardTracker, TrackedConfig};
 use unc_primitives::block::Block;
 use unc_primitives::hash::CryptoHash;
 use unc_primitives::static_clock::StaticClock;
@@ -52,7 +52,7 @@ pub fn get_chain_with_epoch_length_and_num_shards(
         .block_producers_per_epoch(vec![vec!["test1".parse().unwrap()]])
         .num_shards(num_shards);
     let epoch_manager = MockEpochManager::new_with_validators(store.clone(), vs, epoch_length);
-    let shard_tracker = ShardTracker::new_empty(epoch_manager.clone());
+    let shard_tracker = ShardTracker::new(TrackedConfig::AllShards, epoch_manager.clone());
     let runtime = KeyValueRuntime::new(store, epoch_manager.as_ref());
     Chain::new(
         epoch_manager,
@@ -120,7 +120,7 @@ fn setup_with_tx_validity_period(
     let store = create_test_store();
     let epoch_length = 1000;
     let epoch_manager = MockEpochManager::new(store.clone(), epoch_length);
-    let shard_tracker = ShardTracker::new_empty(epoch_manager.clone());
+    let shard_tracker = ShardTracker::new(TrackedConfig::AllShards, epoch_manager.clone());
     let runtime = KeyValueRuntime::new(store, epoch_manager.as_ref());
     let chain = Chain::new(
         epoch_manager.clone(),
@@ -157,7 +157,7 @@ pub fn setup_with_validators(
     let signers =
         vs.all_block_producers().map(|x| Arc::new(create_test_signer(x.as_str()))).collect();
     let epoch_manager = MockEpochManager::new_with_validators(store.clone(), vs, epoch_length);
-    let shard_tracker = ShardTracker::new_empty(epoch_manager.clone());
+    let shard_tracker = ShardTracker::new(TrackedConfig::AllShards, epoch_manager.clone());
     let runtime = KeyValueRuntime::new(store, epoch_manager.as_ref());
     let chain = Chain::new(
         epoch_manager.clone(),
@@ -193,7 +193,7 @@ pub fn setup_with_validators_and_start_time(
     let signers =
         vs.all_block_producers().map(|x| Arc::new(create_test_signer(x.as_str()))).collect();
     let epoch_manager = MockEpochManager::new_with_validators(store.clone(), vs, epoch_length);
-    let shard_tracker = ShardTracker::new_empty(epoch_manager.clone());
+    let shard_tracker = ShardTracker::new(TrackedConfig::AllShards, epoch_manager.clone());
     let runtime = KeyValueRuntime::new(store, epoch_manager.as_ref());
     let chain = Chain::new(
         epoch_manager.clone(),
@@ -43,7 +43,7 @@ use unc_primitives::validator_mandates::AssignmentWeight;
 use unc_primitives::version::{ProtocolVersion, PROTOCOL_VERSION};
 use unc_primitives::views::AllMinersView;
 use unc_primitives::views::{
-    AccessKeyInfoView, AccessKeyList, CallResult, ChipView, ChipsList, ContractCodeView,
+    AccessKeyInfoView, AccessKeyList, CallResult, ChipView, ChipsList, ChipsPage, ContractCodeView,
     EpochValidatorInfo, QueryRequest, QueryResponse, QueryResponseKind, ViewStateResult,
 };
 use unc_store::test_utils::TestTriesBuilder;
@@ -527,6 +527,7 @@ impl EpochManagerAdapter for MockEpochManager {
             BTreeMap::new(),
             BTreeMap::new(),
             HashMap::new(),
+            Default::default(),
             HashMap::new(),
             1,
             1,
@@ -771,6 +772,8 @@ impl EpochManagerAdapter for MockEpochManager {
             current_power_proposals: vec![],
             current_pledge_proposals: vec![],
             prev_epoch_kickout: vec![],
+            prev_epoch_reward: vec![],
+            prev_epoch_reward_weights: Default::default(),
             epoch_start_height: 0,
             epoch_height: 1,
         })
@@ -994,8 +997,15 @@ impl EpochManagerAdapter for MockEpochManager {
     #[cfg(feature = "new_epoch_sync")]
     fn force_update_aggregator(&self, _epoch_id: &EpochId, _hash: &CryptoHash) {}
 
-    fn get_all_miners(&self, _: &CryptoHash) -> Result<AllMinersView, EpochError> {
-        todo!()
+    fn get_all_miners(&self, block_hash: &CryptoHash) -> Result<AllMinersView, EpochError> {
+        let epoch_id = self.get_epoch_id(block_hash)?;
+        self.get_epoch_miners(&epoch_id)
+    }
+
+    fn get_epoch_miners(&self, epoch_id: &EpochId) -> Result<AllMinersView, EpochError> {
+        self.get_valset_for_epoch(epoch_id)?;
+        let epoch_info = self.get_epoch_info(epoch_id)?;
+        Ok(AllMinersView::new(epoch_info.validators_iter(), epoch_id.clone(), &epoch_info))
     }
 }
 
@@ -1292,6 +1302,15 @@ impl RuntimeAdapter for KeyValueRuntime {
                 block_height,
                 block_hash: *block_hash,
             }),
+            QueryRequest::ViewChips { .. } => Ok(QueryResponse {
+                kind: QueryResponseKind::ChipsPage(ChipsPage {
+                    chips: vec![],
+                    next_cursor: None,
+                    proof: vec![],
+                }),
+                block_height,
+                block_hash: *block_hash,
+            }),
             QueryRequest::ViewAccessKey { .. } => Ok(QueryResponse {
                 kind: QueryResponseKind::AccessKey(AccessKey::full_access().into()),
                 block_height,
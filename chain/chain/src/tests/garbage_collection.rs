@@ -11,14 +11,17 @@ use crate::types::Tip;
 use crate::{ChainStoreAccess, StoreValidator};
 
 use unc_chain_configs::{GCConfig, GenesisConfig};
+use unc_epoch_manager::shard_tracker::{ShardTracker, TrackedConfig};
 use unc_epoch_manager::EpochManagerAdapter;
 use unc_primitives::block::Block;
 use unc_primitives::epoch_manager::block_info::BlockInfo;
 use unc_primitives::merkle::PartialMerkleTree;
 use unc_primitives::shard_layout::ShardUId;
+use unc_primitives::state::FlatStateValue;
 use unc_primitives::test_utils::{create_test_signer, TestBlockBuilder};
 use unc_primitives::types::{BlockHeight, NumBlocks, StateRoot};
 use unc_primitives::validator_signer::InMemoryValidatorSigner;
+use unc_store::flat::store_helper;
 use unc_store::test_utils::gen_changes;
 use unc_store::{DBCol, ShardTries, Trie, WrappedTrieChanges};
 
@@ -176,7 +179,11 @@ fn gc_fork_common(simple_chains: Vec<SimpleChain>, max_changes: usize) {
 
     // GC execution
     chain1
-        .clear_data(tries1.clone(), &GCConfig { gc_blocks_limit: 1000, ..GCConfig::default() })
+        .clear_data(
+            tries1.clone(),
+            &GCConfig { gc_blocks_limit: 1000, ..GCConfig::default() },
+            None,
+        )
         .unwrap();
 
     let tries2 = get_chain_with_num_shards(num_shards).runtime_adapter.get_tries();
@@ -641,6 +648,7 @@ fn test_fork_far_away_from_epoch_end() {
                 gc_fork_clean_step: fork_clean_step,
                 ..GCConfig::default()
             },
+            None,
         )
         .expect("Clear data failed");
 
@@ -682,7 +690,7 @@ fn test_fork_far_away_from_epoch_end() {
         );
     }
     chain1
-        .clear_data(tries1, &GCConfig { gc_blocks_limit: 100, ..GCConfig::default() })
+        .clear_data(tries1, &GCConfig { gc_blocks_limit: 100, ..GCConfig::default() }, None)
         .expect("Clear data failed");
     // And now all these blocks should be safely removed.
     for i in 6..50 {
@@ -719,7 +727,9 @@ fn test_clear_old_data() {
     }
 
     let trie = chain.runtime_adapter.get_tries();
-    chain.clear_data(trie, &GCConfig { gc_blocks_limit: 100, ..GCConfig::default() }).unwrap();
+    chain
+        .clear_data(trie, &GCConfig { gc_blocks_limit: 100, ..GCConfig::default() }, None)
+        .unwrap();
 
     // epoch didn't change so no data is garbage collected.
     for i in 0..15 {
@@ -742,6 +752,155 @@ fn test_clear_old_data() {
     }
 }
 
+/// Writes a trie node and a flat state value for the given shard.
+fn write_shard_state(chain: &Chain, shard_uid: ShardUId) {
+    let tries = chain.runtime_adapter.get_tries();
+    let changes = vec![(b"key".to_vec(), Some(b"value".to_vec()))];
+    let trie_changes =
+        tries.get_trie_for_shard(shard_uid, Trie::EMPTY_ROOT).update(changes).unwrap();
+    let mut store_update = chain.chain_store().store().store_update();
+    tries.apply_all(&trie_changes, shard_uid, &mut store_update);
+    store_helper::set_flat_state_value(
+        &mut store_update,
+        shard_uid,
+        b"key".to_vec(),
+        Some(FlatStateValue::inlined(b"value")),
+    );
+    store_update.commit().unwrap();
+}
+
+/// Whether the trie and the flat state of the given shard are in the store.
+fn has_shard_state(chain: &Chain, shard_uid: ShardUId) -> (bool, bool) {
+    let store = chain.chain_store().store();
+    let prefix = shard_uid.to_bytes();
+    (
+        store.iter_prefix(DBCol::State, &prefix).next().is_some(),
+        store.iter_prefix(DBCol::FlatState, &prefix).next().is_some(),
+    )
+}
+
+/// Test that garbage collection removes the state of the shards the node doesn't track and
+/// follows the tracked shards when they change.
+#[test]
+fn test_clear_untracked_shards_data() {
+    let mut chain = get_chain_with_epoch_length_and_num_shards(1, 2);
+    let epoch_manager = chain.epoch_manager.clone();
+    chain.shard_tracker = ShardTracker::new(TrackedConfig::Shards(vec![0]), epoch_manager.clone());
+    let shard_uids = [ShardUId { version: 0, shard_id: 0 }, ShardUId { version: 0, shard_id: 1 }];
+    for &shard_uid in &shard_uids {
+        write_shard_state(&chain, shard_uid);
+    }
+
+    let genesis = chain.get_block_by_height(0).unwrap();
+    let signer = Arc::new(create_test_signer("test1"));
+    let mut prev_block = genesis;
+    let mut blocks = vec![prev_block.clone()];
+    let gc_config = GCConfig { gc_blocks_limit: 100, ..GCConfig::default() };
+    for i in 1..15 {
+        add_block(
+            &mut chain,
+            epoch_manager.as_ref(),
+            &mut prev_block,
+            &mut blocks,
+            signer.clone(),
+            i,
+        );
+    }
+    chain.clear_data(chain.runtime_adapter.get_tries(), &gc_config, None).unwrap();
+    assert_eq!(has_shard_state(&chain, shard_uids[0]), (true, true));
+    assert_eq!(has_shard_state(&chain, shard_uids[1]), (false, false));
+
+    // The node switches to the other shard, which is state synced again.
+    chain.shard_tracker = ShardTracker::new(TrackedConfig::Shards(vec![1]), epoch_manager.clone());
+    write_shard_state(&chain, shard_uids[1]);
+    for i in 15..30 {
+        add_block(
+            &mut chain,
+            epoch_manager.as_ref(),
+            &mut prev_block,
+            &mut blocks,
+            signer.clone(),
+            i,
+        );
+    }
+    chain.clear_data(chain.runtime_adapter.get_tries(), &gc_config, None).unwrap();
+    assert_eq!(has_shard_state(&chain, shard_uids[0]), (false, false));
+    assert_eq!(has_shard_state(&chain, shard_uids[1]), (true, true));
+}
+
+/// Test that nodes tracking all shards never remove shard state.
+#[test]
+fn test_clear_data_keeps_all_tracked_shards() {
+    let mut chain = get_chain_with_epoch_length_and_num_shards(1, 2);
+    let epoch_manager = chain.epoch_manager.clone();
+    let shard_uids = [ShardUId { version: 0, shard_id: 0 }, ShardUId { version: 0, shard_id: 1 }];
+    for &shard_uid in &shard_uids {
+        write_shard_state(&chain, shard_uid);
+    }
+
+    let genesis = chain.get_block_by_height(0).unwrap();
+    let signer = Arc::new(create_test_signer("test1"));
+    let mut prev_block = genesis;
+    let mut blocks = vec![prev_block.clone()];
+    for i in 1..15 {
+        add_block(
+            &mut chain,
+            epoch_manager.as_ref(),
+            &mut prev_block,
+            &mut blocks,
+            signer.clone(),
+            i,
+        );
+    }
+    let gc_config = GCConfig { gc_blocks_limit: 100, ..GCConfig::default() };
+    chain.clear_data(chain.runtime_adapter.get_tries(), &gc_config, None).unwrap();
+    for &shard_uid in &shard_uids {
+        assert_eq!(has_shard_state(&chain, shard_uid), (true, true));
+    }
+}
+
+/// Test that garbage collection keeps the blocks held by an external consumer, such as the
+/// indexer, but no more than `gc_hold_max_blocks` of them past the regular GC horizon.
+#[test]
+fn test_clear_data_gc_hold() {
+    let mut chain = get_chain_with_epoch_length_and_num_shards(1, 1);
+    let epoch_manager = chain.epoch_manager.clone();
+    let genesis = chain.get_block_by_height(0).unwrap();
+    let signer = Arc::new(create_test_signer("test1"));
+    let mut prev_block = genesis;
+    let mut blocks = vec![prev_block.clone()];
+    for i in 1..30 {
+        add_block(
+            &mut chain,
+            epoch_manager.as_ref(),
+            &mut prev_block,
+            &mut blocks,
+            signer.clone(),
+            i,
+        );
+    }
+    let head = chain.head().unwrap();
+    let gc_stop_height = chain.runtime_adapter.get_gc_stop_height(&head.last_block_hash) as usize;
+    assert_eq!(gc_stop_height, 24);
+
+    chain.gc_hold_handle.hold_from(3);
+    let gc_config = GCConfig { gc_blocks_limit: 100, ..GCConfig::default() };
+    chain.clear_data(chain.runtime_adapter.get_tries(), &gc_config, None).unwrap();
+    assert!(chain.get_block(blocks[2].hash()).is_err());
+    assert!(chain.get_block(blocks[3].hash()).is_ok());
+
+    // The hold is too far behind, so only `gc_hold_max_blocks` blocks are kept.
+    let gc_config = GCConfig { gc_blocks_limit: 100, gc_hold_max_blocks: 5, ..GCConfig::default() };
+    chain.clear_data(chain.runtime_adapter.get_tries(), &gc_config, None).unwrap();
+    assert!(chain.get_block(blocks[gc_stop_height - 6].hash()).is_err());
+    assert!(chain.get_block(blocks[gc_stop_height - 5].hash()).is_ok());
+
+    chain.gc_hold_handle.release();
+    chain.clear_data(chain.runtime_adapter.get_tries(), &gc_config, None).unwrap();
+    assert!(chain.get_block(blocks[gc_stop_height - 1].hash()).is_err());
+    assert!(chain.get_block(blocks[gc_stop_height].hash()).is_ok());
+}
+
 // Adds block to the chain at given height after prev_block.
 fn add_block(
     chain: &mut Chain,
@@ -901,7 +1060,7 @@ fn test_clear_old_data_too_many_heights_common(gc_blocks_limit: NumBlocks) {
     for iter in 0..10 {
         println!("ITERATION #{:?}", iter);
         assert!(chain
-            .clear_data(trie.clone(), &GCConfig { gc_blocks_limit, ..GCConfig::default() })
+            .clear_data(trie.clone(), &GCConfig { gc_blocks_limit, ..GCConfig::default() }, None)
             .is_ok());
 
         // epoch didn't change so no data is garbage collected.
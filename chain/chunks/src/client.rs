@@ -2,8 +2,10 @@ use std::collections::HashMap;
 
 use actix::Message;
 
-use unc_pool::types::PoolIterator;
-use unc_pool::{InsertTransactionResult, PoolIteratorWrapper, TransactionPool};
+use unc_chain_configs::{MutableConfigValue, TransactionPoolPolicy, TransactionPoolQuotas};
+use unc_pool::{
+    transaction_priority, InsertTransactionResult, PoolIteratorWrapper, TransactionPool,
+};
 use unc_primitives::shard_layout::{account_id_to_shard_uid, ShardLayout, ShardUId};
 use unc_primitives::{
     epoch_manager::RngSeed,
@@ -42,11 +44,22 @@ pub struct ShardedTransactionPool {
     /// If set, new transactions that bring the size of the pool over this limit will be rejected.
     /// The size is tracked and enforced separately for each shard.
     pool_size_limit: Option<u64>,
+
+    /// Ordering, eviction and replacement policy of the pool for each shard.
+    policy: TransactionPoolPolicy,
+
+    /// Per-signer and per-access-key limits, enforced separately for each shard.
+    quotas: MutableConfigValue<TransactionPoolQuotas>,
 }
 
 impl ShardedTransactionPool {
-    pub fn new(rng_seed: RngSeed, pool_size_limit: Option<u64>) -> Self {
-        Self { tx_pools: HashMap::new(), rng_seed, pool_size_limit }
+    pub fn new(
+        rng_seed: RngSeed,
+        pool_size_limit: Option<u64>,
+        policy: TransactionPoolPolicy,
+        quotas: MutableConfigValue<TransactionPoolQuotas>,
+    ) -> Self {
+        Self { tx_pools: HashMap::new(), rng_seed, pool_size_limit, policy, quotas }
     }
 
     pub fn get_pool_iterator(&mut self, shard_uid: ShardUId) -> Option<PoolIteratorWrapper<'_>> {
@@ -59,7 +72,8 @@ impl ShardedTransactionPool {
         shard_uid: ShardUId,
         tx: SignedTransaction,
     ) -> InsertTransactionResult {
-        self.pool_for_shard(shard_uid).insert_transaction(tx)
+        let priority = transaction_priority(&tx);
+        self.pool_for_shard(shard_uid).insert_transaction(tx, priority)
     }
 
     pub fn remove_transactions(&mut self, shard_uid: ShardUId, transactions: &[SignedTransaction]) {
@@ -84,6 +98,8 @@ impl ShardedTransactionPool {
             TransactionPool::new(
                 Self::random_seed(&self.rng_seed, shard_uid.shard_id()),
                 self.pool_size_limit,
+                self.policy,
+                self.quotas.clone(),
                 &shard_uid.to_string(),
             )
         })
@@ -99,9 +115,12 @@ impl ShardedTransactionPool {
         let mut reintroduced_count = 0;
         let pool = self.pool_for_shard(shard_uid);
         for tx in transactions {
-            reintroduced_count += match pool.insert_transaction(tx.clone()) {
+            let priority = transaction_priority(tx);
+            reintroduced_count += match pool.insert_transaction(tx.clone(), priority) {
                 InsertTransactionResult::Success | InsertTransactionResult::Duplicate => 1,
-                InsertTransactionResult::NoSpaceLeft => 0,
+                InsertTransactionResult::NoSpaceLeft
+                | InsertTransactionResult::ReplacementRejected
+                | InsertTransactionResult::QuotaExceeded => 0,
             }
         }
         reintroduced_count
@@ -124,19 +143,15 @@ impl ShardedTransactionPool {
         let mut transactions = vec![];
 
         for old_shard_uid in old_shard_layout.shard_uids() {
-            if let Some(mut iter) = self.get_pool_iterator(old_shard_uid) {
-                while let Some(group) = iter.next() {
-                    while let Some(tx) = group.next() {
-                        transactions.push(tx);
-                    }
-                }
+            if let Some(pool) = self.tx_pools.get_mut(&old_shard_uid) {
+                transactions.extend(pool.take_transactions());
             }
         }
 
-        for tx in transactions {
+        for (tx, priority) in transactions {
             let signer_id = &tx.transaction.signer_id;
             let new_shard_uid = account_id_to_shard_uid(&signer_id, new_shard_layout);
-            self.insert_transaction(new_shard_uid, tx);
+            let _ = self.pool_for_shard(new_shard_uid).insert_transaction(tx, priority);
         }
     }
 }
@@ -146,6 +161,7 @@ mod tests {
     use crate::client::ShardedTransactionPool;
     use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
     use std::{collections::HashMap, str::FromStr};
+    use unc_chain_configs::{MutableConfigValue, TransactionPoolPolicy, TransactionPoolQuotas};
     use unc_crypto::{InMemorySigner, KeyType};
     use unc_o11y::testonly::init_test_logger;
     use unc_pool::types::PoolIterator;
@@ -185,7 +201,12 @@ mod tests {
         let old_shard_layout = ShardLayout::get_simple_nightshade_layout();
         let new_shard_layout = ShardLayout::get_simple_nightshade_layout_v2();
 
-        let mut pool = ShardedTransactionPool::new(TEST_SEED, None);
+        let mut pool = ShardedTransactionPool::new(
+            TEST_SEED,
+            None,
+            TransactionPoolPolicy::default(),
+            MutableConfigValue::new(TransactionPoolQuotas::default(), "transaction_pool_quotas"),
+        );
 
         let mut shard_id_to_accounts = HashMap::new();
         shard_id_to_accounts.insert(0, vec!["aaa", "abcd", "a-a-a-a-a"]);
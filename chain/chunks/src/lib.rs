@@ -2140,7 +2140,7 @@ mod test {
             2,
         );
         let epoch_manager = Arc::new(epoch_manager.into_handle());
-        let shard_tracker = ShardTracker::new(epoch_manager.clone());
+        let shard_tracker = ShardTracker::new(TrackedConfig::AllShards, epoch_manager.clone());
         let network_adapter = Arc::new(MockPeerManagerAdapter::default());
         let client_adapter = Arc::new(MockClientAdapterForShardsManager::default());
         let clock = FakeClock::default();
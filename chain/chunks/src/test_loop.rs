@@ -7,7 +7,7 @@ use unc_async::{
 };
 use unc_chain::{types::Tip, Chain};
 use unc_epoch_manager::{
-    shard_tracker::ShardTracker,
+    shard_tracker::{ShardTracker, TrackedConfig},
     test_utils::{record_block, setup_epoch_manager_with_block_and_chunk_producers},
     EpochManagerAdapter, EpochManagerHandle,
 };
@@ -186,7 +186,7 @@ impl MockChainForShardsManager {
             )
             .into_handle(),
         );
-        let shard_tracker = ShardTracker::new(epoch_manager.clone());
+        let shard_tracker = ShardTracker::new(TrackedConfig::AllShards, epoch_manager.clone());
         Self {
             account_id: config.account_id,
             epoch_manager,
@@ -3,7 +3,7 @@ use std::sync::{Arc, Mutex, RwLock};
 use unc_async::messaging::CanSend;
 use unc_chain::types::{EpochManagerAdapter, Tip};
 use unc_chain::{Chain, ChainStore};
-use unc_epoch_manager::shard_tracker::ShardTracker;
+use unc_epoch_manager::shard_tracker::{ShardTracker, TrackedConfig};
 use unc_epoch_manager::test_utils::setup_epoch_manager_with_block_and_chunk_producers;
 use unc_epoch_manager::EpochManagerHandle;
 use unc_network::shards_manager::ShardsManagerRequestFromNetwork;
@@ -74,7 +74,8 @@ impl ChunkTestFixture {
             2,
         );
         let epoch_manager = epoch_manager.into_handle();
-        let shard_tracker = ShardTracker::new(Arc::new(epoch_manager.clone()));
+        let shard_tracker =
+            ShardTracker::new(TrackedConfig::AllShards, Arc::new(epoch_manager.clone()));
         let mock_network = Arc::new(MockPeerManagerAdapter::default());
         let mock_client_adapter = Arc::new(MockClientAdapterForShardsManager::default());
 
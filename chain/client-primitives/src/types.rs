@@ -19,9 +19,9 @@ use unc_primitives::views::validator_power_and_pledge_view::ValidatorPowerAndPle
 use unc_primitives::views::{
     AllMinersView, BlockView, ChunkView, DownloadStatusView, EpochValidatorInfo,
     ExecutionOutcomeWithIdView, GasPriceView, LightClientBlockLiteView, LightClientBlockView,
-    MaintenanceWindowsView, QueryRequest, QueryResponse, ReceiptView, ShardSyncDownloadView,
-    SplitStorageInfoView, StateChangesKindsView, StateChangesRequestView, StateChangesView,
-    SyncStatusView, TxStatusView,
+    MaintenanceWindowsView, PowerChangesView, ProducerSelectionView, QueryRequest, QueryResponse,
+    ReceiptView, ShardSyncDownloadView, SplitStorageInfoView, StateChangesKindsView,
+    StateChangesRequestView, StateChangesView, SyncStatusView, TxStatusView,
 };
 pub use unc_primitives::views::{StatusResponse, StatusSyncInfo};
 use yansi::Color::Magenta;
@@ -414,9 +414,12 @@ impl Message for crate::types::GetProvider {
     type Result = Result<AccountId, crate::types::GetProviderError>;
 }
 
-/// Actor message requesting block provider by EpochId and BlockHeight.
+/// Actor message requesting the miner set at a block or of an epoch.
 #[derive(Debug)]
-pub struct GetAllMiners(pub CryptoHash);
+pub enum GetAllMiners {
+    BlockReference(BlockReference),
+    EpochId(EpochId),
+}
 
 #[derive(thiserror::Error, Debug)]
 pub enum GetAllMinersError {
@@ -424,6 +427,10 @@ pub enum GetAllMinersError {
     IOError { error_message: String },
     #[error("Block either has never been observed on the node or has been garbage collected: {error_message}")]
     UnknownBlock { error_message: String },
+    #[error("Epoch not found")]
+    UnknownEpoch,
+    #[error("There are no fully synchronized blocks yet")]
+    NotSyncedYet,
     // NOTE: Currently, the underlying errors are too broad, and while we tried to handle
     // expected cases, we cannot statically guarantee that no other errors will be returned
     // in the future.
@@ -448,7 +455,10 @@ impl From<unc_chain_primitives::Error> for crate::types::GetAllMinersError {
 
 impl From<EpochError> for crate::types::GetAllMinersError {
     fn from(error: EpochError) -> Self {
-        Self::IOError { error_message: error.to_string() }
+        match error {
+            EpochError::EpochOutOfBounds(_) => Self::UnknownEpoch,
+            _ => Self::IOError { error_message: error.to_string() },
+        }
     }
 }
 
@@ -500,6 +510,46 @@ impl Message for GetBlock {
     type Result = Result<BlockView, GetBlockError>;
 }
 
+/// Replay data for the VRF driven selection of the given block's producer.
+#[derive(Debug)]
+pub struct GetProducerSelection(pub BlockReference);
+
+#[derive(thiserror::Error, Debug)]
+pub enum GetProducerSelectionError {
+    #[error("IO Error: {error_message}")]
+    IOError { error_message: String },
+    #[error("Block either has never been observed on the node or has been garbage collected: {error_message}")]
+    UnknownBlock { error_message: String },
+    #[error("There are no fully synchronized blocks yet")]
+    NotSyncedYet,
+    #[error("Block {block_hash} does not select a producer: {error_message}")]
+    NoSelection { block_hash: CryptoHash, error_message: String },
+    // NOTE: Currently, the underlying errors are too broad, and while we tried to handle
+    // expected cases, we cannot statically guarantee that no other errors will be returned
+    // in the future.
+    // TODO #3851: Remove this variant once we can exhaustively match all the underlying errors
+    #[error("It is a bug if you receive this error type, please, report this incident: https://github.com/utnet-org/utility/issues/new/choose. Details: {error_message}")]
+    Unreachable { error_message: String },
+}
+
+impl From<unc_chain_primitives::Error> for GetProducerSelectionError {
+    fn from(error: unc_chain_primitives::Error) -> Self {
+        match error {
+            unc_chain_primitives::Error::IOErr(error) => {
+                Self::IOError { error_message: error.to_string() }
+            }
+            unc_chain_primitives::Error::DBNotFoundErr(error_message) => {
+                Self::UnknownBlock { error_message }
+            }
+            _ => Self::Unreachable { error_message: error.to_string() },
+        }
+    }
+}
+
+impl Message for GetProducerSelection {
+    type Result = Result<ProducerSelectionView, GetProducerSelectionError>;
+}
+
 /// Get block with the block merkle tree. Used for testing
 #[derive(Debug)]
 pub struct GetBlockWithMerkleTree(pub BlockReference);
@@ -790,6 +840,8 @@ pub struct PeerInfo {
     pub id: PeerId,
     pub addr: Option<std::net::SocketAddr>,
     pub account_id: Option<AccountId>,
+    /// Reputation score of the peer. 0 means that the peer behaves well.
+    pub reputation_score: f64,
 }
 
 #[derive(Clone, Debug)]
@@ -922,6 +974,10 @@ pub enum GetStateChangesError {
     UnknownBlock { error_message: String },
     #[error("There are no fully synchronized blocks yet")]
     NotSyncedYet,
+    #[error("The node does not track the shard ID {requested_shard_id}")]
+    UnavailableShard { requested_shard_id: ShardId },
+    #[error("The state for block #{block_height} is garbage collected on this node, use an archival node to fetch historical data")]
+    GarbageCollectedBlock { block_height: BlockHeight, block_hash: CryptoHash },
     // NOTE: Currently, the underlying errors are too broad, and while we tried to handle
     // expected cases, we cannot statically guarantee that no other errors will be returned
     // in the future.
@@ -948,6 +1004,16 @@ impl Message for GetStateChanges {
     type Result = Result<StateChangesView, GetStateChangesError>;
 }
 
+#[derive(Debug)]
+pub struct GetPowerChanges {
+    pub block_hash: CryptoHash,
+    pub account_ids: Vec<AccountId>,
+}
+
+impl Message for GetPowerChanges {
+    type Result = Result<PowerChangesView, GetStateChangesError>;
+}
+
 #[derive(Debug)]
 pub struct GetStateChangesInBlock {
     pub block_hash: CryptoHash,
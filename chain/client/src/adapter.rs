@@ -138,6 +138,11 @@ pub enum ProcessTxResponse {
     /// The node being queried does not track the shard needed and therefore cannot provide userful
     /// response.
     DoesNotTrackShard,
+    /// The signer or the access key of the transaction has used up its transaction pool quota.
+    PoolQuotaExceeded,
+    /// A transaction with the same nonce and a priority at least as high is already in the
+    /// transaction pool.
+    ReplacementRejected,
 }
 
 #[derive(actix::Message, Debug, PartialEq, Eq)]
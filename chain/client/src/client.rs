@@ -198,6 +198,7 @@ impl Client {
         self.config
             .produce_chunk_add_transactions_time_limit
             .update(update_client_config.produce_chunk_add_transactions_time_limit);
+        self.config.transaction_pool_quotas.update(update_client_config.transaction_pool_quotas);
     }
 }
 
@@ -266,8 +267,12 @@ impl Client {
             chain.chain_store(),
             chain_config.background_migration_threads,
         )?;
-        let sharded_tx_pool =
-            ShardedTransactionPool::new(rng_seed, config.transaction_pool_size_limit);
+        let sharded_tx_pool = ShardedTransactionPool::new(
+            rng_seed,
+            config.transaction_pool_size_limit,
+            config.transaction_pool_policy,
+            config.transaction_pool_quotas.clone(),
+        );
         let sync_status = SyncStatus::AwaitingPeers;
         let genesis_block = chain.genesis_block();
         let epoch_sync = EpochSync::new(
@@ -2222,6 +2227,14 @@ impl Client {
                             trace!(target: "client", ?shard_uid, tx_hash = ?tx.get_hash(), "Duplicate transaction, not forwarding it.");
                             return Ok(ProcessTxResponse::ValidTx);
                         }
+                        InsertTransactionResult::ReplacementRejected => {
+                            trace!(target: "client", ?shard_uid, tx_hash = ?tx.get_hash(), "Transaction with the same nonce and a higher priority is in the pool, not forwarding it.");
+                            return Ok(ProcessTxResponse::ReplacementRejected);
+                        }
+                        InsertTransactionResult::QuotaExceeded => {
+                            trace!(target: "client", ?shard_uid, tx_hash = ?tx.get_hash(), "Transaction pool quota exceeded, not forwarding it.");
+                            return Ok(ProcessTxResponse::PoolQuotaExceeded);
+                        }
                         InsertTransactionResult::NoSpaceLeft => {
                             if is_forwarded {
                                 trace!(target: "client", ?shard_uid, tx_hash = ?tx.get_hash(), "Transaction pool is full, dropping the transaction.");
@@ -2499,7 +2512,8 @@ impl Client {
         // A RPC node should do regular garbage collection.
         if !self.config.archive {
             let tries = self.runtime_adapter.get_tries();
-            return self.chain.clear_data(tries, &self.config.gc);
+            let me = self.validator_signer.as_ref().map(|x| x.validator_id());
+            return self.chain.clear_data(tries, &self.config.gc, me);
         }
 
         // An archival node with split storage should perform garbage collection
@@ -2510,7 +2524,8 @@ impl Client {
         let kind = store.get_db_kind()?;
         if kind == Some(DbKind::Hot) {
             let tries = self.runtime_adapter.get_tries();
-            return self.chain.clear_data(tries, &self.config.gc);
+            let me = self.validator_signer.as_ref().map(|x| x.validator_id());
+            return self.chain.clear_data(tries, &self.config.gc, me);
         }
 
         // An archival node with legacy storage or in the midst of migration to split
@@ -2630,8 +2645,16 @@ impl Client {
     /// check_And_update_doomslug_tip, but that would require a bigger refactor.
     pub(crate) fn send_network_chain_info(&mut self) -> Result<(), Error> {
         let tip = self.chain.head()?;
-        // convert tracked shards, runtime will track all shards
-        let tracked_shards = self.epoch_manager.shard_ids(&tip.epoch_id)?;
+        // Advertise only the shards this node keeps the state of.
+        let me = self.validator_signer.as_ref().map(|x| x.validator_id());
+        let tracked_shards = self
+            .epoch_manager
+            .shard_ids(&tip.epoch_id)?
+            .into_iter()
+            .filter(|&shard_id| {
+                self.shard_tracker.care_about_shard(me, &tip.last_block_hash, shard_id, true)
+            })
+            .collect();
         let tier1_accounts = self.get_tier1_accounts(&tip)?;
         let block = self.chain.get_block(&tip.last_block_hash)?;
         self.network_adapter.send(SetChainInfo(ChainInfo {
@@ -47,7 +47,7 @@ use unc_chain::{
     byzantine_assert, unc_chain_primitives, Block, BlockHeader, BlockProcessingArtifact,
     ChainGenesis, DoneApplyChunkCallback, Provenance,
 };
-use unc_chain_configs::{ClientConfig, LogSummaryStyle, ReshardingHandle};
+use unc_chain_configs::{ClientConfig, GCHoldHandle, LogSummaryStyle, ReshardingHandle};
 use unc_chain_primitives::error::EpochErrorResultToChainError;
 use unc_chunks::adapter::ShardsManagerRequestFromClient;
 use unc_chunks::client::ShardsManagerResponse;
@@ -60,7 +60,7 @@ use unc_epoch_manager::shard_tracker::ShardTracker;
 use unc_epoch_manager::EpochManagerAdapter;
 use unc_network::types::ReasonForBan;
 use unc_network::types::{
-    NetworkInfo, NetworkRequests, PeerManagerAdapter, PeerManagerMessageRequest,
+    ConnectedPeerInfo, NetworkInfo, NetworkRequests, PeerManagerAdapter, PeerManagerMessageRequest,
 };
 use unc_o11y::{handler_debug_span, OpenTelemetrySpanExt, WithSpanContext, WithSpanContextExt};
 use unc_performance_metrics;
@@ -740,11 +740,13 @@ impl Handler<WithSpanContext<Status>> for ClientActor {
 }
 
 /// Private to public API conversion.
-fn make_peer_info(from: unc_network::types::PeerInfo) -> unc_client_primitives::types::PeerInfo {
+fn make_peer_info(from: &ConnectedPeerInfo) -> unc_client_primitives::types::PeerInfo {
+    let peer_info = &from.full_peer_info.peer_info;
     unc_client_primitives::types::PeerInfo {
-        id: from.id,
-        addr: from.addr,
-        account_id: from.account_id,
+        id: peer_info.id.clone(),
+        addr: peer_info.addr,
+        account_id: peer_info.account_id.clone(),
+        reputation_score: from.reputation_score,
     }
 }
 
@@ -774,7 +776,7 @@ impl Handler<WithSpanContext<GetNetworkInfo>> for ClientActor {
 
         Ok(NetworkInfoResponse {
             connected_peers: (self.network_info.connected_peers.iter())
-                .map(|fpi| make_peer_info(fpi.full_peer_info.peer_info.clone()))
+                .map(make_peer_info)
                 .collect(),
             num_connected_peers: self.network_info.num_connected_peers,
             peer_max_count: self.network_info.peer_max_count,
@@ -2051,7 +2053,7 @@ pub fn start_client(
     sender: Option<broadcast::Sender<()>>,
     adv: crate::adversarial::Controls,
     config_updater: Option<ConfigUpdater>,
-) -> (Addr<ClientActor>, ArbiterHandle, ReshardingHandle) {
+) -> (Addr<ClientActor>, ArbiterHandle, ReshardingHandle, GCHoldHandle) {
     let client_arbiter = Arbiter::new();
     let client_arbiter_handle = client_arbiter.handle();
 
@@ -2072,6 +2074,7 @@ pub fn start_client(
     )
     .unwrap();
     let resharding_handle = client.chain.resharding_handle.clone();
+    let gc_hold_handle = client.chain.gc_hold_handle.clone();
     let client_addr = ClientActor::start_in_arbiter(&client_arbiter_handle, move |ctx| {
         ClientActor::new(
             client,
@@ -2088,5 +2091,5 @@ pub fn start_client(
         )
         .unwrap()
     });
-    (client_addr, client_arbiter_handle, resharding_handle)
+    (client_addr, client_arbiter_handle, resharding_handle, gc_hold_handle)
 }
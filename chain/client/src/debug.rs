@@ -667,6 +667,7 @@ fn new_peer_info_view(chain: &Chain, connected_peer_info: &ConnectedPeerInfo) ->
             .whole_milliseconds() as u64,
         is_outbound_peer: connected_peer_info.peer_type == PeerType::Outbound,
         nonce: connected_peer_info.nonce,
+        reputation_score: connected_peer_info.reputation_score.round() as u64,
     }
 }
 
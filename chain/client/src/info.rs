@@ -864,7 +864,7 @@ mod tests {
     use unc_chain::test_utils::{KeyValueRuntime, MockEpochManager, ValidatorSchedule};
     use unc_chain::types::ChainConfig;
     use unc_chain::{Chain, ChainGenesis, DoomslugThresholdMode};
-    use unc_epoch_manager::shard_tracker::ShardTracker;
+    use unc_epoch_manager::shard_tracker::{ShardTracker, TrackedConfig};
     use unc_network::test_utils::peer_id_from_seed;
     use unc_primitives::version::PROTOCOL_VERSION;
 
@@ -897,7 +897,7 @@ mod tests {
         let vs =
             ValidatorSchedule::new().block_producers_per_epoch(vec![vec!["test".parse().unwrap()]]);
         let epoch_manager = MockEpochManager::new_with_validators(store.clone(), vs, 123);
-        let shard_tracker = ShardTracker::new_empty(epoch_manager.clone());
+        let shard_tracker = ShardTracker::new(TrackedConfig::AllShards, epoch_manager.clone());
         let runtime = KeyValueRuntime::new(store, epoch_manager.as_ref());
         let chain_genesis = ChainGenesis {
             time: StaticClock::utc(),
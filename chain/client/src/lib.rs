@@ -2,8 +2,8 @@ pub use unc_client_primitives::types::{
     Error, GetBlock, GetBlockProof, GetBlockProofResponse, GetBlockWithMerkleTree, GetChunk,
     GetClientConfig, GetExecutionOutcome, GetExecutionOutcomeResponse,
     GetExecutionOutcomesForBlock, GetGasPrice, GetMaintenanceWindows, GetNetworkInfo,
-    GetNextLightClientBlock, GetProtocolConfig, GetReceipt, GetSplitStorageInfo, GetStateChanges,
-    GetStateChangesInBlock, GetStateChangesWithCauseInBlock,
+    GetNextLightClientBlock, GetPowerChanges, GetProducerSelection, GetProtocolConfig, GetReceipt,
+    GetSplitStorageInfo, GetStateChanges, GetStateChangesInBlock, GetStateChangesWithCauseInBlock,
     GetStateChangesWithCauseInBlockForTrackedShards, GetValidatorInfo, GetValidatorOrdered, Query,
     QueryError, Status, StatusResponse, SyncStatus, TxStatus, TxStatusError,
 };
@@ -29,6 +29,7 @@ mod config_updater;
 pub mod debug;
 mod info;
 mod metrics;
+pub mod streamer;
 pub mod sync;
 mod sync_jobs_actor;
 pub mod test_utils;
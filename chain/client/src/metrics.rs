@@ -530,3 +530,11 @@ pub(crate) static SYNC_REQUIREMENT_CURRENT: Lazy<IntGaugeVec> = Lazy::new(|| {
     )
     .unwrap()
 });
+
+pub(crate) static BUILD_STREAMER_MESSAGE_TIME: Lazy<Histogram> = Lazy::new(|| {
+    try_create_histogram(
+        "unc_indexer_build_streamer_message_time",
+        "Time taken to build a streamer message",
+    )
+    .unwrap()
+});
@@ -1,5 +1,4 @@
-//! Streamer watches the network and collects all the blocks and related chunks
-//! into one struct and pushes in in to the given queue
+//! Fetches blocks and related data from the client actors.
 use std::collections::HashMap;
 
 use actix::Addr;
@@ -12,25 +11,25 @@ use unc_primitives::hash::CryptoHash;
 use unc_primitives::{types, views};
 
 use super::errors::FailedToFetchData;
-use super::INDEXER;
+use super::STREAMER;
 
-pub(crate) async fn fetch_status(
-    client: &Addr<unc_client::ClientActor>,
+pub async fn fetch_status(
+    client: &Addr<crate::ClientActor>,
 ) -> Result<unc_primitives::views::StatusResponse, FailedToFetchData> {
     client
-        .send(unc_client::Status { is_health_check: false, detailed: false }.with_span_context())
+        .send(crate::Status { is_health_check: false, detailed: false }.with_span_context())
         .await?
         .map_err(|err| FailedToFetchData::String(err.to_string()))
 }
 
 /// Fetches the status to retrieve `latest_block_height` to determine if we need to fetch
 /// entire block or we already fetched this block.
-pub(crate) async fn fetch_latest_block(
-    client: &Addr<unc_client::ViewClientActor>,
+pub async fn fetch_latest_block(
+    client: &Addr<crate::ViewClientActor>,
 ) -> Result<views::BlockView, FailedToFetchData> {
     client
         .send(
-            unc_client::GetBlock(unc_primitives::types::BlockReference::Finality(
+            crate::GetBlock(unc_primitives::types::BlockReference::Finality(
                 unc_primitives::types::Finality::Final,
             ))
             .with_span_context(),
@@ -40,13 +39,13 @@ pub(crate) async fn fetch_latest_block(
 }
 
 /// Fetches specific block by it's height
-pub(crate) async fn fetch_block_by_height(
-    client: &Addr<unc_client::ViewClientActor>,
+pub async fn fetch_block_by_height(
+    client: &Addr<crate::ViewClientActor>,
     height: u64,
 ) -> Result<views::BlockView, FailedToFetchData> {
     client
         .send(
-            unc_client::GetBlock(unc_primitives::types::BlockId::Height(height).into())
+            crate::GetBlock(unc_primitives::types::BlockId::Height(height).into())
                 .with_span_context(),
         )
         .await?
@@ -54,27 +53,26 @@ pub(crate) async fn fetch_block_by_height(
 }
 
 /// Fetches specific block by it's hash
-pub(crate) async fn fetch_block(
-    client: &Addr<unc_client::ViewClientActor>,
+pub async fn fetch_block(
+    client: &Addr<crate::ViewClientActor>,
     hash: CryptoHash,
 ) -> Result<views::BlockView, FailedToFetchData> {
     client
         .send(
-            unc_client::GetBlock(unc_primitives::types::BlockId::Hash(hash).into())
-                .with_span_context(),
+            crate::GetBlock(unc_primitives::types::BlockId::Hash(hash).into()).with_span_context(),
         )
         .await?
         .map_err(|err| FailedToFetchData::String(err.to_string()))
 }
 
-pub(crate) async fn fetch_state_changes(
-    client: &Addr<unc_client::ViewClientActor>,
+pub async fn fetch_state_changes(
+    client: &Addr<crate::ViewClientActor>,
     block_hash: CryptoHash,
     epoch_id: unc_primitives::types::EpochId,
 ) -> Result<HashMap<unc_primitives::types::ShardId, views::StateChangesView>, FailedToFetchData> {
     client
         .send(
-            unc_client::GetStateChangesWithCauseInBlockForTrackedShards { block_hash, epoch_id }
+            crate::GetStateChangesWithCauseInBlockForTrackedShards { block_hash, epoch_id }
                 .with_span_context(),
         )
         .await?
@@ -83,15 +81,15 @@ pub(crate) async fn fetch_state_changes(
 
 /// Fetch all ExecutionOutcomeWithId for current block
 /// Returns a HashMap where the key is shard id IndexerExecutionOutcomeWithOptionalReceipt
-pub(crate) async fn fetch_outcomes(
-    client: &Addr<unc_client::ViewClientActor>,
+pub async fn fetch_outcomes(
+    client: &Addr<crate::ViewClientActor>,
     block_hash: CryptoHash,
 ) -> Result<
     HashMap<unc_primitives::types::ShardId, Vec<IndexerExecutionOutcomeWithOptionalReceipt>>,
     FailedToFetchData,
 > {
     let outcomes = client
-        .send(unc_client::GetExecutionOutcomesForBlock { block_hash }.with_span_context())
+        .send(crate::GetExecutionOutcomesForBlock { block_hash }.with_span_context())
         .await?
         .map_err(FailedToFetchData::String)?;
 
@@ -106,7 +104,7 @@ pub(crate) async fn fetch_outcomes(
                 Ok(res) => res,
                 Err(e) => {
                     warn!(
-                        target: INDEXER,
+                        target: STREAMER,
                         "Unable to fetch Receipt with id {}. Skipping it in ExecutionOutcome \n {:#?}",
                         outcome.id,
                         e,
@@ -126,11 +124,11 @@ pub(crate) async fn fetch_outcomes(
 }
 
 async fn fetch_receipt_by_id(
-    client: &Addr<unc_client::ViewClientActor>,
+    client: &Addr<crate::ViewClientActor>,
     receipt_id: CryptoHash,
 ) -> Result<Option<views::ReceiptView>, FailedToFetchData> {
     client
-        .send(unc_client::GetReceipt { receipt_id }.with_span_context())
+        .send(crate::GetReceipt { receipt_id }.with_span_context())
         .await?
         .map_err(|err| FailedToFetchData::String(err.to_string()))
 }
@@ -138,19 +136,19 @@ async fn fetch_receipt_by_id(
 /// Fetches single chunk (as `unc_primitives::views::ChunkView`) by provided
 /// chunk hash.
 async fn fetch_single_chunk(
-    client: &Addr<unc_client::ViewClientActor>,
+    client: &Addr<crate::ViewClientActor>,
     chunk_hash: unc_primitives::hash::CryptoHash,
 ) -> Result<views::ChunkView, FailedToFetchData> {
     client
-        .send(unc_client::GetChunk::ChunkHash(chunk_hash.into()).with_span_context())
+        .send(crate::GetChunk::ChunkHash(chunk_hash.into()).with_span_context())
         .await?
         .map_err(|err| FailedToFetchData::String(err.to_string()))
 }
 
 /// Fetches all chunks belonging to given block.
 /// Includes transactions and receipts in custom struct (to provide more info).
-pub(crate) async fn fetch_block_chunks(
-    client: &Addr<unc_client::ViewClientActor>,
+pub async fn fetch_block_chunks(
+    client: &Addr<crate::ViewClientActor>,
     block: &views::BlockView,
 ) -> Result<Vec<views::ChunkView>, FailedToFetchData> {
     let mut futures: futures::stream::FuturesUnordered<_> = block
@@ -166,16 +164,14 @@ pub(crate) async fn fetch_block_chunks(
     Ok(chunks)
 }
 
-pub(crate) async fn fetch_protocol_config(
-    client: &Addr<unc_client::ViewClientActor>,
+pub async fn fetch_protocol_config(
+    client: &Addr<crate::ViewClientActor>,
     block_hash: unc_primitives::hash::CryptoHash,
 ) -> Result<unc_chain_configs::ProtocolConfigView, FailedToFetchData> {
     Ok(client
         .send(
-            unc_client::GetProtocolConfig(types::BlockReference::from(types::BlockId::Hash(
-                block_hash,
-            )))
-            .with_span_context(),
+            crate::GetProtocolConfig(types::BlockReference::from(types::BlockId::Hash(block_hash)))
+                .with_span_context(),
         )
         .await?
         .map_err(|err| FailedToFetchData::String(err.to_string()))?)
@@ -5,6 +5,8 @@ use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 use std::time::Instant;
+use unc_crypto::PublicKey;
+use unc_primitives::hash::CryptoHash;
 use unc_primitives::types::{EpochId, ShardId};
 
 /// Connection to the external storage.
@@ -24,11 +26,31 @@ pub enum ExternalConnection {
         reqwest_client: Arc<reqwest::Client>,
         bucket: String,
     },
+    /// Read-only storage served over HTTP(S).
+    HTTP {
+        reqwest_client: Arc<reqwest::Client>,
+        url: String,
+    },
+    Azure {
+        reqwest_client: Arc<reqwest::Client>,
+        // URL of the container, e.g. `https://<account>.blob.core.windows.net/<container>`.
+        container_url: String,
+        // Shared access signature authorizing the requests. Containers allowing
+        // anonymous read access can be downloaded from without it.
+        sas_token: Option<String>,
+    },
 }
 
+/// Name prefix of the files listing the state parts dumped for an epoch and a
+/// shard, one per dumping node.
+pub const STATE_PARTS_MANIFEST_FILENAME: &str = "manifest";
+
 const GCS_ENCODE_SET: &percent_encoding::AsciiSet =
     &percent_encoding::NON_ALPHANUMERIC.remove(b'-').remove(b'.').remove(b'_');
 
+/// Version of the Azure Blob service REST API used for the requests.
+const AZURE_API_VERSION: &str = "2021-08-06";
+
 impl ExternalConnection {
     pub async fn get_part(
         &self,
@@ -75,6 +97,43 @@ impl ExternalConnection {
                     }
                 }
             }
+            ExternalConnection::HTTP { reqwest_client, url } => {
+                let url = format!("{}/{}", url.trim_end_matches('/'), location);
+                let response = reqwest_client.get(&url).send().await?.error_for_status();
+
+                match response {
+                    Err(e) => {
+                        tracing::debug!(target: "sync", %shard_id, location, error = ?e, "HTTP state_part request failed");
+                        Err(e.into())
+                    }
+                    Ok(r) => {
+                        let bytes = r.bytes().await?.to_vec();
+                        tracing::debug!(target: "sync", %shard_id, location, num_bytes = bytes.len(), "HTTP state_part request finished");
+                        Ok(bytes)
+                    }
+                }
+            }
+            ExternalConnection::Azure { reqwest_client, container_url, sas_token } => {
+                let url = azure_url(container_url, Some(location), sas_token.as_deref())?;
+                let response = reqwest_client
+                    .get(url)
+                    .header("x-ms-version", AZURE_API_VERSION)
+                    .send()
+                    .await?
+                    .error_for_status();
+
+                match response {
+                    Err(e) => {
+                        tracing::debug!(target: "sync", %shard_id, location, error = ?e, "Azure state_part request failed");
+                        Err(e.into())
+                    }
+                    Ok(r) => {
+                        let bytes = r.bytes().await?.to_vec();
+                        tracing::debug!(target: "sync", %shard_id, location, num_bytes = bytes.len(), "Azure state_part request finished");
+                        Ok(bytes)
+                    }
+                }
+            }
         }
     }
 
@@ -131,6 +190,22 @@ impl ExternalConnection {
                 tracing::debug!(target: "state_sync_dump", shard_id, part_length = state_part.len(), ?location, "Wrote a state part to GCS");
                 Ok(())
             }
+            ExternalConnection::HTTP { .. } => {
+                Err(anyhow::anyhow!("HTTP external storage is read-only"))
+            }
+            ExternalConnection::Azure { reqwest_client, container_url, sas_token } => {
+                let url = azure_url(container_url, Some(location), sas_token.as_deref())?;
+                reqwest_client
+                    .put(url)
+                    .header("x-ms-version", AZURE_API_VERSION)
+                    .header("x-ms-blob-type", "BlockBlob")
+                    .body(state_part.to_vec())
+                    .send()
+                    .await?
+                    .error_for_status()?;
+                tracing::debug!(target: "state_sync_dump", shard_id, part_length = state_part.len(), ?location, "Wrote a state part to Azure");
+                Ok(())
+            }
         }
     }
 
@@ -201,10 +276,109 @@ impl ExternalConnection {
                     .flatten()
                     .collect())
             }
+            ExternalConnection::HTTP { .. } => {
+                Err(anyhow::anyhow!("Listing state parts isn't supported by HTTP external storage"))
+            }
+            ExternalConnection::Azure { reqwest_client, container_url, sas_token } => {
+                let prefix = format!("{}/", directory_path);
+                tracing::debug!(target: "state_sync_dump", shard_id, ?directory_path, "List state parts in Azure");
+                let name_re = regex::Regex::new(r"<Name>([^<]*)</Name>").unwrap();
+                let marker_re = regex::Regex::new(r"<NextMarker>([^<]*)</NextMarker>").unwrap();
+                let mut file_names = vec![];
+                let mut marker = String::new();
+                // The results are paginated, every page points to the following one.
+                loop {
+                    let mut url = azure_url(container_url, None, sas_token.as_deref())?;
+                    url.query_pairs_mut()
+                        .append_pair("restype", "container")
+                        .append_pair("comp", "list")
+                        .append_pair("prefix", &prefix)
+                        .append_pair("marker", &marker);
+                    let response = reqwest_client
+                        .get(url)
+                        .header("x-ms-version", AZURE_API_VERSION)
+                        .send()
+                        .await?
+                        .error_for_status()?
+                        .text()
+                        .await?;
+                    for name in name_re.captures_iter(&response) {
+                        file_names
+                            .push(Self::extract_file_name_from_full_path(name[1].to_string()));
+                    }
+                    match marker_re.captures(&response) {
+                        Some(next_marker) if !next_marker[1].is_empty() => {
+                            marker = next_marker[1].to_string()
+                        }
+                        _ => break,
+                    }
+                }
+                Ok(file_names)
+            }
         }
     }
 }
 
+/// URL of the blob at `location` in the container, or of the container itself,
+/// authorized with the shared access signature.
+fn azure_url(
+    container_url: &str,
+    location: Option<&str>,
+    sas_token: Option<&str>,
+) -> Result<reqwest::Url, anyhow::Error> {
+    let container_url = container_url.trim_end_matches('/');
+    let mut url = match location {
+        Some(location) => reqwest::Url::parse(&format!("{}/{}", container_url, location))?,
+        None => reqwest::Url::parse(container_url)?,
+    };
+    if let Some(sas_token) = sas_token {
+        url.set_query(Some(sas_token.trim_start_matches('?')));
+    }
+    Ok(url)
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+struct AzureCredentialsConfig {
+    sas_token: String,
+}
+
+/// Environment variable with the shared access signature used if no credentials
+/// file is given.
+pub const AZURE_SAS_TOKEN_ENV_VAR: &str = "AZURE_STORAGE_SAS_TOKEN";
+
+/// Connects to a container of an Azure Storage account. Requests are authorized
+/// with the shared access signature read from `credentials_file`, which looks
+/// like `{"sas_token": "sv=...&sig=..."}`, or else from the
+/// `AZURE_STORAGE_SAS_TOKEN` environment variable. Without either, the container
+/// is accessed anonymously, which only allows downloading from public containers.
+/// `endpoint` overrides the default `https://<account>.blob.core.windows.net`,
+/// e.g. to use the Azurite emulator.
+pub fn create_azure_connection(
+    account: &str,
+    container: &str,
+    endpoint: Option<&str>,
+    credentials_file: Option<PathBuf>,
+) -> Result<ExternalConnection, anyhow::Error> {
+    let sas_token = match credentials_file {
+        Some(credentials_file) => {
+            let json_config_str = std::fs::read_to_string(credentials_file)?;
+            let credentials_config: AzureCredentialsConfig =
+                serde_json::from_str(&json_config_str)?;
+            Some(credentials_config.sas_token)
+        }
+        None => std::env::var(AZURE_SAS_TOKEN_ENV_VAR).ok(),
+    };
+    let endpoint = match endpoint {
+        Some(endpoint) => endpoint.trim_end_matches('/').to_string(),
+        None => format!("https://{}.blob.core.windows.net", account),
+    };
+    Ok(ExternalConnection::Azure {
+        reqwest_client: Arc::new(reqwest::Client::default()),
+        container_url: format!("{}/{}", endpoint, container),
+        sas_token,
+    })
+}
+
 /// Construct a location on the external storage.
 pub fn external_storage_location(
     chain_id: &str,
@@ -230,6 +404,24 @@ pub fn external_storage_location_directory(
     location_prefix(chain_id, epoch_height, epoch_id, shard_id)
 }
 
+/// Location of the manifest listing the state parts of an epoch and a shard,
+/// signed by the given node key. Stored next to the state parts.
+/// Each dumping node writes its own manifest, named after the hash of its key.
+pub fn external_storage_manifest_location(
+    chain_id: &str,
+    epoch_id: &EpochId,
+    epoch_height: u64,
+    shard_id: u64,
+    public_key: &PublicKey,
+) -> String {
+    format!(
+        "{}/{}_{}",
+        location_prefix(chain_id, epoch_height, epoch_id, shard_id),
+        STATE_PARTS_MANIFEST_FILENAME,
+        CryptoHash::hash_borsh(public_key)
+    )
+}
+
 pub fn location_prefix(
     chain_id: &str,
     epoch_height: u64,
@@ -280,10 +472,11 @@ pub fn get_part_id_from_filename(s: &str) -> Option<u64> {
 pub fn create_bucket_readonly(
     bucket: &str,
     region: &str,
+    endpoint: Option<&str>,
     timeout: Duration,
 ) -> Result<s3::Bucket, anyhow::Error> {
     let creds = s3::creds::Credentials::anonymous()?;
-    create_bucket(bucket, region, timeout, creds)
+    create_bucket(bucket, region, endpoint, timeout, creds)
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -295,6 +488,7 @@ struct S3CredentialsConfig {
 pub fn create_bucket_readwrite(
     bucket: &str,
     region: &str,
+    endpoint: Option<&str>,
     timeout: Duration,
     credentials_file: Option<PathBuf>,
 ) -> Result<s3::Bucket, anyhow::Error> {
@@ -314,16 +508,26 @@ pub fn create_bucket_readwrite(
         }
         None => s3::creds::Credentials::default(),
     }?;
-    create_bucket(bucket, region, timeout, creds)
+    create_bucket(bucket, region, endpoint, timeout, creds)
 }
 
+/// With an `endpoint`, connects to an S3-compatible storage such as MinIO,
+/// which usually only supports path-style addressing of the buckets.
 fn create_bucket(
     bucket: &str,
     region: &str,
+    endpoint: Option<&str>,
     timeout: Duration,
     creds: s3::creds::Credentials,
 ) -> Result<s3::Bucket, anyhow::Error> {
-    let mut bucket = s3::Bucket::new(bucket, region.parse::<s3::Region>()?, creds)?;
+    let mut bucket = match endpoint {
+        Some(endpoint) => {
+            let region =
+                s3::Region::Custom { region: region.to_string(), endpoint: endpoint.to_string() };
+            s3::Bucket::new(bucket, region, creds)?.with_path_style()
+        }
+        None => s3::Bucket::new(bucket, region.parse::<s3::Region>()?, creds)?,
+    };
     // Ensure requests finish in finite amount of time.
     bucket.set_request_timeout(Some(timeout));
     Ok(bucket)
@@ -332,11 +536,17 @@ fn create_bucket(
 #[cfg(test)]
 mod test {
     use crate::sync::external::{
-        get_num_parts_from_filename, get_part_id_from_filename, is_part_filename, part_filename,
-        ExternalConnection,
+        create_azure_connection, create_bucket_readonly, external_storage_location,
+        external_storage_location_directory, get_num_parts_from_filename,
+        get_part_id_from_filename, is_part_filename, part_filename, ExternalConnection,
     };
     use rand::distributions::{Alphanumeric, DistString};
+    use std::io::{BufRead, Read, Write};
+    use std::path::PathBuf;
+    use std::sync::Arc;
+    use std::time::Duration;
     use unc_o11y::testonly::init_test_logger;
+    use unc_primitives::types::EpochId;
 
     fn random_string(rand_len: usize) -> String {
         Alphanumeric.sample_string(&mut rand::thread_rng(), rand_len)
@@ -409,4 +619,204 @@ mod test {
         let download_data = rt.block_on(async { connection.get_part(0, &full_filename).await });
         assert!(download_data.is_err(), "{:?}", download_data);
     }
+
+    /// Minimal HTTP server answering every request with `handler(method, target, body)`.
+    /// Returns the URL of the server.
+    fn serve(handler: impl Fn(&str, &str, Vec<u8>) -> (u16, Vec<u8>) + Send + 'static) -> String {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = std::io::BufReader::new(stream.try_clone().unwrap());
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let mut content_length = 0;
+                let mut header = String::new();
+                while reader.read_line(&mut header).unwrap() > 2 {
+                    if let Some((name, value)) = header.split_once(':') {
+                        if name.eq_ignore_ascii_case("content-length") {
+                            content_length = value.trim().parse().unwrap();
+                        }
+                    }
+                    header.clear();
+                }
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).unwrap();
+                let mut request_line = request_line.split_whitespace();
+                let method = request_line.next().unwrap();
+                let target = request_line.next().unwrap();
+                let (status, data) = handler(method, target, body);
+                let mut response = format!(
+                    "HTTP/1.1 {status} -\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    data.len()
+                )
+                .into_bytes();
+                response.extend(data);
+                stream.write_all(&response).unwrap();
+            }
+        });
+        url
+    }
+
+    /// Decoded path of the request `target`, relative to the root of the server.
+    fn request_path(target: &str) -> String {
+        let url = reqwest::Url::parse(&format!("http://localhost{target}")).unwrap();
+        let path = url.path().trim_start_matches('/');
+        percent_encoding::percent_decode_str(path).decode_utf8().unwrap().to_string()
+    }
+
+    /// Serves the files in `root_dir` over HTTP, like nginx serving a mirror
+    /// of state dumps would. Returns the URL of the server.
+    fn serve_dir(root_dir: PathBuf) -> String {
+        serve(move |method, target, _| {
+            if method != "GET" {
+                return (405, vec![]);
+            }
+            match std::fs::read(root_dir.join(request_path(target))) {
+                Ok(data) => (200, data),
+                Err(_) => (404, vec![]),
+            }
+        })
+    }
+
+    /// Parts dumped to the filesystem can be downloaded from an HTTP server
+    /// serving the dump directory.
+    #[test]
+    fn test_http_download() {
+        init_test_logger();
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let root_dir = tempfile::tempdir().unwrap();
+        let dump = ExternalConnection::Filesystem { root_dir: root_dir.path().to_path_buf() };
+        let connection = ExternalConnection::HTTP {
+            reqwest_client: Arc::new(reqwest::Client::default()),
+            url: serve_dir(root_dir.path().to_path_buf()),
+        };
+
+        let data: Vec<u8> = random_string(1000).into();
+        let location = external_storage_location("test", &EpochId::default(), 1, 0, 2, 5);
+        rt.block_on(dump.put_state_part(&data, 0, &location)).unwrap();
+        assert_eq!(rt.block_on(connection.get_part(0, &location)).unwrap(), data);
+
+        // Missing parts and uploads fail.
+        let missing_location = external_storage_location("test", &EpochId::default(), 1, 0, 3, 5);
+        assert!(rt.block_on(connection.get_part(0, &missing_location)).is_err());
+        assert!(rt.block_on(connection.put_state_part(&data, 0, &location)).is_err());
+    }
+
+    /// Parts can be downloaded from an S3-compatible storage with a custom
+    /// endpoint, which addresses the buckets path-style like MinIO.
+    #[test]
+    fn test_s3_custom_endpoint() {
+        init_test_logger();
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let root_dir = tempfile::tempdir().unwrap();
+        let endpoint = serve_dir(root_dir.path().to_path_buf());
+        let bucket = create_bucket_readonly(
+            "state-parts",
+            "us-east-1",
+            Some(&endpoint),
+            Duration::from_secs(5),
+        )
+        .unwrap();
+        assert!(bucket.is_path_style());
+        assert_eq!(bucket.url(), format!("{endpoint}/state-parts"));
+
+        // The bucket is a directory of the storage.
+        let dump = ExternalConnection::Filesystem { root_dir: root_dir.path().join("state-parts") };
+        let connection = ExternalConnection::S3 { bucket: Arc::new(bucket) };
+        let data: Vec<u8> = random_string(1000).into();
+        let location = external_storage_location("test", &EpochId::default(), 1, 0, 2, 5);
+        rt.block_on(dump.put_state_part(&data, 0, &location)).unwrap();
+        assert_eq!(rt.block_on(connection.get_part(0, &location)).unwrap(), data);
+        let missing_location = external_storage_location("test", &EpochId::default(), 1, 0, 3, 5);
+        assert!(rt.block_on(connection.get_part(0, &missing_location)).is_err());
+    }
+
+    /// Serves the blobs stored in `root_dir`, like the Azure Blob service would,
+    /// to the requests authorized with `sas_token`. Returns the URL of the server.
+    fn serve_azure(root_dir: PathBuf, sas_token: &'static str) -> String {
+        serve(move |method, target, body| {
+            let url = reqwest::Url::parse(&format!("http://localhost{target}")).unwrap();
+            if url.query_pairs().all(|(key, value)| key != "sig" || value != sas_token) {
+                return (403, vec![]);
+            }
+            let path = root_dir.join(request_path(target));
+            let query: std::collections::HashMap<_, _> = url.query_pairs().collect();
+            match (method, query.get("comp").map(|comp| &**comp)) {
+                ("GET", Some("list")) => {
+                    let prefix = query["prefix"].to_string();
+                    let names = std::fs::read_dir(path.join(&prefix))
+                        .map(|files| {
+                            files
+                                .map(|file| {
+                                    let file_name = file.unwrap().file_name();
+                                    format!(
+                                        "<Blob><Name>{prefix}{}</Name></Blob>",
+                                        file_name.to_str().unwrap()
+                                    )
+                                })
+                                .collect::<String>()
+                        })
+                        .unwrap_or_default();
+                    let response = format!(
+                        "<EnumerationResults><Blobs>{names}</Blobs><NextMarker /></EnumerationResults>"
+                    );
+                    (200, response.into_bytes())
+                }
+                ("GET", None) => match std::fs::read(path) {
+                    Ok(data) => (200, data),
+                    Err(_) => (404, vec![]),
+                },
+                ("PUT", None) => {
+                    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+                    std::fs::write(path, body).unwrap();
+                    (201, vec![])
+                }
+                _ => (400, vec![]),
+            }
+        })
+    }
+
+    #[test]
+    fn test_azure_upload_list_download() {
+        init_test_logger();
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let root_dir = tempfile::tempdir().unwrap();
+        let endpoint = format!("{}/account", serve_azure(root_dir.path().to_path_buf(), "secret"));
+        let credentials_file = root_dir.path().join("credentials.json");
+        std::fs::write(&credentials_file, r#"{"sas_token": "?sv=2021-08-06&sig=secret"}"#).unwrap();
+        let connection = create_azure_connection(
+            "account",
+            "state-parts",
+            Some(&endpoint),
+            Some(credentials_file),
+        )
+        .unwrap();
+
+        let data: Vec<u8> = random_string(1000).into();
+        let dir = external_storage_location_directory("test", &EpochId::default(), 1, 0);
+        let location = external_storage_location("test", &EpochId::default(), 1, 0, 2, 5);
+        assert_eq!(
+            rt.block_on(connection.list_state_parts(0, &dir)).unwrap(),
+            Vec::<String>::new()
+        );
+        rt.block_on(connection.put_state_part(&data, 0, &location)).unwrap();
+        assert_eq!(
+            rt.block_on(connection.list_state_parts(0, &dir)).unwrap(),
+            vec![part_filename(2, 5)]
+        );
+        assert_eq!(rt.block_on(connection.get_part(0, &location)).unwrap(), data);
+        let missing_location = external_storage_location("test", &EpochId::default(), 1, 0, 3, 5);
+        assert!(rt.block_on(connection.get_part(0, &missing_location)).is_err());
+
+        // Requests without the shared access signature are refused.
+        let ExternalConnection::Azure { reqwest_client, container_url, .. } = connection else {
+            unreachable!()
+        };
+        let connection =
+            ExternalConnection::Azure { reqwest_client, container_url, sas_token: None };
+        assert!(rt.block_on(connection.get_part(0, &location)).is_err());
+        assert!(rt.block_on(connection.put_state_part(&data, 0, &location)).is_err());
+    }
 }
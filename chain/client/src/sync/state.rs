@@ -22,10 +22,13 @@
 
 use crate::metrics;
 use crate::sync::external::{
-    create_bucket_readonly, external_storage_location, ExternalConnection,
+    create_azure_connection, create_bucket_readonly, external_storage_location,
+    external_storage_manifest_location, ExternalConnection,
 };
 use actix_rt::ArbiterHandle;
+use borsh::BorshDeserialize;
 use chrono::{DateTime, Duration, Utc};
+use futures::future::{BoxFuture, Shared};
 use futures::{future, FutureExt};
 use rand::seq::SliceRandom;
 use rand::{thread_rng, Rng};
@@ -37,7 +40,7 @@ use std::sync::Arc;
 use std::time::Duration as TimeDuration;
 use tokio::sync::{Semaphore, TryAcquireError};
 use tracing::info;
-use unc_async::messaging::CanSendAsync;
+use unc_async::messaging::{CanSend, CanSendAsync};
 use unc_chain::chain::ApplyStatePartsRequest;
 use unc_chain::resharding::ReshardingRequest;
 use unc_chain::types::RuntimeAdapter;
@@ -47,16 +50,19 @@ use unc_chain_configs::{ExternalStorageConfig, ExternalStorageLocation, SyncConf
 use unc_client_primitives::types::{
     format_shard_sync_phase, DownloadStatus, ShardSyncDownload, ShardSyncStatus,
 };
+use unc_crypto::PublicKey;
 use unc_epoch_manager::EpochManagerAdapter;
 use unc_network::types::PeerManagerMessageRequest;
 use unc_network::types::{
-    HighestHeightPeerInfo, NetworkRequests, NetworkResponses, PeerManagerAdapter,
+    HighestHeightPeerInfo, Misbehavior, NetworkRequests, NetworkResponses, PeerManagerAdapter,
 };
 use unc_primitives::hash::CryptoHash;
 use unc_primitives::network::PeerId;
 use unc_primitives::shard_layout::ShardUId;
 use unc_primitives::state_part::PartId;
-use unc_primitives::state_sync::{ShardStateSyncResponse, StatePartKey};
+use unc_primitives::state_sync::{
+    ShardStateSyncResponse, SignedStatePartsManifest, StatePartKey, StatePartsManifest,
+};
 use unc_primitives::static_clock::StaticClock;
 use unc_primitives::types::{AccountId, EpochHeight, EpochId, ShardId, StateRoot};
 use unc_store::DBCol;
@@ -103,6 +109,10 @@ pub struct StateSyncGetPartResult {
     part_result: Result<u64, String>,
 }
 
+/// Manifest of the state parts of a shard, downloaded and verified once and
+/// then awaited by every state part download of that shard.
+type SharedStatePartsManifest = Shared<BoxFuture<'static, Result<Arc<StatePartsManifest>, String>>>;
+
 /// How to retrieve the state data.
 enum StateSyncInner {
     /// Request both the state header and state parts from the peers.
@@ -121,6 +131,11 @@ enum StateSyncInner {
         semaphore: Arc<tokio::sync::Semaphore>,
         /// Connection to the external storage.
         external: ExternalConnection,
+        /// Node keys of the dumping nodes whose manifests are trusted, or
+        /// `None` if the state parts are downloaded without a manifest.
+        trusted_state_dumper_keys: Option<Vec<PublicKey>>,
+        /// Manifests of the state parts being downloaded, by sync hash and shard.
+        manifests: HashMap<(CryptoHash, ShardId), SharedStatePartsManifest>,
     },
 }
 
@@ -165,10 +180,13 @@ impl StateSync {
                 location,
                 num_concurrent_requests,
                 num_concurrent_requests_during_catchup,
+                verify_state_parts_manifest,
+                trusted_state_dumper_keys,
             }) => {
                 let external = match location {
-                    ExternalStorageLocation::S3 { bucket, region, .. } => {
-                        let bucket = create_bucket_readonly(&bucket, &region, timeout);
+                    ExternalStorageLocation::S3 { bucket, region, endpoint } => {
+                        let bucket =
+                            create_bucket_readonly(&bucket, &region, endpoint.as_deref(), timeout);
                         if let Err(err) = bucket {
                             panic!("Failed to create an S3 bucket: {}", err);
                         }
@@ -182,6 +200,14 @@ impl StateSync {
                         reqwest_client: Arc::new(reqwest::Client::default()),
                         bucket: bucket.clone(),
                     },
+                    ExternalStorageLocation::HTTP { url } => ExternalConnection::HTTP {
+                        reqwest_client: Arc::new(reqwest::Client::default()),
+                        url: url.clone(),
+                    },
+                    ExternalStorageLocation::Azure { account, container, endpoint } => {
+                        create_azure_connection(account, container, endpoint.as_deref(), None)
+                            .expect("Failed to create an Azure connection")
+                    }
                 };
                 let num_permits = if catchup {
                     *num_concurrent_requests_during_catchup
@@ -192,6 +218,9 @@ impl StateSync {
                     chain_id: chain_id.to_string(),
                     semaphore: Arc::new(tokio::sync::Semaphore::new(num_permits)),
                     external,
+                    trusted_state_dumper_keys: verify_state_parts_manifest
+                        .then(|| trusted_state_dumper_keys.clone()),
+                    manifests: HashMap::new(),
                 }
             }
         };
@@ -616,7 +645,13 @@ impl StateSync {
                     );
                 }
             }
-            StateSyncInner::PartsFromExternal { chain_id, semaphore, external } => {
+            StateSyncInner::PartsFromExternal {
+                chain_id,
+                semaphore,
+                external,
+                trusted_state_dumper_keys,
+                manifests,
+            } => {
                 let sync_block_header = chain.get_block_header(&sync_hash).unwrap();
                 let epoch_id = sync_block_header.epoch_id();
                 let epoch_info = chain.epoch_manager.get_epoch_info(epoch_id).unwrap();
@@ -626,6 +661,29 @@ impl StateSync {
                 let state_root = shard_state_header.chunk_prev_state_root();
                 let state_num_parts = shard_state_header.num_state_parts();
 
+                // Forget manifests of other sync hashes, and fetch again the
+                // manifests that failed to download or to verify.
+                manifests.retain(|(hash, _), manifest| {
+                    *hash == sync_hash && !matches!(manifest.peek(), Some(Err(_)))
+                });
+                let manifest = trusted_state_dumper_keys.as_ref().map(|trusted_keys| {
+                    manifests
+                        .entry((sync_hash, shard_id))
+                        .or_insert_with(|| {
+                            fetch_state_parts_manifest(
+                                shard_id,
+                                epoch_id.clone(),
+                                epoch_height,
+                                state_num_parts,
+                                chain_id,
+                                state_root,
+                                trusted_keys,
+                                external.clone(),
+                            )
+                        })
+                        .clone()
+                });
+
                 for (part_id, download) in parts_to_fetch(new_shard_sync_download) {
                     request_part_from_external_storage(
                         part_id,
@@ -637,6 +695,7 @@ impl StateSync {
                         state_num_parts,
                         &chain_id.clone(),
                         state_root,
+                        manifest.clone(),
                         semaphore.clone(),
                         external.clone(),
                         runtime_adapter.clone(),
@@ -845,6 +904,18 @@ impl StateSync {
                 let part_timeout = now - prev > self.timeout; // Retry parts that failed.
                 if part_timeout || part_download.error {
                     download_timeout |= part_timeout;
+                    match &part_download.last_target {
+                        // The peer didn't respond in time, lower its reputation.
+                        Some(peer_id) if part_timeout => {
+                            self.network_adapter.send(PeerManagerMessageRequest::NetworkRequests(
+                                NetworkRequests::ReportPeer {
+                                    peer_id: peer_id.clone(),
+                                    misbehavior: Misbehavior::SlowStatePart,
+                                },
+                            ));
+                        }
+                        _ => {}
+                    }
                     if part_timeout || part_download.last_target.is_some() {
                         // Don't immediately retry failed requests from external
                         // storage. Most often error is a state part not
@@ -1042,6 +1113,7 @@ fn request_part_from_external_storage(
     num_parts: u64,
     chain_id: &str,
     state_root: StateRoot,
+    manifest: Option<SharedStatePartsManifest>,
     semaphore: Arc<Semaphore>,
     external: ExternalConnection,
     runtime_adapter: Arc<dyn RuntimeAdapter>,
@@ -1062,23 +1134,33 @@ fn request_part_from_external_storage(
         Ok(permit) => {
             if state_parts_arbiter_handle.spawn({
                 async move {
-                    let result = external.get_part(shard_id, &location).await;
                     let part_id = PartId{ idx: part_id, total: num_parts };
-                    let part_result = match result {
-                        Ok(data) => {
-                            info!(target: "sync", ?shard_id, ?part_id, "downloaded state part");
-                            if runtime_adapter.validate_state_part(&state_root, part_id, &data) {
-                                let mut store_update = runtime_adapter.store().store_update();
-                                let part_result = borsh::to_vec(&StatePartKey(sync_hash, shard_id, part_id.idx)).and_then(|key|{
-                                    store_update.set(DBCol::StateParts, &key, &data);
-                                    store_update.commit()
-                                }).and_then(|_|Ok(data.len() as u64)).map_err(|err|format!("Failed to store a state part. err={err:?}, state_root={state_root:?}, part_id={part_id:?}, shard_id={shard_id:?}"));
-                                part_result
-                            } else {
-                                Err(format!("validate_state_part failed. state_root={state_root:?}, part_id={part_id:?}, shard_id={shard_id}"))
-                            }
+                    // Don't download anything until the manifest, if any, is verified.
+                    let manifest = match manifest {
+                        Some(manifest) => manifest.await.map(Some),
+                        None => Ok(None),
+                    };
+                    let part_result = match manifest {
+                        Ok(manifest) => match external.get_part(shard_id, &location).await {
+                            Ok(data) => {
+                                info!(target: "sync", ?shard_id, ?part_id, "downloaded state part");
+                                let in_manifest = manifest.map_or(Ok(()), |manifest| check_state_part_in_manifest(&manifest, part_id, &data));
+                                if let Err(err) = in_manifest {
+                                    Err(err)
+                                } else if runtime_adapter.validate_state_part(&state_root, part_id, &data) {
+                                    let mut store_update = runtime_adapter.store().store_update();
+                                    let part_result = borsh::to_vec(&StatePartKey(sync_hash, shard_id, part_id.idx)).and_then(|key|{
+                                        store_update.set(DBCol::StateParts, &key, &data);
+                                        store_update.commit()
+                                    }).and_then(|_|Ok(data.len() as u64)).map_err(|err|format!("Failed to store a state part. err={err:?}, state_root={state_root:?}, part_id={part_id:?}, shard_id={shard_id:?}"));
+                                    part_result
+                                } else {
+                                    Err(format!("validate_state_part failed. state_root={state_root:?}, part_id={part_id:?}, shard_id={shard_id}"))
+                                }
+                            },
+                            Err(err) => Err(err.to_string()),
                         },
-                        Err(err) => Err(err.to_string()),
+                        Err(err) => Err(err),
                     };
                     match state_parts_mpsc_tx.send(StateSyncGetPartResult {
                         sync_hash,
@@ -1097,10 +1179,10 @@ fn request_part_from_external_storage(
             {
                 tracing::error!(target: "sync", %shard_id, part_id, "Unable to spawn download. state_parts_arbiter has died.");
             }
-        },
+        }
         Err(TryAcquireError::NoPermits) => {
             download.run_me.store(true, Ordering::SeqCst);
-        },
+        }
         Err(TryAcquireError::Closed) => {
             download.run_me.store(true, Ordering::SeqCst);
             tracing::warn!(target: "sync", %shard_id, part_id, "Failed to schedule download. Semaphore closed.");
@@ -1108,6 +1190,134 @@ fn request_part_from_external_storage(
     }
 }
 
+/// Starts downloading the manifest of the state parts of the given shard.
+/// The manifests of the trusted dumping nodes are tried in order, and the
+/// first one that is correctly signed and describes the state that the node is
+/// going to sync is used.
+fn fetch_state_parts_manifest(
+    shard_id: ShardId,
+    epoch_id: EpochId,
+    epoch_height: EpochHeight,
+    num_parts: u64,
+    chain_id: &str,
+    state_root: StateRoot,
+    trusted_keys: &[PublicKey],
+    external: ExternalConnection,
+) -> SharedStatePartsManifest {
+    let locations: Vec<_> = trusted_keys
+        .iter()
+        .map(|public_key| {
+            let location = external_storage_manifest_location(
+                chain_id,
+                &epoch_id,
+                epoch_height,
+                shard_id,
+                public_key,
+            );
+            (public_key.clone(), location)
+        })
+        .collect();
+    async move {
+        let mut errors = vec![];
+        for (public_key, location) in locations {
+            // The manifest is missing until the dump of all parts is complete.
+            let data = match external.get_part(shard_id, &location).await {
+                Ok(data) => data,
+                Err(err) => {
+                    tracing::debug!(target: "sync", %shard_id, ?err, location, "Failed to get the state parts manifest, will retry");
+                    errors.push(format!("Failed to get the state parts manifest. err={err:?}, location={location}"));
+                    continue;
+                }
+            };
+            let signed_manifest = SignedStatePartsManifest::try_from_slice(&data)
+                .map_err(|err| format!("Failed to parse the state parts manifest. err={err:?}"))
+                .and_then(|signed_manifest| {
+                    verify_state_parts_manifest(
+                        &signed_manifest,
+                        &public_key,
+                        shard_id,
+                        &epoch_id,
+                        num_parts,
+                        &state_root,
+                    )?;
+                    Ok(signed_manifest)
+                });
+            match signed_manifest {
+                Ok(signed_manifest) => {
+                    tracing::debug!(target: "sync", %shard_id, num_parts, %public_key, "Verified the state parts manifest");
+                    return Ok(Arc::new(signed_manifest.manifest));
+                }
+                Err(err) => {
+                    tracing::warn!(target: "sync", %shard_id, ?err, location, "Rejected the state parts manifest");
+                    errors.push(err);
+                }
+            }
+        }
+        Err(format!("No usable state parts manifest of a trusted dumper. errors={errors:?}"))
+    }
+    .boxed()
+    .shared()
+}
+
+/// Checks that the manifest is signed by the expected trusted key and that it
+/// matches the state header.
+fn verify_state_parts_manifest(
+    signed_manifest: &SignedStatePartsManifest,
+    trusted_key: &PublicKey,
+    shard_id: ShardId,
+    epoch_id: &EpochId,
+    num_parts: u64,
+    state_root: &StateRoot,
+) -> Result<(), String> {
+    if &signed_manifest.public_key != trusted_key {
+        return Err(format!(
+            "The state parts manifest is signed by an untrusted key. public_key={}, trusted_key={trusted_key}",
+            signed_manifest.public_key
+        ));
+    }
+    if !signed_manifest.verify_signature() {
+        return Err(format!(
+            "Invalid signature of the state parts manifest. public_key={}",
+            signed_manifest.public_key
+        ));
+    }
+    let manifest = &signed_manifest.manifest;
+    if manifest.shard_id != shard_id
+        || &manifest.epoch_id != epoch_id
+        || &manifest.state_root != state_root
+    {
+        return Err(format!("The state parts manifest describes a different state. manifest_shard_id={}, manifest_epoch_id={:?}, manifest_state_root={:?}, state_root={state_root:?}", manifest.shard_id, manifest.epoch_id, manifest.state_root));
+    }
+    if manifest.num_parts() != num_parts
+        || manifest.parts.iter().enumerate().any(|(idx, entry)| entry.part_id != idx as u64)
+    {
+        return Err(format!(
+            "The state parts manifest doesn't list all parts. manifest_num_parts={}, num_parts={num_parts}",
+            manifest.num_parts()
+        ));
+    }
+    Ok(())
+}
+
+/// Checks that the downloaded state part is the one listed in the manifest.
+fn check_state_part_in_manifest(
+    manifest: &StatePartsManifest,
+    part_id: PartId,
+    data: &[u8],
+) -> Result<(), String> {
+    let entry = manifest
+        .part(part_id.idx)
+        .ok_or_else(|| format!("State part is missing from the manifest. part_id={part_id:?}"))?;
+    if entry.size != data.len() as u64 || entry.hash != CryptoHash::hash_bytes(data) {
+        return Err(format!(
+            "State part doesn't match the manifest. part_id={part_id:?}, size={}, expected_size={}",
+            data.len(),
+            entry.size
+        ));
+    }
+    Ok(())
+}
+
 /// Asynchronously requests a state part from a suitable peer.
 fn request_part_from_peers(
     part_id: u64,
@@ -1263,12 +1473,12 @@ mod test {
     use unc_actix_test_utils::run_actix;
     use unc_chain::test_utils;
     use unc_chain::{test_utils::process_block_sync, BlockProcessingArtifact, Provenance};
-    use unc_crypto::SecretKey;
+    use unc_crypto::{KeyType, SecretKey};
     use unc_epoch_manager::EpochManagerAdapter;
     use unc_network::test_utils::MockPeerManagerAdapter;
     use unc_network::types::PeerInfo;
     use unc_primitives::state_sync::{
-        CachedParts, ShardStateSyncResponseHeader, ShardStateSyncResponseV3,
+        CachedParts, ShardStateSyncResponseHeader, ShardStateSyncResponseV3, StatePartManifestEntry,
     };
     use unc_primitives::{test_utils::TestBlockBuilder, types::EpochId};
 
@@ -1403,4 +1613,36 @@ mod test {
             System::current().stop()
         });
     }
+
+    #[test]
+    fn test_verify_state_parts_manifest() {
+        let epoch_id = EpochId::default();
+        let state_root = CryptoHash::hash_bytes(b"root");
+        let manifest = StatePartsManifest {
+            epoch_id: epoch_id.clone(),
+            epoch_height: 1,
+            shard_id: 0,
+            sync_hash: CryptoHash::hash_bytes(b"sync"),
+            state_root,
+            parts: (0..2)
+                .map(|part_id| StatePartManifestEntry::new(part_id, &[part_id as u8]))
+                .collect(),
+        };
+        let dumper_key = SecretKey::from_seed(KeyType::ED25519, "dumper");
+        let other_key = SecretKey::from_seed(KeyType::ED25519, "other");
+        let signed = SignedStatePartsManifest::new(manifest, &dumper_key);
+        let verify = |signed: &SignedStatePartsManifest, trusted_key: &PublicKey, num_parts| {
+            verify_state_parts_manifest(signed, trusted_key, 0, &epoch_id, num_parts, &state_root)
+        };
+        assert_eq!(verify(&signed, &dumper_key.public_key(), 2), Ok(()));
+        assert!(verify(&signed, &dumper_key.public_key(), 3).is_err());
+        assert!(verify(&signed, &other_key.public_key(), 2).is_err());
+
+        // A valid manifest signed by an untrusted node is rejected.
+        let untrusted = SignedStatePartsManifest::new(signed.manifest.clone(), &other_key);
+        assert!(verify(&untrusted, &dumper_key.public_key(), 2).is_err());
+
+        assert_eq!(check_state_part_in_manifest(&signed.manifest, PartId::new(1, 2), &[1]), Ok(()));
+        assert!(check_state_part_in_manifest(&signed.manifest, PartId::new(1, 2), &[2]).is_err());
+    }
 }
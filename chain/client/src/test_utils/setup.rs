@@ -36,7 +36,7 @@ use unc_chunks::shards_manager_actor::start_shards_manager;
 use unc_chunks::test_utils::SynchronousShardsManagerAdapter;
 use unc_chunks::ShardsManager;
 use unc_crypto::{KeyType, PublicKey};
-use unc_epoch_manager::shard_tracker::ShardTracker;
+use unc_epoch_manager::shard_tracker::{ShardTracker, TrackedConfig};
 use unc_epoch_manager::EpochManagerAdapter;
 use unc_network::shards_manager::ShardsManagerRequestFromNetwork;
 use unc_network::types::{BlockInfo, PeerChainInfo};
@@ -85,7 +85,7 @@ pub fn setup(
     let store = create_test_store();
     let num_validator_seats = vs.all_block_producers().count() as NumSeats;
     let epoch_manager = MockEpochManager::new_with_validators(store.clone(), vs, epoch_length);
-    let shard_tracker = ShardTracker::new(epoch_manager.clone());
+    let shard_tracker = ShardTracker::new(TrackedConfig::AllShards, epoch_manager.clone());
     let runtime = KeyValueRuntime::new_with_no_gc(store.clone(), epoch_manager.as_ref(), archive);
     let chain_genesis = ChainGenesis {
         time: genesis_time,
@@ -210,7 +210,7 @@ pub fn setup_only_view(
     let store = create_test_store();
     let num_validator_seats = vs.all_block_producers().count() as NumSeats;
     let epoch_manager = MockEpochManager::new_with_validators(store.clone(), vs, epoch_length);
-    let shard_tracker = ShardTracker::new_empty(epoch_manager.clone());
+    let shard_tracker = ShardTracker::new(TrackedConfig::AllShards, epoch_manager.clone());
     let runtime = KeyValueRuntime::new_with_no_gc(store, epoch_manager.as_ref(), archive);
     let chain_genesis = ChainGenesis {
         time: genesis_time,
@@ -524,6 +524,7 @@ pub fn setup_mock_all_validators(
                                 connection_established_time: unc_async::time::Instant::now(),
                                 peer_type: PeerType::Outbound,
                                 nonce: 3,
+                                reputation_score: 0.,
                             })
                             .collect();
                         let peers2 = peers
@@ -824,6 +825,7 @@ pub fn setup_mock_all_validators(
                         }
                         NetworkRequests::ForwardTx(_, _)
                         | NetworkRequests::BanPeer { .. }
+                        | NetworkRequests::ReportPeer { .. }
                         | NetworkRequests::TxStatus(_, _, _)
                         | NetworkRequests::SnapshotHostInfo { .. }
                         | NetworkRequests::Challenge(_) => {}
@@ -965,7 +967,7 @@ pub fn setup_client(
     let num_validator_seats = vs.all_block_producers().count() as NumSeats;
     let epoch_manager =
         MockEpochManager::new_with_validators(store.clone(), vs, chain_genesis.epoch_length);
-    let shard_tracker = ShardTracker::new_empty(epoch_manager.clone());
+    let shard_tracker = ShardTracker::new(TrackedConfig::AllShards, epoch_manager.clone());
     let runtime = KeyValueRuntime::new(store, epoch_manager.as_ref());
     setup_client_with_runtime(
         num_validator_seats,
@@ -1049,7 +1051,7 @@ pub fn setup_client_with_synchronous_shards_manager(
     let num_validator_seats = vs.all_block_producers().count() as NumSeats;
     let epoch_manager =
         MockEpochManager::new_with_validators(store.clone(), vs, chain_genesis.epoch_length);
-    let shard_tracker = ShardTracker::new_empty(epoch_manager.clone());
+    let shard_tracker = ShardTracker::new(TrackedConfig::AllShards, epoch_manager.clone());
     let runtime = KeyValueRuntime::new(store, epoch_manager.as_ref());
     let shards_manager_adapter = setup_synchronous_shards_manager(
         account_id.clone(),
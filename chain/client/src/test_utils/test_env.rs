@@ -523,6 +523,8 @@ impl TestEnv {
             | ProcessTxResponse::ValidTx => (),
             ProcessTxResponse::InvalidTx(e) => return Err(e),
             ProcessTxResponse::DoesNotTrackShard => panic!("test setup is buggy"),
+            ProcessTxResponse::PoolQuotaExceeded => panic!("transaction pool quota exceeded"),
+            ProcessTxResponse::ReplacementRejected => panic!("transaction replacement rejected"),
         }
         let max_iters = 100;
         let tip = self.clients[0].chain.head().unwrap();
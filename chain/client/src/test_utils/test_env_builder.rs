@@ -13,7 +13,7 @@ use unc_chain::types::RuntimeAdapter;
 use unc_chain::ChainGenesis;
 use unc_chain_configs::GenesisConfig;
 use unc_chunks::test_utils::MockClientAdapterForShardsManager;
-use unc_epoch_manager::shard_tracker::ShardTracker;
+use unc_epoch_manager::shard_tracker::{ShardTracker, TrackedConfig};
 use unc_epoch_manager::{EpochManager, EpochManagerAdapter, EpochManagerHandle};
 use unc_network::test_utils::MockPeerManagerAdapter;
 use unc_parameters::RuntimeConfigStore;
@@ -346,7 +346,9 @@ impl TestEnvBuilder {
             .as_ref()
             .unwrap()
             .iter()
-            .map(|epoch_manager| ShardTracker::new(epoch_manager.clone().into_adapter()))
+            .map(|epoch_manager| {
+                ShardTracker::new(TrackedConfig::AllShards, epoch_manager.clone().into_adapter())
+            })
             .collect();
         ret.shard_trackers(shard_trackers)
     }
@@ -362,7 +364,9 @@ impl TestEnvBuilder {
             .as_ref()
             .unwrap()
             .iter()
-            .map(|epoch_manager| ShardTracker::new(epoch_manager.clone().into_adapter()))
+            .map(|epoch_manager| {
+                ShardTracker::new(TrackedConfig::AllShards, epoch_manager.clone().into_adapter())
+            })
             .collect();
         ret.shard_trackers(shard_trackers)
     }
@@ -28,12 +28,13 @@ use unc_client_primitives::types::{
     GetBlockProofError, GetBlockProofResponse, GetBlockWithMerkleTree, GetChunkError,
     GetExecutionOutcome, GetExecutionOutcomeError, GetExecutionOutcomesForBlock, GetGasPrice,
     GetGasPriceError, GetMaintenanceWindows, GetMaintenanceWindowsError,
-    GetNextLightClientBlockError, GetProtocolConfig, GetProtocolConfigError, GetProvider,
-    GetProviderError, GetReceipt, GetReceiptError, GetSplitStorageInfo, GetSplitStorageInfoError,
-    GetStateChangesError, GetStateChangesWithCauseInBlock,
-    GetStateChangesWithCauseInBlockForTrackedShards, GetValidatorInfoError, Query, QueryError,
-    TxStatus, TxStatusError,
+    GetNextLightClientBlockError, GetPowerChanges, GetProducerSelection, GetProducerSelectionError,
+    GetProtocolConfig, GetProtocolConfigError, GetProvider, GetProviderError, GetReceipt,
+    GetReceiptError, GetSplitStorageInfo, GetSplitStorageInfoError, GetStateChangesError,
+    GetStateChangesWithCauseInBlock, GetStateChangesWithCauseInBlockForTrackedShards,
+    GetValidatorInfoError, Query, QueryError, TxStatus, TxStatusError,
 };
+use unc_crypto::PublicKey;
 use unc_epoch_manager::shard_tracker::ShardTracker;
 use unc_epoch_manager::EpochManagerAdapter;
 use unc_network::types::{
@@ -42,29 +43,35 @@ use unc_network::types::{
 };
 use unc_o11y::{handler_debug_span, OpenTelemetrySpanExt, WithSpanContext, WithSpanContextExt};
 use unc_performance_metrics_macros::perf;
+use unc_primitives::action::{Action, Rsa2048KeysOperation};
 use unc_primitives::block::{Block, BlockHeader};
 use unc_primitives::epoch_manager::epoch_info::EpochInfo;
 use unc_primitives::hash::CryptoHash;
 use unc_primitives::merkle::{merklize, PartialMerkleTree};
 use unc_primitives::network::AnnounceAccount;
-use unc_primitives::receipt::Receipt;
+use unc_primitives::producer_selection::select_producer;
+use unc_primitives::receipt::{Receipt, ReceiptEnum};
 use unc_primitives::sharding::ShardChunk;
 use unc_primitives::state_sync::{
     ShardStateSyncResponse, ShardStateSyncResponseHeader, ShardStateSyncResponseV3,
 };
 use unc_primitives::static_clock::StaticClock;
 use unc_primitives::transaction::SignedTransaction;
+use unc_primitives::types::validator_power::ValidatorPower;
 use unc_primitives::types::{
-    AccountId, BlockHeight, BlockId, BlockReference, EpochReference, Finality, MaybeBlockId,
-    ShardId, SyncCheckpoint, TransactionOrReceiptId, ValidatorInfoIdentifier,
+    AccountId, BlockHeight, BlockId, BlockReference, EpochReference, Finality, MaybeBlockId, Power,
+    ShardId, StateChangeCause, StateChangeValue, StateChangeWithCause, StateChangesRequest,
+    SyncCheckpoint, TransactionOrReceiptId, ValidatorInfoIdentifier,
 };
 use unc_primitives::views::validator_power_and_pledge_view::ValidatorPowerAndPledgeView;
+use unc_primitives::views::validator_power_view::ValidatorPowerView;
 use unc_primitives::views::{
-    AllMinersView, BlockView, ChunkView, EpochValidatorInfo, ExecutionOutcomeWithIdView,
-    ExecutionStatusView, FinalExecutionOutcomeView, FinalExecutionOutcomeViewEnum,
-    FinalExecutionStatus, GasPriceView, LightClientBlockView, MaintenanceWindowsView, QueryRequest,
-    QueryResponse, ReceiptView, SignedTransactionView, SplitStorageInfoView, StateChangesKindsView,
-    StateChangesView, TxExecutionStatus, TxStatusView,
+    AccountPowerChangeView, AllMinersView, BlockView, ChunkView, EpochValidatorInfo,
+    ExecutionOutcomeWithIdView, ExecutionStatusView, FinalExecutionOutcomeView,
+    FinalExecutionOutcomeViewEnum, FinalExecutionStatus, GasPriceView, LightClientBlockView,
+    MaintenanceWindowsView, PowerChangesView, ProducerSelectionView, QueryRequest, QueryResponse,
+    QueryResponseKind, ReceiptView, SignedTransactionView, SplitStorageInfoView,
+    StateChangesKindsView, StateChangesView, TxExecutionStatus, TxStatusView,
 };
 
 use unc_store::flat::{FlatStorageReadyStatus, FlatStorageStatus};
@@ -309,6 +316,95 @@ impl ViewClientActor {
         Ok(windows)
     }
 
+    /// Returns the power of `account_id` after block `block_hash` has been
+    /// applied, or zero if the account does not exist at that point.
+    fn get_account_power(
+        &mut self,
+        block_hash: &CryptoHash,
+        account_id: &AccountId,
+    ) -> Result<Power, GetStateChangesError> {
+        let query = Query::new(
+            BlockReference::BlockId(BlockId::Hash(*block_hash)),
+            QueryRequest::ViewAccount { account_id: account_id.clone() },
+        );
+        match self.handle_query(query) {
+            Ok(QueryResponse { kind: QueryResponseKind::ViewAccount(account), .. }) => {
+                Ok(account.power)
+            }
+            Ok(response) => Err(GetStateChangesError::Unreachable {
+                error_message: format!("Unexpected query response {:?}", response.kind),
+            }),
+            Err(QueryError::UnknownAccount { .. }) => Ok(0),
+            Err(QueryError::UnavailableShard { requested_shard_id }) => {
+                Err(GetStateChangesError::UnavailableShard { requested_shard_id })
+            }
+            Err(QueryError::GarbageCollectedBlock { block_height, block_hash }) => {
+                Err(GetStateChangesError::GarbageCollectedBlock { block_height, block_hash })
+            }
+            Err(err) => Err(GetStateChangesError::IOError { error_message: err.to_string() }),
+        }
+    }
+
+    /// Makes sure this node has the state needed to report the power changes of
+    /// `account_ids` in the block, so that a shard it does not track or state it
+    /// already collected is not reported as the absence of changes.
+    fn check_power_changes_available(
+        &self,
+        header: &BlockHeader,
+        account_ids: &[AccountId],
+    ) -> Result<(), GetStateChangesError> {
+        if !self.config.archive {
+            let tip = self.chain.head()?;
+            // The power before the changes is read from the state of the previous block.
+            let gc_stop_height = self.runtime.get_gc_stop_height(&tip.last_block_hash);
+            if header.height() <= gc_stop_height {
+                return Err(GetStateChangesError::GarbageCollectedBlock {
+                    block_height: header.height(),
+                    block_hash: *header.hash(),
+                });
+            }
+        }
+        for account_id in account_ids {
+            let shard_id = self
+                .epoch_manager
+                .account_id_to_shard_id(account_id, header.epoch_id())
+                .into_chain_error()?;
+            if !self.shard_tracker.care_about_shard(None, header.prev_hash(), shard_id, true) {
+                return Err(GetStateChangesError::UnavailableShard {
+                    requested_shard_id: shard_id,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the chip key of the `CreateRsa2048Challenge` action or the
+    /// `RegisterRsa2048Keys` key revocation executed by the receipt behind
+    /// `cause`, if there is one.
+    fn get_power_changing_chip(
+        &self,
+        cause: &StateChangeCause,
+    ) -> Result<Option<PublicKey>, GetStateChangesError> {
+        let StateChangeCause::ReceiptProcessing { receipt_hash } = cause else {
+            return Ok(None);
+        };
+        let Some(receipt) = self.chain.chain_store().get_receipt(receipt_hash)? else {
+            return Ok(None);
+        };
+        let ReceiptEnum::Action(action_receipt) = &receipt.receipt else {
+            return Ok(None);
+        };
+        Ok(action_receipt.actions.iter().find_map(|action| match action {
+            Action::CreateRsa2048Challenge(challenge) => Some(challenge.public_key.clone()),
+            Action::RegisterRsa2048Keys(register_key)
+                if register_key.operation() == Some(Rsa2048KeysOperation::DeleteKeys) =>
+            {
+                Some(register_key.public_key.clone())
+            }
+            _ => None,
+        }))
+    }
+
     fn handle_query(&mut self, msg: Query) -> Result<QueryResponse, QueryError> {
         let header = self.get_block_header_by_reference(&msg.block_reference);
         let header = match header {
@@ -329,6 +425,7 @@ impl ViewClientActor {
             QueryRequest::ViewAccessKey { account_id, .. } => account_id,
             QueryRequest::ViewAccessKeyList { account_id, .. } => account_id,
             QueryRequest::ViewChipList { account_id, .. } => account_id,
+            QueryRequest::ViewChips { account_id, .. } => account_id,
             QueryRequest::CallFunction { account_id, .. } => account_id,
             QueryRequest::ViewCode { account_id, .. } => account_id,
         };
@@ -688,9 +785,17 @@ impl Handler<WithSpanContext<GetAllMiners>> for ViewClientActor {
     ) -> Self::Result {
         let (_span, msg) = handler_debug_span!(target: "client", msg);
         tracing::debug!(target: "client", ?msg);
-        let block_hash = msg.0;
-        let all_miners = self.epoch_manager.get_all_miners(&block_hash).into_chain_error()?;
-        Ok(all_miners)
+        match msg {
+            GetAllMiners::BlockReference(block_reference) => {
+                let header = self
+                    .get_block_header_by_reference(&block_reference)?
+                    .ok_or(GetAllMinersError::NotSyncedYet)?;
+                Ok(self.epoch_manager.get_all_miners(header.hash()).into_chain_error()?)
+            }
+            GetAllMiners::EpochId(epoch_id) => {
+                Ok(self.epoch_manager.get_epoch_miners(&epoch_id)?)
+            }
+        }
     }
 }
 
@@ -736,6 +841,102 @@ impl Handler<WithSpanContext<GetBlockWithMerkleTree>> for ViewClientActor {
     }
 }
 
+/// Returns everything needed to replay the VRF driven producer selection made from a block.
+impl Handler<WithSpanContext<GetProducerSelection>> for ViewClientActor {
+    type Result = Result<ProducerSelectionView, GetProducerSelectionError>;
+
+    #[perf]
+    fn handle(
+        &mut self,
+        msg: WithSpanContext<GetProducerSelection>,
+        _: &mut Self::Context,
+    ) -> Self::Result {
+        let (_span, msg) = handler_debug_span!(target: "client", msg);
+        tracing::debug!(target: "client", ?msg);
+        let _timer = metrics::VIEW_CLIENT_MESSAGE_TIME
+            .with_label_values(&["GetProducerSelection"])
+            .start_timer();
+        let block =
+            self.get_block_by_reference(&msg.0)?.ok_or(GetProducerSelectionError::NotSyncedYet)?;
+        let header = block.header();
+        let block_hash = *header.hash();
+        let genesis_height = self.chain.genesis().height();
+        if header.height() == genesis_height {
+            return Err(GetProducerSelectionError::NoSelection {
+                block_hash,
+                error_message: "genesis block has no producer".to_string(),
+            });
+        }
+        let prev_block = self.chain.get_block(header.prev_hash())?;
+        let prev_header = prev_block.header();
+        if prev_header.height() == genesis_height {
+            return Err(GetProducerSelectionError::NoSelection {
+                block_hash,
+                error_message: "genesis block has no VRF output".to_string(),
+            });
+        }
+        let vrf_input = *self.chain.get_previous_header(prev_header)?.random_value();
+        let prev_author = self
+            .epoch_manager
+            .get_block_producer(prev_header.epoch_id(), prev_header.height())
+            .into_chain_error()?;
+        let (prev_author_info, _) = self
+            .epoch_manager
+            .get_validator_by_account_id(
+                prev_header.epoch_id(),
+                prev_header.prev_hash(),
+                &prev_author,
+            )
+            .into_chain_error()?;
+        let author = self
+            .epoch_manager
+            .get_block_producer(header.epoch_id(), header.height())
+            .into_chain_error()?;
+
+        let prev_block_hash = *prev_header.hash();
+        let block_info = self.epoch_manager.get_block_info(&prev_block_hash).into_chain_error()?;
+        let validators: Vec<ValidatorPowerView> = block_info
+            .validators_iter()
+            .map(|validator| {
+                let (account_id, public_key, power, _) = validator.destructure();
+                ValidatorPower::new_v1(account_id, public_key, power).into()
+            })
+            .collect();
+        let selection =
+            select_producer(validators.iter().map(|v| v.power()), block_info.random_value())
+                .map_err(|err| GetProducerSelectionError::NoSelection {
+                    block_hash,
+                    error_message: err.to_string(),
+                })?;
+        let selected = validators[selection.index].account_id().clone();
+        if selected != author {
+            return Err(GetProducerSelectionError::NoSelection {
+                block_hash,
+                error_message: format!(
+                    "block author {} is not the power weighted choice {}",
+                    author, selected
+                ),
+            });
+        }
+
+        Ok(ProducerSelectionView {
+            block_hash,
+            block_height: header.height(),
+            author,
+            prev_block_hash,
+            vrf_public_key: prev_author_info.take_public_key(),
+            vrf_input,
+            vrf_value: *prev_block.vrf_value(),
+            vrf_proof: *prev_block.vrf_proof(),
+            random_value: *block_info.random_value(),
+            selected,
+            validators,
+            total_power: selection.total_power,
+            target: selection.target,
+        })
+    }
+}
+
 impl Handler<WithSpanContext<GetChunk>> for ViewClientActor {
     type Result = Result<ChunkView, GetChunkError>;
 
@@ -930,6 +1131,60 @@ impl Handler<WithSpanContext<GetStateChanges>> for ViewClientActor {
     }
 }
 
+/// Returns the changes of `Account::power` for the requested accounts in a given block.
+impl Handler<WithSpanContext<GetPowerChanges>> for ViewClientActor {
+    type Result = Result<PowerChangesView, GetStateChangesError>;
+
+    #[perf]
+    fn handle(
+        &mut self,
+        msg: WithSpanContext<GetPowerChanges>,
+        _: &mut Self::Context,
+    ) -> Self::Result {
+        let (_span, msg) = handler_debug_span!(target: "client", msg);
+        tracing::debug!(target: "client", ?msg);
+        let _timer =
+            metrics::VIEW_CLIENT_MESSAGE_TIME.with_label_values(&["GetPowerChanges"]).start_timer();
+        let header = self.chain.get_block_header(&msg.block_hash)?;
+        self.check_power_changes_available(&header, &msg.account_ids)?;
+        let prev_block_hash = *header.prev_hash();
+        let state_changes = self.chain.chain_store().get_state_changes(
+            &msg.block_hash,
+            &StateChangesRequest::AccountChanges { account_ids: msg.account_ids },
+        )?;
+
+        // Changes of one account come in the order they were applied, so the
+        // power before a change is the power after the previous one.
+        let mut powers = HashMap::new();
+        let mut power_changes = vec![];
+        for StateChangeWithCause { cause, value } in state_changes {
+            let (account_id, power_after) = match value {
+                StateChangeValue::AccountUpdate { account_id, account } => {
+                    (account_id, account.power())
+                }
+                StateChangeValue::AccountDeletion { account_id } => (account_id, 0),
+                _ => continue,
+            };
+            let power_before = match powers.get(&account_id) {
+                Some(power) => *power,
+                None => self.get_account_power(&prev_block_hash, &account_id)?,
+            };
+            powers.insert(account_id.clone(), power_after);
+            if power_before == power_after {
+                continue;
+            }
+            power_changes.push(AccountPowerChangeView {
+                chip_public_key: self.get_power_changing_chip(&cause)?,
+                account_id,
+                cause: cause.into(),
+                power_before,
+                power_after,
+            });
+        }
+        Ok(power_changes)
+    }
+}
+
 /// Returns a list of changes in a store with causes for a given block.
 impl Handler<WithSpanContext<GetStateChangesWithCauseInBlock>> for ViewClientActor {
     type Result = Result<StateChangesView, GetStateChangesError>;
@@ -185,6 +185,9 @@ pub trait EpochManagerAdapter: Send + Sync {
     /// All Miners for given block hash. Return BlockError if outside of known boundaries.
     fn get_all_miners(&self, block_hash: &CryptoHash) -> Result<AllMinersView, EpochError>;
 
+    /// Miners selected for the given epoch, with their per-role and per-shard assignment.
+    fn get_epoch_miners(&self, epoch_id: &EpochId) -> Result<AllMinersView, EpochError>;
+
     /// Chunk producer for given height for given shard. Return EpochError if outside of known boundaries.
     fn get_chunk_producer(
         &self,
@@ -665,7 +668,12 @@ impl EpochManagerAdapter for EpochManagerHandle {
 
     fn get_all_miners(&self, block_hash: &CryptoHash) -> Result<AllMinersView, EpochError> {
         let epoch_manager = self.read();
-        Ok(epoch_manager.get_all_miners(block_hash)?)
+        epoch_manager.get_all_miners(block_hash)
+    }
+
+    fn get_epoch_miners(&self, epoch_id: &EpochId) -> Result<AllMinersView, EpochError> {
+        let epoch_manager = self.read();
+        epoch_manager.get_epoch_miners(epoch_id)
     }
 
     fn get_chunk_producer(
@@ -1,9 +1,7 @@
 use crate::proposals::proposals_to_block_summary;
 use crate::proposals::proposals_to_epoch_info;
 use crate::types::EpochInfoAggregator;
-use num_bigint::{BigInt, ToBigInt};
 use num_rational::Rational64;
-use num_traits::Zero;
 use primitive_types::U256;
 use std::cmp::Ordering;
 use std::collections::{BTreeMap, HashMap, HashSet};
@@ -12,21 +10,21 @@ use tracing::{debug, warn};
 use types::BlockHeaderInfo;
 use unc_cache::SyncLruCache;
 use unc_chain_configs::GenesisConfig;
+use unc_crypto::PublicKey;
 use unc_primitives::checked_feature;
 use unc_primitives::epoch_manager::block_info::{BlockInfo, BlockInfoV2};
 use unc_primitives::epoch_manager::block_summary::{BlockSummary, BlockSummaryV1};
 use unc_primitives::epoch_manager::epoch_info::{EpochInfo, EpochSummary};
 use unc_primitives::epoch_manager::{
-    AllEpochConfig, AllEpochConfigTestOverrides, EpochConfig, ShardConfig, SlashState,
-    AGGREGATOR_KEY,
+    AllEpochConfig, AllEpochConfigTestOverrides, EpochConfig, RewardWeights, ShardConfig,
+    SlashState, AGGREGATOR_KEY,
 };
 use unc_primitives::errors::{BlockError, EpochError};
 use unc_primitives::hash::CryptoHash;
+use unc_primitives::producer_selection::select_producer;
 use unc_primitives::shard_layout::ShardLayout;
 use unc_primitives::types::validator_power::ValidatorPower;
-use unc_primitives::types::validator_power_and_pledge::{
-    ValidatorPowerAndPledge, ValidatorPowerAndPledgeIter,
-};
+use unc_primitives::types::validator_power_and_pledge::ValidatorPowerAndPledge;
 use unc_primitives::types::validator_stake::ValidatorPledge;
 use unc_primitives::types::{
     AccountId, ApprovalPledge, Balance, BlockChunkValidatorStats, BlockHeight, EpochId,
@@ -37,7 +35,7 @@ use unc_primitives::validator_mandates::AssignmentWeight;
 use unc_primitives::version::{ProtocolVersion, UPGRADABILITY_FIX_PROTOCOL_VERSION};
 use unc_primitives::views::{
     AllMinersView, CurrentEpochValidatorInfo, EpochValidatorInfo, NextEpochValidatorInfo,
-    ValidatorKickoutView,
+    ValidatorKickoutView, ValidatorRewardView,
 };
 use unc_store::{DBCol, Store, StoreUpdate};
 
@@ -155,6 +153,23 @@ impl EpochInfoProvider for EpochManagerHandle {
         let epoch_manager = self.read();
         epoch_manager.minimum_pledge(prev_block_hash)
     }
+
+    fn validator_public_key(
+        &self,
+        epoch_id: &EpochId,
+        last_block_hash: &CryptoHash,
+        account_id: &AccountId,
+    ) -> Result<Option<PublicKey>, EpochError> {
+        let epoch_manager = self.read();
+        let last_block_info = epoch_manager.get_block_info(last_block_hash)?;
+        if last_block_info.slashed().contains_key(account_id) {
+            return Ok(None);
+        }
+        let epoch_info = epoch_manager.get_epoch_info(epoch_id)?;
+        Ok(epoch_info
+            .get_validator_id(account_id)
+            .map(|id| epoch_info.get_validator(*id).public_key().clone()))
+    }
 }
 
 /// Tracks epoch information across different forks, such as validators.
@@ -321,6 +336,7 @@ impl EpochManager {
                 pledge_validators.clone(),
                 HashMap::default(),
                 validator_reward.clone(),
+                RewardWeights::default(),
                 0,
                 genesis_protocol_version,
                 genesis_protocol_version,
@@ -776,6 +792,8 @@ impl EpochManager {
     ) -> Result<BlockSummary, BlockError> {
         let validator_stake =
             block_info.validators_iter().map(|r| r.account_and_pledge()).collect::<HashMap<_, _>>();
+        let validator_power =
+            block_info.validators_iter().map(|r| r.account_and_power()).collect::<HashMap<_, _>>();
 
         let (all_power_proposals, all_pledge_proposals, validator_kickout) = match block_info {
             // Assuming last_block_summary is wrapped in an Arc
@@ -822,6 +840,7 @@ impl EpochManager {
             self.reward_calculator.calculate_reward(
                 validator_block_chunk_stats,
                 &validator_stake,
+                &validator_power,
                 *block_info.total_supply(),
                 0u32,
                 self.genesis_protocol_version,
@@ -872,6 +891,8 @@ impl EpochManager {
         let epoch_protocol_version = epoch_info.protocol_version();
         let validator_stake =
             epoch_info.validators_iter().map(|r| r.account_and_pledge()).collect::<HashMap<_, _>>();
+        let validator_power =
+            epoch_info.validators_iter().map(|r| r.account_and_power()).collect::<HashMap<_, _>>();
         let next_epoch_id = self.get_next_epoch_id_from_info(block_info)?;
         let next_epoch_info = self.get_epoch_info(&next_epoch_id)?;
         self.save_epoch_validator_info(store_update, block_info.epoch_id(), &epoch_summary)?;
@@ -911,6 +932,11 @@ impl EpochManager {
             }
         };
         // end james savechives
+        let reward_weights = self.reward_calculator.reward_weights(
+            validator_stake.values().sum(),
+            validator_power.values().sum(),
+            epoch_protocol_version,
+        );
         let (validator_reward, minted_amount) = {
             let last_epoch_last_block_hash =
                 *self.get_block_info(block_info.epoch_first_block())?.prev_hash();
@@ -921,6 +947,7 @@ impl EpochManager {
             self.reward_calculator.calculate_reward(
                 validator_block_chunk_stats,
                 &validator_stake,
+                &validator_power,
                 *block_info.total_supply(),
                 epoch_protocol_version,
                 self.genesis_protocol_version,
@@ -936,6 +963,7 @@ impl EpochManager {
             all_pledge_proposals,
             validator_kickout,
             validator_reward,
+            reward_weights,
             minted_amount,
             next_version,
             epoch_protocol_version,
@@ -1085,15 +1113,20 @@ impl EpochManager {
     }
 
     /// Given block hash, return all the miners
-    pub fn get_all_miners(
-        &self,
-        block_hash: &CryptoHash,
-        // height: BlockHeight,
-    ) -> Result<AllMinersView, BlockError> {
+    pub fn get_all_miners(&self, block_hash: &CryptoHash) -> Result<AllMinersView, EpochError> {
         let block_info = self.get_block_info(block_hash)?;
-        let validators = block_info.validators_iter();
-        let all_miners_view: AllMinersView = validators.into();
-        Ok(all_miners_view)
+        let epoch_info = self.get_epoch_info(block_info.epoch_id())?;
+        Ok(AllMinersView::new(
+            block_info.validators_iter(),
+            block_info.epoch_id().clone(),
+            &epoch_info,
+        ))
+    }
+
+    /// Returns the miners selected for `epoch_id`.
+    pub fn get_epoch_miners(&self, epoch_id: &EpochId) -> Result<AllMinersView, EpochError> {
+        let epoch_info = self.get_epoch_info(epoch_id)?;
+        Ok(AllMinersView::new(epoch_info.validators_iter(), epoch_id.clone(), &epoch_info))
     }
 
     /// Given epoch id and height, returns validator information that suppose to produce
@@ -1119,49 +1152,15 @@ impl EpochManager {
         // if current_height +1 != height {
         //     return Err(BlockError::BlockOutOfBounds(*block_hash));
         // }
-        let random_value = block_info.random_value();
-        let validators = block_info.validators_iter();
-        Self::choose_validator_vrf(validators, Self::hash_to_bigint(random_value))
-    }
-
-    fn hash_to_bigint(hash: &CryptoHash) -> BigInt {
-        BigInt::from_bytes_be(num_bigint::Sign::Plus, hash.as_ref())
-    }
-
-    fn choose_validator_vrf(
-        validators_iter: ValidatorPowerAndPledgeIter,
-        random_value: BigInt,
-    ) -> Result<ValidatorPowerAndPledge, BlockError> {
-        let mut total_weight: BigInt = Zero::zero();
-        for validator in validators_iter.clone() {
-            let validator_power = match validator {
-                ValidatorPowerAndPledge::V1(v) => v.power.to_bigint().unwrap_or_else(Zero::zero),
-            };
-            total_weight += validator_power;
-        }
-
-        if total_weight.is_zero() {
-            return Err(BlockError::ValidatorTotalPowerError(String::from("Total Power is zero")));
-        }
-
-        let mut cumulative_weight = Zero::zero();
-        let target = random_value % &total_weight;
-
-        for validator in validators_iter {
-            let validator_power = match validator {
-                ValidatorPowerAndPledge::V1(ref v) => {
-                    v.power.to_bigint().unwrap_or_else(Zero::zero)
-                }
-            };
-            cumulative_weight += &validator_power;
-            if target < cumulative_weight {
-                return Ok(validator.clone());
-            }
-        }
-
-        return Err(BlockError::NoAvailableValidator(String::from(
-            "Block Producer is not available",
-        )));
+        let mut validators = block_info.validators_iter();
+        let selection =
+            select_producer(validators.clone().map(|v| v.power()), block_info.random_value())
+                .map_err(|_| {
+                    BlockError::ValidatorTotalPowerError(String::from("Total Power is zero"))
+                })?;
+        validators.nth(selection.index).ok_or_else(|| {
+            BlockError::NoAvailableValidator(String::from("Block Producer is not available"))
+        })
     }
 
     /// Returns settlement of all block producers in current epoch, with indicator on whether they are slashed or not.
@@ -1835,6 +1834,14 @@ impl EpochManager {
             .into_iter()
             .map(|(account_id, reason)| ValidatorKickoutView { account_id, reason })
             .collect();
+        let prev_epoch_reward = next_epoch_info
+            .validator_reward()
+            .clone()
+            .into_iter()
+            .collect::<BTreeMap<_, _>>()
+            .into_iter()
+            .map(|(account_id, reward)| ValidatorRewardView { account_id, reward })
+            .collect();
 
         Ok(EpochValidatorInfo {
             current_validators,
@@ -1844,6 +1851,8 @@ impl EpochManager {
             current_power_proposals: all_power_proposals,
             current_pledge_proposals: all_pledge_proposals,
             prev_epoch_kickout,
+            prev_epoch_reward,
+            prev_epoch_reward_weights: next_epoch_info.reward_weights(),
             epoch_start_height,
             epoch_height,
         })
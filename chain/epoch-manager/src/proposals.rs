@@ -4,7 +4,7 @@ use unc_primitives::checked_feature;
 use unc_primitives::epoch_manager::block_info::BlockInfo;
 use unc_primitives::epoch_manager::block_summary::BlockSummary;
 use unc_primitives::epoch_manager::epoch_info::EpochInfo;
-use unc_primitives::epoch_manager::{EpochConfig, RngSeed};
+use unc_primitives::epoch_manager::{EpochConfig, RewardWeights, RngSeed};
 use unc_primitives::errors::{BlockError, EpochError};
 use unc_primitives::hash::CryptoHash;
 use unc_primitives::types::validator_power::ValidatorPower;
@@ -76,6 +76,7 @@ pub fn proposals_to_epoch_info(
     pledge_proposals: Vec<ValidatorPledge>,
     validator_kickout: HashMap<AccountId, ValidatorKickoutReason>,
     validator_reward: HashMap<AccountId, Balance>,
+    reward_weights: RewardWeights,
     minted_amount: Balance,
     next_version: ProtocolVersion,
     last_epoch_version: ProtocolVersion,
@@ -89,6 +90,7 @@ pub fn proposals_to_epoch_info(
             pledge_proposals,
             validator_kickout,
             validator_reward,
+            reward_weights,
             minted_amount,
             next_version,
             last_epoch_version,
@@ -102,6 +104,7 @@ pub fn proposals_to_epoch_info(
             pledge_proposals,
             validator_kickout,
             validator_reward,
+            reward_weights,
             minted_amount,
             next_version,
         );
@@ -115,7 +118,7 @@ mod old_validator_selection {
     use rand::{RngCore, SeedableRng};
     use rand_hc::Hc128Rng;
     use unc_primitives::epoch_manager::epoch_info::EpochInfo;
-    use unc_primitives::epoch_manager::EpochConfig;
+    use unc_primitives::epoch_manager::{EpochConfig, RewardWeights};
     use unc_primitives::errors::EpochError;
     use unc_primitives::types::validator_power::ValidatorPower;
     use unc_primitives::types::validator_power_and_pledge::ValidatorPowerAndPledge;
@@ -138,6 +141,7 @@ mod old_validator_selection {
         pledge_proposals: Vec<ValidatorPledge>,
         mut validator_kickout: HashMap<AccountId, ValidatorKickoutReason>,
         validator_reward: HashMap<AccountId, Balance>,
+        reward_weights: RewardWeights,
         minted_amount: Balance,
         next_version: ProtocolVersion,
     ) -> Result<EpochInfo, EpochError> {
@@ -337,6 +341,7 @@ mod old_validator_selection {
             power_change,
             pledge_change,
             validator_reward,
+            reward_weights,
             validator_kickout,
             minted_amount,
             threshold,
@@ -5,7 +5,8 @@ use primitive_types::U256;
 
 use unc_chain_configs::GenesisConfig;
 use unc_primitives::checked_feature;
-use unc_primitives::types::{AccountId, Balance, BlockChunkValidatorStats};
+use unc_primitives::epoch_manager::RewardWeights;
+use unc_primitives::types::{AccountId, Balance, BlockChunkValidatorStats, Power};
 use unc_primitives::version::{ProtocolVersion, ENABLE_INFLATION_PROTOCOL_VERSION};
 
 pub(crate) const NUM_NS_IN_SECOND: u64 = 1_000_000_000;
@@ -21,6 +22,7 @@ pub struct RewardCalculator {
     pub online_min_threshold: Rational32,
     pub online_max_threshold: Rational32,
     pub num_seconds_per_year: u64,
+    pub power_reward_rate: Rational32,
 }
 
 impl RewardCalculator {
@@ -34,14 +36,46 @@ impl RewardCalculator {
             online_max_threshold: config.online_max_threshold,
             online_min_threshold: config.online_min_threshold,
             num_seconds_per_year: NUM_SECONDS_IN_A_YEAR,
+            power_reward_rate: config.power_reward_rate,
         }
     }
+    /// Weights the validator reward of an epoch is split with.
+    /// Without any power the whole reward goes by pledge, and vice versa.
+    pub fn reward_weights(
+        &self,
+        total_pledge: Balance,
+        total_power: Power,
+        protocol_version: ProtocolVersion,
+    ) -> RewardWeights {
+        if !checked_feature!("stable", PowerWeightedReward, protocol_version) || total_power == 0 {
+            RewardWeights::default()
+        } else if total_pledge == 0 {
+            RewardWeights { pledge: 0, power: 1 }
+        } else {
+            // Genesis validation keeps `power_reward_rate` within [0, 1]. A rate
+            // below it splits by pledge alone and a rate above it by power alone.
+            let rate = self.power_reward_rate.reduced();
+            let Ok(power) = u64::try_from(*rate.numer()) else {
+                return RewardWeights::default();
+            };
+            let denom = u64::try_from(*rate.denom()).unwrap_or(1);
+            match denom.checked_sub(power) {
+                // Both come from a non-negative `i32`, so they fit into `u32`.
+                Some(pledge) => RewardWeights { pledge: pledge as u32, power: power as u32 },
+                None => RewardWeights { pledge: 0, power: 1 },
+            }
+        }
+    }
+
     /// Calculate validator reward for an epoch based on their block and chunk production stats.
     /// Returns map of validators with their rewards and amount of newly minted tokens including to protocol's treasury.
+    /// With `PowerWeightedReward`, `power_reward_rate` of each validator's reward is split by
+    /// power and the rest by pledge.
     pub fn calculate_reward(
         &self,
         validator_block_chunk_stats: HashMap<AccountId, BlockChunkValidatorStats>,
         validator_stake: &HashMap<AccountId, Balance>,
+        validator_power: &HashMap<AccountId, Power>,
         total_supply: Balance,
         protocol_version: ProtocolVersion,
         genesis_protocol_version: ProtocolVersion,
@@ -86,6 +120,9 @@ impl RewardCalculator {
         let epoch_validator_reward = epoch_total_reward - epoch_protocol_treasury;
         let mut epoch_actual_reward = epoch_protocol_treasury;
         let total_pledge: Balance = validator_stake.values().sum();
+        let total_power: Power = validator_power.values().sum();
+        let reward_weights = checked_feature!("stable", PowerWeightedReward, protocol_version)
+            .then(|| self.reward_weights(total_pledge, total_power, protocol_version));
         for (account_id, stats) in validator_block_chunk_stats {
             // Uptime is an average of block produced / expected and chunk produced / expected.
             let (average_produced_numer, average_produced_denom) =
@@ -137,10 +174,33 @@ impl RewardCalculator {
                 // Apply min between 1. and computed uptime.
                 uptime_numer =
                     if uptime_numer > uptime_denum { uptime_denum } else { uptime_numer };
-                (U256::from(epoch_validator_reward) * uptime_numer * U256::from(pledge)
-                    / uptime_denum
-                    / U256::from(total_pledge))
-                .as_u128()
+                match reward_weights {
+                    None => {
+                        (U256::from(epoch_validator_reward) * uptime_numer * U256::from(pledge)
+                            / uptime_denum
+                            / U256::from(total_pledge))
+                        .as_u128()
+                    }
+                    Some(reward_weights) => {
+                        let power = validator_power.get(&account_id).copied().unwrap_or_default();
+                        let share = |amount: U256, total: U256| {
+                            if total.is_zero() {
+                                U256::zero()
+                            } else {
+                                U256::from(epoch_validator_reward) * uptime_numer * amount
+                                    / uptime_denum
+                                    / total
+                            }
+                        };
+                        let pledge_share = share(U256::from(pledge), U256::from(total_pledge));
+                        let power_share = share(U256::from(power), U256::from(total_power));
+                        let pledge_weight = U256::from(reward_weights.pledge);
+                        let power_weight = U256::from(reward_weights.power);
+                        ((pledge_share * pledge_weight + power_share * power_weight)
+                            / (pledge_weight + power_weight))
+                            .as_u128()
+                    }
+                }
             };
             res.insert(account_id, reward);
             epoch_actual_reward += reward;
@@ -152,10 +212,11 @@ impl RewardCalculator {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_utils::default_reward_calculator;
     use num_rational::Ratio;
     use std::collections::HashMap;
     use unc_primitives::types::{BlockChunkValidatorStats, ValidatorStats};
-    use unc_primitives::version::PROTOCOL_VERSION;
+    use unc_primitives::version::{ProtocolFeature, PROTOCOL_VERSION};
 
     #[test]
     fn test_zero_produced_and_expected() {
@@ -169,6 +230,7 @@ mod tests {
             online_min_threshold: Ratio::new(9, 10),
             online_max_threshold: Ratio::new(1, 1),
             num_seconds_per_year: 1000000,
+            ..default_reward_calculator()
         };
         let validator_block_chunk_stats = HashMap::from([
             (
@@ -192,6 +254,7 @@ mod tests {
         let result = reward_calculator.calculate_reward(
             validator_block_chunk_stats,
             &validator_stake,
+            &HashMap::new(),
             total_supply,
             PROTOCOL_VERSION,
             PROTOCOL_VERSION,
@@ -220,6 +283,7 @@ mod tests {
             online_min_threshold: Ratio::new(9, 10),
             online_max_threshold: Ratio::new(99, 100),
             num_seconds_per_year: 1000,
+            ..default_reward_calculator()
         };
         let validator_block_chunk_stats = HashMap::from([
             (
@@ -253,6 +317,7 @@ mod tests {
         let result = reward_calculator.calculate_reward(
             validator_block_chunk_stats,
             &validator_stake,
+            &HashMap::new(),
             total_supply,
             PROTOCOL_VERSION,
             PROTOCOL_VERSION,
@@ -272,6 +337,100 @@ mod tests {
         assert_eq!(result.1, 4_999_999u128);
     }
 
+    /// Test that with `PowerWeightedReward` the reward is split by both pledge and power.
+    #[test]
+    fn test_reward_power_weighted() {
+        let epoch_length = 1000;
+        let reward_calculator = RewardCalculator {
+            max_inflation_rate: Ratio::new(1, 100),
+            num_blocks_per_year: 1000,
+            epoch_length,
+            protocol_reward_rate: Ratio::new(0, 10),
+            protocol_treasury_account: "unc".parse().unwrap(),
+            online_min_threshold: Ratio::new(9, 10),
+            online_max_threshold: Ratio::new(99, 100),
+            num_seconds_per_year: 1000,
+            power_reward_rate: Ratio::new(1, 2),
+        };
+        let validator_stake = HashMap::from([
+            ("test1".parse().unwrap(), 750_000),
+            ("test2".parse().unwrap(), 250_000),
+        ]);
+        let validator_power =
+            HashMap::from([("test1".parse().unwrap(), 0), ("test2".parse().unwrap(), 100)]);
+        let total_supply = 1_000_000_000;
+        let calculate_reward = |protocol_version| {
+            let stats = || BlockChunkValidatorStats {
+                block_stats: ValidatorStats { produced: 1000, expected: 1000 },
+                chunk_stats: ValidatorStats { produced: 1000, expected: 1000 },
+            };
+            let validator_block_chunk_stats = HashMap::from([
+                ("test1".parse().unwrap(), stats()),
+                ("test2".parse().unwrap(), stats()),
+            ]);
+            reward_calculator.calculate_reward(
+                validator_block_chunk_stats,
+                &validator_stake,
+                &validator_power,
+                total_supply,
+                protocol_version,
+                protocol_version,
+                epoch_length * NUM_NS_IN_SECOND,
+            )
+        };
+
+        // Total reward is 10_000_000. Before the feature it is split by pledge only.
+        let result = calculate_reward(ProtocolFeature::PowerWeightedReward.protocol_version() - 1);
+        assert_eq!(
+            result.0,
+            HashMap::from([
+                ("unc".parse().unwrap(), 0),
+                ("test1".parse().unwrap(), 7_500_000u128),
+                ("test2".parse().unwrap(), 2_500_000u128),
+            ])
+        );
+        assert_eq!(result.1, 10_000_000u128);
+
+        // Half of the reward is split by pledge (7_500_000 : 2_500_000) and the other half by
+        // power (0 : 10_000_000).
+        let result = calculate_reward(ProtocolFeature::PowerWeightedReward.protocol_version());
+        assert_eq!(
+            result.0,
+            HashMap::from([
+                ("unc".parse().unwrap(), 0),
+                ("test1".parse().unwrap(), 3_750_000u128),
+                ("test2".parse().unwrap(), 6_250_000u128),
+            ])
+        );
+        assert_eq!(result.1, 10_000_000u128);
+        assert_eq!(
+            reward_calculator.reward_weights(
+                1_000_000,
+                100,
+                ProtocolFeature::PowerWeightedReward.protocol_version()
+            ),
+            RewardWeights { pledge: 1, power: 1 }
+        );
+        assert_eq!(
+            reward_calculator.reward_weights(
+                1_000_000,
+                0,
+                ProtocolFeature::PowerWeightedReward.protocol_version()
+            ),
+            RewardWeights::default()
+        );
+        let reward_calculator =
+            RewardCalculator { power_reward_rate: Ratio::new(3, 2), ..reward_calculator };
+        assert_eq!(
+            reward_calculator.reward_weights(
+                1_000_000,
+                100,
+                ProtocolFeature::PowerWeightedReward.protocol_version()
+            ),
+            RewardWeights { pledge: 0, power: 1 }
+        );
+    }
+
     /// Test reward calculation for chunk only or block only producers
     #[test]
     fn test_reward_chunk_only_producer() {
@@ -285,6 +444,7 @@ mod tests {
             online_min_threshold: Ratio::new(9, 10),
             online_max_threshold: Ratio::new(99, 100),
             num_seconds_per_year: 1000,
+            ..default_reward_calculator()
         };
         let validator_block_chunk_stats = HashMap::from([
             (
@@ -330,6 +490,7 @@ mod tests {
         let result = reward_calculator.calculate_reward(
             validator_block_chunk_stats,
             &validator_stake,
+            &HashMap::new(),
             total_supply,
             PROTOCOL_VERSION,
             PROTOCOL_VERSION,
@@ -367,6 +528,7 @@ mod tests {
             online_min_threshold: Ratio::new(9, 10),
             online_max_threshold: Ratio::new(1, 1),
             num_seconds_per_year: 60 * 60 * 24 * 365,
+            ..default_reward_calculator()
         };
         let validator_block_chunk_stats = HashMap::from([(
             "test".parse().unwrap(),
@@ -381,6 +543,7 @@ mod tests {
         reward_calculator.calculate_reward(
             validator_block_chunk_stats,
             &validator_stake,
+            &HashMap::new(),
             total_supply,
             PROTOCOL_VERSION,
             PROTOCOL_VERSION,
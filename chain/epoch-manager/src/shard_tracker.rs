@@ -1,22 +1,88 @@
 use std::sync::Arc;
 
 use crate::EpochManagerAdapter;
+use unc_cache::SyncLruCache;
+pub use unc_chain_configs::TrackedConfig;
+use unc_primitives::errors::EpochError;
 use unc_primitives::hash::CryptoHash;
-use unc_primitives::types::{AccountId, ShardId};
+use unc_primitives::shard_layout::account_id_to_shard_id;
+use unc_primitives::types::{AccountId, EpochId, ShardId};
 
-/// TrackedConfig::AllShards: track all shards
+/// Number of epochs for which the shards of the tracked accounts are cached.
+const TRACKING_SHARDS_CACHE_SIZE: usize = 1024;
+
+/// Decides which shards the node tracks in addition to the shards of its
+/// validator duties, see `TrackedConfig`.
 #[derive(Clone)]
 pub struct ShardTracker {
+    tracked_config: TrackedConfig,
+    /// Stores shard tracking information by epoch, only useful if TrackedConfig is Accounts.
+    tracking_shards_cache: Arc<SyncLruCache<EpochId, Vec<bool>>>,
     epoch_manager: Arc<dyn EpochManagerAdapter>,
 }
 
 impl ShardTracker {
-    pub fn new(epoch_manager: Arc<dyn EpochManagerAdapter>) -> Self {
-        ShardTracker { epoch_manager }
+    pub fn new(tracked_config: TrackedConfig, epoch_manager: Arc<dyn EpochManagerAdapter>) -> Self {
+        ShardTracker {
+            tracked_config,
+            tracking_shards_cache: Arc::new(SyncLruCache::new(TRACKING_SHARDS_CACHE_SIZE)),
+            epoch_manager,
+        }
     }
 
-    pub fn new_empty(epoch_manager: Arc<dyn EpochManagerAdapter>) -> Self {
-        Self::new(epoch_manager)
+    /// Whether the node tracks all shards regardless of the epoch.
+    pub fn tracks_all_shards(&self) -> bool {
+        matches!(self.tracked_config, TrackedConfig::AllShards)
+    }
+
+    fn tracks_shard_at_epoch(
+        &self,
+        shard_id: ShardId,
+        epoch_id: &EpochId,
+    ) -> Result<bool, EpochError> {
+        match &self.tracked_config {
+            TrackedConfig::AllShards => Ok(true),
+            TrackedConfig::Shards(shards) => Ok(shards.contains(&shard_id)),
+            TrackedConfig::Accounts(tracked_accounts) => {
+                let shard_layout = self.epoch_manager.get_shard_layout(epoch_id)?;
+                let tracking_mask = self.tracking_shards_cache.get_or_put(epoch_id.clone(), |_| {
+                    let mut tracking_mask: Vec<_> =
+                        shard_layout.shard_ids().map(|_| false).collect();
+                    for account_id in tracked_accounts {
+                        let shard_id = account_id_to_shard_id(account_id, &shard_layout);
+                        tracking_mask[shard_id as usize] = true;
+                    }
+                    tracking_mask
+                });
+                Ok(tracking_mask.get(shard_id as usize).copied().unwrap_or(false))
+            }
+            TrackedConfig::Schedule(schedule) => {
+                assert_ne!(schedule.len(), 0);
+                let epoch_height = self.epoch_manager.get_epoch_info(epoch_id)?.epoch_height();
+                let index = epoch_height % schedule.len() as u64;
+                Ok(schedule[index as usize].contains(&shard_id))
+            }
+        }
+    }
+
+    fn tracks_shard(&self, shard_id: ShardId, prev_hash: &CryptoHash) -> Result<bool, EpochError> {
+        if self.tracks_all_shards() {
+            return Ok(true);
+        }
+        let epoch_id = self.epoch_manager.get_epoch_id_from_prev_block(prev_hash)?;
+        self.tracks_shard_at_epoch(shard_id, &epoch_id)
+    }
+
+    fn will_track_shard(
+        &self,
+        shard_id: ShardId,
+        prev_hash: &CryptoHash,
+    ) -> Result<bool, EpochError> {
+        if self.tracks_all_shards() {
+            return Ok(true);
+        }
+        let next_epoch_id = self.epoch_manager.get_next_epoch_id_from_prev_block(prev_hash)?;
+        self.tracks_shard_at_epoch(shard_id, &next_epoch_id)
     }
 
     /// Whether the client cares about some shard right now.
@@ -51,7 +117,7 @@ impl ShardTracker {
                 // We have access to the node config. Use the config to find a definite answer.
             }
         }
-        true
+        self.tracks_shard(shard_id, parent_hash).unwrap_or(false)
     }
 
     /// Whether the client cares about some shard in the next epoch.
@@ -88,27 +154,27 @@ impl ShardTracker {
                 // We have access to the node config. Use the config to find a definite answer.
             }
         }
-
-        true
+        self.will_track_shard(shard_id, parent_hash).unwrap_or(false)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::ShardTracker;
-    use crate::test_utils::hash_range;
+    use super::{ShardTracker, TrackedConfig};
+    use crate::test_utils::{default_reward_calculator, hash_range};
     use crate::{EpochManager, EpochManagerAdapter, EpochManagerHandle, RewardCalculator};
     use num_rational::Ratio;
-    use std::collections::HashSet;
+    use std::collections::{BTreeMap, HashMap, HashSet};
     use std::sync::Arc;
     use unc_crypto::{KeyType, PublicKey};
     use unc_primitives::epoch_manager::block_info::BlockInfo;
     use unc_primitives::epoch_manager::{AllEpochConfig, EpochConfig};
     use unc_primitives::hash::CryptoHash;
-    use unc_primitives::shard_layout::ShardLayout;
+    use unc_primitives::shard_layout::{account_id_to_shard_id, ShardLayout};
     use unc_primitives::types::validator_power::ValidatorPower;
     use unc_primitives::types::validator_stake::ValidatorPledge;
     use unc_primitives::types::{BlockHeight, EpochId, NumShards, ProtocolVersion, ShardId};
+    use unc_primitives::validator_mandates::ValidatorMandates;
     use unc_primitives::version::ProtocolFeature::SimpleNightshade;
     use unc_primitives::version::PROTOCOL_VERSION;
     use unc_store::test_utils::create_test_store;
@@ -146,6 +212,7 @@ mod tests {
             online_max_threshold: initial_epoch_config.online_max_threshold,
             online_min_threshold: initial_epoch_config.online_min_threshold,
             num_seconds_per_year: 1000000,
+            ..default_reward_calculator()
         };
         EpochManager::new(
             store,
@@ -157,6 +224,11 @@ mod tests {
                 PublicKey::empty(KeyType::ED25519),
                 100,
             )],
+            vec![ValidatorPledge::new(
+                "test".parse().unwrap(),
+                PublicKey::empty(KeyType::ED25519),
+                100,
+            )],
         )
         .unwrap()
         .into_handle()
@@ -179,13 +251,29 @@ mod tests {
                     0,
                     prev_h,
                     prev_h,
-                    power_proposals,
-                    pledge_proposals,
+                    power_proposals.clone(),
+                    pledge_proposals.clone(),
                     vec![],
                     vec![],
                     DEFAULT_TOTAL_SUPPLY,
                     protocol_version,
                     height * 10u64.pow(9),
+                    CryptoHash::default(),
+                    vec![],
+                    HashMap::default(),
+                    vec![],
+                    vec![],
+                    vec![],
+                    HashMap::default(),
+                    BTreeMap::default(),
+                    BTreeMap::default(),
+                    HashMap::default(),
+                    0,
+                    0,
+                    power_proposals,
+                    pledge_proposals,
+                    HashMap::default(),
+                    ValidatorMandates::default(),
                 ),
                 [0; 32],
             )
@@ -261,6 +349,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_track_shards() {
+        let shard_ids: Vec<_> = (0..4).collect();
+        let epoch_manager =
+            get_epoch_manager(PROTOCOL_VERSION, shard_ids.len() as NumShards, false);
+        let tracker = ShardTracker::new(TrackedConfig::Shards(vec![1, 3]), Arc::new(epoch_manager));
+        let total_tracked_shards = HashSet::from([1, 3]);
+
+        assert_eq!(
+            get_all_shards_care_about(&tracker, &shard_ids, &CryptoHash::default()),
+            total_tracked_shards
+        );
+        assert_eq!(
+            get_all_shards_will_care_about(&tracker, &shard_ids, &CryptoHash::default()),
+            total_tracked_shards
+        );
+    }
+
+    #[test]
+    fn test_track_no_shards() {
+        let shard_ids: Vec<_> = (0..4).collect();
+        let epoch_manager =
+            get_epoch_manager(PROTOCOL_VERSION, shard_ids.len() as NumShards, false);
+        let tracker = ShardTracker::new(TrackedConfig::Shards(vec![]), Arc::new(epoch_manager));
+
+        assert!(get_all_shards_care_about(&tracker, &shard_ids, &CryptoHash::default()).is_empty());
+        assert!(
+            get_all_shards_will_care_about(&tracker, &shard_ids, &CryptoHash::default()).is_empty()
+        );
+    }
+
     #[test]
     fn test_track_schedule() {
         // Creates a ShardTracker that changes every epoch tracked shards.
@@ -289,6 +408,7 @@ mod tests {
                     h[i],
                     i as u64,
                     vec![],
+                    vec![],
                     PROTOCOL_VERSION,
                 );
             }
@@ -325,6 +445,7 @@ mod tests {
                 h[0],
                 0,
                 vec![],
+                vec![],
                 simple_nightshade_version,
             );
             for i in 1..8 {
@@ -334,6 +455,7 @@ mod tests {
                     h[i],
                     i as u64,
                     vec![],
+                    vec![],
                     simple_nightshade_version,
                 );
             }
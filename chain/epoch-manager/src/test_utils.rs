@@ -134,6 +134,7 @@ pub fn epoch_info_with_num_seats(
         power_change,
         pledge_change,
         validator_reward,
+        Default::default(),
         validator_kickout.into_iter().collect(),
         minted_amount,
         seat_price,
@@ -219,6 +220,7 @@ pub fn default_reward_calculator() -> RewardCalculator {
         online_min_threshold: Ratio::new(90, 100),
         online_max_threshold: Ratio::new(99, 100),
         num_seconds_per_year: NUM_SECONDS_IN_A_YEAR,
+        power_reward_rate: Ratio::from_integer(0),
     }
 }
 
@@ -19,6 +19,7 @@ use unc_primitives::types::Power;
 use unc_primitives::types::ValidatorKickoutReason::{NotEnoughBlocks, NotEnoughChunks};
 use unc_primitives::version::ProtocolFeature::SimpleNightshade;
 use unc_primitives::version::PROTOCOL_VERSION;
+use unc_primitives::views::MinerSetView;
 use unc_store::test_utils::create_test_store;
 
 impl EpochManager {
@@ -681,6 +682,7 @@ fn test_validator_reward_one_validator() {
         online_min_threshold: Ratio::new(90, 100),
         online_max_threshold: Ratio::new(99, 100),
         num_seconds_per_year: 50,
+        ..default_reward_calculator()
     };
     let mut epoch_manager = setup_epoch_manager(
         validators,
@@ -736,6 +738,7 @@ fn test_validator_reward_one_validator() {
     let (validator_reward, inflation) = reward_calculator.calculate_reward(
         validator_online_ratio,
         &validator_pledges,
+        &HashMap::new(),
         total_supply,
         PROTOCOL_VERSION,
         PROTOCOL_VERSION,
@@ -781,6 +784,7 @@ fn test_validator_reward_weight_by_pledge() {
         online_min_threshold: Ratio::new(90, 100),
         online_max_threshold: Ratio::new(99, 100),
         num_seconds_per_year: 50,
+        ..default_reward_calculator()
     };
     let mut epoch_manager = setup_epoch_manager(
         validators,
@@ -836,6 +840,7 @@ fn test_validator_reward_weight_by_pledge() {
     let (validator_reward, inflation) = reward_calculator.calculate_reward(
         validator_online_ratio,
         &validators_pledges,
+        &HashMap::new(),
         total_supply,
         PROTOCOL_VERSION,
         PROTOCOL_VERSION,
@@ -887,6 +892,7 @@ fn test_reward_multiple_shards() {
         online_min_threshold: Ratio::new(90, 100),
         online_max_threshold: Ratio::new(99, 100),
         num_seconds_per_year: 1_000_000,
+        ..default_reward_calculator()
     };
     let num_shards = 2;
     let mut epoch_manager = setup_epoch_manager(
@@ -952,6 +958,7 @@ fn test_reward_multiple_shards() {
     let (validator_reward, inflation) = reward_calculator.calculate_reward(
         validator_online_ratio,
         &validators_pledges,
+        &HashMap::new(),
         total_supply,
         PROTOCOL_VERSION,
         PROTOCOL_VERSION,
@@ -2468,6 +2475,48 @@ fn test_chunk_producers() {
     );
 }
 
+#[test]
+fn test_epoch_miners() {
+    let amount_pledged = 1_000_000;
+    let validators = vec![
+        ("test1".parse().unwrap(), amount_pledged),
+        ("test2".parse().unwrap(), amount_pledged),
+        ("chunk_only".parse().unwrap(), 200),
+        ("not_enough_producer".parse().unwrap(), 100),
+    ];
+    let mut epoch_manager = setup_default_epoch_manager(validators, 2, 2, 2, 0, 90, 60);
+    let h = hash_range(10);
+    record_block(&mut epoch_manager, CryptoHash::default(), h[0], 0, vec![]);
+    for i in 1..=4 {
+        record_block(&mut epoch_manager, h[i - 1], h[i], i as u64, vec![]);
+    }
+
+    let epoch_id = EpochId(h[2]);
+    let miners = epoch_manager.get_epoch_miners(&epoch_id).unwrap();
+    assert_eq!(miners.epoch_id, epoch_id);
+    let accounts = |set: &MinerSetView| {
+        let mut accounts =
+            set.miners.iter().map(|miner| miner.account_id().to_string()).collect::<Vec<_>>();
+        accounts.sort();
+        accounts
+    };
+    assert_eq!(accounts(&miners.block_producers), vec!["test1", "test2"]);
+    assert_eq!(accounts(&miners.chunk_producers), vec!["chunk_only", "test1", "test2"]);
+    assert_eq!(miners.block_producers.total_pledge, 2 * amount_pledged);
+    assert_eq!(miners.shards.len(), 2);
+    for (shard_id, shard) in miners.shards.iter().enumerate() {
+        assert_eq!(shard.shard_id, shard_id as ShardId);
+        assert!(!shard.chunk_producers.miners.is_empty());
+    }
+
+    let block_epoch_id = epoch_manager.get_epoch_id(&h[4]).unwrap();
+    assert_eq!(epoch_manager.get_all_miners(&h[4]).unwrap().epoch_id, block_epoch_id);
+    assert!(matches!(
+        epoch_manager.get_epoch_miners(&EpochId(h[9])),
+        Err(EpochError::EpochOutOfBounds(_))
+    ));
+}
+
 /// A sanity test for the compute_kickout_info function, tests that
 /// the validators that don't meet the block/chunk producer kickout threshold is kicked out
 #[test]
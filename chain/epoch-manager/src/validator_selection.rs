@@ -7,7 +7,7 @@ use unc_primitives::checked_feature;
 use unc_primitives::epoch_manager::block_info::BlockInfo;
 use unc_primitives::epoch_manager::block_summary::BlockSummary;
 use unc_primitives::epoch_manager::epoch_info::EpochInfo;
-use unc_primitives::epoch_manager::{EpochConfig, RngSeed};
+use unc_primitives::epoch_manager::{EpochConfig, RewardWeights, RngSeed};
 use unc_primitives::errors::{BlockError, EpochError};
 use unc_primitives::hash::CryptoHash;
 use unc_primitives::types::validator_power::ValidatorPower;
@@ -290,6 +290,7 @@ pub fn proposals_to_epoch_info(
     pledge_proposals: Vec<ValidatorPledge>,
     mut validator_kickout: HashMap<AccountId, ValidatorKickoutReason>,
     validator_reward: HashMap<AccountId, Balance>,
+    reward_weights: RewardWeights,
     minted_amount: Balance,
     next_version: ProtocolVersion,
     last_version: ProtocolVersion,
@@ -502,6 +503,7 @@ pub fn proposals_to_epoch_info(
         power_change,
         pledge_change,
         validator_reward,
+        reward_weights,
         validator_kickout,
         minted_amount,
         threshold,
@@ -872,6 +874,7 @@ mod tests {
             pledge_proposals.clone(),
             Default::default(),
             Default::default(),
+            Default::default(),
             0,
             PROTOCOL_VERSION,
             PROTOCOL_VERSION,
@@ -958,6 +961,7 @@ mod tests {
             pledge_proposals.clone(),
             Default::default(),
             Default::default(),
+            Default::default(),
             0,
             PROTOCOL_VERSION,
             PROTOCOL_VERSION,
@@ -1032,6 +1036,7 @@ mod tests {
             pledge_proposals,
             Default::default(),
             Default::default(),
+            Default::default(),
             0,
             PROTOCOL_VERSION,
             PROTOCOL_VERSION,
@@ -1085,6 +1090,7 @@ mod tests {
             pledge_proposals,
             Default::default(),
             Default::default(),
+            Default::default(),
             0,
             PROTOCOL_VERSION,
             PROTOCOL_VERSION,
@@ -1119,6 +1125,7 @@ mod tests {
             pledge_proposals,
             Default::default(),
             Default::default(),
+            Default::default(),
             0,
             PROTOCOL_VERSION,
             PROTOCOL_VERSION,
@@ -1191,6 +1198,7 @@ mod tests {
             pledge_proposals,
             Default::default(),
             Default::default(),
+            Default::default(),
             0,
             PROTOCOL_VERSION,
             PROTOCOL_VERSION,
@@ -1272,6 +1280,7 @@ mod tests {
             pledge_proposals,
             Default::default(),
             Default::default(),
+            Default::default(),
             0,
             PROTOCOL_VERSION,
             PROTOCOL_VERSION,
@@ -1326,6 +1335,7 @@ mod tests {
             pledge_proposals,
             Default::default(),
             Default::default(),
+            Default::default(),
             0,
             PROTOCOL_VERSION,
             PROTOCOL_VERSION,
@@ -1360,6 +1370,7 @@ mod tests {
             pledge_proposals,
             Default::default(),
             Default::default(),
+            Default::default(),
             0,
             PROTOCOL_VERSION,
             PROTOCOL_VERSION,
@@ -1389,6 +1400,7 @@ mod tests {
             Default::default(),
             kick_out,
             Default::default(),
+            Default::default(),
             0,
             PROTOCOL_VERSION,
             PROTOCOL_VERSION,
@@ -1420,6 +1432,7 @@ mod tests {
             Default::default(),
             rewards_map,
             Default::default(),
+            Default::default(),
             0,
             PROTOCOL_VERSION,
             PROTOCOL_VERSION,
@@ -0,0 +1,143 @@
+use std::sync::{Arc, Mutex};
+
+use anyhow::Context;
+use rocksdb::DB;
+use unc_chain_configs::GCHoldHandle;
+use unc_primitives::types::BlockHeight;
+
+const LAST_ACKED_BLOCK_HEIGHT_KEY: &[u8] = b"last_acked_block_height";
+
+/// Persistent position of the consumer in the stream of `StreamerMessage`s,
+/// returned by `Indexer::streamer_with_cursor`.
+///
+/// The consumer calls [`IndexerCursor::ack`] once it has durably processed a
+/// block.  After a restart streaming resumes from the block following the last
+/// acknowledged one, so every block is delivered at least once.  Blocks
+/// received but not acknowledged before a crash are delivered again.
+#[derive(Clone)]
+pub struct IndexerCursor {
+    db: Arc<DB>,
+    acked_height: Arc<Mutex<Option<BlockHeight>>>,
+    gc_hold_handle: Option<GCHoldHandle>,
+}
+
+impl IndexerCursor {
+    pub(crate) fn open(
+        db: Arc<DB>,
+        gc_hold_handle: Option<GCHoldHandle>,
+    ) -> Result<Self, anyhow::Error> {
+        let acked_height = match db
+            .get(LAST_ACKED_BLOCK_HEIGHT_KEY)
+            .with_context(|| "failed to read the indexer cursor")?
+        {
+            Some(value) => Some(
+                String::from_utf8(value)
+                    .ok()
+                    .and_then(|value| value.parse::<BlockHeight>().ok())
+                    .with_context(|| "indexer cursor is corrupted")?,
+            ),
+            None => None,
+        };
+        let cursor = Self { db, acked_height: Arc::new(Mutex::new(acked_height)), gc_hold_handle };
+        if let Some(height) = acked_height {
+            cursor.hold_gc_from(height + 1);
+        }
+        Ok(cursor)
+    }
+
+    /// Height of the last block acknowledged by the consumer, if any.
+    pub fn last_acked_block_height(&self) -> Option<BlockHeight> {
+        *self.acked_height.lock().unwrap()
+    }
+
+    /// Marks all the blocks up to and including `height` as processed and
+    /// persists the position.  Acknowledging a height lower than the current
+    /// one is a no-op.
+    pub fn ack(&self, height: BlockHeight) -> Result<(), anyhow::Error> {
+        let mut acked_height = self.acked_height.lock().unwrap();
+        if acked_height.is_some_and(|acked| acked >= height) {
+            return Ok(());
+        }
+        self.db
+            .put(LAST_ACKED_BLOCK_HEIGHT_KEY, height.to_string())
+            .with_context(|| format!("failed to store the indexer cursor at #{}", height))?;
+        *acked_height = Some(height);
+        self.hold_gc_from(height + 1);
+        Ok(())
+    }
+
+    /// Keeps the node from garbage collecting blocks starting at `height`, if
+    /// the cursor was configured to hold GC.
+    pub(crate) fn hold_gc_from(&self, height: BlockHeight) {
+        if let Some(gc_hold_handle) = &self.gc_hold_handle {
+            gc_hold_handle.hold_from(height);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IndexerCursor;
+    use crate::streamer::initial_block_height;
+    use crate::SyncModeEnum;
+    use rocksdb::DB;
+    use std::sync::Arc;
+    use unc_chain_configs::GCHoldHandle;
+
+    fn open_db(dir: &tempfile::TempDir) -> Arc<DB> {
+        Arc::new(DB::open_default(dir.path()).unwrap())
+    }
+
+    #[test]
+    fn test_cursor_persistence() {
+        let dir = tempfile::tempdir().unwrap();
+        {
+            let cursor = IndexerCursor::open(open_db(&dir), None).unwrap();
+            assert_eq!(cursor.last_acked_block_height(), None);
+            cursor.ack(5).unwrap();
+            // Acknowledging an older block doesn't move the cursor back.
+            cursor.ack(3).unwrap();
+            assert_eq!(cursor.last_acked_block_height(), Some(5));
+        }
+        let cursor = IndexerCursor::open(open_db(&dir), None).unwrap();
+        assert_eq!(cursor.last_acked_block_height(), Some(5));
+    }
+
+    #[test]
+    fn test_cursor_resume() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = open_db(&dir);
+        db.put(b"last_synced_block_height", "20").unwrap();
+        let cursor = IndexerCursor::open(db.clone(), None).unwrap();
+        let mode = SyncModeEnum::FromInterruption;
+        // Without acknowledged blocks the streamer resumes from the last streamed block.
+        assert_eq!(initial_block_height(&mode, &db, None, 100), 20);
+        assert_eq!(initial_block_height(&mode, &db, Some(&cursor), 100), 20);
+        // Blocks streamed but not acknowledged are streamed again.
+        cursor.ack(10).unwrap();
+        assert_eq!(initial_block_height(&mode, &db, Some(&cursor), 100), 11);
+        assert_eq!(initial_block_height(&SyncModeEnum::LatestSynced, &db, Some(&cursor), 100), 100);
+    }
+
+    #[test]
+    fn test_cursor_gc_hold() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = open_db(&dir);
+        let gc_hold_handle = GCHoldHandle::new();
+        let cursor = IndexerCursor::open(db.clone(), Some(gc_hold_handle.clone())).unwrap();
+        assert_eq!(gc_hold_handle.get(), None);
+        // Streaming from a later block still holds the unacknowledged ones.
+        cursor.ack(10).unwrap();
+        assert_eq!(gc_hold_handle.get(), Some(11));
+        initial_block_height(&SyncModeEnum::LatestSynced, &db, Some(&cursor), 100);
+        assert_eq!(gc_hold_handle.get(), Some(11));
+        cursor.ack(15).unwrap();
+        assert_eq!(gc_hold_handle.get(), Some(16));
+        drop(cursor);
+
+        // The hold is restored on restart.
+        let gc_hold_handle = GCHoldHandle::new();
+        let _cursor = IndexerCursor::open(db, Some(gc_hold_handle.clone())).unwrap();
+        assert_eq!(gc_hold_handle.get(), Some(16));
+    }
+}
@@ -1,10 +1,11 @@
 #![doc = include_str!("../README.md")]
 
 use anyhow::Context;
+use std::sync::Arc;
 use tokio::sync::mpsc;
 
 pub use framework::{get_default_home, init_configs, UncConfig};
-use unc_chain_configs::GenesisValidationMode;
+use unc_chain_configs::{GCHoldHandle, GenesisValidationMode};
 pub use unc_primitives;
 use unc_primitives::types::Gas;
 
@@ -14,6 +15,9 @@ pub use unc_indexer_primitives::{
     StreamerMessage,
 };
 
+pub use cursor::IndexerCursor;
+
+mod cursor;
 mod streamer;
 
 pub const INDEXER: &str = "indexer";
@@ -55,7 +59,9 @@ pub struct InitConfigArgs {
 pub enum SyncModeEnum {
     /// Real-time syncing, always taking the latest finalized block to stream
     LatestSynced,
-    /// Starts syncing from the block UNC Indexer was interrupted last time
+    /// Starts syncing from the block UNC Indexer was interrupted last time.
+    /// With `Indexer::streamer_with_cursor` it starts from the block following
+    /// the last acknowledged one.
     FromInterruption,
     /// Specific block height to start syncing from
     BlockHeight(u64),
@@ -81,6 +87,26 @@ pub struct IndexerConfig {
     pub await_for_node_synced: AwaitForNodeSyncedEnum,
     /// Tells whether to validate the genesis file before starting
     pub validate_genesis: bool,
+    /// Keeps the node from garbage collecting the blocks which haven't been
+    /// acknowledged via `IndexerCursor::ack` yet.  Only has an effect with
+    /// `Indexer::streamer_with_cursor`.  The node still collects the blocks
+    /// more than `gc.gc_hold_max_blocks` behind its regular GC horizon.
+    pub hold_gc_until_acked: bool,
+}
+
+impl Default for IndexerConfig {
+    /// Streams from the block the indexer was interrupted at, once the node in
+    /// the default home directory is fully synced.  Use it to set only the
+    /// fields you care about, e.g. `IndexerConfig { home_dir, ..Default::default() }`.
+    fn default() -> Self {
+        Self {
+            home_dir: get_default_home(),
+            sync_mode: SyncModeEnum::FromInterruption,
+            await_for_node_synced: AwaitForNodeSyncedEnum::WaitForFullSync,
+            validate_genesis: true,
+            hold_gc_until_acked: false,
+        }
+    }
 }
 
 /// This is the core component, which handles `framework` and internal `streamer`.
@@ -89,6 +115,7 @@ pub struct Indexer {
     unc_config: framework::UncConfig,
     view_client: actix::Addr<unc_client::ViewClientActor>,
     client: actix::Addr<unc_client::ClientActor>,
+    gc_hold_handle: GCHoldHandle,
 }
 
 impl Indexer {
@@ -108,21 +135,62 @@ impl Indexer {
         let unc_config =
             framework::config::load_config(&indexer_config.home_dir, genesis_validation_mode)
                 .unwrap_or_else(|e| panic!("Error loading config: {:#}", e));
-        let framework::UncNode { client, view_client, .. } =
+        let framework::UncNode { client, view_client, gc_hold_handle, .. } =
             framework::start_with_config(&indexer_config.home_dir, unc_config.clone())
                 .with_context(|| "start_with_config")?;
-        Ok(Self { view_client, client, unc_config, indexer_config })
+        Ok(Self { view_client, client, unc_config, indexer_config, gc_hold_handle })
     }
 
     /// Boots up `unc_indexer::streamer`, so it monitors the new blocks with chunks, transactions, receipts, and execution outcomes inside. The returned stream handler should be drained and handled on the user side.
     pub fn streamer(&self) -> mpsc::Receiver<StreamerMessage> {
+        // TODO: implement proper error handling
+        let db = self.open_db().unwrap();
+        self.start_streamer(db, None)
+    }
+
+    /// Same as [`Indexer::streamer`], but also returns a persistent cursor the
+    /// consumer acknowledges processed blocks with.  Streaming in
+    /// `SyncModeEnum::FromInterruption` mode resumes from the block following
+    /// the last acknowledged one, so blocks are delivered at least once.
+    ///
+    /// If `IndexerConfig::hold_gc_until_acked` is set, the node doesn't garbage
+    /// collect the blocks which haven't been acknowledged yet.
+    pub fn streamer_with_cursor(
+        &self,
+    ) -> Result<(mpsc::Receiver<StreamerMessage>, IndexerCursor), anyhow::Error> {
+        let db = self.open_db()?;
+        let gc_hold_handle =
+            self.indexer_config.hold_gc_until_acked.then(|| self.gc_hold_handle.clone());
+        let cursor = IndexerCursor::open(db.clone(), gc_hold_handle)?;
+        Ok((self.start_streamer(db, Some(cursor.clone())), cursor))
+    }
+
+    fn open_db(&self) -> Result<Arc<rocksdb::DB>, anyhow::Error> {
+        let indexer_db_path = unc_store::NodeStorage::opener(
+            &self.indexer_config.home_dir,
+            self.unc_config.config.archive,
+            &self.unc_config.config.store,
+            None,
+        )
+        .path()
+        .join("indexer");
+        let db = rocksdb::DB::open_default(&indexer_db_path)
+            .with_context(|| format!("failed to open {}", indexer_db_path.display()))?;
+        Ok(Arc::new(db))
+    }
+
+    fn start_streamer(
+        &self,
+        db: Arc<rocksdb::DB>,
+        cursor: Option<IndexerCursor>,
+    ) -> mpsc::Receiver<StreamerMessage> {
         let (sender, receiver) = mpsc::channel(100);
         actix::spawn(streamer::start(
             self.view_client.clone(),
             self.client.clone(),
             self.indexer_config.clone(),
-            self.unc_config.config.store.clone(),
-            self.unc_config.config.archive,
+            db,
+            cursor,
             sender,
         ));
         receiver
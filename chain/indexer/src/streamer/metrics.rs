@@ -1,8 +1,5 @@
 use once_cell::sync::Lazy;
-use unc_o11y::metrics::{
-    try_create_histogram, try_create_int_counter, try_create_int_gauge, Histogram, IntCounter,
-    IntGauge,
-};
+use unc_o11y::metrics::{try_create_int_counter, try_create_int_gauge, IntCounter, IntGauge};
 
 pub(crate) static START_BLOCK_HEIGHT: Lazy<IntGauge> = Lazy::new(|| {
     try_create_int_gauge(
@@ -35,11 +32,3 @@ pub(crate) static NUM_STREAMER_MESSAGES_SENT: Lazy<IntCounter> = Lazy::new(|| {
     )
     .unwrap()
 });
-
-pub(crate) static BUILD_STREAMER_MESSAGE_TIME: Lazy<Histogram> = Lazy::new(|| {
-    try_create_histogram(
-        "unc_indexer_build_streamer_message_time",
-        "Time taken to build a streamer message",
-    )
-    .unwrap()
-});
@@ -142,6 +142,18 @@ pub struct Notification {
     pub params: Value,
 }
 
+impl From<Notification> for Request {
+    /// A request without an ID, so that a notification can be processed like one.
+    fn from(notification: Notification) -> Self {
+        Request {
+            jsonrpc: Version,
+            method: notification.method,
+            params: notification.params,
+            id: Value::Null,
+        }
+    }
+}
+
 /// One message of the JSON RPC protocol.
 ///
 /// One message, directly mapped from the structures of the protocol. See the
@@ -1,31 +1,44 @@
 use serde_json::Value;
-use unc_primitives::types::{CryptoHash, Power};
-use unc_primitives::views::validator_power_view::ValidatorPowerView;
+use unc_primitives::types::{BlockReference, EpochId};
 
 #[derive(thiserror::Error, Debug, serde::Serialize, serde::Deserialize)]
 #[serde(tag = "name", content = "info", rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum RpcAllMinersError {
     #[error("Block not found")]
     UnknownBlock,
+    #[error("Epoch not found")]
+    UnknownEpoch,
+    #[error("There are no fully synchronized blocks yet")]
+    NotSyncedYet,
     #[error("The node reached its limits. Try again later. More details: {error_message}")]
     InternalError { error_message: String },
 }
 
+/// Either the miners at a given block or the miners selected for an epoch.
 #[derive(serde::Serialize, serde::Deserialize, Debug, arbitrary::Arbitrary, PartialEq, Eq)]
-pub struct RpcAllMinersRequest {
-    pub block_hash: CryptoHash,
+#[serde(untagged)]
+pub enum RpcAllMinersRequest {
+    EpochId {
+        epoch_id: EpochId,
+    },
+    BlockReference {
+        #[serde(flatten)]
+        block_reference: BlockReference,
+    },
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Debug)]
 pub struct RpcAllMinersResponse {
-    pub total_power: Power,
-    pub miners: Vec<ValidatorPowerView>,
+    #[serde(flatten)]
+    pub all_miners: unc_primitives::views::AllMinersView,
 }
 
 impl From<RpcAllMinersError> for crate::errors::RpcError {
     fn from(error: RpcAllMinersError) -> Self {
         let error_data = match &error {
             RpcAllMinersError::UnknownBlock => Some(Value::String("Unknown Block".to_string())),
+            RpcAllMinersError::UnknownEpoch => Some(Value::String("Unknown Epoch".to_string())),
+            RpcAllMinersError::NotSyncedYet => Some(Value::String(error.to_string())),
             RpcAllMinersError::InternalError { .. } => Some(Value::String(error.to_string())),
         };
 
@@ -8,7 +8,7 @@ pub enum RpcBlockError {
         // We are skipping this field for now
         // until we can provide useful struct like block_height or block_hash
         // that was requested
-        #[serde(skip_serializing)]
+        #[serde(skip_serializing, default)]
         error_message: String,
     },
     #[error("There are no fully synchronized blocks yet")]
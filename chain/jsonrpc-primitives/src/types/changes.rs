@@ -24,16 +24,38 @@ pub struct RpcStateChangesInBlockByTypeResponse {
     pub changes: unc_primitives::views::StateChangesKindsView,
 }
 
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct RpcPowerChangesRequest {
+    #[serde(flatten)]
+    pub block_reference: unc_primitives::types::BlockReference,
+    pub account_ids: Vec<unc_primitives::types::AccountId>,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct RpcPowerChangesResponse {
+    pub block_hash: unc_primitives::hash::CryptoHash,
+    pub changes: unc_primitives::views::PowerChangesView,
+}
+
 #[derive(thiserror::Error, Debug, serde::Serialize, serde::Deserialize)]
 #[serde(tag = "name", content = "info", rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum RpcStateChangesError {
     #[error("Block not found: {error_message}")]
     UnknownBlock {
-        #[serde(skip_serializing)]
+        #[serde(skip_serializing, default)]
         error_message: String,
     },
     #[error("There are no fully synchronized blocks yet")]
     NotSyncedYet,
+    #[error("The node does not track the shard ID {requested_shard_id}")]
+    UnavailableShard { requested_shard_id: unc_primitives::types::ShardId },
+    #[error(
+        "The state for block #{block_height} is garbage collected on this node, use an archival node to fetch historical data"
+    )]
+    GarbageCollectedBlock {
+        block_height: unc_primitives::types::BlockHeight,
+        block_hash: unc_primitives::hash::CryptoHash,
+    },
     #[error("The node reached its limits. Try again later. More details: {error_message}")]
     InternalError { error_message: String },
 }
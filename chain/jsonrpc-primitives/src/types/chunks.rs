@@ -31,7 +31,7 @@ pub enum RpcChunkError {
     InternalError { error_message: String },
     #[error("Block either has never been observed on the node or has been garbage collected: {error_message}")]
     UnknownBlock {
-        #[serde(skip_serializing)]
+        #[serde(skip_serializing, default)]
         error_message: String,
     },
     #[error("Shard id {shard_id} does not exist")]
@@ -1,4 +1,4 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 #[derive(Serialize)]
@@ -7,7 +7,7 @@ pub struct RpcClientConfigResponse {
     pub client_config: unc_chain_configs::ClientConfig,
 }
 
-#[derive(thiserror::Error, Debug, Serialize)]
+#[derive(thiserror::Error, Debug, Serialize, Deserialize)]
 #[serde(tag = "name", content = "info", rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum RpcClientConfigError {
     #[error("The node reached its limits. Try again later. More details: {error_message}")]
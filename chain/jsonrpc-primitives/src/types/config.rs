@@ -17,7 +17,7 @@ pub struct RpcProtocolConfigResponse {
 pub enum RpcProtocolConfigError {
     #[error("Block has never been observed: {error_message}")]
     UnknownBlock {
-        #[serde(skip_serializing)]
+        #[serde(skip_serializing, default)]
         error_message: String,
     },
     #[error("The node reached its limits. Try again later. More details: {error_message}")]
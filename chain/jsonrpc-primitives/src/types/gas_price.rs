@@ -19,7 +19,7 @@ pub enum RpcGasPriceError {
     InternalError { error_message: String },
     #[error("Block either has never been observed on the node or has been garbage collected: {error_message}")]
     UnknownBlock {
-        #[serde(skip_serializing)]
+        #[serde(skip_serializing, default)]
         error_message: String,
     },
 }
@@ -21,7 +21,7 @@ pub struct RpcLightClientExecutionProofResponse {
     pub block_proof: unc_primitives::merkle::MerklePath,
 }
 
-#[derive(Debug, serde::Serialize)]
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
 pub struct RpcLightClientNextBlockResponse {
     #[serde(flatten)]
     pub light_client_block: Option<Arc<unc_primitives::views::LightClientBlockView>>,
@@ -32,7 +32,7 @@ pub struct RpcLightClientNextBlockResponse {
 pub enum RpcLightClientProofError {
     #[error("Block either has never been observed on the node or has been garbage collected: {error_message}")]
     UnknownBlock {
-        #[serde(skip_serializing)]
+        #[serde(skip_serializing, default)]
         error_message: String,
     },
     #[error("Inconsistent state. Total number of shards is {number_or_shards} but the execution outcome is in shard {execution_outcome_shard_id}")]
@@ -60,7 +60,7 @@ pub enum RpcLightClientNextBlockError {
     InternalError { error_message: String },
     #[error("Block either has never been observed on the node or has been garbage collected: {error_message}")]
     UnknownBlock {
-        #[serde(skip_serializing)]
+        #[serde(skip_serializing, default)]
         error_message: String,
     },
     #[error("Epoch Out Of Bounds {epoch_id:?}")]
@@ -9,11 +9,13 @@ pub mod gas_price;
 pub mod light_client;
 pub mod maintenance;
 pub mod network_info;
+pub mod producer_selection;
 pub mod provider;
 pub mod query;
 pub mod receipts;
 pub mod sandbox;
 pub mod split_storage;
 pub mod status;
+pub mod subscriptions;
 pub mod transactions;
 pub mod validator;
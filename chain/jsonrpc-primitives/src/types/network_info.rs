@@ -7,6 +7,9 @@ pub struct RpcPeerInfo {
     pub id: PeerId,
     pub addr: Option<SocketAddr>,
     pub account_id: Option<AccountId>,
+    /// Reputation score of the peer. 0 means that the peer behaves well.
+    #[serde(default)]
+    pub reputation_score: f64,
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Debug)]
@@ -0,0 +1,56 @@
+use serde_json::Value;
+
+#[derive(thiserror::Error, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "name", content = "info", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum RpcProducerSelectionError {
+    #[error("Block not found: {error_message}")]
+    UnknownBlock {
+        #[serde(skip_serializing, default)]
+        error_message: String,
+    },
+    #[error("There are no fully synchronized blocks yet")]
+    NotSyncedYet,
+    #[error("Block {block_hash} does not select a producer: {error_message}")]
+    NoSelection { block_hash: unc_primitives::hash::CryptoHash, error_message: String },
+    #[error("The node reached its limits. Try again later. More details: {error_message}")]
+    InternalError { error_message: String },
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, arbitrary::Arbitrary)]
+pub struct RpcProducerSelectionRequest {
+    #[serde(flatten)]
+    pub block_reference: unc_primitives::types::BlockReference,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct RpcProducerSelectionResponse {
+    #[serde(flatten)]
+    pub selection: unc_primitives::views::ProducerSelectionView,
+}
+
+impl From<RpcProducerSelectionError> for crate::errors::RpcError {
+    fn from(error: RpcProducerSelectionError) -> Self {
+        let error_data = match &error {
+            RpcProducerSelectionError::UnknownBlock { error_message } => Some(Value::String(
+                format!("DB Not Found Error: {} \n Cause: Unknown", error_message),
+            )),
+            RpcProducerSelectionError::NotSyncedYet
+            | RpcProducerSelectionError::NoSelection { .. }
+            | RpcProducerSelectionError::InternalError { .. } => {
+                Some(Value::String(error.to_string()))
+            }
+        };
+
+        let error_data_value = match serde_json::to_value(error) {
+            Ok(value) => value,
+            Err(err) => {
+                return Self::new_internal_error(
+                    None,
+                    format!("Failed to serialize RpcProducerSelectionError: {:?}", err),
+                )
+            }
+        };
+
+        Self::new_internal_or_handler_error(error_data, error_data_value)
+    }
+}
@@ -88,6 +88,7 @@ pub enum QueryResponseKind {
     AccessKey(unc_primitives::views::AccessKeyView),
     AccessKeyList(unc_primitives::views::AccessKeyList),
     ChipList(unc_primitives::views::ChipsList),
+    ChipsPage(unc_primitives::views::ChipsPage),
 }
 
 impl From<RpcQueryError> for crate::errors::RpcError {
@@ -0,0 +1,85 @@
+use serde_json::Value;
+
+use unc_primitives::types::{AccountId, ShardId, StoreKey};
+
+/// Identifier of a subscription, unique within a single WebSocket connection.
+pub type RpcSubscriptionId = u64;
+
+/// The stream of events a WebSocket client subscribes to.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RpcSubscriptionKind {
+    /// New final blocks.
+    Blocks,
+    /// Headers of new chunks, optionally limited to a single shard.
+    Chunks {
+        #[serde(default)]
+        shard_id: Option<ShardId>,
+    },
+    /// Transaction and receipt execution outcomes the account is a party of.
+    Outcomes { account_id: AccountId },
+    /// State changes of the account, optionally limited to contract data
+    /// keys starting with the given prefix.
+    StateChanges {
+        account_id: AccountId,
+        #[serde(rename = "key_prefix_base64", default)]
+        key_prefix: Option<StoreKey>,
+    },
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct RpcSubscribeRequest {
+    #[serde(flatten)]
+    pub kind: RpcSubscriptionKind,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct RpcSubscribeResponse {
+    pub subscription: RpcSubscriptionId,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct RpcUnsubscribeRequest {
+    pub subscription: RpcSubscriptionId,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct RpcUnsubscribeResponse {
+    pub subscription: RpcSubscriptionId,
+}
+
+/// Params of the `subscription` notification pushed to the client for every
+/// event matching one of its subscriptions.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct RpcSubscriptionNotification {
+    pub subscription: RpcSubscriptionId,
+    pub result: Value,
+}
+
+#[derive(thiserror::Error, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "name", content = "info", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum RpcSubscriptionError {
+    #[error("Connection already has the maximum of {limit} active subscriptions")]
+    TooManySubscriptions { limit: usize },
+    #[error("Subscription {subscription} does not exist")]
+    UnknownSubscription { subscription: RpcSubscriptionId },
+    #[error("The node reached its limits. Try again later. More details: {error_message}")]
+    InternalError { error_message: String },
+}
+
+impl From<RpcSubscriptionError> for crate::errors::RpcError {
+    fn from(error: RpcSubscriptionError) -> Self {
+        let error_data = Some(Value::String(error.to_string()));
+        let error_data_value = match serde_json::to_value(error) {
+            Ok(value) => value,
+            Err(err) => {
+                return Self::new_internal_error(
+                    None,
+                    format!("Failed to serialize RpcSubscriptionError: {:?}", err),
+                )
+            }
+        };
+
+        Self::new_internal_or_handler_error(error_data, error_data_value)
+    }
+}
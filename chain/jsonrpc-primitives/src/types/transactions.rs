@@ -41,6 +41,10 @@ pub enum RpcTransactionError {
     },
     #[error("Node doesn't track this shard. Cannot determine whether the transaction is valid")]
     DoesNotTrackShard,
+    #[error("The signer or the access key of the transaction has too many transactions in the transaction pool. Try again later")]
+    PoolQuotaExceeded,
+    #[error("A transaction with the same nonce and a priority at least as high is already in the transaction pool")]
+    ReplacementRejected,
     #[error("Transaction with hash {transaction_hash} was routed")]
     RequestRouted { transaction_hash: unc_primitives::hash::CryptoHash },
     #[error("Transaction {requested_transaction_hash} doesn't exist")]
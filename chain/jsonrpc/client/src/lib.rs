@@ -1,22 +1,29 @@
 use awc::{Client, Connector};
 use futures::{future, future::LocalBoxFuture, FutureExt, TryFutureExt};
 use std::time::Duration;
+use unc_crypto::PublicKey;
 use unc_jsonrpc_primitives::errors::RpcError;
 use unc_jsonrpc_primitives::message::{from_slice, Message};
 use unc_jsonrpc_primitives::types::changes::{
-    RpcStateChangesInBlockByTypeRequest, RpcStateChangesInBlockByTypeResponse,
+    RpcPowerChangesRequest, RpcPowerChangesResponse, RpcStateChangesInBlockByTypeRequest,
+    RpcStateChangesInBlockByTypeResponse,
 };
 use unc_jsonrpc_primitives::types::transactions::{
     RpcTransactionResponse, RpcTransactionStatusRequest,
 };
 use unc_jsonrpc_primitives::types::validator::RpcValidatorsOrderedRequest;
 use unc_primitives::hash::CryptoHash;
-use unc_primitives::types::{BlockId, BlockReference, EpochReference, MaybeBlockId, ShardId};
+use unc_primitives::types::{
+    AccountId, BlockId, BlockReference, EpochReference, MaybeBlockId, ShardId,
+};
 use unc_primitives::views::validator_power_view::ValidatorPowerView;
 use unc_primitives::views::{
-    BlockView, ChunkView, EpochValidatorInfo, GasPriceView, StatusResponse,
+    BlockView, ChipsPage, ChunkView, EpochValidatorInfo, GasPriceView, ProducerSelectionView,
+    QueryRequest, StatusResponse,
 };
 
+pub mod typed;
+
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 #[serde(untagged)]
 pub enum ChunkId {
@@ -208,6 +215,45 @@ impl JsonRpcClient {
         call_method(&self.client, &self.server_addr, "query", request)
     }
 
+    /// Fetches one page of chips registered to `account_id`.
+    ///
+    /// Pass the `next_cursor` of the returned page as `cursor` to fetch the
+    /// following one.
+    pub fn view_chips(
+        &self,
+        block_reference: BlockReference,
+        account_id: AccountId,
+        miner_id: Option<String>,
+        sn: Option<String>,
+        cursor: Option<PublicKey>,
+        limit: Option<u32>,
+        include_proof: bool,
+    ) -> RpcRequest<ChipsPage> {
+        let request = unc_jsonrpc_primitives::types::query::RpcQueryRequest {
+            block_reference,
+            request: QueryRequest::ViewChips {
+                account_id,
+                miner_id,
+                sn,
+                cursor,
+                limit,
+                include_proof,
+            },
+        };
+        self.query(request)
+            .and_then(|response| {
+                future::ready(match response.kind {
+                    unc_jsonrpc_primitives::types::query::QueryResponseKind::ChipsPage(page) => {
+                        Ok(page)
+                    }
+                    kind => {
+                        Err(RpcError::parse_error(format!("Unexpected query response: {:?}", kind)))
+                    }
+                })
+            })
+            .boxed_local()
+    }
+
     pub fn block_by_id(&self, block_id: BlockId) -> RpcRequest<BlockView> {
         call_method(&self.client, &self.server_addr, "block", [block_id])
     }
@@ -228,6 +274,22 @@ impl JsonRpcClient {
         call_method(&self.client, &self.server_addr, "EXPERIMENTAL_changes", request)
     }
 
+    #[allow(non_snake_case)]
+    pub fn EXPERIMENTAL_power_changes(
+        &self,
+        request: RpcPowerChangesRequest,
+    ) -> RpcRequest<RpcPowerChangesResponse> {
+        call_method(&self.client, &self.server_addr, "EXPERIMENTAL_power_changes", request)
+    }
+
+    #[allow(non_snake_case)]
+    pub fn EXPERIMENTAL_producer_selection(
+        &self,
+        request: BlockReference,
+    ) -> RpcRequest<ProducerSelectionView> {
+        call_method(&self.client, &self.server_addr, "EXPERIMENTAL_producer_selection", request)
+    }
+
     #[allow(non_snake_case)]
     pub fn EXPERIMENTAL_validators_ordered(
         &self,
@@ -0,0 +1,102 @@
+use unc_jsonrpc_primitives::errors::{RpcError, RpcErrorKind, RpcRequestValidationErrorKind};
+
+/// Error returned by [`RpcClient::call`](super::RpcClient::call).
+///
+/// `E` is the error type of the called method, e.g.
+/// [`RpcBlockError`](unc_jsonrpc_primitives::types::blocks::RpcBlockError)
+/// for `block`.
+#[derive(Debug, thiserror::Error)]
+pub enum JsonRpcError<E> {
+    /// The request never got a JSON-RPC response from the server.
+    #[error(transparent)]
+    Transport(#[from] TransportError),
+    /// The server processed the request and responded with an error.
+    #[error(transparent)]
+    Server(JsonRpcServerError<E>),
+}
+
+impl<E> JsonRpcError<E> {
+    /// Returns the error of the method handler, if that's what failed.
+    pub fn handler_error(&self) -> Option<&E> {
+        match self {
+            Self::Server(JsonRpcServerError::HandlerError(error)) => Some(error),
+            _ => None,
+        }
+    }
+
+    /// Whether the same request may succeed if it's sent again later.
+    pub(crate) fn is_retryable(&self) -> bool {
+        match self {
+            Self::Transport(error) => error.is_retryable(),
+            Self::Server(JsonRpcServerError::InternalError { .. }) => true,
+            Self::Server(_) => false,
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum TransportError {
+    #[error("Failed to serialize the request params: {0}")]
+    InvalidParams(serde_json::Error),
+    #[error("Failed to send the request: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("Server responded with HTTP status {status}")]
+    UnexpectedStatus { status: reqwest::StatusCode },
+    #[error("Failed to parse the response: {error_message}")]
+    InvalidResponse { error_message: String },
+}
+
+impl TransportError {
+    fn is_retryable(&self) -> bool {
+        match self {
+            Self::Http(error) => error.is_connect() || error.is_timeout() || error.is_request(),
+            Self::UnexpectedStatus { status } => {
+                status.is_server_error() || *status == reqwest::StatusCode::TOO_MANY_REQUESTS
+            }
+            Self::InvalidParams(_) | Self::InvalidResponse { .. } => false,
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum JsonRpcServerError<E> {
+    /// The method handler failed with one of its own errors.
+    #[error("Handler error: {0:?}")]
+    HandlerError(E),
+    /// The server rejected the request before dispatching it, e.g. because
+    /// the method doesn't exist or the params couldn't be parsed.
+    #[error("Request validation error: {0:?}")]
+    RequestValidationError(RpcRequestValidationErrorKind),
+    /// The node failed to process the request, usually because it's
+    /// overloaded.  Sending the request again later may succeed.
+    #[error("Internal error: {error_message}")]
+    InternalError { error_message: String },
+    /// An error which couldn't be decoded into any of the above, e.g. one
+    /// sent by an older node.
+    #[error("Unrecognized error: {0:?}")]
+    Unrecognized(RpcError),
+}
+
+impl<E: serde::de::DeserializeOwned> From<RpcError> for JsonRpcServerError<E> {
+    fn from(error: RpcError) -> Self {
+        match &error.error_struct {
+            Some(RpcErrorKind::RequestValidationError(kind)) => {
+                Self::RequestValidationError(kind.clone())
+            }
+            Some(RpcErrorKind::HandlerError(cause)) => {
+                match serde_json::from_value(cause.clone()) {
+                    Ok(handler_error) => Self::HandlerError(handler_error),
+                    Err(_) => Self::Unrecognized(error),
+                }
+            }
+            Some(RpcErrorKind::InternalError(cause)) => {
+                let error_message = match cause["info"].get("error_message") {
+                    Some(serde_json::Value::String(error_message)) => error_message.clone(),
+                    _ => cause.to_string(),
+                };
+                Self::InternalError { error_message }
+            }
+            None => Self::Unrecognized(error),
+        }
+    }
+}
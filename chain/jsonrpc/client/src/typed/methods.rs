@@ -0,0 +1,340 @@
+//! Typed requests of every JSON-RPC method served over HTTP.
+//!
+//! Most methods take the request type defined in
+//! [`unc_jsonrpc_primitives::types`].  Methods without params, and methods
+//! which share the request type with another method, have a dedicated request
+//! type defined here.
+//!
+//! `EXPERIMENTAL_light_client_proof` is an alias of `light_client_proof` and
+//! isn't exposed separately.  WebSocket subscriptions aren't served over HTTP
+//! and are not covered by this client.
+
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+
+use unc_jsonrpc_primitives::types::all_miners::{
+    RpcAllMinersError, RpcAllMinersRequest, RpcAllMinersResponse,
+};
+use unc_jsonrpc_primitives::types::blocks::{RpcBlockError, RpcBlockRequest, RpcBlockResponse};
+use unc_jsonrpc_primitives::types::changes::{
+    RpcPowerChangesRequest, RpcPowerChangesResponse, RpcStateChangesError,
+    RpcStateChangesInBlockByTypeRequest, RpcStateChangesInBlockByTypeResponse,
+    RpcStateChangesInBlockRequest, RpcStateChangesInBlockResponse,
+};
+use unc_jsonrpc_primitives::types::chunks::{RpcChunkError, RpcChunkRequest, RpcChunkResponse};
+use unc_jsonrpc_primitives::types::client_config::RpcClientConfigError;
+use unc_jsonrpc_primitives::types::config::{
+    RpcProtocolConfigError, RpcProtocolConfigRequest, RpcProtocolConfigResponse,
+};
+use unc_jsonrpc_primitives::types::gas_price::{
+    RpcGasPriceError, RpcGasPriceRequest, RpcGasPriceResponse,
+};
+use unc_jsonrpc_primitives::types::light_client::{
+    RpcLightClientExecutionProofRequest, RpcLightClientExecutionProofResponse,
+    RpcLightClientNextBlockError, RpcLightClientNextBlockRequest, RpcLightClientNextBlockResponse,
+    RpcLightClientProofError,
+};
+use unc_jsonrpc_primitives::types::maintenance::{
+    RpcMaintenanceWindowsError, RpcMaintenanceWindowsRequest, RpcMaintenanceWindowsResponse,
+};
+use unc_jsonrpc_primitives::types::network_info::{RpcNetworkInfoError, RpcNetworkInfoResponse};
+use unc_jsonrpc_primitives::types::producer_selection::{
+    RpcProducerSelectionError, RpcProducerSelectionRequest, RpcProducerSelectionResponse,
+};
+use unc_jsonrpc_primitives::types::provider::{
+    RpcProviderError, RpcProviderRequest, RpcProviderResponse,
+};
+use unc_jsonrpc_primitives::types::query::{RpcQueryError, RpcQueryRequest, RpcQueryResponse};
+use unc_jsonrpc_primitives::types::receipts::{
+    RpcReceiptError, RpcReceiptRequest, RpcReceiptResponse,
+};
+use unc_jsonrpc_primitives::types::sandbox::{
+    RpcSandboxFastForwardError, RpcSandboxFastForwardRequest, RpcSandboxFastForwardResponse,
+    RpcSandboxPatchStateError, RpcSandboxPatchStateRequest, RpcSandboxPatchStateResponse,
+};
+use unc_jsonrpc_primitives::types::split_storage::{
+    RpcSplitStorageInfoError, RpcSplitStorageInfoRequest, RpcSplitStorageInfoResponse,
+};
+use unc_jsonrpc_primitives::types::status::{RpcHealthResponse, RpcStatusError, RpcStatusResponse};
+use unc_jsonrpc_primitives::types::transactions::{
+    RpcSendTransactionRequest, RpcTransactionError, RpcTransactionResponse,
+    RpcTransactionStatusRequest,
+};
+use unc_jsonrpc_primitives::types::validator::{
+    RpcValidatorError, RpcValidatorRequest, RpcValidatorResponse, RpcValidatorsOrderedRequest,
+    RpcValidatorsOrderedResponse,
+};
+use unc_primitives::hash::CryptoHash;
+use unc_primitives::transaction::SignedTransaction;
+use unc_primitives::types::EpochReference;
+
+/// A request of a single JSON-RPC method.
+pub trait RpcMethod {
+    /// Result of a successful call.
+    type Response: DeserializeOwned;
+    /// Error returned by the method handler.
+    type Error: DeserializeOwned;
+
+    const METHOD_NAME: &'static str;
+
+    /// Whether calling the method again has no effect beyond the first call.
+    /// Only idempotent methods are retried unless
+    /// [`RetryPolicy::retry_non_idempotent`](super::RetryPolicy::retry_non_idempotent)
+    /// is set.
+    const IDEMPOTENT: bool = true;
+
+    fn params(&self) -> Result<Value, serde_json::Error>;
+}
+
+/// Implements [`RpcMethod`] for requests whose params are the request itself
+/// serialized as a JSON object.  Methods are idempotent unless stated
+/// otherwise.
+macro_rules! rpc_method {
+    ($request:ty, $method_name:literal, $response:ty, $error:ty) => {
+        rpc_method!($request, $method_name, $response, $error, idempotent = true);
+    };
+    (
+        $request:ty,
+        $method_name:literal,
+        $response:ty,
+        $error:ty,
+        idempotent = $idempotent:literal
+    ) => {
+        impl RpcMethod for $request {
+            type Response = $response;
+            type Error = $error;
+
+            const METHOD_NAME: &'static str = $method_name;
+            const IDEMPOTENT: bool = $idempotent;
+
+            fn params(&self) -> Result<Value, serde_json::Error> {
+                serde_json::to_value(self)
+            }
+        }
+    };
+}
+
+/// Declares a request of a method without params.
+macro_rules! nullary_rpc_method {
+    ($(#[$attr:meta])* $request:ident, $method_name:literal, $response:ty, $error:ty) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone, Copy, Default)]
+        pub struct $request;
+
+        impl RpcMethod for $request {
+            type Response = $response;
+            type Error = $error;
+
+            const METHOD_NAME: &'static str = $method_name;
+
+            fn params(&self) -> Result<Value, serde_json::Error> {
+                Ok(json!([]))
+            }
+        }
+    };
+}
+
+nullary_rpc_method!(
+    /// `status`: the current status of the node.
+    RpcStatusRequest,
+    "status",
+    RpcStatusResponse,
+    RpcStatusError
+);
+nullary_rpc_method!(
+    /// `health`: fails unless the node is healthy.
+    RpcHealthRequest,
+    "health",
+    RpcHealthResponse,
+    RpcStatusError
+);
+nullary_rpc_method!(
+    /// `network_info`: peers the node is connected to.
+    RpcNetworkInfoRequest,
+    "network_info",
+    RpcNetworkInfoResponse,
+    RpcNetworkInfoError
+);
+nullary_rpc_method!(
+    /// `client_config`: the client configuration of the node.
+    RpcClientConfigRequest,
+    "client_config",
+    Value,
+    RpcClientConfigError
+);
+nullary_rpc_method!(
+    /// `EXPERIMENTAL_genesis_config`: the genesis config of the chain.  The
+    /// handler can't fail, so any error is reported as unrecognized.
+    RpcGenesisConfigRequest,
+    "EXPERIMENTAL_genesis_config",
+    unc_chain_configs::GenesisConfig,
+    Value
+);
+
+/// `broadcast_tx_async`: submits the transaction and returns its hash right
+/// away.  The handler can't fail, so any error is reported as unrecognized.
+#[derive(Debug, Clone)]
+pub struct RpcBroadcastTxAsyncRequest {
+    pub signed_transaction: SignedTransaction,
+}
+
+impl RpcMethod for RpcBroadcastTxAsyncRequest {
+    type Response = CryptoHash;
+    type Error = Value;
+
+    const METHOD_NAME: &'static str = "broadcast_tx_async";
+    const IDEMPOTENT: bool = false;
+
+    fn params(&self) -> Result<Value, serde_json::Error> {
+        serde_json::to_value([&self.signed_transaction])
+    }
+}
+
+/// `broadcast_tx_commit`: submits the transaction and waits until it's
+/// executed.
+#[derive(Debug, Clone)]
+pub struct RpcBroadcastTxCommitRequest {
+    pub signed_transaction: SignedTransaction,
+}
+
+impl RpcMethod for RpcBroadcastTxCommitRequest {
+    type Response = RpcTransactionResponse;
+    type Error = RpcTransactionError;
+
+    const METHOD_NAME: &'static str = "broadcast_tx_commit";
+    const IDEMPOTENT: bool = false;
+
+    fn params(&self) -> Result<Value, serde_json::Error> {
+        serde_json::to_value([&self.signed_transaction])
+    }
+}
+
+/// `EXPERIMENTAL_tx_status`: same as `tx`, but the outcome also includes the
+/// receipts.
+#[derive(Debug)]
+pub struct RpcExperimentalTxStatusRequest(pub RpcTransactionStatusRequest);
+
+impl RpcMethod for RpcExperimentalTxStatusRequest {
+    type Response = RpcTransactionResponse;
+    type Error = RpcTransactionError;
+
+    const METHOD_NAME: &'static str = "EXPERIMENTAL_tx_status";
+
+    fn params(&self) -> Result<Value, serde_json::Error> {
+        serde_json::to_value(&self.0)
+    }
+}
+
+impl RpcMethod for RpcGasPriceRequest {
+    type Response = RpcGasPriceResponse;
+    type Error = RpcGasPriceError;
+
+    const METHOD_NAME: &'static str = "gas_price";
+
+    fn params(&self) -> Result<Value, serde_json::Error> {
+        // The server only accepts the block id as a positional param.
+        serde_json::to_value([&self.block_id])
+    }
+}
+
+impl RpcMethod for RpcValidatorRequest {
+    type Response = RpcValidatorResponse;
+    type Error = RpcValidatorError;
+
+    const METHOD_NAME: &'static str = "validators";
+
+    fn params(&self) -> Result<Value, serde_json::Error> {
+        match &self.epoch_reference {
+            EpochReference::Latest => Ok(json!([null])),
+            _ => serde_json::to_value(self),
+        }
+    }
+}
+
+rpc_method!(RpcAllMinersRequest, "all_miners", RpcAllMinersResponse, RpcAllMinersError);
+rpc_method!(RpcBlockRequest, "block", RpcBlockResponse, RpcBlockError);
+rpc_method!(RpcChunkRequest, "chunk", RpcChunkResponse, RpcChunkError);
+rpc_method!(
+    RpcLightClientExecutionProofRequest,
+    "light_client_proof",
+    RpcLightClientExecutionProofResponse,
+    RpcLightClientProofError
+);
+rpc_method!(
+    RpcLightClientNextBlockRequest,
+    "next_light_client_block",
+    RpcLightClientNextBlockResponse,
+    RpcLightClientNextBlockError
+);
+rpc_method!(RpcProviderRequest, "provider", RpcProviderResponse, RpcProviderError);
+rpc_method!(RpcQueryRequest, "query", RpcQueryResponse, RpcQueryError);
+rpc_method!(
+    RpcSendTransactionRequest,
+    "send_tx",
+    RpcTransactionResponse,
+    RpcTransactionError,
+    idempotent = false
+);
+rpc_method!(RpcTransactionStatusRequest, "tx", RpcTransactionResponse, RpcTransactionError);
+rpc_method!(
+    RpcStateChangesInBlockByTypeRequest,
+    "EXPERIMENTAL_changes",
+    RpcStateChangesInBlockResponse,
+    RpcStateChangesError
+);
+rpc_method!(
+    RpcStateChangesInBlockRequest,
+    "EXPERIMENTAL_changes_in_block",
+    RpcStateChangesInBlockByTypeResponse,
+    RpcStateChangesError
+);
+rpc_method!(
+    RpcMaintenanceWindowsRequest,
+    "EXPERIMENTAL_maintenance_windows",
+    RpcMaintenanceWindowsResponse,
+    RpcMaintenanceWindowsError
+);
+rpc_method!(
+    RpcPowerChangesRequest,
+    "EXPERIMENTAL_power_changes",
+    RpcPowerChangesResponse,
+    RpcStateChangesError
+);
+rpc_method!(
+    RpcProducerSelectionRequest,
+    "EXPERIMENTAL_producer_selection",
+    RpcProducerSelectionResponse,
+    RpcProducerSelectionError
+);
+rpc_method!(
+    RpcProtocolConfigRequest,
+    "EXPERIMENTAL_protocol_config",
+    RpcProtocolConfigResponse,
+    RpcProtocolConfigError
+);
+rpc_method!(RpcReceiptRequest, "EXPERIMENTAL_receipt", RpcReceiptResponse, RpcReceiptError);
+rpc_method!(
+    RpcSplitStorageInfoRequest,
+    "EXPERIMENTAL_split_storage_info",
+    RpcSplitStorageInfoResponse,
+    RpcSplitStorageInfoError
+);
+rpc_method!(
+    RpcValidatorsOrderedRequest,
+    "EXPERIMENTAL_validators_ordered",
+    RpcValidatorsOrderedResponse,
+    RpcValidatorError
+);
+rpc_method!(
+    RpcSandboxFastForwardRequest,
+    "sandbox_fast_forward",
+    RpcSandboxFastForwardResponse,
+    RpcSandboxFastForwardError,
+    idempotent = false
+);
+rpc_method!(
+    RpcSandboxPatchStateRequest,
+    "sandbox_patch_state",
+    RpcSandboxPatchStateResponse,
+    RpcSandboxPatchStateError
+);
@@ -0,0 +1,143 @@
+//! Async JSON-RPC client with typed requests, responses and errors.
+//!
+//! ```ignore
+//! let client = RpcClient::new("http://localhost:3030");
+//! let block = client
+//!     .call(RpcBlockRequest { block_reference: BlockReference::latest() })
+//!     .await?;
+//! ```
+//!
+//! Every method served over HTTP has a request type implementing
+//! [`RpcMethod`], see [`methods`].
+
+use std::time::Duration;
+
+use unc_jsonrpc_primitives::message::{from_slice, Message};
+
+pub use self::errors::{JsonRpcError, JsonRpcServerError, TransportError};
+pub use self::methods::RpcMethod;
+pub use self::retry::RetryPolicy;
+
+mod errors;
+pub mod methods;
+mod retry;
+
+/// Timeout for establishing connection.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Timeout for the whole request.  Generous since `broadcast_tx_commit` and
+/// `send_tx` wait for the transaction to be executed.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Max size of the payload the client can receive.
+const PAYLOAD_LIMIT: usize = 100 * 1024 * 1024;
+
+/// Async JSON-RPC client of a single node.
+///
+/// Cloning is cheap; clones share the connection pool.
+#[derive(Clone, Debug)]
+pub struct RpcClient {
+    server_addr: String,
+    client: reqwest::Client,
+    retry_policy: RetryPolicy,
+}
+
+impl RpcClient {
+    /// Creates a client of the node listening at `server_addr`, e.g.
+    /// `http://localhost:3030`, with the default [`RetryPolicy`].
+    pub fn new(server_addr: &str) -> Self {
+        let client = reqwest::Client::builder()
+            .connect_timeout(CONNECT_TIMEOUT)
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .expect("failed to build the HTTP client");
+        Self::with_client(server_addr, client)
+    }
+
+    /// Creates a client sending requests through the given HTTP client.
+    pub fn with_client(server_addr: &str, client: reqwest::Client) -> Self {
+        Self { server_addr: server_addr.to_string(), client, retry_policy: RetryPolicy::default() }
+    }
+
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    pub fn server_addr(&self) -> &str {
+        &self.server_addr
+    }
+
+    /// Calls the method of the request.
+    ///
+    /// Requests failing with a transient error are retried according to the
+    /// [`RetryPolicy`].  Methods which submit a transaction are only retried
+    /// if the policy opts in, see [`RetryPolicy::retry_non_idempotent`].
+    pub async fn call<M: RpcMethod>(
+        &self,
+        request: M,
+    ) -> Result<M::Response, JsonRpcError<M::Error>> {
+        let params = request.params().map_err(TransportError::InvalidParams)?;
+        let body: Vec<u8> = Message::request(M::METHOD_NAME.to_string(), params).into();
+        let max_retries = self.retry_policy.max_retries::<M>();
+        let mut retry = 0;
+        loop {
+            let result = self.call_once::<M>(body.clone()).await;
+            match result {
+                Err(error) if retry < max_retries && error.is_retryable() => {
+                    retry += 1;
+                    tokio::time::sleep(self.retry_policy.backoff(retry)).await;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    async fn call_once<M: RpcMethod>(
+        &self,
+        body: Vec<u8>,
+    ) -> Result<M::Response, JsonRpcError<M::Error>> {
+        let mut response = self
+            .client
+            .post(&self.server_addr)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body)
+            .send()
+            .await
+            .map_err(TransportError::Http)?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(TransportError::UnexpectedStatus { status }.into());
+        }
+
+        let mut payload = Vec::new();
+        while let Some(chunk) = response.chunk().await.map_err(TransportError::Http)? {
+            if payload.len() + chunk.len() > PAYLOAD_LIMIT {
+                return Err(TransportError::InvalidResponse {
+                    error_message: format!("payload exceeds {} bytes", PAYLOAD_LIMIT),
+                }
+                .into());
+            }
+            payload.extend_from_slice(&chunk);
+        }
+
+        let result = match from_slice(&payload) {
+            Ok(Message::Response(response)) => response.result,
+            Ok(message) => {
+                return Err(TransportError::InvalidResponse {
+                    error_message: format!("expected a response, got {:?}", message),
+                }
+                .into())
+            }
+            Err(err) => {
+                return Err(
+                    TransportError::InvalidResponse { error_message: format!("{:?}", err) }.into()
+                )
+            }
+        };
+        let value = result.map_err(|error| JsonRpcError::Server(error.into()))?;
+        serde_json::from_value(value).map_err(|err| {
+            TransportError::InvalidResponse { error_message: err.to_string() }.into()
+        })
+    }
+}
@@ -0,0 +1,98 @@
+use std::time::Duration;
+
+use super::RpcMethod;
+
+/// Defines how [`RpcClient`](super::RpcClient) retries requests which failed
+/// with a transient error: a connection failure, a timeout, an HTTP 5xx or
+/// 429 status, or an internal error of the node.
+///
+/// By default only idempotent queries are retried.  Methods which submit a
+/// transaction, like `send_tx` and `broadcast_tx_commit`, aren't: if the
+/// first attempt timed out after the node accepted the transaction, the
+/// retry can't tell that apart from a failure and reports a misleading
+/// result.  Set [`RetryPolicy::retry_non_idempotent`] to retry them too.
+///
+/// The delay before the n-th retry is `initial_backoff * multiplier^(n - 1)`,
+/// capped at `max_backoff`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RetryPolicy {
+    /// How many times a request is retried after the first attempt.
+    pub max_retries: u32,
+    /// Delay before the first retry.
+    pub initial_backoff: Duration,
+    /// Upper bound of the delay between retries.
+    pub max_backoff: Duration,
+    /// Factor the delay grows by after every retry.
+    pub multiplier: u32,
+    /// Whether methods which aren't idempotent, see
+    /// [`RpcMethod::IDEMPOTENT`](super::RpcMethod::IDEMPOTENT), are retried.
+    pub retry_non_idempotent: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(5),
+            multiplier: 2,
+            retry_non_idempotent: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Policy which never retries.
+    pub fn none() -> Self {
+        Self { max_retries: 0, ..Self::default() }
+    }
+
+    /// How many times a call of the method `M` is retried.
+    pub(crate) fn max_retries<M: RpcMethod>(&self) -> u32 {
+        if M::IDEMPOTENT || self.retry_non_idempotent {
+            self.max_retries
+        } else {
+            0
+        }
+    }
+
+    /// Delay before the retry number `retry`, counting from 1.
+    pub(crate) fn backoff(&self, retry: u32) -> Duration {
+        let factor = self.multiplier.saturating_pow(retry.saturating_sub(1));
+        self.initial_backoff.saturating_mul(factor).min(self.max_backoff)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RetryPolicy;
+    use crate::typed::methods::{RpcBroadcastTxCommitRequest, RpcStatusRequest};
+    use std::time::Duration;
+    use unc_jsonrpc_primitives::types::transactions::RpcSendTransactionRequest;
+
+    #[test]
+    fn test_backoff_grows_up_to_max() {
+        let policy = RetryPolicy {
+            max_retries: 10,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(1),
+            multiplier: 2,
+            retry_non_idempotent: false,
+        };
+        let backoffs: Vec<_> = (1..=6).map(|retry| policy.backoff(retry)).collect();
+        assert_eq!(backoffs, [100, 200, 400, 800, 1000, 1000].map(Duration::from_millis).to_vec());
+        assert_eq!(policy.backoff(u32::MAX), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_only_idempotent_methods_are_retried_by_default() {
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.max_retries::<RpcStatusRequest>(), 3);
+        assert_eq!(policy.max_retries::<RpcSendTransactionRequest>(), 0);
+        assert_eq!(policy.max_retries::<RpcBroadcastTxCommitRequest>(), 0);
+
+        let policy = RetryPolicy { retry_non_idempotent: true, ..RetryPolicy::default() };
+        assert_eq!(policy.max_retries::<RpcSendTransactionRequest>(), 3);
+        assert_eq!(policy.max_retries::<RpcBroadcastTxCommitRequest>(), 3);
+    }
+}
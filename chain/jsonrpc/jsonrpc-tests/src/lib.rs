@@ -7,7 +7,7 @@ use serde_json::json;
 use unc_chain_configs::GenesisConfig;
 use unc_client::test_utils::setup_no_network_with_validity_period_and_no_epoch_sync;
 use unc_client::ViewClientActor;
-use unc_jsonrpc::{start_http, RpcConfig};
+use unc_jsonrpc::{start_http, RpcConfig, RpcSubscriptionsConfig, StreamerFeed};
 use unc_jsonrpc_primitives::{
     message::{from_slice, Message},
     types::entity_debug::DummyEntityDebugHandler,
@@ -27,11 +27,44 @@ pub fn start_all(node_type: NodeType) -> (Addr<ViewClientActor>, tcp::ListenerAd
     start_all_with_validity_period_and_no_epoch_sync(node_type, 100, false)
 }
 
+/// Like [`start_all`] but with WebSocket subscriptions enabled, also returns
+/// their feed.
+pub fn start_all_with_streamer_feed(
+    node_type: NodeType,
+) -> (Addr<ViewClientActor>, tcp::ListenerAddr, StreamerFeed) {
+    let subscriptions_config = RpcSubscriptionsConfig { enabled: true, ..Default::default() };
+    start_all_with_subscriptions_config(node_type, subscriptions_config)
+}
+
+/// Like [`start_all_with_streamer_feed`] but with the given limits of the
+/// WebSocket subscriptions.
+pub fn start_all_with_subscriptions_config(
+    node_type: NodeType,
+    subscriptions_config: RpcSubscriptionsConfig,
+) -> (Addr<ViewClientActor>, tcp::ListenerAddr, StreamerFeed) {
+    start_all_with_streamer_feed_impl(node_type, 100, false, subscriptions_config)
+}
+
 pub fn start_all_with_validity_period_and_no_epoch_sync(
     node_type: NodeType,
     transaction_validity_period: NumBlocks,
     enable_doomslug: bool,
 ) -> (Addr<ViewClientActor>, tcp::ListenerAddr) {
+    let (view_client_addr, addr, _streamer_feed) = start_all_with_streamer_feed_impl(
+        node_type,
+        transaction_validity_period,
+        enable_doomslug,
+        RpcSubscriptionsConfig::default(),
+    );
+    (view_client_addr, addr)
+}
+
+fn start_all_with_streamer_feed_impl(
+    node_type: NodeType,
+    transaction_validity_period: NumBlocks,
+    enable_doomslug: bool,
+    subscriptions_config: RpcSubscriptionsConfig,
+) -> (Addr<ViewClientActor>, tcp::ListenerAddr, StreamerFeed) {
     let actor_handles: unc_client::test_utils::ActorHandlesForTesting =
         setup_no_network_with_validity_period_and_no_epoch_sync(
             vec!["test1".parse().unwrap()],
@@ -46,15 +79,18 @@ pub fn start_all_with_validity_period_and_no_epoch_sync(
         );
 
     let addr = tcp::ListenerAddr::reserve_for_test();
+    let config = RpcConfig { subscriptions_config, ..RpcConfig::new(addr) };
+    let streamer_feed = StreamerFeed::new(config.subscriptions_config.streamer_buffer_size);
     start_http(
-        RpcConfig::new(addr),
+        config,
         TEST_GENESIS_CONFIG.clone(),
         actor_handles.client_actor,
         actor_handles.view_client_actor.clone(),
         None,
         Arc::new(DummyEntityDebugHandler {}),
+        streamer_feed.clone(),
     );
-    (actor_handles.view_client_actor, addr)
+    (actor_handles.view_client_actor, addr, streamer_feed)
 }
 
 #[macro_export]
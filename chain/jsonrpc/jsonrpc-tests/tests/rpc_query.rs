@@ -8,6 +8,7 @@ use serde_json::json;
 use unc_actix_test_utils::run_actix;
 use unc_crypto::{KeyType, PublicKey, Signature};
 use unc_jsonrpc::client::{new_client, ChunkId};
+use unc_jsonrpc_primitives::types::changes::RpcPowerChangesRequest;
 use unc_jsonrpc_primitives::types::query::QueryResponseKind;
 use unc_jsonrpc_primitives::types::validator::RpcValidatorsOrderedRequest;
 use unc_network::test_utils::wait_or_timeout;
@@ -459,6 +460,23 @@ fn test_validators_ordered() {
     });
 }
 
+/// Query power changes of the genesis block, which has none.
+#[test]
+fn test_power_changes() {
+    test_with_client!(test_utils::NodeType::NonValidator, client, async move {
+        let block = client.block(BlockReference::BlockId(BlockId::Height(0))).await.unwrap();
+        let response = client
+            .EXPERIMENTAL_power_changes(RpcPowerChangesRequest {
+                block_reference: BlockReference::BlockId(BlockId::Height(0)),
+                account_ids: vec!["test1".parse().unwrap()],
+            })
+            .await
+            .unwrap();
+        assert_eq!(response.block_hash, block.header.hash);
+        assert!(response.changes.is_empty());
+    });
+}
+
 /// Retrieve genesis config via JSON RPC.
 /// WARNING: Be mindful about changing genesis structure as it is part of the public protocol!
 #[test]
@@ -546,6 +564,57 @@ fn test_invalid_methods() {
     });
 }
 
+#[test]
+fn test_batch_requests() {
+    test_with_client!(test_utils::NodeType::NonValidator, client, async move {
+        let post = |json: serde_json::Value| {
+            let request = client
+                .client
+                .post(&client.server_addr)
+                .insert_header(("Content-Type", "application/json"));
+            async move {
+                let response = &mut request.send_json(&json).await.unwrap();
+                response.json::<serde_json::Value>().await.unwrap()
+            }
+        };
+
+        let response = post(json!([
+            {"jsonrpc": "2.0", "id": 1, "method": "block", "params": {"block_id": 0}},
+            {"jsonrpc": "2.0", "method": "status", "params": []},
+            {"jsonrpc": "2.0", "id": "two", "method": "no_such_method", "params": []},
+            true,
+        ]))
+        .await;
+        let entries = response.as_array().unwrap();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0]["id"], json!(1));
+        assert_eq!(entries[0]["result"]["header"]["height"], json!(0));
+        assert_eq!(entries[1]["id"], json!("two"));
+        assert_eq!(entries[1]["error"]["code"], json!(-32_601));
+        assert_eq!(entries[2]["id"], json!(null));
+        assert_eq!(entries[2]["error"]["code"], json!(-32_600));
+
+        // Notifications are never answered, not even with an empty batch.
+        let response = client
+            .client
+            .post(&client.server_addr)
+            .insert_header(("Content-Type", "application/json"))
+            .send_json(&json!([{"jsonrpc": "2.0", "method": "status", "params": []}]))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 204);
+
+        let response = post(json!([])).await;
+        assert_eq!(response["error"]["code"], json!(-32_600));
+
+        let oversized: Vec<_> = (0..101)
+            .map(|id| json!({"jsonrpc": "2.0", "id": id, "method": "status", "params": []}))
+            .collect();
+        let response = post(json!(oversized)).await;
+        assert_eq!(response["error"]["code"], json!(-32_600));
+    });
+}
+
 #[test]
 fn test_get_chunk_with_object_in_params() {
     test_with_client!(test_utils::NodeType::NonValidator, client, async move {
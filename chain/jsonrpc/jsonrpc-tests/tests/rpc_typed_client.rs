@@ -0,0 +1,90 @@
+use actix::System;
+
+use unc_actix_test_utils::run_actix;
+use unc_jsonrpc::client::typed::methods::{RpcHealthRequest, RpcStatusRequest};
+use unc_jsonrpc::client::typed::{RetryPolicy, RpcClient};
+use unc_jsonrpc_primitives::types::blocks::{RpcBlockError, RpcBlockRequest};
+use unc_jsonrpc_primitives::types::validator::RpcValidatorRequest;
+use unc_o11y::testonly::init_test_logger;
+use unc_primitives::types::{BlockId, BlockReference, EpochReference};
+
+use unc_jsonrpc_tests as test_utils;
+
+macro_rules! test_with_typed_client {
+    ($node_type:expr, $client:ident, $block:expr) => {
+        init_test_logger();
+
+        run_actix(async {
+            let (_view_client_addr, addr) = test_utils::start_all($node_type);
+
+            let $client =
+                RpcClient::new(&format!("http://{}", addr)).with_retry_policy(RetryPolicy::none());
+
+            actix::spawn(async move {
+                $block.await;
+                System::current().stop();
+            });
+        });
+    };
+}
+
+/// Retrieve the genesis block via the typed client.
+#[test]
+fn test_typed_client_block() {
+    test_with_typed_client!(test_utils::NodeType::NonValidator, client, async move {
+        let block = client
+            .call(RpcBlockRequest { block_reference: BlockReference::BlockId(BlockId::Height(0)) })
+            .await
+            .unwrap();
+        assert_eq!(block.block_view.author, "test1");
+        assert_eq!(block.block_view.header.height, 0);
+    });
+}
+
+/// Methods without params are sent with empty params.
+#[test]
+fn test_typed_client_status_and_health() {
+    test_with_typed_client!(test_utils::NodeType::NonValidator, client, async move {
+        let status = client.call(RpcStatusRequest).await.unwrap();
+        assert_eq!(status.status_response.chain_id, "unittest");
+        client.call(RpcHealthRequest).await.unwrap();
+    });
+}
+
+/// `validators` for the latest epoch is sent as a positional `null`.
+#[test]
+fn test_typed_client_validators_latest() {
+    test_with_typed_client!(test_utils::NodeType::Validator, client, async move {
+        let validators = client
+            .call(RpcValidatorRequest { epoch_reference: EpochReference::Latest })
+            .await
+            .unwrap();
+        assert_eq!(
+            validators
+                .validator_info
+                .current_validators
+                .into_iter()
+                .map(|v| v.account_id)
+                .collect::<Vec<_>>(),
+            vec!["test1"]
+        );
+    });
+}
+
+/// Handler errors are decoded into the error type of the method.
+#[test]
+fn test_typed_client_handler_error() {
+    test_with_typed_client!(test_utils::NodeType::NonValidator, client, async move {
+        let error = client
+            .call(RpcBlockRequest {
+                block_reference: BlockReference::BlockId(BlockId::Height(1_000_000)),
+            })
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(error.handler_error(), Some(RpcBlockError::UnknownBlock { .. })),
+            "{:?}",
+            error
+        );
+    });
+}
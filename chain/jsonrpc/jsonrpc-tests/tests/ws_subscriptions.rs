@@ -0,0 +1,255 @@
+use std::fmt::Debug;
+
+use actix::{Addr, System};
+use awc::error::WsClientError;
+use awc::http::StatusCode;
+use awc::ws;
+use futures::{Sink, SinkExt, Stream, StreamExt};
+use serde_json::{json, Value};
+
+use unc_actix_test_utils::run_actix;
+use unc_client::{GetBlock, ViewClientActor};
+use unc_indexer_primitives::StreamerMessage;
+use unc_o11y::testonly::init_test_logger;
+use unc_o11y::WithSpanContextExt;
+use unc_primitives::types::{BlockId, BlockReference};
+
+use unc_jsonrpc::RpcSubscriptionsConfig;
+use unc_jsonrpc_tests as test_utils;
+
+async fn request<C, E>(connection: &mut C, method: &str, params: Value) -> Value
+where
+    C: Sink<ws::Message> + Stream<Item = Result<ws::Frame, E>> + Unpin,
+    <C as Sink<ws::Message>>::Error: Debug,
+    E: Debug,
+{
+    let request = json!({"jsonrpc": "2.0", "id": "dontcare", "method": method, "params": params});
+    connection.send(ws::Message::Text(request.to_string().into())).await.unwrap();
+    next_message(connection).await
+}
+
+async fn next_message<C, E>(connection: &mut C) -> Value
+where
+    C: Stream<Item = Result<ws::Frame, E>> + Unpin,
+    E: Debug,
+{
+    loop {
+        match connection.next().await.unwrap().unwrap() {
+            ws::Frame::Text(text) => return serde_json::from_slice(&text).unwrap(),
+            ws::Frame::Ping(_) | ws::Frame::Pong(_) => continue,
+            frame => panic!("unexpected frame {:?}", frame),
+        }
+    }
+}
+
+/// Message with the genesis block and no shards.
+async fn genesis_message(view_client: &Addr<ViewClientActor>) -> StreamerMessage {
+    let block = view_client
+        .send(GetBlock(BlockReference::BlockId(BlockId::Height(0))).with_span_context())
+        .await
+        .unwrap()
+        .unwrap();
+    StreamerMessage { block, shards: vec![] }
+}
+
+/// Subscribe to blocks over WebSocket and receive a published block.
+#[test]
+fn test_subscribe_to_blocks() {
+    init_test_logger();
+
+    run_actix(async {
+        let (view_client, addr, streamer_feed) =
+            test_utils::start_all_with_streamer_feed(test_utils::NodeType::NonValidator);
+
+        actix::spawn(async move {
+            let (_, mut connection) =
+                awc::Client::new().ws(format!("ws://{}/ws", addr)).connect().await.unwrap();
+
+            let response = request(&mut connection, "subscribe", json!({"type": "blocks"})).await;
+            assert_eq!(response["result"], json!({"subscription": 0}));
+            let response = request(
+                &mut connection,
+                "subscribe",
+                json!({"type": "outcomes", "account_id": "test1"}),
+            )
+            .await;
+            assert_eq!(response["result"], json!({"subscription": 1}));
+
+            streamer_feed.publish(genesis_message(&view_client).await);
+            let notification = next_message(&mut connection).await;
+            assert_eq!(notification["method"], json!("subscription"));
+            assert_eq!(notification["params"]["subscription"], json!(0));
+            assert_eq!(notification["params"]["result"]["header"]["height"], json!(0));
+
+            let response = request(&mut connection, "unsubscribe", json!([0])).await;
+            assert_eq!(response["result"], json!({"subscription": 0}));
+            let response = request(&mut connection, "unsubscribe", json!([0])).await;
+            assert_eq!(response["error"]["cause"]["name"], json!("UNKNOWN_SUBSCRIPTION"));
+
+            let response = request(&mut connection, "block", json!({"finality": "final"})).await;
+            assert_eq!(response["error"]["code"], json!(-32_601));
+
+            System::current().stop();
+        });
+    });
+}
+
+/// The node publishes its new final blocks without anybody feeding it.
+#[test]
+fn test_subscribe_to_new_final_blocks() {
+    init_test_logger();
+
+    run_actix(async {
+        let (_, addr, _) =
+            test_utils::start_all_with_streamer_feed(test_utils::NodeType::Validator);
+
+        actix::spawn(async move {
+            let (_, mut connection) =
+                awc::Client::new().ws(format!("ws://{}/ws", addr)).connect().await.unwrap();
+
+            let response = request(&mut connection, "subscribe", json!({"type": "blocks"})).await;
+            assert_eq!(response["result"], json!({"subscription": 0}));
+
+            let first = next_message(&mut connection).await;
+            let second = next_message(&mut connection).await;
+            let first_height = first["params"]["result"]["header"]["height"].as_u64().unwrap();
+            let second_height = second["params"]["result"]["header"]["height"].as_u64().unwrap();
+            assert!(first_height > 0);
+            assert!(second_height > first_height);
+
+            System::current().stop();
+        });
+    });
+}
+
+/// Subscriptions are not served unless enabled in the config.
+#[test]
+fn test_subscriptions_disabled() {
+    init_test_logger();
+
+    run_actix(async {
+        let (_, addr) = test_utils::start_all(test_utils::NodeType::NonValidator);
+
+        actix::spawn(async move {
+            match awc::Client::new().ws(format!("ws://{}/ws", addr)).connect().await {
+                Err(WsClientError::InvalidResponseStatus(status)) => {
+                    assert_eq!(status, StatusCode::NOT_FOUND)
+                }
+                Err(err) => panic!("unexpected error {:?}", err),
+                Ok(_) => panic!("subscriptions are served while disabled"),
+            }
+
+            System::current().stop();
+        });
+    });
+}
+
+/// Connections above `max_connections` are refused.
+#[test]
+fn test_connection_limit() {
+    init_test_logger();
+
+    run_actix(async {
+        let config =
+            RpcSubscriptionsConfig { enabled: true, max_connections: 1, ..Default::default() };
+        let (_, addr, _) = test_utils::start_all_with_subscriptions_config(
+            test_utils::NodeType::NonValidator,
+            config,
+        );
+
+        actix::spawn(async move {
+            let url = format!("ws://{}/ws", addr);
+            let (_, mut connection) = awc::Client::new().ws(&url).connect().await.unwrap();
+            let response = request(&mut connection, "subscribe", json!({"type": "blocks"})).await;
+            assert_eq!(response["result"], json!({"subscription": 0}));
+
+            match awc::Client::new().ws(&url).connect().await {
+                Err(WsClientError::InvalidResponseStatus(status)) => {
+                    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE)
+                }
+                Err(err) => panic!("unexpected error {:?}", err),
+                Ok(_) => panic!("connection above the limit was accepted"),
+            }
+
+            System::current().stop();
+        });
+    });
+}
+
+/// A client whose queue of notifications fills up is disconnected instead of
+/// being waited for.
+#[test]
+fn test_disconnect_on_too_many_pending_messages() {
+    init_test_logger();
+
+    run_actix(async {
+        let config =
+            RpcSubscriptionsConfig { enabled: true, max_pending_messages: 1, ..Default::default() };
+        let (view_client, addr, streamer_feed) = test_utils::start_all_with_subscriptions_config(
+            test_utils::NodeType::NonValidator,
+            config,
+        );
+
+        actix::spawn(async move {
+            let (_, mut connection) =
+                awc::Client::new().ws(format!("ws://{}/ws", addr)).connect().await.unwrap();
+            let response = request(&mut connection, "subscribe", json!({"type": "blocks"})).await;
+            assert_eq!(response["result"], json!({"subscription": 0}));
+
+            let message = genesis_message(&view_client).await;
+            for _ in 0..3 {
+                streamer_feed.publish(message.clone());
+            }
+
+            // Only the notification which fit into the queue is delivered
+            // before the connection ends.
+            let mut notifications = 0;
+            loop {
+                match connection.next().await {
+                    Some(Ok(ws::Frame::Text(_))) => notifications += 1,
+                    Some(Ok(ws::Frame::Ping(_) | ws::Frame::Pong(_))) => continue,
+                    Some(Ok(ws::Frame::Close(_)) | Err(_)) | None => break,
+                    Some(Ok(frame)) => panic!("unexpected frame {:?}", frame),
+                }
+            }
+            assert_eq!(notifications, 1);
+
+            System::current().stop();
+        });
+    });
+}
+
+/// A client lagging further behind the feed than it buffers is disconnected.
+#[test]
+fn test_disconnect_lagging_client() {
+    init_test_logger();
+
+    run_actix(async {
+        let config =
+            RpcSubscriptionsConfig { enabled: true, streamer_buffer_size: 1, ..Default::default() };
+        let (view_client, addr, streamer_feed) = test_utils::start_all_with_subscriptions_config(
+            test_utils::NodeType::NonValidator,
+            config,
+        );
+
+        actix::spawn(async move {
+            let (_, mut connection) =
+                awc::Client::new().ws(format!("ws://{}/ws", addr)).connect().await.unwrap();
+            let response = request(&mut connection, "subscribe", json!({"type": "blocks"})).await;
+            assert_eq!(response["result"], json!({"subscription": 0}));
+
+            // The session doesn't get to run before all messages are published.
+            let message = genesis_message(&view_client).await;
+            for _ in 0..3 {
+                streamer_feed.publish(message.clone());
+            }
+
+            match connection.next().await.unwrap().unwrap() {
+                ws::Frame::Close(Some(reason)) => assert_eq!(reason.code, ws::CloseCode::Policy),
+                frame => panic!("unexpected frame {:?}", frame),
+            }
+
+            System::current().stop();
+        });
+    });
+}
@@ -1,37 +1,35 @@
 use serde_json::Value;
 
-use unc_client_primitives::types::GetAllMinersError;
+use unc_client_primitives::types::{GetAllMiners, GetAllMinersError};
 use unc_jsonrpc_primitives::errors::RpcParseError;
 use unc_jsonrpc_primitives::types::all_miners::{RpcAllMinersError, RpcAllMinersRequest};
 use unc_primitives::hash::CryptoHash;
+use unc_primitives::types::{BlockId, BlockReference};
 
-use super::{RpcFrom, RpcRequest};
+use super::{Params, RpcFrom, RpcRequest};
 
 impl RpcRequest for RpcAllMinersRequest {
     fn parse(value: Value) -> Result<Self, RpcParseError> {
-        // Extract block_hash_str from value
-        let block_hash_str = value.get("block_hash").and_then(|v| v.as_str()).ok_or_else(|| {
-            RpcParseError("block_hash not found or not a string".parse().unwrap())
-        })?;
-
-        // Decode the base58-encoded string to bytes
-        let bytes = bs58::decode(block_hash_str)
-            .into_vec()
-            .map_err(|_| RpcParseError("Invalid base58-encoded hash".parse().unwrap()))?;
-
-        // Ensure the decoded bytes have the correct length for a CryptoHash
-        if bytes.len() != 32 {
-            return Err(RpcParseError(
-                "Decoded hash does not match expected length".parse().unwrap(),
-            ));
+        // `{"block_hash": ...}` is the original form of the request, keep accepting it.
+        if let Some(block_hash) = value.get("block_hash") {
+            let block_hash: CryptoHash = serde_json::from_value(block_hash.clone())
+                .map_err(|err| RpcParseError(format!("Failed to parse block_hash: {}", err)))?;
+            return Ok(Self::BlockReference {
+                block_reference: BlockReference::BlockId(BlockId::Hash(block_hash)),
+            });
         }
+        Params::parse(value)
+    }
+}
 
-        // Construct the CryptoHash from the decoded bytes
-        let block_hash: CryptoHash = block_hash_str.parse().map_err(|_| {
-            RpcParseError("Failed to parse block_hash from base58".parse().unwrap())
-        })?;
-
-        Ok(Self { block_hash })
+impl RpcFrom<RpcAllMinersRequest> for GetAllMiners {
+    fn rpc_from(request: RpcAllMinersRequest) -> Self {
+        match request {
+            RpcAllMinersRequest::EpochId { epoch_id } => Self::EpochId(epoch_id),
+            RpcAllMinersRequest::BlockReference { block_reference } => {
+                Self::BlockReference(block_reference)
+            }
+        }
     }
 }
 
@@ -45,6 +43,8 @@ impl RpcFrom<GetAllMinersError> for RpcAllMinersError {
     fn rpc_from(error: GetAllMinersError) -> Self {
         match error {
             GetAllMinersError::UnknownBlock { .. } => Self::UnknownBlock {},
+            GetAllMinersError::UnknownEpoch => Self::UnknownEpoch,
+            GetAllMinersError::NotSyncedYet => Self::NotSyncedYet,
             GetAllMinersError::IOError { error_message } => Self::InternalError { error_message },
             GetAllMinersError::Unreachable { ref error_message } => {
                 tracing::warn!(target: "jsonrpc", "Unreachable error occurred: {}", error_message);
@@ -56,3 +56,53 @@ impl RpcFrom<GetAllMinersError> for RpcAllMinersError {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::api::RpcRequest;
+    use unc_jsonrpc_primitives::types::all_miners::RpcAllMinersRequest;
+    use unc_primitives::hash::CryptoHash;
+    use unc_primitives::types::{BlockId, BlockReference, EpochId, Finality};
+
+    #[test]
+    fn test_parse_all_miners_params_legacy_block_hash() {
+        let block_hash = CryptoHash::hash_bytes(b"block");
+        let params = serde_json::json!({"block_hash": block_hash.to_string()});
+        assert_eq!(
+            RpcAllMinersRequest::parse(params).unwrap(),
+            RpcAllMinersRequest::BlockReference {
+                block_reference: BlockReference::BlockId(BlockId::Hash(block_hash)),
+            }
+        );
+        let params = serde_json::json!({"block_hash": "not a hash"});
+        assert!(RpcAllMinersRequest::parse(params).is_err());
+    }
+
+    #[test]
+    fn test_parse_all_miners_params_block_reference() {
+        let params = serde_json::json!({"block_id": 12345});
+        assert_eq!(
+            RpcAllMinersRequest::parse(params).unwrap(),
+            RpcAllMinersRequest::BlockReference {
+                block_reference: BlockReference::BlockId(BlockId::Height(12345)),
+            }
+        );
+        let params = serde_json::json!({"finality": "final"});
+        assert_eq!(
+            RpcAllMinersRequest::parse(params).unwrap(),
+            RpcAllMinersRequest::BlockReference {
+                block_reference: BlockReference::Finality(Finality::Final),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_all_miners_params_epoch_id() {
+        let epoch_id = CryptoHash::hash_bytes(b"epoch");
+        let params = serde_json::json!({"epoch_id": epoch_id.to_string()});
+        assert_eq!(
+            RpcAllMinersRequest::parse(params).unwrap(),
+            RpcAllMinersRequest::EpochId { epoch_id: EpochId(epoch_id) }
+        );
+    }
+}
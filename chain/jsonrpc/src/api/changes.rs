@@ -3,7 +3,8 @@ use serde_json::Value;
 use unc_client_primitives::types::{GetBlockError, GetStateChangesError};
 use unc_jsonrpc_primitives::errors::RpcParseError;
 use unc_jsonrpc_primitives::types::changes::{
-    RpcStateChangesError, RpcStateChangesInBlockByTypeRequest, RpcStateChangesInBlockRequest,
+    RpcPowerChangesRequest, RpcStateChangesError, RpcStateChangesInBlockByTypeRequest,
+    RpcStateChangesInBlockRequest,
 };
 
 use super::{Params, RpcFrom, RpcRequest};
@@ -20,6 +21,12 @@ impl RpcRequest for RpcStateChangesInBlockByTypeRequest {
     }
 }
 
+impl RpcRequest for RpcPowerChangesRequest {
+    fn parse(value: Value) -> Result<Self, RpcParseError> {
+        Params::parse(value)
+    }
+}
+
 impl RpcFrom<actix::MailboxError> for RpcStateChangesError {
     fn rpc_from(error: actix::MailboxError) -> Self {
         Self::InternalError { error_message: error.to_string() }
@@ -53,6 +60,12 @@ impl RpcFrom<GetStateChangesError> for RpcStateChangesError {
                 Self::UnknownBlock { error_message }
             }
             GetStateChangesError::NotSyncedYet => Self::NotSyncedYet,
+            GetStateChangesError::UnavailableShard { requested_shard_id } => {
+                Self::UnavailableShard { requested_shard_id }
+            }
+            GetStateChangesError::GarbageCollectedBlock { block_height, block_hash } => {
+                Self::GarbageCollectedBlock { block_height, block_hash }
+            }
             GetStateChangesError::Unreachable { ref error_message } => {
                 tracing::warn!(target: "jsonrpc", "Unreachable error occurred: {}", error_message);
                 crate::metrics::RPC_UNREACHABLE_ERROR_COUNT
@@ -13,12 +13,14 @@ mod gas_price;
 mod light_client;
 mod maintenance;
 mod network_info;
+mod producer_selection;
 mod provider;
 mod query;
 mod receipts;
 mod sandbox;
 mod split_storage;
 mod status;
+mod subscriptions;
 mod transactions;
 mod validator;
 
@@ -0,0 +1,49 @@
+use serde_json::Value;
+
+use unc_client_primitives::types::GetProducerSelectionError;
+use unc_jsonrpc_primitives::errors::RpcParseError;
+use unc_jsonrpc_primitives::types::producer_selection::{
+    RpcProducerSelectionError, RpcProducerSelectionRequest,
+};
+use unc_primitives::types::BlockReference;
+
+use super::{Params, RpcFrom, RpcRequest};
+
+impl RpcRequest for RpcProducerSelectionRequest {
+    fn parse(value: Value) -> Result<Self, RpcParseError> {
+        let block_reference = Params::new(value)
+            .try_singleton(|block_id| Ok(BlockReference::BlockId(block_id)))
+            .unwrap_or_parse()?;
+        Ok(Self { block_reference })
+    }
+}
+
+impl RpcFrom<actix::MailboxError> for RpcProducerSelectionError {
+    fn rpc_from(error: actix::MailboxError) -> Self {
+        Self::InternalError { error_message: error.to_string() }
+    }
+}
+
+impl RpcFrom<GetProducerSelectionError> for RpcProducerSelectionError {
+    fn rpc_from(error: GetProducerSelectionError) -> Self {
+        match error {
+            GetProducerSelectionError::UnknownBlock { error_message } => {
+                Self::UnknownBlock { error_message }
+            }
+            GetProducerSelectionError::NotSyncedYet => Self::NotSyncedYet,
+            GetProducerSelectionError::NoSelection { block_hash, error_message } => {
+                Self::NoSelection { block_hash, error_message }
+            }
+            GetProducerSelectionError::IOError { error_message } => {
+                Self::InternalError { error_message }
+            }
+            GetProducerSelectionError::Unreachable { ref error_message } => {
+                tracing::warn!(target: "jsonrpc", "Unreachable error occurred: {}", error_message);
+                crate::metrics::RPC_UNREACHABLE_ERROR_COUNT
+                    .with_label_values(&["RpcProducerSelectionError"])
+                    .inc();
+                Self::InternalError { error_message: error.to_string() }
+            }
+        }
+    }
+}
@@ -173,6 +173,9 @@ impl RpcFrom<unc_primitives::views::QueryResponseKind>
             unc_primitives::views::QueryResponseKind::ChipList(chip_list) => {
                 Self::ChipList(chip_list)
             }
+            unc_primitives::views::QueryResponseKind::ChipsPage(chips_page) => {
+                Self::ChipsPage(chips_page)
+            }
         }
     }
 }
@@ -19,9 +19,9 @@ use unc_chain_configs::GenesisConfig;
 use unc_client::{
     ClientActor, DebugStatus, GetBlock, GetBlockProof, GetChunk, GetClientConfig,
     GetExecutionOutcome, GetGasPrice, GetMaintenanceWindows, GetNetworkInfo,
-    GetNextLightClientBlock, GetProtocolConfig, GetReceipt, GetStateChanges,
-    GetStateChangesInBlock, GetValidatorInfo, GetValidatorOrdered, ProcessTxRequest,
-    ProcessTxResponse, Query, Status, TxStatus, ViewClientActor,
+    GetNextLightClientBlock, GetPowerChanges, GetProducerSelection, GetProtocolConfig, GetReceipt,
+    GetStateChanges, GetStateChangesInBlock, GetValidatorInfo, GetValidatorOrdered,
+    ProcessTxRequest, ProcessTxResponse, Query, Status, TxStatus, ViewClientActor,
 };
 use unc_client_primitives::types::{GetAllMiners, GetProvider, GetSplitStorageInfo};
 pub use unc_jsonrpc_client as client;
@@ -47,6 +47,9 @@ use unc_primitives::views::{QueryRequest, TxExecutionStatus};
 
 mod api;
 mod metrics;
+mod subscriptions;
+
+pub use subscriptions::StreamerFeed;
 
 #[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug)]
 pub struct RpcPollingConfig {
@@ -67,11 +70,48 @@ impl Default for RpcPollingConfig {
 pub struct RpcLimitsConfig {
     /// Maximum byte size of the json payload.
     pub json_payload_max_size: usize,
+    /// Maximum number of entries in a single JSON RPC batch request.
+    #[serde(default = "default_max_batch_size")]
+    pub max_batch_size: usize,
+}
+
+fn default_max_batch_size() -> usize {
+    100
 }
 
 impl Default for RpcLimitsConfig {
     fn default() -> Self {
-        Self { json_payload_max_size: 10 * 1024 * 1024 }
+        Self { json_payload_max_size: 10 * 1024 * 1024, max_batch_size: default_max_batch_size() }
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+pub struct RpcSubscriptionsConfig {
+    /// Whether WebSocket subscriptions are served at `/ws`.  While disabled the
+    /// node doesn't build streamer messages for new blocks at all.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Maximum number of simultaneously open WebSocket connections.
+    pub max_connections: usize,
+    /// Maximum number of active subscriptions on a single connection.
+    pub max_subscriptions_per_connection: usize,
+    /// Maximum number of messages queued for a single connection.  Connections
+    /// which don't read their notifications fast enough are closed.
+    pub max_pending_messages: usize,
+    /// Number of streamer messages buffered for all connections.  Connections
+    /// lagging further behind than that are closed.
+    pub streamer_buffer_size: usize,
+}
+
+impl Default for RpcSubscriptionsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_connections: 1000,
+            max_subscriptions_per_connection: 32,
+            max_pending_messages: 1024,
+            streamer_buffer_size: 32,
+        }
     }
 }
 
@@ -88,6 +128,8 @@ pub struct RpcConfig {
     pub polling_config: RpcPollingConfig,
     #[serde(default)]
     pub limits_config: RpcLimitsConfig,
+    #[serde(default)]
+    pub subscriptions_config: RpcSubscriptionsConfig,
     // If true, enable some debug RPC endpoints (like one to get the latest block).
     // We disable it by default, as some of those endpoints might be quite CPU heavy.
     #[serde(default = "default_enable_debug_rpc")]
@@ -106,6 +148,7 @@ impl Default for RpcConfig {
             cors_allowed_origins: vec!["*".to_owned()],
             polling_config: Default::default(),
             limits_config: Default::default(),
+            subscriptions_config: Default::default(),
             enable_debug_rpc: false,
             experimental_debug_pages_src_path: None,
         }
@@ -150,6 +193,8 @@ impl unc_jsonrpc_primitives::types::transactions::RpcTransactionError {
         match resp {
             ProcessTxResponse::InvalidTx(context) => Self::InvalidTransaction { context },
             ProcessTxResponse::NoResponse => Self::TimeoutError,
+            ProcessTxResponse::PoolQuotaExceeded => Self::PoolQuotaExceeded,
+            ProcessTxResponse::ReplacementRejected => Self::ReplacementRejected,
             ProcessTxResponse::DoesNotTrackShard | ProcessTxResponse::RequestRouted => {
                 Self::DoesNotTrackShard
             }
@@ -224,6 +269,7 @@ struct JsonRpcHandler {
     view_client_addr: Addr<ViewClientActor>,
     peer_manager_addr: Option<Addr<PeerManagerActor>>,
     polling_config: RpcPollingConfig,
+    max_batch_size: usize,
     genesis_config: GenesisConfig,
     enable_debug_rpc: bool,
     debug_pages_src_path: Option<PathBuf>,
@@ -231,18 +277,61 @@ struct JsonRpcHandler {
 }
 
 impl JsonRpcHandler {
-    pub async fn process(&self, message: Message) -> Result<Message, HttpError> {
+    /// Returns `None` when nothing must be sent back, i.e. for a batch of
+    /// notifications.
+    pub async fn process(&self, message: Message) -> Result<Option<Message>, HttpError> {
         let id = message.id();
         match message {
             Message::Request(request) => {
-                Ok(Message::response(id, self.process_request(request).await))
+                Ok(Some(Message::response(id, self.process_request(request).await)))
             }
-            _ => Ok(Message::error(RpcError::parse_error(
+            Message::Batch(messages) => Ok(self.process_batch(messages).await),
+            _ => Ok(Some(Message::error(RpcError::parse_error(
                 "JSON RPC Request format was expected".to_owned(),
-            ))),
+            )))),
         }
     }
 
+    /// Processes all entries of a batch concurrently.
+    ///
+    /// Every request gets its own response (in the order of the batch) and is
+    /// accounted for in the per-method metrics just like a standalone request.
+    /// Notifications are processed as well but get no response, so a batch of
+    /// notifications only is answered with nothing at all. Entries which are
+    /// neither are answered with an Invalid Request error instead of failing
+    /// the whole batch.
+    async fn process_batch(&self, messages: Vec<Message>) -> Option<Message> {
+        if messages.is_empty() {
+            return Some(Message::error(RpcError::invalid_request("Empty batch".to_owned())));
+        }
+        if messages.len() > self.max_batch_size {
+            return Some(Message::error(RpcError::invalid_request(format!(
+                "Batch of {} entries exceeds the limit of {} entries",
+                messages.len(),
+                self.max_batch_size
+            ))));
+        }
+        metrics::RPC_BATCH_SIZE.observe(messages.len() as f64);
+        let responses = futures::future::join_all(messages.into_iter().map(|message| async move {
+            match message {
+                Message::Request(request) => {
+                    let id = request.id.clone();
+                    Some(Message::response(id, self.process_request(request).await))
+                }
+                Message::Notification(notification) => {
+                    let _ = self.process_request(notification.into()).await;
+                    None
+                }
+                _ => Some(Message::error(RpcError::invalid_request(
+                    "JSON RPC Request format was expected".to_owned(),
+                ))),
+            }
+        }))
+        .await;
+        let responses: Vec<_> = responses.into_iter().flatten().collect();
+        (!responses.is_empty()).then(|| Message::Batch(responses))
+    }
+
     // `process_request` increments affected metrics but the request processing is done by
     // `process_request_internal`.
     async fn process_request(&self, request: Request) -> Result<Value, RpcError> {
@@ -300,6 +389,13 @@ impl JsonRpcHandler {
                     QueryRequest::ViewAccessKey { .. } => "query_view_access_key",
                     QueryRequest::ViewAccessKeyList { .. } => "query_view_access_key_list",
                     QueryRequest::ViewChipList { .. } => "query_view_chip_list",
+                    QueryRequest::ViewChips { include_proof, .. } => {
+                        if include_proof {
+                            "query_view_chips_with_proof"
+                        } else {
+                            "query_view_chips"
+                        }
+                    }
                     QueryRequest::CallFunction { .. } => "query_call_function",
                 };
                 (metrics_name.to_string(), process_query_response(self.query(params).await))
@@ -367,6 +463,12 @@ impl JsonRpcHandler {
                 })
                 .await
             }
+            "EXPERIMENTAL_power_changes" => {
+                process_method_call(request, |params| self.power_changes_in_block(params)).await
+            }
+            "EXPERIMENTAL_producer_selection" => {
+                process_method_call(request, |params| self.producer_selection(params)).await
+            }
             "EXPERIMENTAL_protocol_config" => {
                 process_method_call(request, |params| self.protocol_config(params)).await
             }
@@ -871,11 +973,8 @@ impl JsonRpcHandler {
         unc_jsonrpc_primitives::types::all_miners::RpcAllMinersResponse,
         unc_jsonrpc_primitives::types::all_miners::RpcAllMinersError,
     > {
-        let all_miners = self.view_client_send(GetAllMiners(request_data.block_hash)).await?;
-        Ok(unc_jsonrpc_primitives::types::all_miners::RpcAllMinersResponse {
-            total_power: all_miners.total_power,
-            miners: all_miners.miners,
-        })
+        let all_miners = self.view_client_send(GetAllMiners::rpc_from(request_data)).await?;
+        Ok(unc_jsonrpc_primitives::types::all_miners::RpcAllMinersResponse { all_miners })
     }
 
     async fn block(
@@ -889,6 +988,20 @@ impl JsonRpcHandler {
         Ok(unc_jsonrpc_primitives::types::blocks::RpcBlockResponse { block_view })
     }
 
+    async fn producer_selection(
+        &self,
+        request_data: unc_jsonrpc_primitives::types::producer_selection::RpcProducerSelectionRequest,
+    ) -> Result<
+        unc_jsonrpc_primitives::types::producer_selection::RpcProducerSelectionResponse,
+        unc_jsonrpc_primitives::types::producer_selection::RpcProducerSelectionError,
+    > {
+        let selection =
+            self.view_client_send(GetProducerSelection(request_data.block_reference)).await?;
+        Ok(unc_jsonrpc_primitives::types::producer_selection::RpcProducerSelectionResponse {
+            selection,
+        })
+    }
+
     async fn chunk(
         &self,
         request_data: unc_jsonrpc_primitives::types::chunks::RpcChunkRequest,
@@ -964,6 +1077,24 @@ impl JsonRpcHandler {
         })
     }
 
+    async fn power_changes_in_block(
+        &self,
+        request: unc_jsonrpc_primitives::types::changes::RpcPowerChangesRequest,
+    ) -> Result<
+        unc_jsonrpc_primitives::types::changes::RpcPowerChangesResponse,
+        unc_jsonrpc_primitives::types::changes::RpcStateChangesError,
+    > {
+        let block: unc_primitives::views::BlockView =
+            self.view_client_send(GetBlock(request.block_reference)).await?;
+
+        let block_hash = block.header.hash;
+        let changes = self
+            .view_client_send(GetPowerChanges { block_hash, account_ids: request.account_ids })
+            .await?;
+
+        Ok(unc_jsonrpc_primitives::types::changes::RpcPowerChangesResponse { block_hash, changes })
+    }
+
     async fn next_light_client_block(
         &self,
         request: unc_jsonrpc_primitives::types::light_client::RpcLightClientNextBlockRequest,
@@ -1313,8 +1444,10 @@ fn rpc_handler(
     handler: web::Data<JsonRpcHandler>,
 ) -> impl Future<Output = Result<HttpResponse, HttpError>> {
     let response = async move {
-        let message = handler.process(message.0).await?;
-        Ok(HttpResponse::Ok().json(&message))
+        match handler.process(message.0).await? {
+            Some(message) => Ok(HttpResponse::Ok().json(&message)),
+            None => Ok(HttpResponse::NoContent().finish()),
+        }
     };
     response.boxed()
 }
@@ -1518,6 +1651,10 @@ fn tx_execution_status_meets_expectations(
 /// configuration may also start another HTTP server just for providing
 /// Prometheus metrics (i.e. covering the `/metrics` path).
 ///
+/// If enabled in the config, WebSocket subscriptions are served at `/ws` with
+/// events taken from the `streamer_feed`, into which every new final block is
+/// published.
+///
 /// Returns a vector of servers that have been started.  Each server is returned
 /// as a tuple containing a name of the server (e.g. `"JSON RPC"`) which can be
 /// used in diagnostic messages and a [`actix_web::dev::Server`] object which
@@ -1529,6 +1666,7 @@ pub fn start_http(
     view_client_addr: Addr<ViewClientActor>,
     peer_manager_addr: Option<Addr<PeerManagerActor>>,
     entity_debug_handler: Arc<dyn EntityDebugHandler>,
+    streamer_feed: StreamerFeed,
 ) -> Vec<(&'static str, actix_web::dev::ServerHandle)> {
     let RpcConfig {
         addr,
@@ -1536,9 +1674,16 @@ pub fn start_http(
         cors_allowed_origins,
         polling_config,
         limits_config,
+        subscriptions_config,
         enable_debug_rpc,
         experimental_debug_pages_src_path: debug_pages_src_path,
     } = config;
+    let subscriptions_enabled = subscriptions_config.enabled;
+    if subscriptions_enabled {
+        actix::spawn(streamer_feed.clone().run_publisher(view_client_addr.clone()));
+    }
+    let subscriptions_state =
+        web::Data::new(subscriptions::SubscriptionsState::new(streamer_feed, subscriptions_config));
     let prometheus_addr = prometheus_addr.filter(|it| it != &addr.to_string());
     let cors_allowed_origins_clone = cors_allowed_origins.clone();
     info!(target:"network", "Starting http server at {}", addr);
@@ -1551,14 +1696,24 @@ pub fn start_http(
                 view_client_addr: view_client_addr.clone(),
                 peer_manager_addr: peer_manager_addr.clone(),
                 polling_config,
+                max_batch_size: limits_config.max_batch_size,
                 genesis_config: genesis_config.clone(),
                 enable_debug_rpc,
                 debug_pages_src_path: debug_pages_src_path.clone().map(Into::into),
                 entity_debug_handler: entity_debug_handler.clone(),
             }))
+            .app_data(subscriptions_state.clone())
             .app_data(web::JsonConfig::default().limit(limits_config.json_payload_max_size))
             .wrap(middleware::Logger::default())
             .service(web::resource("/").route(web::post().to(rpc_handler)))
+            .configure(|cfg| {
+                if subscriptions_enabled {
+                    cfg.service(
+                        web::resource("/ws")
+                            .route(web::get().to(subscriptions::subscriptions_handler)),
+                    );
+                }
+            })
             .service(
                 web::resource("/status")
                     .route(web::get().to(status_handler))
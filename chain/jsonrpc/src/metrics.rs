@@ -1,5 +1,7 @@
 use once_cell::sync::Lazy;
-use unc_o11y::metrics::{exponential_buckets, HistogramVec, IntCounter, IntCounterVec};
+use unc_o11y::metrics::{
+    exponential_buckets, Histogram, HistogramVec, IntCounter, IntCounterVec, IntGauge,
+};
 
 pub static RPC_PROCESSING_TIME: Lazy<HistogramVec> = Lazy::new(|| {
     unc_o11y::metrics::try_create_histogram_vec(
@@ -10,6 +12,14 @@ pub static RPC_PROCESSING_TIME: Lazy<HistogramVec> = Lazy::new(|| {
     )
     .unwrap()
 });
+pub static RPC_BATCH_SIZE: Lazy<Histogram> = Lazy::new(|| {
+    unc_o11y::metrics::try_create_histogram_with_buckets(
+        "unc_rpc_batch_size",
+        "Number of entries in JSON RPC batch requests",
+        exponential_buckets(1.0, 2.0, 12).unwrap(),
+    )
+    .unwrap()
+});
 pub static RPC_TIMEOUT_TOTAL: Lazy<IntCounter> = Lazy::new(|| {
     unc_o11y::metrics::try_create_int_counter(
         "unc_rpc_timeout_total",
@@ -55,3 +65,33 @@ pub static RPC_UNREACHABLE_ERROR_COUNT: Lazy<IntCounterVec> = Lazy::new(|| {
     )
     .unwrap()
 });
+pub static RPC_SUBSCRIPTION_CONNECTIONS: Lazy<IntGauge> = Lazy::new(|| {
+    unc_o11y::metrics::try_create_int_gauge(
+        "unc_rpc_subscription_connections",
+        "Number of open WebSocket subscription connections",
+    )
+    .unwrap()
+});
+pub static RPC_ACTIVE_SUBSCRIPTIONS: Lazy<IntGauge> = Lazy::new(|| {
+    unc_o11y::metrics::try_create_int_gauge(
+        "unc_rpc_active_subscriptions",
+        "Number of active subscriptions over all WebSocket connections",
+    )
+    .unwrap()
+});
+pub static RPC_SUBSCRIPTION_NOTIFICATIONS_TOTAL: Lazy<IntCounterVec> = Lazy::new(|| {
+    unc_o11y::metrics::try_create_int_counter_vec(
+        "unc_rpc_subscription_notifications_total",
+        "Total count of notifications sent to WebSocket subscribers, by subscription type",
+        &["type"],
+    )
+    .unwrap()
+});
+pub static RPC_SUBSCRIPTION_DISCONNECTS_TOTAL: Lazy<IntCounterVec> = Lazy::new(|| {
+    unc_o11y::metrics::try_create_int_counter_vec(
+        "unc_rpc_subscription_disconnects_total",
+        "Total count of WebSocket connections refused or closed by the node, by reason",
+        &["reason"],
+    )
+    .unwrap()
+});
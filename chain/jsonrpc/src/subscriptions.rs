@@ -0,0 +1,445 @@
+//! WebSocket transport serving `subscribe` and `unsubscribe` requests.
+//!
+//! Events are taken from the [`StreamerMessage`]s the node builds for every
+//! new final block and publishes into its [`StreamerFeed`].  Every connection
+//! has a bounded queue of outgoing messages; a client which does not keep up
+//! with either its queue or the feed is disconnected rather than slowing down
+//! the rest of the node.
+
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use actix::Addr;
+use actix_http::ws::{self, CloseCode, CloseReason, Codec, Frame};
+use actix_web::body::BodyStream;
+use actix_web::web::{self, BytesMut};
+use actix_web::{Error as HttpError, HttpRequest, HttpResponse};
+use futures::StreamExt;
+use serde_json::{json, Value};
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::mpsc::error::TrySendError;
+use tokio::sync::{broadcast, mpsc};
+use tokio_util::codec::{Decoder, Encoder};
+use unc_client::streamer::build_streamer_message;
+use unc_client::streamer::fetchers::{fetch_block_by_height, fetch_latest_block};
+use unc_client::ViewClientActor;
+use unc_indexer_primitives::StreamerMessage;
+use unc_jsonrpc_primitives::errors::RpcError;
+use unc_jsonrpc_primitives::message::{self, Message, Request};
+use unc_jsonrpc_primitives::types::subscriptions::{
+    RpcSubscribeRequest, RpcSubscribeResponse, RpcSubscriptionError, RpcSubscriptionId,
+    RpcSubscriptionKind, RpcSubscriptionNotification, RpcUnsubscribeRequest,
+    RpcUnsubscribeResponse,
+};
+use unc_parameters::RuntimeConfigStore;
+use unc_primitives::views::StateChangeValueView;
+
+use crate::api::RpcRequest;
+use crate::{metrics, serialize_response, RpcSubscriptionsConfig};
+
+/// How often the publisher checks for a new final block.
+const PUBLISH_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Fan-out of streamer messages to WebSocket subscribers.
+#[derive(Clone, Debug)]
+pub struct StreamerFeed {
+    sender: broadcast::Sender<Arc<StreamerMessage>>,
+    capacity: usize,
+}
+
+impl StreamerFeed {
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        let (sender, _) = broadcast::channel(capacity);
+        Self { sender, capacity }
+    }
+
+    /// Whether any WebSocket connection currently listens to the feed.
+    ///
+    /// Lets the publisher skip cloning messages nobody is going to read.
+    pub fn has_subscribers(&self) -> bool {
+        self.sender.receiver_count() > 0
+    }
+
+    pub fn publish(&self, message: StreamerMessage) {
+        // Sending only fails if there are no receivers, which is fine.
+        let _ = self.sender.send(Arc::new(message));
+    }
+
+    /// Publishes every block which becomes final after the publisher started.
+    ///
+    /// Messages are only built while somebody listens.  If the node advanced by
+    /// more blocks than the feed buffers since the last check, only the latest
+    /// ones are published; subscribers would have been disconnected otherwise.
+    /// Blocks whose data can't be fetched are logged and skipped.
+    pub(crate) async fn run_publisher(self, view_client: Addr<ViewClientActor>) {
+        let runtime_config_store = RuntimeConfigStore::new(None);
+        let mut last_published_height = None;
+        loop {
+            tokio::time::sleep(PUBLISH_INTERVAL).await;
+            let latest_height = match fetch_latest_block(&view_client).await {
+                Ok(block) => block.header.height,
+                Err(_) => continue,
+            };
+            let Some(last_height) = last_published_height.replace(latest_height) else {
+                continue;
+            };
+            if !self.has_subscribers() {
+                continue;
+            }
+            let start_height =
+                (last_height + 1).max((latest_height + 1).saturating_sub(self.capacity as u64));
+            for height in start_height..=latest_height {
+                // Heights without a block are skipped.
+                let Ok(block) = fetch_block_by_height(&view_client, height).await else {
+                    continue;
+                };
+                match build_streamer_message(&view_client, &runtime_config_store, block).await {
+                    Ok(message) => self.publish(message),
+                    Err(err) => {
+                        tracing::warn!(
+                            target: "jsonrpc",
+                            height,
+                            ?err,
+                            "Skipping block in the subscriptions feed"
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct SubscriptionsState {
+    feed: StreamerFeed,
+    config: RpcSubscriptionsConfig,
+    connections: Arc<AtomicUsize>,
+}
+
+impl SubscriptionsState {
+    pub(crate) fn new(feed: StreamerFeed, config: RpcSubscriptionsConfig) -> Self {
+        Self { feed, config, connections: Arc::new(AtomicUsize::new(0)) }
+    }
+}
+
+pub(crate) async fn subscriptions_handler(
+    req: HttpRequest,
+    payload: web::Payload,
+    state: web::Data<SubscriptionsState>,
+) -> Result<HttpResponse, HttpError> {
+    let mut response = ws::handshake(req.head())?;
+    if state.connections.fetch_add(1, Ordering::SeqCst) >= state.config.max_connections {
+        state.connections.fetch_sub(1, Ordering::SeqCst);
+        metrics::RPC_SUBSCRIPTION_DISCONNECTS_TOTAL
+            .with_label_values(&["too_many_connections"])
+            .inc();
+        return Ok(HttpResponse::ServiceUnavailable().finish());
+    }
+    metrics::RPC_SUBSCRIPTION_CONNECTIONS.inc();
+
+    let (outgoing, outgoing_receiver) = mpsc::channel(state.config.max_pending_messages.max(1));
+    let session = Session {
+        max_subscriptions: state.config.max_subscriptions_per_connection,
+        connections: state.connections.clone(),
+        outgoing,
+        subscriptions: BTreeMap::new(),
+        next_subscription_id: 0,
+    };
+    actix::spawn(session.run(payload, state.feed.sender.subscribe()));
+
+    // The response body ends, closing the connection, once the session drops
+    // its sending half of the queue.
+    let body = futures::stream::unfold(
+        (outgoing_receiver, Codec::new()),
+        |(mut receiver, mut codec)| async move {
+            let message = receiver.recv().await?;
+            let mut buffer = BytesMut::new();
+            let bytes = codec.encode(message, &mut buffer).map(|()| buffer.freeze());
+            Some((bytes, (receiver, codec)))
+        },
+    );
+    Ok(HttpResponse::from(response.body(BodyStream::new(body))).map_into_boxed_body())
+}
+
+struct Session {
+    max_subscriptions: usize,
+    connections: Arc<AtomicUsize>,
+    outgoing: mpsc::Sender<ws::Message>,
+    subscriptions: BTreeMap<RpcSubscriptionId, RpcSubscriptionKind>,
+    next_subscription_id: RpcSubscriptionId,
+}
+
+impl Drop for Session {
+    fn drop(&mut self) {
+        self.connections.fetch_sub(1, Ordering::SeqCst);
+        metrics::RPC_SUBSCRIPTION_CONNECTIONS.dec();
+        metrics::RPC_ACTIVE_SUBSCRIPTIONS.sub(self.subscriptions.len() as i64);
+    }
+}
+
+impl Session {
+    async fn run(
+        mut self,
+        mut payload: web::Payload,
+        mut feed: broadcast::Receiver<Arc<StreamerMessage>>,
+    ) {
+        let mut codec = Codec::new();
+        let mut buffer = BytesMut::new();
+        let close_reason = loop {
+            tokio::select! {
+                chunk = payload.next() => match chunk {
+                    Some(Ok(chunk)) => {
+                        buffer.extend_from_slice(&chunk);
+                        if let Err(reason) = self.handle_frames(&mut codec, &mut buffer).await {
+                            break reason;
+                        }
+                    }
+                    // The client went away without sending a close frame.
+                    Some(Err(_)) | None => return,
+                },
+                message = feed.recv() => match message {
+                    Ok(message) => {
+                        if let Err(reason) = self.notify(&message) {
+                            break reason;
+                        }
+                    }
+                    Err(RecvError::Lagged(skipped)) => {
+                        metrics::RPC_SUBSCRIPTION_DISCONNECTS_TOTAL
+                            .with_label_values(&["lagged"])
+                            .inc();
+                        break Some(CloseReason {
+                            code: CloseCode::Policy,
+                            description: Some(format!("Lagged behind by {skipped} blocks")),
+                        });
+                    }
+                    Err(RecvError::Closed) => break Some(CloseCode::Away.into()),
+                },
+            }
+        };
+        // If the queue is full the client is not reading anything anyway.
+        let _ = self.outgoing.try_send(ws::Message::Close(close_reason));
+    }
+
+    /// Handles all complete frames in the buffer.  Returns the reason to
+    /// close the connection with if it should be closed.
+    async fn handle_frames(
+        &mut self,
+        codec: &mut Codec,
+        buffer: &mut BytesMut,
+    ) -> Result<(), Option<CloseReason>> {
+        loop {
+            let frame = match codec.decode(buffer) {
+                Ok(Some(frame)) => frame,
+                Ok(None) => return Ok(()),
+                Err(err) => {
+                    return Err(Some(CloseReason {
+                        code: CloseCode::Protocol,
+                        description: Some(err.to_string()),
+                    }))
+                }
+            };
+            match frame {
+                Frame::Text(text) => {
+                    let response: String = self.process_message(&text).into();
+                    self.send(ws::Message::Text(response.into())).await?;
+                }
+                Frame::Ping(payload) => self.send(ws::Message::Pong(payload)).await?,
+                Frame::Pong(_) => {}
+                Frame::Binary(_) | Frame::Continuation(_) => {
+                    return Err(Some(CloseReason {
+                        code: CloseCode::Unsupported,
+                        description: Some("Only text messages are supported".to_owned()),
+                    }))
+                }
+                Frame::Close(reason) => return Err(reason),
+            }
+        }
+    }
+
+    /// Queues a response, waiting for space in the queue.  This way a client
+    /// flooding the node with requests is throttled by its own reading speed.
+    async fn send(&self, message: ws::Message) -> Result<(), Option<CloseReason>> {
+        self.outgoing.send(message).await.map_err(|_| None)
+    }
+
+    fn process_message(&mut self, text: &[u8]) -> Message {
+        match message::from_slice(text) {
+            Ok(Message::Request(request)) => {
+                let id = request.id.clone();
+                Message::response(id, self.process_request(request))
+            }
+            Ok(_) => Message::error(RpcError::parse_error(
+                "JSON RPC Request format was expected".to_owned(),
+            )),
+            Err(broken) => broken.reply(),
+        }
+    }
+
+    fn process_request(&mut self, request: Request) -> Result<Value, RpcError> {
+        let Request { method, params, .. } = request;
+        match method.as_str() {
+            "subscribe" => {
+                let request = RpcSubscribeRequest::parse(params)?;
+                serialize_response(self.subscribe(request.kind)?)
+            }
+            "unsubscribe" => {
+                let request = RpcUnsubscribeRequest::parse(params)?;
+                serialize_response(self.unsubscribe(request.subscription)?)
+            }
+            _ => Err(RpcError::method_not_found(method)),
+        }
+    }
+
+    fn subscribe(
+        &mut self,
+        kind: RpcSubscriptionKind,
+    ) -> Result<RpcSubscribeResponse, RpcSubscriptionError> {
+        if self.subscriptions.len() >= self.max_subscriptions {
+            return Err(RpcSubscriptionError::TooManySubscriptions {
+                limit: self.max_subscriptions,
+            });
+        }
+        let subscription = self.next_subscription_id;
+        self.next_subscription_id += 1;
+        self.subscriptions.insert(subscription, kind);
+        metrics::RPC_ACTIVE_SUBSCRIPTIONS.inc();
+        Ok(RpcSubscribeResponse { subscription })
+    }
+
+    fn unsubscribe(
+        &mut self,
+        subscription: RpcSubscriptionId,
+    ) -> Result<RpcUnsubscribeResponse, RpcSubscriptionError> {
+        if self.subscriptions.remove(&subscription).is_none() {
+            return Err(RpcSubscriptionError::UnknownSubscription { subscription });
+        }
+        metrics::RPC_ACTIVE_SUBSCRIPTIONS.dec();
+        Ok(RpcUnsubscribeResponse { subscription })
+    }
+
+    /// Queues notifications for all subscriptions matching the message.
+    ///
+    /// Unlike responses, notifications never wait for space in the queue: a
+    /// client which cannot keep up gets disconnected.
+    fn notify(&self, message: &StreamerMessage) -> Result<(), Option<CloseReason>> {
+        for (&subscription, kind) in &self.subscriptions {
+            for result in events(kind, message) {
+                let notification = Message::notification(
+                    "subscription".to_owned(),
+                    json!(RpcSubscriptionNotification { subscription, result }),
+                );
+                let text: String = notification.into();
+                match self.outgoing.try_send(ws::Message::Text(text.into())) {
+                    Ok(()) => {
+                        metrics::RPC_SUBSCRIPTION_NOTIFICATIONS_TOTAL
+                            .with_label_values(&[kind_name(kind)])
+                            .inc();
+                    }
+                    Err(TrySendError::Full(_)) => {
+                        metrics::RPC_SUBSCRIPTION_DISCONNECTS_TOTAL
+                            .with_label_values(&["too_many_pending_messages"])
+                            .inc();
+                        return Err(Some(CloseReason {
+                            code: CloseCode::Policy,
+                            description: Some("Too many pending messages".to_owned()),
+                        }));
+                    }
+                    Err(TrySendError::Closed(_)) => return Err(None),
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn kind_name(kind: &RpcSubscriptionKind) -> &'static str {
+    match kind {
+        RpcSubscriptionKind::Blocks => "blocks",
+        RpcSubscriptionKind::Chunks { .. } => "chunks",
+        RpcSubscriptionKind::Outcomes { .. } => "outcomes",
+        RpcSubscriptionKind::StateChanges { .. } => "state_changes",
+    }
+}
+
+/// Returns the notification payloads the message contains for the subscription.
+fn events(kind: &RpcSubscriptionKind, message: &StreamerMessage) -> Vec<Value> {
+    let block_height = message.block.header.height;
+    let block_hash = message.block.header.hash;
+    let mut events = Vec::new();
+    match kind {
+        RpcSubscriptionKind::Blocks => events.push(json!(message.block)),
+        RpcSubscriptionKind::Chunks { shard_id } => {
+            for shard in &message.shards {
+                if shard_id.map_or(false, |shard_id| shard_id != shard.shard_id) {
+                    continue;
+                }
+                if let Some(chunk) = &shard.chunk {
+                    events.push(json!({
+                        "block_height": block_height,
+                        "block_hash": block_hash,
+                        "author": chunk.author,
+                        "header": chunk.header,
+                    }));
+                }
+            }
+        }
+        RpcSubscriptionKind::Outcomes { account_id } => {
+            for shard in &message.shards {
+                let transactions = shard.chunk.iter().flat_map(|chunk| &chunk.transactions);
+                for transaction in transactions {
+                    if &transaction.transaction.signer_id == account_id
+                        || &transaction.transaction.receiver_id == account_id
+                    {
+                        events.push(json!({
+                            "block_height": block_height,
+                            "block_hash": block_hash,
+                            "shard_id": shard.shard_id,
+                            "transaction": transaction,
+                        }));
+                    }
+                }
+                for outcome in &shard.receipt_execution_outcomes {
+                    if &outcome.receipt.predecessor_id == account_id
+                        || &outcome.receipt.receiver_id == account_id
+                    {
+                        events.push(json!({
+                            "block_height": block_height,
+                            "block_hash": block_hash,
+                            "shard_id": shard.shard_id,
+                            "receipt": outcome,
+                        }));
+                    }
+                }
+            }
+        }
+        RpcSubscriptionKind::StateChanges { account_id, key_prefix } => {
+            for shard in &message.shards {
+                for change in &shard.state_changes {
+                    if change.value.affected_account_id() != account_id {
+                        continue;
+                    }
+                    if let Some(key_prefix) = key_prefix {
+                        let key = match &change.value {
+                            StateChangeValueView::DataUpdate { key, .. }
+                            | StateChangeValueView::DataDeletion { key, .. } => key,
+                            _ => continue,
+                        };
+                        if !key.starts_with(key_prefix) {
+                            continue;
+                        }
+                    }
+                    events.push(json!({
+                        "block_height": block_height,
+                        "block_hash": block_hash,
+                        "shard_id": shard.shard_id,
+                        "change": change,
+                    }));
+                }
+            }
+        }
+    }
+    events
+}
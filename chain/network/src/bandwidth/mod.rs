@@ -0,0 +1,329 @@
+//! Bandwidth shaping of the peer connections.
+//!
+//! Every message sent to a peer belongs to a `Category`. A connection keeps a separate FIFO
+//! queue for each category and always sends the message of the highest priority category
+//! which is allowed to be sent, so that the consensus messages never wait behind a block or
+//! a state part.
+//!
+//! Bandwidth is limited with token buckets, in which a token is a single byte:
+//! - per node (shared by all the connections), separately for upload and download,
+//! - per connection, separately for upload and download,
+//! - per connection and category, for upload only.
+//!
+//! A frame is charged to the buckets after it has been written, so a large frame may put
+//! a bucket into debt, and the following frames wait until the debt is paid off.
+//! Consensus frames are charged as well, but never wait, so that the consensus latency
+//! doesn't depend on the amount of bulk traffic. Download limits are implemented by not
+//! reading from the socket, which makes TCP slow down the sender.
+//!
+//! Download limits can't exempt consensus frames the way upload limits do: the category
+//! of a frame is known only once it has been decoded, and all the frames of a connection
+//! share a single TCP stream, so pausing the reads delays every frame behind them anyway.
+//! Instead, the frame exceeding the limit is still delivered right away and only the
+//! following reads wait, and TIER1 connections, which carry the consensus traffic between
+//! the validators, are not subject to the download limits at all.
+use crate::concurrency::rate;
+use crate::network_protocol::{PeerMessage, RoutedMessageBody};
+use crate::stats::metrics;
+use crate::tcp;
+use anyhow::Context as _;
+use parking_lot::Mutex;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::Arc;
+use unc_async::time;
+
+#[cfg(test)]
+mod tests;
+
+/// Category of a PeerMessage, from the bandwidth shaping point of view.
+/// The variants are ordered by priority: a lower variant is always sent first.
+#[derive(
+    Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, strum::IntoStaticStr, strum::EnumIter,
+)]
+pub enum Category {
+    /// Blocks and messages required for chunk production. Never delayed.
+    Consensus,
+    /// Handshakes, routing, peer discovery and other small control messages.
+    Control,
+    /// Block and block header requests and block headers.
+    BlockSync,
+    /// State sync requests and responses.
+    StateSync,
+}
+
+impl Category {
+    /// Category of `msg` sent over a connection of the given tier.
+    /// TIER1 connections are reserved for consensus messages, so everything sent over
+    /// them is treated as consensus traffic.
+    pub(crate) fn of(tier: tcp::Tier, msg: &PeerMessage) -> Self {
+        if tier == tcp::Tier::T1 {
+            return Category::Consensus;
+        }
+        match msg {
+            // Newly produced blocks are broadcast with the same message as the block sync
+            // responses. Delaying them would slow down the chain, so they are consensus traffic.
+            PeerMessage::Block(_) => Category::Consensus,
+            PeerMessage::BlockHeadersRequest(_)
+            | PeerMessage::BlockHeaders(_)
+            | PeerMessage::BlockRequest(_) => Category::BlockSync,
+            PeerMessage::StateRequestHeader(..)
+            | PeerMessage::StateRequestPart(..)
+            | PeerMessage::VersionedStateResponse(_) => Category::StateSync,
+            PeerMessage::Routed(msg) => match &msg.body {
+                RoutedMessageBody::BlockApproval(_)
+                | RoutedMessageBody::ChunkEndorsement(_)
+                | RoutedMessageBody::ChunkStateWitness(_)
+                | RoutedMessageBody::VersionedPartialEncodedChunk(_)
+                | RoutedMessageBody::PartialEncodedChunkRequest(_)
+                | RoutedMessageBody::PartialEncodedChunkResponse(_)
+                | RoutedMessageBody::PartialEncodedChunkForward(_) => Category::Consensus,
+                RoutedMessageBody::StateResponse(_) => Category::StateSync,
+                _ => Category::Control,
+            },
+            _ => Category::Control,
+        }
+    }
+}
+
+/// Bandwidth limits, in bytes. `None` stands for no limit.
+#[derive(Clone, Default)]
+pub struct Config {
+    /// Upload limit of all the connections together.
+    pub global_upload: Option<rate::Limit>,
+    /// Download limit of all the connections together.
+    pub global_download: Option<rate::Limit>,
+    /// Upload limit of a single connection.
+    pub peer_upload: Option<rate::Limit>,
+    /// Download limit of a single connection.
+    pub peer_download: Option<rate::Limit>,
+    /// Upload limits of a single connection, per message category.
+    /// A limit for `Category::Consensus` has no effect.
+    pub category_upload: HashMap<Category, rate::Limit>,
+}
+
+impl Config {
+    pub fn validate(&self) -> anyhow::Result<()> {
+        let limits = [
+            ("global_upload", &self.global_upload),
+            ("global_download", &self.global_download),
+            ("peer_upload", &self.peer_upload),
+            ("peer_download", &self.peer_download),
+        ];
+        for (name, limit) in limits {
+            if let Some(limit) = limit {
+                limit.validate().context(name)?;
+            }
+        }
+        for (category, limit) in &self.category_upload {
+            limit.validate().with_context(|| format!("category_upload[{category:?}]"))?;
+        }
+        Ok(())
+    }
+}
+
+/// Token bucket with bytes as tokens, which is allowed to go into debt.
+struct TokenBucket {
+    limit: rate::Limit,
+    /// Number of bytes which can be transferred right away. Negative in case of debt.
+    tokens: f64,
+    updated: time::Instant,
+}
+
+impl TokenBucket {
+    fn new(limit: rate::Limit, now: time::Instant) -> Self {
+        Self { limit, tokens: limit.burst as f64, updated: now }
+    }
+
+    fn refill(&mut self, now: time::Instant) {
+        if now <= self.updated {
+            return;
+        }
+        let elapsed = (now - self.updated).as_seconds_f64();
+        self.tokens = (self.tokens + elapsed * self.limit.qps).min(self.limit.burst as f64);
+        self.updated = now;
+    }
+
+    /// Time left until the debt is paid off.
+    fn delay(&mut self, now: time::Instant) -> time::Duration {
+        self.refill(now);
+        if self.tokens >= 0. {
+            return time::Duration::ZERO;
+        }
+        time::Duration::seconds_f64(-self.tokens / self.limit.qps)
+    }
+
+    fn charge(&mut self, now: time::Instant, bytes: usize) {
+        self.refill(now);
+        self.tokens -= bytes as f64;
+    }
+}
+
+/// Bandwidth limits shared by all the connections of the node.
+pub(crate) struct Global {
+    config: Config,
+    upload: Option<Mutex<TokenBucket>>,
+    download: Option<Mutex<TokenBucket>>,
+}
+
+impl Global {
+    pub fn new(clock: &time::Clock, config: Config) -> Self {
+        let now = clock.now();
+        Self {
+            upload: config.global_upload.map(|l| Mutex::new(TokenBucket::new(l, now))),
+            download: config.global_download.map(|l| Mutex::new(TokenBucket::new(l, now))),
+            config,
+        }
+    }
+}
+
+/// Upload limits of a single connection.
+pub(crate) struct UploadLimiter {
+    global: Arc<Global>,
+    peer: Option<TokenBucket>,
+    categories: HashMap<Category, TokenBucket>,
+}
+
+impl UploadLimiter {
+    pub fn new(clock: &time::Clock, global: Arc<Global>) -> Self {
+        let now = clock.now();
+        Self {
+            peer: global.config.peer_upload.map(|l| TokenBucket::new(l, now)),
+            categories: global
+                .config
+                .category_upload
+                .iter()
+                .map(|(c, l)| (*c, TokenBucket::new(*l, now)))
+                .collect(),
+            global,
+        }
+    }
+
+    /// Time left until a frame of the given category can be sent.
+    pub fn delay(&mut self, now: time::Instant, category: Category) -> time::Duration {
+        if category == Category::Consensus {
+            return time::Duration::ZERO;
+        }
+        let mut delay = time::Duration::ZERO;
+        if let Some(global) = &self.global.upload {
+            delay = delay.max(global.lock().delay(now));
+        }
+        if let Some(peer) = &mut self.peer {
+            delay = delay.max(peer.delay(now));
+        }
+        if let Some(bucket) = self.categories.get_mut(&category) {
+            delay = delay.max(bucket.delay(now));
+        }
+        delay
+    }
+
+    /// Charges the buckets for a sent frame.
+    pub fn charge(&mut self, now: time::Instant, category: Category, bytes: usize) {
+        if let Some(global) = &self.global.upload {
+            global.lock().charge(now, bytes);
+        }
+        if let Some(peer) = &mut self.peer {
+            peer.charge(now, bytes);
+        }
+        if let Some(bucket) = self.categories.get_mut(&category) {
+            bucket.charge(now, bytes);
+        }
+    }
+}
+
+/// Download limits of a single connection.
+pub(crate) struct DownloadLimiter {
+    global: Arc<Global>,
+    peer: Option<TokenBucket>,
+}
+
+impl DownloadLimiter {
+    pub fn new(clock: &time::Clock, global: Arc<Global>) -> Self {
+        let now = clock.now();
+        Self { peer: global.config.peer_download.map(|l| TokenBucket::new(l, now)), global }
+    }
+
+    /// Charges the buckets for a received frame.
+    /// Returns the time for which the connection should stop reading.
+    pub fn charge(&mut self, now: time::Instant, bytes: usize) -> time::Duration {
+        let mut delay = time::Duration::ZERO;
+        if let Some(global) = &self.global.download {
+            let mut global = global.lock();
+            global.charge(now, bytes);
+            delay = delay.max(global.delay(now));
+        }
+        if let Some(peer) = &mut self.peer {
+            peer.charge(now, bytes);
+            delay = delay.max(peer.delay(now));
+        }
+        delay
+    }
+}
+
+/// Result of `SendQueue::pop`.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum Next<T> {
+    /// Item which should be sent right away.
+    Ready(Category, T),
+    /// All the queued items have to wait for bandwidth for at least the given duration.
+    Wait(time::Duration),
+    /// The queue is empty.
+    Empty,
+}
+
+/// Send queue of a connection, with a FIFO queue per category.
+pub(crate) struct SendQueue<T> {
+    limiter: UploadLimiter,
+    queues: BTreeMap<Category, VecDeque<(time::Instant, T)>>,
+}
+
+impl<T> SendQueue<T> {
+    pub fn new(limiter: UploadLimiter) -> Self {
+        Self { limiter, queues: BTreeMap::new() }
+    }
+
+    pub fn push(&mut self, now: time::Instant, category: Category, item: T) {
+        self.queues.entry(category).or_default().push_back((now, item));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queues.values().all(|q| q.is_empty())
+    }
+
+    /// Pops the oldest item of the highest priority category which can be sent right away.
+    /// The caller is expected to `charge()` the queue once the item has been sent.
+    pub fn pop(&mut self, now: time::Instant) -> Next<T> {
+        let mut wait: Option<(Category, time::Duration)> = None;
+        for (category, queue) in &mut self.queues {
+            if queue.is_empty() {
+                continue;
+            }
+            let delay = self.limiter.delay(now, *category);
+            if delay > time::Duration::ZERO {
+                if wait.map_or(true, |(_, w)| delay < w) {
+                    wait = Some((*category, delay));
+                }
+                continue;
+            }
+            let (queued_at, item) = queue.pop_front().unwrap();
+            metrics::PEER_SEND_QUEUE_LATENCY
+                .with_label_values(&[category.into()])
+                .observe((now - queued_at).as_seconds_f64());
+            return Next::Ready(*category, item);
+        }
+        match wait {
+            Some((category, delay)) => {
+                metrics::PEER_UPLOAD_THROTTLED_TOTAL.with_label_values(&[category.into()]).inc();
+                Next::Wait(delay)
+            }
+            None => Next::Empty,
+        }
+    }
+
+    /// Charges the bandwidth limits for an item of `bytes` size which has been sent.
+    pub fn charge(&mut self, now: time::Instant, category: Category, bytes: usize) {
+        metrics::PEER_SENT_BYTES_BY_CATEGORY
+            .with_label_values(&[category.into()])
+            .inc_by(bytes as u64);
+        self.limiter.charge(now, category, bytes);
+    }
+}
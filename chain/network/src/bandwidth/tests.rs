@@ -0,0 +1,157 @@
+use crate::bandwidth::{Category, Config, DownloadLimiter, Global, Next, SendQueue, UploadLimiter};
+use crate::concurrency::rate;
+use crate::network_protocol::testonly::{make_genesis_block, make_peer_id, make_routed_message};
+use crate::network_protocol::{PeerMessage, Ping, RoutedMessageBody};
+use crate::tcp;
+use crate::testonly::bandwidth::{run, Link};
+use crate::testonly::make_rng;
+use std::sync::Arc;
+use strum::IntoEnumIterator as _;
+use unc_async::time;
+use unc_primitives::hash::CryptoHash;
+
+fn limit(bytes_per_sec: u64) -> Option<rate::Limit> {
+    Some(rate::Limit { qps: bytes_per_sec as f64, burst: bytes_per_sec })
+}
+
+#[test]
+fn test_category() {
+    let mut rng = make_rng(921853233);
+    let rng = &mut rng;
+    let ping = PeerMessage::Routed(Box::new(make_routed_message(
+        rng,
+        RoutedMessageBody::Ping(Ping { nonce: 0, source: make_peer_id(rng) }),
+    )));
+    let clock = time::FakeClock::default();
+    let block = PeerMessage::Block(make_genesis_block(&clock.clock(), vec![]));
+    let block_request = PeerMessage::BlockRequest(CryptoHash::default());
+    let state_request = PeerMessage::StateRequestPart(0, CryptoHash::default(), 0);
+
+    assert_eq!(Category::Control, Category::of(tcp::Tier::T2, &ping));
+    assert_eq!(Category::BlockSync, Category::of(tcp::Tier::T2, &block_request));
+    assert_eq!(Category::Consensus, Category::of(tcp::Tier::T2, &block));
+    assert_eq!(Category::StateSync, Category::of(tcp::Tier::T2, &state_request));
+    tracing::debug!(target:"test", "everything sent over TIER1 is consensus traffic");
+    for msg in [&ping, &block, &block_request, &state_request] {
+        assert_eq!(Category::Consensus, Category::of(tcp::Tier::T1, msg));
+    }
+}
+
+#[test]
+fn test_priority() {
+    let clock = time::FakeClock::default();
+    let global = Arc::new(Global::new(&clock.clock(), Config::default()));
+    let mut queue = SendQueue::new(UploadLimiter::new(&clock.clock(), global));
+    for category in Category::iter().rev() {
+        queue.push(clock.now(), category, 1);
+        queue.push(clock.now(), category, 2);
+    }
+    for category in Category::iter() {
+        assert_eq!(Next::Ready(category, 1), queue.pop(clock.now()));
+        assert_eq!(Next::Ready(category, 2), queue.pop(clock.now()));
+    }
+    assert_eq!(Next::Empty, queue.pop(clock.now()));
+    assert!(queue.is_empty());
+}
+
+#[test]
+fn test_peer_upload_limit() {
+    let clock = time::FakeClock::default();
+    let start = clock.now();
+    let config = Config { peer_upload: limit(1000), ..Config::default() };
+    let global = Arc::new(Global::new(&clock.clock(), config));
+    let mut link = Link::new(&clock, global);
+    for _ in 0..10 {
+        link.push(&clock, Category::BlockSync, 500);
+    }
+
+    tracing::debug!(target:"test", "the burst is sent right away");
+    run(&clock, &mut [&mut link], start);
+    assert_eq!(1500, link.bytes_sent(Category::BlockSync));
+
+    tracing::debug!(target:"test", "the rest is sent at the limited rate");
+    run(&clock, &mut [&mut link], start + time::Duration::minutes(1));
+    assert_eq!(5000, link.bytes_sent(Category::BlockSync));
+    assert_eq!(
+        Some(start + time::Duration::milliseconds(3500)),
+        link.last_sent(Category::BlockSync)
+    );
+}
+
+#[test]
+fn test_consensus_is_not_delayed() {
+    let clock = time::FakeClock::default();
+    let start = clock.now();
+    let config = Config { peer_upload: limit(1000), ..Config::default() };
+    let global = Arc::new(Global::new(&clock.clock(), config));
+    let mut link = Link::new(&clock, global);
+    for _ in 0..5 {
+        link.push(&clock, Category::StateSync, 1000);
+    }
+    run(&clock, &mut [&mut link], start + time::Duration::milliseconds(500));
+    assert_eq!(2000, link.bytes_sent(Category::StateSync));
+
+    tracing::debug!(target:"test", "consensus frames skip the throttled state sync frames");
+    for _ in 0..3 {
+        link.push(&clock, Category::Consensus, 1000);
+    }
+    run(&clock, &mut [&mut link], clock.now());
+    assert_eq!(3000, link.bytes_sent(Category::Consensus));
+    assert_eq!(Some(clock.now()), link.last_sent(Category::Consensus));
+
+    tracing::debug!(target:"test", "but they use up the bandwidth of the other categories");
+    run(&clock, &mut [&mut link], start + time::Duration::minutes(1));
+    assert_eq!(5000, link.bytes_sent(Category::StateSync));
+    assert_eq!(Some(start + time::Duration::seconds(6)), link.last_sent(Category::StateSync));
+}
+
+#[test]
+fn test_category_upload_limit() {
+    let clock = time::FakeClock::default();
+    let start = clock.now();
+    let config = Config {
+        category_upload: [(Category::StateSync, limit(1000).unwrap())].into_iter().collect(),
+        ..Config::default()
+    };
+    let global = Arc::new(Global::new(&clock.clock(), config));
+    let mut link = Link::new(&clock, global);
+    for _ in 0..4 {
+        link.push(&clock, Category::StateSync, 1000);
+        link.push(&clock, Category::BlockSync, 1000);
+    }
+    run(&clock, &mut [&mut link], start + time::Duration::minutes(1));
+    assert_eq!(Some(start), link.last_sent(Category::BlockSync));
+    assert_eq!(Some(start + time::Duration::seconds(2)), link.last_sent(Category::StateSync));
+}
+
+#[test]
+fn test_global_upload_limit() {
+    let clock = time::FakeClock::default();
+    let start = clock.now();
+    let config = Config { global_upload: limit(1000), ..Config::default() };
+    let global = Arc::new(Global::new(&clock.clock(), config));
+    let mut link1 = Link::new(&clock, global.clone());
+    let mut link2 = Link::new(&clock, global);
+    for _ in 0..5 {
+        link1.push(&clock, Category::BlockSync, 500);
+        link2.push(&clock, Category::BlockSync, 500);
+    }
+    run(&clock, &mut [&mut link1, &mut link2], start + time::Duration::minutes(1));
+    assert_eq!(2500, link1.bytes_sent(Category::BlockSync));
+    assert_eq!(2500, link2.bytes_sent(Category::BlockSync));
+    let last = link1.last_sent(Category::BlockSync).max(link2.last_sent(Category::BlockSync));
+    assert_eq!(Some(start + time::Duration::milliseconds(3500)), last);
+}
+
+#[test]
+fn test_download_limit() {
+    let clock = time::FakeClock::default();
+    let config = Config { peer_download: limit(1000), ..Config::default() };
+    let global = Arc::new(Global::new(&clock.clock(), config));
+    let mut limiter = DownloadLimiter::new(&clock.clock(), global);
+    assert_eq!(time::Duration::ZERO, limiter.charge(clock.now(), 500));
+    assert_eq!(time::Duration::ZERO, limiter.charge(clock.now(), 500));
+    assert_eq!(time::Duration::milliseconds(500), limiter.charge(clock.now(), 500));
+    clock.advance(time::Duration::milliseconds(500));
+    assert_eq!(time::Duration::ZERO, limiter.charge(clock.now(), 0));
+}
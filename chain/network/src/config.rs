@@ -1,9 +1,11 @@
+use crate::bandwidth;
 use crate::blacklist;
 use crate::concurrency::rate;
 use crate::network_protocol::PeerAddr;
 use crate::network_protocol::PeerInfo;
 use crate::peer_manager::peer_manager_actor::Event;
 use crate::peer_manager::peer_store;
+use crate::peer_manager::reputation;
 use crate::sink::Sink;
 use crate::snapshot_hosts;
 use crate::stun;
@@ -18,6 +20,7 @@ use unc_primitives::network::PeerId;
 use unc_primitives::test_utils::create_test_signer;
 use unc_primitives::types::AccountId;
 use unc_primitives::validator_signer::ValidatorSigner;
+use unc_primitives::version::{ProtocolFeature, PROTOCOL_VERSION};
 
 /// How much height horizon to give to consider peer up to date.
 pub const HIGHEST_PEER_HORIZON: u64 = 5;
@@ -97,6 +100,8 @@ pub struct NetworkConfig {
     pub validator: Option<ValidatorConfig>,
 
     pub peer_store: peer_store::Config,
+    pub reputation: reputation::Config,
+    pub bandwidth: bandwidth::Config,
     pub snapshot_hosts: snapshot_hosts::Config,
     pub whitelist_nodes: Vec<PeerInfo>,
     pub handshake_timeout: time::Duration,
@@ -164,6 +169,13 @@ pub struct NetworkConfig {
     //   * not broadcasting deleted edges
     //   * ignoring received deleted edges as well
     pub skip_tombstones: Option<time::Duration>,
+    /// Whether to refuse peers which don't encrypt the connection,
+    /// see `peer::encryption`.
+    pub require_encrypted_transport: bool,
+    /// Messages of at least this size in bytes (once encoded) are compressed,
+    /// if the peer supports it. Compression is disabled if `None`.
+    /// See `network_protocol::Compression`.
+    pub message_compression_threshold: Option<usize>,
 
     /// TEST-ONLY
     /// TODO(gprusak): make it pub(crate), once all integration tests
@@ -287,6 +299,25 @@ impl NetworkConfig {
                 ban_window: cfg.ban_window.try_into()?,
                 peer_expiration_duration: cfg.peer_expiration_duration.try_into()?,
             },
+            reputation: reputation::Config {
+                decay_half_life: cfg.reputation.decay_half_life.try_into()?,
+                downrank_threshold: cfg.reputation.downrank_threshold,
+                ban_threshold: cfg.reputation.ban_threshold,
+                ban_duration: cfg.reputation.ban_duration.try_into()?,
+            },
+            bandwidth: bandwidth::Config {
+                global_upload: cfg.bandwidth.global_upload_bytes_per_sec.map(bytes_per_sec),
+                global_download: cfg.bandwidth.global_download_bytes_per_sec.map(bytes_per_sec),
+                peer_upload: cfg.bandwidth.peer_upload_bytes_per_sec.map(bytes_per_sec),
+                peer_download: cfg.bandwidth.peer_download_bytes_per_sec.map(bytes_per_sec),
+                category_upload: [
+                    (bandwidth::Category::BlockSync, cfg.bandwidth.block_sync_upload_bytes_per_sec),
+                    (bandwidth::Category::StateSync, cfg.bandwidth.state_sync_upload_bytes_per_sec),
+                ]
+                .into_iter()
+                .filter_map(|(category, limit)| Some((category, bytes_per_sec(limit?))))
+                .collect(),
+            },
             snapshot_hosts: snapshot_hosts::Config {
                 snapshot_hosts_cache_size: cfg.snapshot_hosts_cache_size,
             },
@@ -340,6 +371,8 @@ impl NetworkConfig {
             } else {
                 None
             },
+            require_encrypted_transport: cfg.require_encrypted_transport,
+            message_compression_threshold: cfg.message_compression_threshold,
             event_sink: Sink::null(),
         };
         this.override_config(cfg.experimental.network_config_overrides);
@@ -372,6 +405,13 @@ impl NetworkConfig {
                 peer_expiration_duration: time::Duration::seconds(60 * 60),
                 connect_only_to_boot_nodes: false,
             },
+            reputation: reputation::Config {
+                decay_half_life: time::Duration::minutes(10),
+                downrank_threshold: 50.,
+                ban_threshold: 100.,
+                ban_duration: time::Duration::hours(1),
+            },
+            bandwidth: bandwidth::Config::default(),
             snapshot_hosts: snapshot_hosts::Config { snapshot_hosts_cache_size: 1000 },
             whitelist_nodes: vec![],
             handshake_timeout: time::Duration::seconds(5),
@@ -407,6 +447,9 @@ impl NetworkConfig {
                 enable_outbound: true,
             }),
             skip_tombstones: None,
+            require_encrypted_transport: false,
+            message_compression_threshold:
+                crate::config_json::default_message_compression_threshold(),
             event_sink: Sink::null(),
         }
     }
@@ -450,6 +493,34 @@ impl NetworkConfig {
             );
         }
 
+        if !(0. < self.reputation.downrank_threshold
+            && self.reputation.downrank_threshold <= self.reputation.ban_threshold)
+        {
+            anyhow::bail!(
+                "reputation thresholds must satisfy 0 < downrank_threshold({}) <= ban_threshold({}).",
+                self.reputation.downrank_threshold,
+                self.reputation.ban_threshold
+            );
+        }
+
+        if !self.reputation.decay_half_life.is_positive() {
+            anyhow::bail!(
+                "reputation.decay_half_life({}) must be positive.",
+                self.reputation.decay_half_life
+            );
+        }
+
+        if self.require_encrypted_transport
+            && ProtocolFeature::EncryptedPeerTransport.protocol_version() > PROTOCOL_VERSION
+        {
+            anyhow::bail!(
+                "require_encrypted_transport is not supported by this binary: peer transport encryption is not enabled in protocol version {}.",
+                PROTOCOL_VERSION
+            );
+        }
+
+        self.bandwidth.validate().context("bandwidth")?;
+
         self.accounts_data_broadcast_rate_limit
             .validate()
             .context("accounts_Data_broadcast_rate_limit")?;
@@ -460,6 +531,11 @@ impl NetworkConfig {
     }
 }
 
+/// Bandwidth limit of the given rate, which allows for bursts of up to 1s worth of traffic.
+fn bytes_per_sec(bytes: u64) -> rate::Limit {
+    rate::Limit { qps: bytes as f64, burst: bytes }
+}
+
 /// On every message from peer don't update `last_time_received_message`
 /// but wait some "small" timeout between updates to avoid a lot of messages between
 /// Peer and PeerManager.
@@ -497,6 +573,7 @@ mod test {
     use crate::tcp;
     use crate::testonly::make_rng;
     use unc_async::time;
+    use unc_primitives::version::{ProtocolFeature, PROTOCOL_VERSION};
 
     #[test]
     fn test_network_config() {
@@ -518,6 +595,14 @@ mod test {
         let mut nc = config::NetworkConfig::from_seed("123", tcp::ListenerAddr::reserve_for_test());
         nc.peer_recent_time_window = UPDATE_INTERVAL_LAST_TIME_RECEIVED_MESSAGE;
         assert!(nc.verify().is_err());
+
+        // Encryption can be required only if the protocol version of the binary supports it.
+        let mut nc = config::NetworkConfig::from_seed("123", tcp::ListenerAddr::reserve_for_test());
+        nc.require_encrypted_transport = true;
+        assert_eq!(
+            ProtocolFeature::EncryptedPeerTransport.protocol_version() <= PROTOCOL_VERSION,
+            nc.verify().is_ok()
+        );
     }
 
     #[test]
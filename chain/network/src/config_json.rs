@@ -60,6 +60,12 @@ fn default_peer_expiration_duration() -> Duration {
     Duration::from_secs(7 * 24 * 60 * 60)
 }
 
+/// Blocks, chunks, state witnesses and state parts are usually larger than that,
+/// while compressing small control messages isn't worth the CPU time.
+pub(crate) fn default_message_compression_threshold() -> Option<usize> {
+    Some(16 * 1024)
+}
+
 /// This is a list of public STUN servers provided by Google,
 /// which are known to have good availability. To avoid trusting
 /// a centralized entity (and DNS used for domain resolution),
@@ -190,12 +196,74 @@ pub struct Config {
     /// such a case.
     #[serde(default = "default_trusted_stun_servers")]
     pub trusted_stun_servers: Vec<stun::ServerAddr>,
+    /// Whether to refuse connections to and from peers which don't support
+    /// encryption of the connection. By default such connections fall back to
+    /// plaintext, which lets a node in the middle read and alter the messages.
+    #[serde(default)]
+    pub require_encrypted_transport: bool,
+    /// Messages of at least this size in bytes are sent compressed with zstd,
+    /// if the peer supports it. Set to `null` to disable compression.
+    #[serde(default = "default_message_compression_threshold")]
+    pub message_compression_threshold: Option<usize>,
+    /// Scoring of the misbehavior of peers, see `ReputationConfig`.
+    #[serde(default)]
+    pub reputation: ReputationConfig,
+    /// Bandwidth limits of the peer connections, see `BandwidthConfig`.
+    #[serde(default)]
+    pub bandwidth: BandwidthConfig,
     // Experimental part of the JSON config. Regular users/validators should not have to set any values there.
     // Field names in here can change/disappear at any moment without warning.
     #[serde(default)]
     pub experimental: ExperimentalConfig,
 }
 
+/// See `unc_network::peer_manager::reputation::Config`.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct ReputationConfig {
+    /// Time after which a penalty for misbehavior loses half of its weight.
+    pub decay_half_life: Duration,
+    /// Peers with at least this score are the last ones to connect to
+    /// and the first ones to disconnect from.
+    pub downrank_threshold: f64,
+    /// Peers with at least this score are disconnected and banned for `ban_duration`.
+    pub ban_threshold: f64,
+    /// Duration of the ban of peers with bad reputation.
+    pub ban_duration: Duration,
+}
+
+impl Default for ReputationConfig {
+    fn default() -> Self {
+        ReputationConfig {
+            decay_half_life: Duration::from_secs(10 * 60),
+            downrank_threshold: 50.,
+            ban_threshold: 100.,
+            ban_duration: Duration::from_secs(60 * 60),
+        }
+    }
+}
+
+/// See `unc_network::bandwidth::Config`.
+/// All the limits are in bytes per second and are unlimited if not set.
+/// Consensus messages are never delayed, but they count towards the limits.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct BandwidthConfig {
+    /// Upload limit of all the peer connections together.
+    pub global_upload_bytes_per_sec: Option<u64>,
+    /// Download limit of all the peer connections together.
+    pub global_download_bytes_per_sec: Option<u64>,
+    /// Upload limit of a single peer connection.
+    pub peer_upload_bytes_per_sec: Option<u64>,
+    /// Download limit of a single peer connection.
+    pub peer_download_bytes_per_sec: Option<u64>,
+    /// Upload limit of block requests and block headers sent over a single peer connection.
+    /// Blocks are consensus traffic and are not limited by it.
+    pub block_sync_upload_bytes_per_sec: Option<u64>,
+    /// Upload limit of state sync messages sent over a single peer connection.
+    pub state_sync_upload_bytes_per_sec: Option<u64>,
+}
+
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
 pub struct ExperimentalConfig {
     // If true - don't allow any inbound connections.
@@ -285,6 +353,10 @@ impl Default for Config {
             public_addrs: vec![],
             allow_private_ip_in_public_addrs: false,
             trusted_stun_servers: default_trusted_stun_servers(),
+            require_encrypted_transport: false,
+            message_compression_threshold: default_message_compression_threshold(),
+            reputation: Default::default(),
+            bandwidth: Default::default(),
             experimental: Default::default(),
         }
     }
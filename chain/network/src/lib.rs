@@ -2,6 +2,7 @@ pub use crate::peer_manager::peer_manager_actor::{Event, PeerManagerActor};
 
 mod accounts_data;
 mod announce_accounts;
+mod bandwidth;
 mod network_protocol;
 mod peer;
 mod peer_manager;
@@ -4,7 +4,7 @@
 //! WARNING WARNING WARNING
 //! We need to maintain backwards compatibility, all changes to this file needs to be reviews.
 use crate::network_protocol::edge::{Edge, PartialEdgeInfo};
-use crate::network_protocol::SyncSnapshotHosts;
+use crate::network_protocol::{CompressedMessage, SyncSnapshotHosts};
 use crate::network_protocol::{PeerChainInfoV2, PeerInfo, RoutedMessage, StateResponseInfo};
 use borsh::{BorshDeserialize, BorshSerialize};
 use std::fmt;
@@ -160,6 +160,8 @@ pub(super) enum PeerMessage {
     StateRequestPart(ShardId, CryptoHash, u64),
     VersionedStateResponse(StateResponseInfo),
     SyncSnapshotHosts(SyncSnapshotHosts),
+    /// Another PeerMessage, borsh encoded and then compressed.
+    Compressed(CompressedMessage),
 }
 //#[cfg(target_arch = "x86_64")] // Non-x86_64 doesn't match this requirement yet but it's not bad as it's not production-ready
 //const _: () = assert!(std::mem::size_of::<PeerMessage>() <= 1500, "PeerMessage > 1500 bytes");
@@ -2,6 +2,7 @@
 use crate::network_protocol as mem;
 use crate::network_protocol::borsh_ as net;
 use crate::network_protocol::{PeersRequest, PeersResponse, RoutedMessageV2};
+use borsh::BorshDeserialize as _;
 
 impl From<&net::Handshake> for mem::Handshake {
     fn from(x: &net::Handshake) -> Self {
@@ -14,6 +15,8 @@ impl From<&net::Handshake> for mem::Handshake {
             sender_chain_info: x.sender_chain_info.clone(),
             partial_edge_info: x.partial_edge_info.clone(),
             owned_account: None,
+            transport_key: None,
+            compression: None,
         }
     }
 }
@@ -136,6 +139,12 @@ pub enum ParsePeerMessageError {
     DeprecatedEpochSync,
     #[error("ResponseUpdateNonce is deprecated")]
     DeprecatedResponseUpdateNonce,
+    #[error("decompress: {0}")]
+    Decompress(mem::DecompressError),
+    #[error("compressed message: {0}")]
+    CompressedDecode(std::io::Error),
+    #[error("compressed message cannot contain another compressed message")]
+    NestedCompression,
 }
 
 impl TryFrom<&net::PeerMessage> for mem::PeerMessage {
@@ -203,10 +212,26 @@ impl TryFrom<&net::PeerMessage> for mem::PeerMessage {
                 mem::PeerMessage::VersionedStateResponse(sri)
             }
             net::PeerMessage::SyncSnapshotHosts(ssh) => mem::PeerMessage::SyncSnapshotHosts(ssh),
+            // `decompress` unwraps the outer compressed message before the conversion.
+            net::PeerMessage::Compressed(_) => return Err(Self::Error::NestedCompression),
         })
     }
 }
 
+/// Decompresses and decodes the message wrapped in `x`, if `x` is compressed with the
+/// compression `negotiated` on the connection. Other messages are returned as they are.
+pub(crate) fn decompress(
+    x: net::PeerMessage,
+    negotiated: Option<mem::Compression>,
+) -> Result<net::PeerMessage, ParsePeerMessageError> {
+    let net::PeerMessage::Compressed(c) = x else {
+        return Ok(x);
+    };
+    let data =
+        c.compression.decompress(negotiated, &c.data).map_err(ParsePeerMessageError::Decompress)?;
+    net::PeerMessage::try_from_slice(&data).map_err(ParsePeerMessageError::CompressedDecode)
+}
+
 // We are working on deprecating Borsh support for network messages altogether,
 // so any new message variants are simply unsupported.
 impl From<&mem::PeerMessage> for net::PeerMessage {
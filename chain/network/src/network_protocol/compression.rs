@@ -0,0 +1,85 @@
+//! Compression of large PeerMessages.
+//!
+//! Each side of a connection announces in its Handshake which compression it is able
+//! to decompress. If both sides support the same compression, messages above a size threshold
+//! are sent as a `CompressedMessage`, which wraps the message encoded in the same encoding
+//! (borsh or proto) as the connection uses. Compressed messages cannot be nested, and are
+//! rejected on connections which haven't negotiated the compression.
+use bytesize::MIB;
+use std::io::Read as _;
+
+/// zstd compression level. Higher levels give little gain for the messages
+/// we send (blocks, chunks, state parts) at a significantly higher CPU cost.
+const ZSTD_LEVEL: i32 = 3;
+
+/// Maximal size of a decompressed message. The messages worth compressing (blocks, chunks,
+/// state witnesses and state parts) are far smaller, and a few kilobytes of zstd can expand
+/// into hundreds of megabytes, so the general `NETWORK_MESSAGE_MAX_SIZE_BYTES` is too generous.
+pub(crate) const MAX_DECOMPRESSED_SIZE_BYTES: usize = 64 * MIB as usize;
+
+#[derive(
+    borsh::BorshSerialize,
+    borsh::BorshDeserialize,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    Debug,
+    Hash,
+    strum::IntoStaticStr,
+)]
+pub enum Compression {
+    Zstd,
+}
+
+/// Message encoded in the connection's encoding and then compressed.
+#[derive(borsh::BorshSerialize, borsh::BorshDeserialize, PartialEq, Eq, Clone, Debug)]
+pub struct CompressedMessage {
+    pub compression: Compression,
+    pub data: Vec<u8>,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum DecompressError {
+    #[error("{0:?} compression wasn't negotiated on this connection")]
+    NotNegotiated(Compression),
+    #[error("zstd: {0}")]
+    Zstd(#[source] std::io::Error),
+    #[error("decompressed message is larger than {MAX_DECOMPRESSED_SIZE_BYTES}B")]
+    TooLarge,
+}
+
+impl Compression {
+    pub(crate) fn compress(self, data: &[u8]) -> Vec<u8> {
+        match self {
+            Compression::Zstd => zstd::bulk::compress(data, ZSTD_LEVEL).unwrap(),
+        }
+    }
+
+    /// Decompresses `data`, provided that `self` is the compression `negotiated` on the
+    /// connection. The output is bounded by [`MAX_DECOMPRESSED_SIZE_BYTES`], so that a small
+    /// message cannot make the node allocate an arbitrary amount of memory.
+    pub(crate) fn decompress(
+        self,
+        negotiated: Option<Compression>,
+        data: &[u8],
+    ) -> Result<Vec<u8>, DecompressError> {
+        if negotiated != Some(self) {
+            return Err(DecompressError::NotNegotiated(self));
+        }
+        let mut out = vec![];
+        match self {
+            Compression::Zstd => {
+                zstd::stream::read::Decoder::new(data)
+                    .map_err(DecompressError::Zstd)?
+                    .take(MAX_DECOMPRESSED_SIZE_BYTES as u64 + 1)
+                    .read_to_end(&mut out)
+                    .map_err(DecompressError::Zstd)?;
+            }
+        }
+        if out.len() > MAX_DECOMPRESSED_SIZE_BYTES {
+            return Err(DecompressError::TooLarge);
+        }
+        Ok(out)
+    }
+}
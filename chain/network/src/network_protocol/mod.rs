@@ -2,10 +2,12 @@
 #[path = "borsh.rs"]
 mod borsh_;
 mod borsh_conv;
+mod compression;
 mod edge;
 mod peer;
 mod proto_conv;
 mod state_sync;
+pub use compression::*;
 pub use edge::*;
 pub use peer::*;
 pub use state_sync::*;
@@ -350,6 +352,42 @@ pub struct Handshake {
     pub(crate) partial_edge_info: PartialEdgeInfo,
     /// Account owned by the sender.
     pub(crate) owned_account: Option<SignedOwnedAccount>,
+    /// Ephemeral key offered to encrypt the connection, see `peer::encryption`.
+    pub(crate) transport_key: Option<TransportKey>,
+    /// Compression which the sender is able to decompress.
+    /// Large messages are compressed only if both sides of the connection support it.
+    pub(crate) compression: Option<Compression>,
+}
+
+/// Ephemeral X25519 public key offered in the handshake to set up an encrypted
+/// connection. It is signed with the node key of the sender, so that nobody
+/// in the middle can replace it with their own.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct TransportKey {
+    pub(crate) public_key: [u8; 32],
+    pub(crate) signature: Signature,
+}
+
+impl TransportKey {
+    fn payload(sender: &PeerId, target: &PeerId, public_key: &[u8; 32]) -> CryptoHash {
+        CryptoHash::hash_borsh(("TransportKey", sender, target, public_key))
+    }
+
+    pub(crate) fn new(
+        node_key: &unc_crypto::SecretKey,
+        sender: &PeerId,
+        target: &PeerId,
+        public_key: [u8; 32],
+    ) -> Self {
+        let signature = node_key.sign(Self::payload(sender, target, &public_key).as_ref());
+        Self { public_key, signature }
+    }
+
+    /// Verifies that the key has been offered by `sender` to `target`.
+    pub(crate) fn verify(&self, sender: &PeerId, target: &PeerId) -> bool {
+        self.signature
+            .verify(Self::payload(sender, target, &self.public_key).as_ref(), sender.public_key())
+    }
 }
 
 #[derive(PartialEq, Eq, Clone, Debug, strum::IntoStaticStr)]
@@ -470,22 +508,48 @@ impl PeerMessage {
         }
     }
 
+    /// Wraps a message serialized in the given encoding into a compressed message,
+    /// serialized in the same encoding.
+    pub(crate) fn compress(enc: Encoding, compression: Compression, data: &[u8]) -> Vec<u8> {
+        let compressed = CompressedMessage { compression, data: compression.compress(data) };
+        match enc {
+            Encoding::Borsh => borsh::to_vec(&borsh_::PeerMessage::Compressed(compressed)).unwrap(),
+            Encoding::Proto => {
+                let mut msg = proto::PeerMessage::from(compressed);
+                let cx = Span::current().context();
+                msg.trace_context = inject_trace_context(&cx);
+                msg.write_to_bytes().unwrap()
+            }
+        }
+    }
+
+    /// Deserializes a message in the given encoding.
+    /// Compressed messages are accepted only with the `compression` negotiated on the connection.
     pub(crate) fn deserialize(
         enc: Encoding,
         data: &[u8],
+        compression: Option<Compression>,
     ) -> Result<PeerMessage, ParsePeerMessageError> {
         let span = tracing::trace_span!(target: "network", "deserialize").entered();
         Ok(match enc {
-            Encoding::Borsh => (&borsh_::PeerMessage::try_from_slice(data)
-                .map_err(ParsePeerMessageError::BorshDecode)?)
-                .try_into()
-                .map_err(ParsePeerMessageError::BorshConv)?,
+            Encoding::Borsh => {
+                let msg = borsh_::PeerMessage::try_from_slice(data)
+                    .map_err(ParsePeerMessageError::BorshDecode)?;
+                let msg = borsh_conv::decompress(msg, compression)
+                    .map_err(ParsePeerMessageError::BorshConv)?;
+                (&msg).try_into().map_err(ParsePeerMessageError::BorshConv)?
+            }
             Encoding::Proto => {
                 let proto_msg: proto::PeerMessage = proto::PeerMessage::parse_from_bytes(data)
                     .map_err(ParsePeerMessageError::ProtoDecode)?;
                 if let Ok(extracted_span_context) = extract_span_context(&proto_msg.trace_context) {
                     span.clone().or_current().add_link(extracted_span_context);
                 }
+                let proto_msg = proto_conv::decompress(proto_msg, compression).map_err(|err| {
+                    ParsePeerMessageError::ProtoConv(proto_conv::ParsePeerMessageError::Compressed(
+                        err,
+                    ))
+                })?;
                 (&proto_msg).try_into().map_err(|err| ParsePeerMessageError::ProtoConv(err))?
             }
         })
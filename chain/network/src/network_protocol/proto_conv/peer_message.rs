@@ -5,12 +5,13 @@ use crate::network_protocol::proto::peer_message::Message_type as ProtoMT;
 use crate::network_protocol::proto::{self};
 use crate::network_protocol::state_sync::{SnapshotHostInfo, SyncSnapshotHosts};
 use crate::network_protocol::{
-    AdvertisedPeerDistance, Disconnect, DistanceVector, PeerMessage, PeersRequest, PeersResponse,
-    RoutingTableUpdate, SyncAccountsData,
+    AdvertisedPeerDistance, CompressedMessage, Compression, DecompressError, Disconnect,
+    DistanceVector, PeerMessage, PeersRequest, PeersResponse, RoutingTableUpdate, SyncAccountsData,
 };
 use crate::network_protocol::{RoutedMessage, RoutedMessageV2};
 use crate::types::StateResponseInfo;
 use borsh::BorshDeserialize as _;
+use protobuf::Message as _;
 use protobuf::MessageField as MF;
 use std::sync::Arc;
 use unc_async::time::error::ComponentRange;
@@ -228,6 +229,73 @@ impl TryFrom<&proto::SyncSnapshotHosts> for SyncSnapshotHosts {
 
 //////////////////////////////////////////
 
+#[derive(thiserror::Error, Debug)]
+#[error("unknown compression {0}")]
+pub struct ParseCompressionError(i32);
+
+impl From<Compression> for proto::compressed_message::Compression {
+    fn from(x: Compression) -> Self {
+        match x {
+            Compression::Zstd => Self::ZSTD,
+        }
+    }
+}
+
+impl TryFrom<protobuf::EnumOrUnknown<proto::compressed_message::Compression>> for Compression {
+    type Error = ParseCompressionError;
+    fn try_from(
+        x: protobuf::EnumOrUnknown<proto::compressed_message::Compression>,
+    ) -> Result<Self, Self::Error> {
+        match x.enum_value() {
+            Ok(proto::compressed_message::Compression::ZSTD) => Ok(Compression::Zstd),
+            _ => Err(ParseCompressionError(x.value())),
+        }
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum ParseCompressedMessageError {
+    #[error("compression: {0}")]
+    Compression(ParseCompressionError),
+    #[error("decompress: {0}")]
+    Decompress(DecompressError),
+    #[error("decode: {0}")]
+    Decode(protobuf::Error),
+    #[error("compressed message cannot contain another compressed message")]
+    Nested,
+}
+
+/// Takes `CompressedMessage` by value, to avoid copying the (large) compressed data.
+impl From<CompressedMessage> for proto::PeerMessage {
+    fn from(x: CompressedMessage) -> Self {
+        Self {
+            message_type: Some(ProtoMT::Compressed(proto::CompressedMessage {
+                compression: proto::compressed_message::Compression::from(x.compression).into(),
+                data: x.data,
+                ..Default::default()
+            })),
+            ..Default::default()
+        }
+    }
+}
+
+/// Decompresses and decodes the PeerMessage wrapped in `x`, if `x` is compressed with the
+/// compression `negotiated` on the connection. Other messages are returned as they are.
+pub(crate) fn decompress(
+    x: proto::PeerMessage,
+    negotiated: Option<Compression>,
+) -> Result<proto::PeerMessage, ParseCompressedMessageError> {
+    type Error = ParseCompressedMessageError;
+    let Some(ProtoMT::Compressed(c)) = &x.message_type else {
+        return Ok(x);
+    };
+    let compression = Compression::try_from(c.compression).map_err(Error::Compression)?;
+    let data = compression.decompress(negotiated, &c.data).map_err(Error::Decompress)?;
+    proto::PeerMessage::parse_from_bytes(&data).map_err(Error::Decode)
+}
+
+//////////////////////////////////////////
+
 impl From<&PeerMessage> for proto::PeerMessage {
     fn from(x: &PeerMessage) -> Self {
         Self {
@@ -386,6 +454,8 @@ pub enum ParsePeerMessageError {
     StateResponse(ParseRequiredError<ParseStateInfoError>),
     #[error("sync_snapshot_hosts: {0}")]
     SyncSnapshotHosts(ParseSyncSnapshotHostsError),
+    #[error("compressed: {0}")]
+    Compressed(ParseCompressedMessageError),
 }
 
 impl TryFrom<&proto::PeerMessage> for PeerMessage {
@@ -487,6 +557,10 @@ impl TryFrom<&proto::PeerMessage> for PeerMessage {
             ProtoMT::SyncSnapshotHosts(srh) => PeerMessage::SyncSnapshotHosts(
                 srh.try_into().map_err(Self::Error::SyncSnapshotHosts)?,
             ),
+            // `decompress` unwraps the outer compressed message before the conversion.
+            ProtoMT::Compressed(_) => {
+                return Err(Self::Error::Compressed(ParseCompressedMessageError::Nested))
+            }
         })
     }
 }
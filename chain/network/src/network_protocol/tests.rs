@@ -1,3 +1,4 @@
+use super::compression::MAX_DECOMPRESSED_SIZE_BYTES;
 use super::*;
 use crate::network_protocol::testonly as data;
 use crate::network_protocol::{Encoding, PeersResponse};
@@ -5,6 +6,7 @@ use crate::testonly::make_rng;
 use crate::types::{Disconnect, HandshakeFailureReason, PeerMessage};
 use crate::types::{PartialEncodedChunkRequestMsg, PartialEncodedChunkResponseMsg};
 use anyhow::{bail, Context as _};
+use assert_matches::assert_matches;
 use itertools::Itertools as _;
 use rand::Rng as _;
 use unc_async::time;
@@ -72,7 +74,7 @@ fn serialize_deserialize_protobuf_only() {
         }),
     ];
     for m in msgs {
-        let m2 = PeerMessage::deserialize(Encoding::Proto, &m.serialize(Encoding::Proto))
+        let m2 = PeerMessage::deserialize(Encoding::Proto, &m.serialize(Encoding::Proto), None)
             .with_context(|| m.to_string())
             .unwrap();
         assert_eq!(m, m2);
@@ -135,7 +137,7 @@ fn serialize_deserialize() -> anyhow::Result<()> {
     for enc in [Encoding::Proto, Encoding::Borsh] {
         for m in &msgs {
             (|| {
-                let m2 = PeerMessage::deserialize(enc, &m.serialize(enc))
+                let m2 = PeerMessage::deserialize(enc, &m.serialize(enc), None)
                     .with_context(|| m.to_string())?;
                 if *m != m2 {
                     bail!("deserialize(serialize({m}) = {m2}");
@@ -159,7 +161,7 @@ fn serialize_deserialize() -> anyhow::Result<()> {
     for (from, to) in [(Encoding::Proto, Encoding::Borsh), (Encoding::Borsh, Encoding::Proto)] {
         for m in &msgs {
             let bytes = &m.serialize(from);
-            match PeerMessage::deserialize(to, bytes) {
+            match PeerMessage::deserialize(to, bytes, None) {
                 Err(_) => {}
                 Ok(m2) => {
                     bail!("from={from:?},to={to:?}: deserialize(serialize({m})) = {m2}, want error")
@@ -170,3 +172,52 @@ fn serialize_deserialize() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn serialize_deserialize_compressed() {
+    let mut rng = make_rng(89028037453);
+    let mut clock = time::FakeClock::default();
+    let chain = data::Chain::make(&mut clock, &mut rng, 12);
+    let msgs = [
+        PeerMessage::Block(chain.blocks[5].clone()),
+        PeerMessage::BlockHeaders(chain.get_block_headers()),
+        PeerMessage::Transaction(data::make_signed_transaction(&mut rng)),
+    ];
+    for enc in [Encoding::Proto, Encoding::Borsh] {
+        for m in &msgs {
+            let compressed = PeerMessage::compress(enc, Compression::Zstd, &m.serialize(enc));
+            let m2 = PeerMessage::deserialize(enc, &compressed, Some(Compression::Zstd))
+                .with_context(|| format!("{m}, encoding={enc:?}"))
+                .unwrap();
+            assert_eq!(m, &m2);
+
+            tracing::debug!(target:"test", "compression has to be negotiated");
+            assert!(PeerMessage::deserialize(enc, &compressed, None).is_err());
+
+            tracing::debug!(target:"test", "compressed messages cannot be nested");
+            let nested = PeerMessage::compress(enc, Compression::Zstd, &compressed);
+            assert!(PeerMessage::deserialize(enc, &nested, Some(Compression::Zstd)).is_err());
+        }
+    }
+}
+
+#[test]
+fn compression_is_bounded() {
+    let data = vec![0; 10 * 1024];
+    let compressed = Compression::Zstd.compress(&data);
+    assert!(compressed.len() < data.len());
+    let negotiated = Some(Compression::Zstd);
+    assert_eq!(data, Compression::Zstd.decompress(negotiated, &compressed).unwrap());
+    assert!(Compression::Zstd.decompress(negotiated, &data).is_err());
+    assert_matches!(
+        Compression::Zstd.decompress(None, &compressed),
+        Err(DecompressError::NotNegotiated(Compression::Zstd))
+    );
+
+    let bomb = Compression::Zstd.compress(&vec![0; MAX_DECOMPRESSED_SIZE_BYTES + 1]);
+    assert!(bomb.len() < 64 * 1024);
+    assert_matches!(
+        Compression::Zstd.decompress(negotiated, &bomb),
+        Err(DecompressError::TooLarge)
+    );
+}
@@ -0,0 +1,261 @@
+//! Encryption of the frames sent over a peer connection.
+//!
+//! Both sides of a connection offer an ephemeral X25519 key in the
+//! Tier1/Tier2 handshake, signed by their node key (see
+//! `network_protocol::TransportKey`). Once both keys are known, the shared
+//! secret is expanded with HKDF-SHA256 into one ChaCha20-Poly1305 key per
+//! direction, salted with a hash of the whole key exchange. The keys are never
+//! reused: every connection attempt generates a new ephemeral key.
+//!
+//! Each side switches its outgoing frames to encryption by sending
+//! `ENCRYPTION_MARKER` as a plaintext frame, all the frames after that are
+//! sealed with a counter nonce. See `peer::stream` for the framing.
+use ring::aead;
+use ring::agreement;
+use ring::digest;
+use ring::hkdf;
+use ring::rand::SystemRandom;
+use unc_primitives::network::PeerId;
+
+/// Length of the ephemeral X25519 public key.
+pub(crate) const PUBLIC_KEY_LEN: usize = 32;
+/// Number of bytes the encryption adds to every frame.
+pub(crate) const TAG_LEN: usize = 16;
+/// Plaintext frame announcing that all the following frames in the same
+/// direction are encrypted. It starts with a zero byte, so it is never a valid
+/// proto encoded `PeerMessage`.
+pub(crate) const ENCRYPTION_MARKER: &[u8] = b"\0unc-encrypted-transport-v1";
+
+const KEY_EXCHANGE_DOMAIN: &[u8] = b"unc-encrypted-transport-v1";
+const OUTBOUND_KEY_INFO: &[u8] = b"outbound";
+const INBOUND_KEY_INFO: &[u8] = b"inbound";
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Error {
+    #[error("ephemeral key has already been used")]
+    KeyAlreadyUsed,
+    #[error("key agreement failed")]
+    KeyAgreement,
+    #[error("nonce exhausted")]
+    NonceExhausted,
+    #[error("frame authentication failed")]
+    Authentication,
+}
+
+/// Ephemeral X25519 key pair offered during a single handshake.
+pub(crate) struct EphemeralKey {
+    private: Option<agreement::EphemeralPrivateKey>,
+    public: [u8; PUBLIC_KEY_LEN],
+}
+
+impl std::fmt::Debug for EphemeralKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EphemeralKey").field("public", &self.public).finish()
+    }
+}
+
+impl EphemeralKey {
+    pub fn generate() -> Self {
+        let private =
+            agreement::EphemeralPrivateKey::generate(&agreement::X25519, &SystemRandom::new())
+                .expect("failed to generate X25519 key");
+        let mut public = [0; PUBLIC_KEY_LEN];
+        public.copy_from_slice(
+            private.compute_public_key().expect("failed to compute X25519 public key").as_ref(),
+        );
+        Self { private: Some(private), public }
+    }
+
+    pub fn public_key(&self) -> &[u8; PUBLIC_KEY_LEN] {
+        &self.public
+    }
+
+    /// Derives the ciphers of both directions of the connection from this key
+    /// and the key offered by the peer. The private key is consumed, so this
+    /// can be called only once.
+    /// `outbound` tells whether this node has initiated the connection.
+    pub fn derive_ciphers(
+        &mut self,
+        outbound: bool,
+        my_id: &PeerId,
+        peer_id: &PeerId,
+        peer_key: &[u8; PUBLIC_KEY_LEN],
+    ) -> Result<Ciphers, Error> {
+        let private = self.private.take().ok_or(Error::KeyAlreadyUsed)?;
+        let (outbound_id, outbound_key, inbound_id, inbound_key) = if outbound {
+            (my_id, &self.public, peer_id, peer_key)
+        } else {
+            (peer_id, peer_key, my_id, &self.public)
+        };
+        let mut transcript = digest::Context::new(&digest::SHA256);
+        transcript.update(KEY_EXCHANGE_DOMAIN);
+        transcript.update(&borsh::to_vec(outbound_id).unwrap());
+        transcript.update(outbound_key);
+        transcript.update(&borsh::to_vec(inbound_id).unwrap());
+        transcript.update(inbound_key);
+        let salt = hkdf::Salt::new(hkdf::HKDF_SHA256, transcript.finish().as_ref());
+        let (outbound_cipher, inbound_cipher) = agreement::agree_ephemeral(
+            private,
+            &agreement::UnparsedPublicKey::new(&agreement::X25519, peer_key),
+            Error::KeyAgreement,
+            |shared_secret| {
+                let prk = salt.extract(shared_secret);
+                Ok((
+                    Cipher::derive(&prk, OUTBOUND_KEY_INFO)?,
+                    Cipher::derive(&prk, INBOUND_KEY_INFO)?,
+                ))
+            },
+        )?;
+        Ok(if outbound {
+            Ciphers { send: outbound_cipher, recv: inbound_cipher }
+        } else {
+            Ciphers { send: inbound_cipher, recv: outbound_cipher }
+        })
+    }
+}
+
+/// Ciphers of both directions of a connection.
+#[derive(Debug)]
+pub(crate) struct Ciphers {
+    pub send: Cipher,
+    pub recv: Cipher,
+}
+
+/// ChaCha20-Poly1305 key of a single direction of a connection together with
+/// the nonce of the next frame.
+pub(crate) struct Cipher {
+    key: aead::LessSafeKey,
+    nonce: u64,
+}
+
+impl std::fmt::Debug for Cipher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Cipher").field("nonce", &self.nonce).finish()
+    }
+}
+
+impl Cipher {
+    fn derive(prk: &hkdf::Prk, info: &'static [u8]) -> Result<Self, Error> {
+        let info = [info];
+        let okm = prk.expand(&info, &aead::CHACHA20_POLY1305).map_err(|_| Error::KeyAgreement)?;
+        Ok(Self { key: aead::LessSafeKey::new(aead::UnboundKey::from(okm)), nonce: 0 })
+    }
+
+    fn next_nonce(&mut self) -> Result<aead::Nonce, Error> {
+        let mut nonce = [0; aead::NONCE_LEN];
+        nonce[..8].copy_from_slice(&self.nonce.to_le_bytes());
+        self.nonce = self.nonce.checked_add(1).ok_or(Error::NonceExhausted)?;
+        Ok(aead::Nonce::assume_unique_for_key(nonce))
+    }
+
+    /// Encrypts `frame` in place, appending the authentication tag.
+    pub fn seal(&mut self, frame: &mut Vec<u8>) -> Result<(), Error> {
+        let nonce = self.next_nonce()?;
+        self.key
+            .seal_in_place_append_tag(nonce, aead::Aad::empty(), frame)
+            .map_err(|_| Error::Authentication)
+    }
+
+    /// Decrypts `frame` in place, removing the authentication tag.
+    /// Fails if the frame has been tampered with, reordered or replayed.
+    pub fn open(&mut self, frame: &mut Vec<u8>) -> Result<(), Error> {
+        let nonce = self.next_nonce()?;
+        let len = self
+            .key
+            .open_in_place(nonce, aead::Aad::empty(), frame)
+            .map_err(|_| Error::Authentication)?
+            .len();
+        frame.truncate(len);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use unc_crypto::{KeyType, SecretKey};
+
+    fn peer_id(seed: &str) -> PeerId {
+        PeerId::new(SecretKey::from_seed(KeyType::ED25519, seed).public_key())
+    }
+
+    fn handshake() -> (Ciphers, Ciphers) {
+        let (outbound_id, inbound_id) = (peer_id("outbound"), peer_id("inbound"));
+        let mut outbound_key = EphemeralKey::generate();
+        let mut inbound_key = EphemeralKey::generate();
+        let outbound = outbound_key
+            .derive_ciphers(true, &outbound_id, &inbound_id, inbound_key.public_key())
+            .unwrap();
+        let inbound = inbound_key
+            .derive_ciphers(false, &inbound_id, &outbound_id, outbound_key.public_key())
+            .unwrap();
+        (outbound, inbound)
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let (mut outbound, mut inbound) = handshake();
+        for msg in [&b"hello"[..], &b""[..], &[7; 1000][..]] {
+            let mut frame = msg.to_vec();
+            outbound.send.seal(&mut frame).unwrap();
+            assert_eq!(frame.len(), msg.len() + TAG_LEN);
+            assert!(msg.is_empty() || &frame[..msg.len()] != msg);
+            inbound.recv.open(&mut frame).unwrap();
+            assert_eq!(frame, msg);
+
+            let mut frame = msg.to_vec();
+            inbound.send.seal(&mut frame).unwrap();
+            outbound.recv.open(&mut frame).unwrap();
+            assert_eq!(frame, msg);
+        }
+    }
+
+    #[test]
+    fn test_directions_use_different_keys() {
+        let (mut outbound, _) = handshake();
+        let mut frame = b"hello".to_vec();
+        outbound.send.seal(&mut frame).unwrap();
+        assert_eq!(outbound.recv.open(&mut frame), Err(Error::Authentication));
+    }
+
+    #[test]
+    fn test_tampered_frame() {
+        let (mut outbound, mut inbound) = handshake();
+        let mut frame = b"hello".to_vec();
+        outbound.send.seal(&mut frame).unwrap();
+        frame[0] ^= 1;
+        assert_eq!(inbound.recv.open(&mut frame), Err(Error::Authentication));
+    }
+
+    #[test]
+    fn test_replayed_frame() {
+        let (mut outbound, mut inbound) = handshake();
+        let mut frame = b"hello".to_vec();
+        outbound.send.seal(&mut frame).unwrap();
+        let replayed = frame.clone();
+        inbound.recv.open(&mut frame).unwrap();
+        assert_eq!(inbound.recv.open(&mut replayed.clone()), Err(Error::Authentication));
+    }
+
+    #[test]
+    fn test_peer_ids_are_bound() {
+        let (outbound_id, inbound_id) = (peer_id("outbound"), peer_id("inbound"));
+        let mut outbound_key = EphemeralKey::generate();
+        let mut inbound_key = EphemeralKey::generate();
+        let mut outbound = outbound_key
+            .derive_ciphers(true, &outbound_id, &inbound_id, inbound_key.public_key())
+            .unwrap();
+        let mut inbound = inbound_key
+            .derive_ciphers(false, &inbound_id, &peer_id("other"), outbound_key.public_key())
+            .unwrap();
+        let mut frame = b"hello".to_vec();
+        outbound.send.seal(&mut frame).unwrap();
+        assert_eq!(inbound.recv.open(&mut frame), Err(Error::Authentication));
+        assert_eq!(
+            outbound_key
+                .derive_ciphers(true, &outbound_id, &inbound_id, &[0; PUBLIC_KEY_LEN])
+                .err(),
+            Some(Error::KeyAlreadyUsed)
+        );
+    }
+}
@@ -1,14 +1,16 @@
 use crate::accounts_data::AccountDataError;
+use crate::bandwidth;
 use crate::concurrency::atomic_cell::AtomicCell;
 use crate::concurrency::demux;
 use crate::config::PEERS_RESPONSE_MAX_PEERS;
 use crate::network_protocol::SnapshotHostInfoVerificationError;
 use crate::network_protocol::{
-    DistanceVector, Edge, EdgeState, Encoding, OwnedAccount, ParsePeerMessageError,
+    Compression, DistanceVector, Edge, EdgeState, Encoding, OwnedAccount, ParsePeerMessageError,
     PartialEdgeInfo, PeerChainInfoV2, PeerIdOrHash, PeerInfo, PeersRequest, PeersResponse,
     RawRoutedMessage, RoutedMessageBody, RoutingTableUpdate, StateResponseInfo, SyncAccountsData,
-    SyncSnapshotHosts,
+    SyncSnapshotHosts, TransportKey,
 };
+use crate::peer::encryption;
 use crate::peer::stream;
 use crate::peer::tracker::Tracker;
 use crate::peer_manager::connection;
@@ -23,7 +25,8 @@ use crate::snapshot_hosts::SnapshotHostInfoError;
 use crate::stats::metrics;
 use crate::tcp;
 use crate::types::{
-    BlockInfo, Disconnect, Handshake, HandshakeFailureReason, PeerMessage, PeerType, ReasonForBan,
+    BlockInfo, Disconnect, Handshake, HandshakeFailureReason, Misbehavior, PeerMessage, PeerType,
+    ReasonForBan,
 };
 use actix::fut::future::wrap_future;
 use actix::{Actor as _, ActorContext as _, ActorFutureExt as _, AsyncContext as _};
@@ -47,9 +50,14 @@ use unc_primitives::network::{AnnounceAccount, PeerId};
 use unc_primitives::types::EpochId;
 use unc_primitives::utils::DisplayOption;
 use unc_primitives::version::{
-    ProtocolVersion, PEER_MIN_ALLOWED_PROTOCOL_VERSION, PROTOCOL_VERSION,
+    ProtocolFeature, ProtocolVersion, PEER_MIN_ALLOWED_PROTOCOL_VERSION, PROTOCOL_VERSION,
 };
 
+/// Whether the peers talking at `protocol_version` can encrypt the connection.
+fn supports_encryption(protocol_version: ProtocolVersion) -> bool {
+    ProtocolFeature::EncryptedPeerTransport.protocol_version() <= protocol_version
+}
+
 /// How often to request peers from active peers.
 const REQUEST_PEERS_INTERVAL: time::Duration = time::Duration::seconds(60);
 
@@ -64,6 +72,9 @@ const MAX_TRANSACTIONS_PER_BLOCK_MESSAGE: usize = 1000;
 const ROUTED_MESSAGE_CACHE_SIZE: usize = 1000;
 /// Duplicated messages will be dropped if routed through the same peer multiple times.
 pub(crate) const DROP_DUPLICATED_MESSAGES_PERIOD: time::Duration = time::Duration::milliseconds(50);
+/// Peers sending more dropped duplicated messages per second, averaged over
+/// `peer_stats_period`, lose reputation.
+pub(crate) const MAX_DUPLICATE_ROUTED_MESSAGES_PER_SEC: f64 = 10.;
 /// How often to send the latest block to peers.
 const SYNC_LATEST_BLOCK_INTERVAL: time::Duration = time::Duration::seconds(60);
 /// How often to perform a full sync of AccountsData with the peer.
@@ -114,6 +125,8 @@ pub(crate) enum ClosingReason {
     TooLargeClockSkew,
     #[error("owned_account.peer_id doesn't match handshake.sender_peer_id")]
     OwnedAccountMismatch,
+    #[error("peer doesn't support encrypted connections, but they are required")]
+    EncryptionRequired,
     #[error("PeerActor stopped NOT via PeerActor::stop()")]
     Unknown,
 }
@@ -135,6 +148,7 @@ impl ClosingReason {
             ClosingReason::DisconnectMessage => false, // graceful disconnect
             ClosingReason::TooLargeClockSkew => true, // reconnect will fail for the same reason
             ClosingReason::OwnedAccountMismatch => true, // misbehaving peer
+            ClosingReason::EncryptionRequired => true, // reconnect will fail for the same reason
             ClosingReason::Unknown => false,        // only happens in tests
         }
     }
@@ -169,6 +183,13 @@ pub(crate) struct PeerActor {
     /// Whether the PeerActor should skip protobuf support detection and use
     /// a given encoding right away.
     force_encoding: Option<Encoding>,
+    /// Ephemeral key offered in the handshake to encrypt the connection.
+    /// Outbound connections generate it upfront, inbound connections only once
+    /// the peer has offered its own key.
+    transport_key: Option<encryption::EphemeralKey>,
+    /// Compression of the large messages sent to the peer.
+    /// Set once both sides have offered it in the handshake.
+    compression: Option<Compression>,
 
     /// Peer status.
     peer_status: PeerStatus,
@@ -303,7 +324,13 @@ impl PeerActor {
                 let peer_addr = stream.peer_addr;
                 let stream_type = stream.type_.clone();
                 let stats = Arc::new(connection::Stats::default());
-                let framed = stream::FramedStream::spawn(ctx, stream, stats.clone());
+                let framed = stream::FramedStream::spawn(
+                    ctx,
+                    clock.clone(),
+                    stream,
+                    stats.clone(),
+                    network_state.bandwidth.clone(),
+                );
                 Self {
                     closing_reason: None,
                     clock,
@@ -321,6 +348,12 @@ impl PeerActor {
                     routed_message_cache: LruCache::new(ROUTED_MESSAGE_CACHE_SIZE),
                     protocol_buffers_supported: false,
                     force_encoding,
+                    transport_key: match &stream_type {
+                        tcp::StreamType::Inbound => None,
+                        tcp::StreamType::Outbound { .. } => supports_encryption(PROTOCOL_VERSION)
+                            .then(encryption::EphemeralKey::generate),
+                    },
+                    compression: None,
                     peer_info: match &stream_type {
                         tcp::StreamType::Inbound => None,
                         tcp::StreamType::Outbound { peer_id, .. } => Some(PeerInfo {
@@ -356,13 +389,13 @@ impl PeerActor {
 
     fn parse_message(&mut self, msg: &[u8]) -> Result<PeerMessage, ParsePeerMessageError> {
         if let Some(e) = self.encoding() {
-            return PeerMessage::deserialize(e, msg);
+            return PeerMessage::deserialize(e, msg, self.compression);
         }
-        if let Ok(msg) = PeerMessage::deserialize(Encoding::Proto, msg) {
+        if let Ok(msg) = PeerMessage::deserialize(Encoding::Proto, msg, self.compression) {
             self.protocol_buffers_supported = true;
             return Ok(msg);
         }
-        return PeerMessage::deserialize(Encoding::Borsh, msg);
+        return PeerMessage::deserialize(Encoding::Borsh, msg, self.compression);
     }
 
     fn send_message_or_log(&self, msg: &PeerMessage) {
@@ -408,11 +441,29 @@ impl PeerActor {
             _ => (),
         };
 
-        let bytes = msg.serialize(enc);
+        // Only handshake messages are sent before the connection is ready.
+        let category = match &self.peer_status {
+            PeerStatus::Ready(conn) => bandwidth::Category::of(conn.tier, msg),
+            _ => bandwidth::Category::Control,
+        };
+        let mut bytes = msg.serialize(enc);
+        let threshold = self.network_state.config.message_compression_threshold;
+        if let (Some(compression), Some(threshold)) = (self.compression, threshold) {
+            if bytes.len() >= threshold {
+                let compressed = PeerMessage::compress(enc, compression, &bytes);
+                metrics::PEER_MESSAGE_COMPRESSION_RATIO
+                    .with_label_values(&[msg_type])
+                    .observe(compressed.len() as f64 / bytes.len() as f64);
+                // Messages which don't compress well are sent as is.
+                if compressed.len() < bytes.len() {
+                    bytes = compressed;
+                }
+            }
+        }
         self.tracker.lock().increment_sent(&self.clock, bytes.len() as u64);
         let bytes_len = bytes.len();
         tracing::trace!(target: "network", msg_len = bytes_len);
-        self.framed.send(stream::Frame(bytes));
+        self.framed.send(category, stream::Frame(bytes));
         metrics::PEER_DATA_SENT_BYTES.inc_by(bytes_len as u64);
         metrics::PEER_MESSAGE_SENT_BY_TYPE_TOTAL.with_label_values(&[msg_type]).inc();
         metrics::PEER_MESSAGE_SENT_BY_TYPE_BYTES
@@ -421,6 +472,15 @@ impl PeerActor {
     }
 
     fn send_handshake(&self, spec: HandshakeSpec) {
+        let transport_key = match &self.transport_key {
+            Some(key) if supports_encryption(spec.protocol_version) => Some(TransportKey::new(
+                &self.network_state.config.node_key,
+                &self.my_node_info.id,
+                &spec.peer_id,
+                *key.public_key(),
+            )),
+            _ => None,
+        };
         let (height, tracked_shards) =
             if let Some(chain_info) = self.network_state.chain_info.load().as_ref() {
                 (chain_info.block.header().height(), chain_info.tracked_shards.clone())
@@ -449,6 +509,12 @@ impl PeerActor {
                 }
                 .sign(vc.signer.as_ref())
             }),
+            transport_key,
+            compression: self
+                .network_state
+                .config
+                .message_compression_threshold
+                .map(|_| Compression::Zstd),
         };
         let msg = match spec.tier {
             tcp::Tier::T1 => PeerMessage::Tier1Handshake(handshake),
@@ -598,6 +664,28 @@ impl PeerActor {
                 self.network_state.propose_edge(&self.clock, &handshake.sender_peer_id, Some(nonce))
             }
         };
+
+        // Set up the encryption of the connection, see `peer::encryption`.
+        let mut send_cipher = match self.negotiate_encryption(&handshake) {
+            Ok(send_cipher) => send_cipher,
+            Err(reason) => {
+                self.stop(ctx, reason);
+                return;
+            }
+        };
+        let encrypted = send_cipher.is_some();
+        // Large messages are compressed only if both sides offered to.
+        if self.network_state.config.message_compression_threshold.is_some() {
+            self.compression = handshake.compression;
+        }
+        // Outbound side knows both keys at this point, so it can switch right away.
+        // Inbound side switches after it responds with its own key.
+        if self.peer_type == PeerType::Outbound {
+            if let Some(cipher) = send_cipher.take() {
+                self.framed.start_encryption(cipher);
+            }
+        }
+
         let edge = Edge::new(
             self.my_node_id().clone(),
             handshake.sender_peer_id.clone(),
@@ -619,6 +707,9 @@ impl PeerActor {
             account_id: None,
         };
 
+        if tier == tcp::Tier::T1 {
+            self.framed.disable_download_limit();
+        }
         let now = self.clock.now();
         let conn = Arc::new(connection::Connection {
             tier,
@@ -630,6 +721,7 @@ impl PeerActor {
             archival: handshake.sender_chain_info.archival,
             last_block: Default::default(),
             peer_type: self.peer_type,
+            encrypted,
             stats: self.stats.clone(),
             _peer_connections_metric: metrics::PEER_CONNECTIONS.new_point(&metrics::Connection {
                 type_: self.peer_type,
@@ -649,10 +741,11 @@ impl PeerActor {
         let tracker = self.tracker.clone();
         let clock = self.clock.clone();
 
-        let mut interval =
-            time::Interval::new(clock.now(), self.network_state.config.peer_stats_period);
+        let peer_stats_period = self.network_state.config.peer_stats_period;
+        let mut interval = time::Interval::new(clock.now(), peer_stats_period);
         ctx.spawn({
             let conn = conn.clone();
+            let network_state = self.network_state.clone();
             wrap_future(async move {
                 loop {
                     interval.tick(&clock).await;
@@ -662,6 +755,17 @@ impl PeerActor {
                         .received_bytes_per_sec
                         .store(received.bytes_per_min / 60, Ordering::Relaxed);
                     conn.stats.sent_bytes_per_sec.store(sent.bytes_per_min / 60, Ordering::Relaxed);
+                    let duplicates =
+                        conn.stats.duplicate_routed_messages.swap(0, Ordering::Relaxed);
+                    if duplicates as f64
+                        > MAX_DUPLICATE_ROUTED_MESSAGES_PER_SEC * peer_stats_period.as_seconds_f64()
+                    {
+                        network_state.report_peer(
+                            &clock,
+                            &conn.peer_info.id,
+                            Misbehavior::UselessRoutedMessage,
+                        );
+                    }
                 }
             })
         });
@@ -695,6 +799,9 @@ impl PeerActor {
                                 protocol_version: handshake.protocol_version,
                                 partial_edge_info: partial_edge_info,
                             });
+                            if let Some(cipher) = send_cipher {
+                                act.framed.start_encryption(cipher);
+                            }
                         }
                         // TIER1 is strictly reserved for BFT consensensus messages,
                         // so all kinds of periodical syncs happen only on TIER2 connections.
@@ -827,6 +934,50 @@ impl PeerActor {
         self.send_message_or_log(&PeerMessage::SyncSnapshotHosts(SyncSnapshotHosts { hosts }));
     }
 
+    /// Derives the ciphers of the connection from the keys offered in the handshakes.
+    /// Starts decrypting the received frames once the peer switches to encryption
+    /// and returns the cipher to encrypt the sent frames with.
+    /// Returns None if the negotiated protocol version predates encryption.
+    /// A peer which supports encryption has to offer a transport key.
+    fn negotiate_encryption(
+        &mut self,
+        handshake: &Handshake,
+    ) -> Result<Option<encryption::Cipher>, ClosingReason> {
+        if !supports_encryption(PROTOCOL_VERSION.min(handshake.protocol_version)) {
+            if self.network_state.config.require_encrypted_transport {
+                return Err(ClosingReason::EncryptionRequired);
+            }
+            return Ok(None);
+        }
+        let Some(peer_key) = &handshake.transport_key else {
+            // Stripping the key must not downgrade the connection to plaintext.
+            return Err(ClosingReason::EncryptionRequired);
+        };
+        if !peer_key.verify(&handshake.sender_peer_id, &self.my_node_info.id) {
+            return Err(ClosingReason::Ban(ReasonForBan::InvalidSignature));
+        }
+        if self.peer_type == PeerType::Inbound {
+            self.transport_key = Some(encryption::EphemeralKey::generate());
+        }
+        let Some(my_key) = &mut self.transport_key else {
+            // Peer has offered a key, even though we haven't.
+            return Err(ClosingReason::HandshakeFailed);
+        };
+        let ciphers = my_key
+            .derive_ciphers(
+                self.peer_type == PeerType::Outbound,
+                &self.my_node_info.id,
+                &handshake.sender_peer_id,
+                &peer_key.public_key,
+            )
+            .map_err(|err| {
+                tracing::debug!(target: "network", peer_id = ?handshake.sender_peer_id, "key exchange failed: {err}");
+                ClosingReason::HandshakeFailed
+            })?;
+        self.framed.expect_encryption(ciphers.recv);
+        Ok(Some(ciphers.send))
+    }
+
     fn handle_msg_connecting(&mut self, ctx: &mut actix::Context<Self>, msg: PeerMessage) {
         match (&mut self.peer_status, msg) {
             (
@@ -1326,6 +1477,7 @@ impl PeerActor {
                         metrics::MessageDropped::Duplicate.inc(&msg.body);
                         self.network_state.config.event_sink.push(Event::RoutedMessageDropped);
                         tracing::debug!(target: "network", "Dropping duplicated message from {} to {:?}", msg.author, msg.target);
+                        conn.stats.duplicate_routed_messages.fetch_add(1, Ordering::Relaxed);
                         return;
                     }
                 }
@@ -1622,6 +1774,12 @@ impl actix::Handler<stream::Frame> for PeerActor {
         match &self.peer_status {
             PeerStatus::Connecting { .. } => self.handle_msg_connecting(ctx, peer_msg),
             PeerStatus::Ready(conn) => {
+                // Plaintext frames sent before the peer switched to encryption
+                // (like the duplicated borsh handshake) are not trusted.
+                if conn.encrypted && !self.framed.is_recv_encrypted() {
+                    tracing::debug!(target: "network", "Received unencrypted {} from {}. Ignoring", peer_msg, self.peer_info);
+                    return;
+                }
                 if self.closing_reason.is_some() {
                     tracing::warn!(target: "network", "Received {} from closing connection {:?}. Ignoring", peer_msg, self.peer_type);
                     return;
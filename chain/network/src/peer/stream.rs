@@ -1,19 +1,23 @@
+use crate::bandwidth;
+use crate::peer::encryption;
 use crate::peer_manager::connection;
 use crate::stats::metrics;
 use crate::tcp;
 use actix::fut::future::wrap_future;
 use actix::AsyncContext as _;
 use bytesize::{GIB, MIB};
+use parking_lot::Mutex;
 use std::io;
 use std::net::SocketAddr;
-use std::sync::atomic::Ordering;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use tokio::io::AsyncReadExt as _;
 use tokio::io::AsyncWriteExt as _;
+use unc_async::time;
 
 /// Maximum size of network message in encoded format.
 /// We encode length as `u32`, and therefore maximum size can't be larger than `u32::MAX`.
-const NETWORK_MESSAGE_MAX_SIZE_BYTES: usize = 512 * MIB as usize;
+pub(crate) const NETWORK_MESSAGE_MAX_SIZE_BYTES: usize = 512 * MIB as usize;
 /// Maximum capacity of write buffer in bytes.
 const MAX_WRITE_BUFFER_CAPACITY_BYTES: usize = GIB as usize;
 
@@ -34,12 +38,30 @@ pub(crate) enum RecvError {
     IO(#[source] io::Error),
     #[error("message too large: got {got_bytes}B, want <={want_max_bytes}B")]
     MessageTooLarge { got_bytes: usize, want_max_bytes: usize },
+    #[error("decryption: {0}")]
+    Decryption(#[source] encryption::Error),
 }
 
 #[derive(actix::Message, PartialEq, Eq, Clone, Debug)]
 #[rtype(result = "()")]
 pub(crate) struct Frame(pub Vec<u8>);
 
+/// Item of the send queue.
+enum Command {
+    Send(bandwidth::Category, Frame),
+    /// Sends `encryption::ENCRYPTION_MARKER` and encrypts all the following frames.
+    StartEncryption(encryption::Cipher),
+}
+
+/// Decryption state of the received frames, shared between the Actor and the recv loop.
+#[derive(Default)]
+struct RecvEncryption {
+    /// Cipher to use once the peer sends `encryption::ENCRYPTION_MARKER`.
+    pending: Mutex<Option<encryption::Cipher>>,
+    /// Whether the peer has switched to encryption.
+    enabled: AtomicBool,
+}
+
 /// Stream critical error.
 /// Actor is responsible for calling ctx.stop() after receiving stream::Error.
 /// Actor might receive more than 1 stream::Error, but should call ctx.stop() just after the
@@ -57,7 +79,10 @@ pub(crate) enum Error {
 }
 
 pub(crate) struct FramedStream<Actor: actix::Actor> {
-    queue_send: tokio::sync::mpsc::UnboundedSender<Frame>,
+    queue_send: tokio::sync::mpsc::UnboundedSender<Command>,
+    recv_encryption: Arc<RecvEncryption>,
+    /// Whether the received frames are subject to the download bandwidth limits.
+    download_limited: Arc<AtomicBool>,
     stats: Arc<connection::Stats>,
     send_buf_size_metric: Arc<metrics::IntGaugeGuard>,
     addr: actix::Addr<Actor>,
@@ -71,21 +96,31 @@ where
 {
     pub fn spawn(
         ctx: &mut actix::Context<Actor>,
+        clock: time::Clock,
         stream: tcp::Stream,
         stats: Arc<connection::Stats>,
+        bandwidth: Arc<bandwidth::Global>,
     ) -> Self {
         let (tcp_recv, tcp_send) = tokio::io::split(stream.stream);
         let (queue_send, queue_recv) = tokio::sync::mpsc::unbounded_channel();
+        let recv_encryption = Arc::new(RecvEncryption::default());
+        let download_limited = Arc::new(AtomicBool::new(true));
+        let send_queue =
+            bandwidth::SendQueue::new(bandwidth::UploadLimiter::new(&clock, bandwidth.clone()));
+        let download_limiter = bandwidth::DownloadLimiter::new(&clock, bandwidth);
         let send_buf_size_metric = Arc::new(metrics::MetricGuard::new(
             &*metrics::PEER_DATA_WRITE_BUFFER_SIZE,
             vec![stream.peer_addr.to_string()],
         ));
         ctx.spawn(wrap_future({
             let addr = ctx.address();
+            let clock = clock.clone();
             let stats = stats.clone();
             let m = send_buf_size_metric.clone();
             async move {
-                if let Err(err) = Self::run_send_loop(tcp_send, queue_recv, stats, m).await {
+                if let Err(err) =
+                    Self::run_send_loop(&clock, tcp_send, queue_recv, send_queue, stats, m).await
+                {
                     addr.do_send(Error::Send(SendError::IO(err)));
                 }
             }
@@ -93,22 +128,66 @@ where
         ctx.spawn(wrap_future({
             let addr = ctx.address();
             let stats = stats.clone();
+            let recv_encryption = recv_encryption.clone();
+            let download_limited = download_limited.clone();
             async move {
-                if let Err(err) =
-                    Self::run_recv_loop(stream.peer_addr, tcp_recv, addr.clone(), stats).await
+                if let Err(err) = Self::run_recv_loop(
+                    &clock,
+                    stream.peer_addr,
+                    tcp_recv,
+                    addr.clone(),
+                    stats,
+                    recv_encryption,
+                    download_limiter,
+                    download_limited,
+                )
+                .await
                 {
                     addr.do_send(Error::Recv(err));
                 }
             }
         }));
-        Self { queue_send, stats, send_buf_size_metric, addr: ctx.address() }
+        Self {
+            queue_send,
+            recv_encryption,
+            download_limited,
+            stats,
+            send_buf_size_metric,
+            addr: ctx.address(),
+        }
+    }
+
+    /// Stops applying the download bandwidth limits to the received frames.
+    /// Used for TIER1 connections, which carry consensus traffic only.
+    pub fn disable_download_limit(&self) {
+        self.download_limited.store(false, Ordering::Relaxed);
     }
 
-    /// Pushes `msg` to the send queue.
+    /// Encrypts all the frames sent after this call with `cipher`.
+    /// The peer is notified by sending `encryption::ENCRYPTION_MARKER` first.
+    pub fn start_encryption(&self, cipher: encryption::Cipher) {
+        let _ = self.queue_send.send(Command::StartEncryption(cipher));
+    }
+
+    /// Decrypts the received frames with `cipher` once the peer sends
+    /// `encryption::ENCRYPTION_MARKER`. Frames received before that are passed through as is.
+    /// Must be called before the peer can possibly send the marker, i.e. before
+    /// the handler of the frame which triggered the key exchange returns.
+    pub fn expect_encryption(&self, cipher: encryption::Cipher) {
+        *self.recv_encryption.pending.lock() = Some(cipher);
+    }
+
+    /// Whether the peer has switched to encryption, i.e. whether the frame
+    /// which is being handled right now has been decrypted.
+    pub fn is_recv_encrypted(&self) -> bool {
+        self.recv_encryption.enabled.load(Ordering::Acquire)
+    }
+
+    /// Pushes `msg` to the send queue of the given category.
     /// Silently drops message if the connection has been closed.
     /// If the message is too large, it will be silently dropped inside run_send_loop.
     /// Emits a critical error to Actor if send queue is full.
-    pub fn send(&self, frame: Frame) {
+    pub fn send(&self, category: bandwidth::Category, frame: Frame) {
         let msg = &frame.0;
         let mut buf_size =
             self.stats.bytes_to_send.fetch_add(msg.len() as u64, Ordering::Acquire) as usize;
@@ -125,7 +204,7 @@ where
                 want_max_bytes: MAX_WRITE_BUFFER_CAPACITY_BYTES,
             }));
         }
-        let _ = self.queue_send.send(frame);
+        let _ = self.queue_send.send(Command::Send(category, frame));
     }
 
     /// Event loop receiving and processing messages.
@@ -134,13 +213,21 @@ where
     /// then the loop will start reading the next message before the subhandler returns.
     /// Loop uses a fixed small buffer allocated by BufReader.
     /// For each message it allocates a Vec with exact size of the message.
+    /// Once the peer sends `encryption::ENCRYPTION_MARKER`, all the following
+    /// messages are decrypted and any frame failing authentication is a critical error.
+    /// Once the download bandwidth limits are exceeded, the loop stops reading for a while,
+    /// so that TCP flow control slows down the peer.
     // TODO(gprusak): once borsh support is dropped, we can parse a proto
     // directly from the stream.
     async fn run_recv_loop(
+        clock: &time::Clock,
         peer_addr: SocketAddr,
         read: ReadHalf,
         addr: actix::Addr<Actor>,
         stats: Arc<connection::Stats>,
+        recv_encryption: Arc<RecvEncryption>,
+        mut download_limiter: bandwidth::DownloadLimiter,
+        download_limited: Arc<AtomicBool>,
     ) -> Result<(), RecvError> {
         const READ_BUFFER_CAPACITY: usize = 8 * 1024;
         let mut read = tokio::io::BufReader::with_capacity(READ_BUFFER_CAPACITY, read);
@@ -151,13 +238,15 @@ where
             &metrics::PEER_DATA_READ_BUFFER_SIZE,
             vec![peer_addr.to_string()],
         );
+        let mut cipher: Option<encryption::Cipher> = None;
         loop {
             let n = read.read_u32_le().await.map_err(RecvError::IO)? as usize;
-            if n > NETWORK_MESSAGE_MAX_SIZE_BYTES {
-                return Err(RecvError::MessageTooLarge {
-                    got_bytes: n,
-                    want_max_bytes: NETWORK_MESSAGE_MAX_SIZE_BYTES,
-                });
+            let max_size = match cipher {
+                Some(_) => NETWORK_MESSAGE_MAX_SIZE_BYTES + encryption::TAG_LEN,
+                None => NETWORK_MESSAGE_MAX_SIZE_BYTES,
+            };
+            if n > max_size {
+                return Err(RecvError::MessageTooLarge { got_bytes: n, want_max_bytes: max_size });
             }
             msg_size_metric.observe(n as f64);
             buf_size_metric.set(n as i64);
@@ -168,40 +257,98 @@ where
             buf_size_metric.set(0);
             stats.received_messages.fetch_add(1, Ordering::Relaxed);
             stats.received_bytes.fetch_add(n as u64, Ordering::Relaxed);
+            // The frame is delivered even if it exceeds the limits; only the following
+            // reads wait. See `bandwidth` for why consensus frames are not exempt here.
+            let delay = match download_limited.load(Ordering::Relaxed) {
+                true => download_limiter.charge(clock.now(), n),
+                false => time::Duration::ZERO,
+            };
+            match &mut cipher {
+                Some(cipher) => cipher.open(&mut buf).map_err(RecvError::Decryption)?,
+                None if buf == encryption::ENCRYPTION_MARKER => {
+                    if let Some(pending) = recv_encryption.pending.lock().take() {
+                        cipher = Some(pending);
+                        recv_encryption.enabled.store(true, Ordering::Release);
+                        continue;
+                    }
+                }
+                None => {}
+            }
             if let Err(_) = addr.send(Frame(buf)).await {
                 // We got mailbox error, which means that Actor has stopped,
                 // so we should just close the stream.
                 return Ok(());
             }
+            if delay > time::Duration::ZERO {
+                metrics::PEER_DOWNLOAD_THROTTLE_DELAY.observe(delay.as_seconds_f64());
+                clock.sleep(delay).await;
+            }
         }
     }
+    /// Event loop writing the frames from the send queue to the socket.
+    /// Frames are sent in the order of priority of their categories, as soon as the upload
+    /// bandwidth limits allow it. The socket is flushed whenever there is nothing more to send
+    /// right away.
     async fn run_send_loop(
+        clock: &time::Clock,
         tcp_send: WriteHalf,
-        mut queue_recv: tokio::sync::mpsc::UnboundedReceiver<Frame>,
+        mut queue_recv: tokio::sync::mpsc::UnboundedReceiver<Command>,
+        mut send_queue: bandwidth::SendQueue<Frame>,
         stats: Arc<connection::Stats>,
         buf_size_metric: Arc<metrics::IntGaugeGuard>,
     ) -> io::Result<()> {
         const WRITE_BUFFER_CAPACITY: usize = 8 * 1024;
         let mut writer = tokio::io::BufWriter::with_capacity(WRITE_BUFFER_CAPACITY, tcp_send);
-        while let Some(Frame(mut msg)) = queue_recv.recv().await {
-            // Try writing a batch of messages and flush once at the end.
-            loop {
-                // TODO(gprusak): sending a too large message should probably be treated as a bug,
-                // since dropping messages may lead to hard-to-debug high-level issues.
-                if msg.len() > NETWORK_MESSAGE_MAX_SIZE_BYTES {
-                    metrics::MessageDropped::InputTooLong.inc_unknown_msg();
-                } else {
-                    writer.write_u32_le(msg.len() as u32).await?;
-                    writer.write_all(&msg[..]).await?;
+        let mut cipher: Option<encryption::Cipher> = None;
+        // StartEncryption command waiting for the frames queued before it to be sent.
+        let mut pending_cipher: Option<encryption::Cipher> = None;
+        let mut closed = false;
+        loop {
+            // Move the commands from the channel to the send queue.
+            // StartEncryption is a barrier: no frames are queued after it until it is executed.
+            while pending_cipher.is_none() && !closed {
+                match queue_recv.try_recv() {
+                    Ok(Command::Send(category, frame)) => {
+                        send_queue.push(clock.now(), category, frame)
+                    }
+                    Ok(Command::StartEncryption(it)) => pending_cipher = Some(it),
+                    Err(tokio::sync::mpsc::error::TryRecvError::Empty) => break,
+                    Err(tokio::sync::mpsc::error::TryRecvError::Disconnected) => closed = true,
                 }
-                stats.messages_to_send.fetch_sub(1, Ordering::Release);
-                stats.bytes_to_send.fetch_sub(msg.len() as u64, Ordering::Release);
-                buf_size_metric.sub(msg.len() as i64);
-                msg = match queue_recv.try_recv() {
-                    Ok(Frame(it)) => it,
-                    Err(_) => break,
-                };
             }
+            let now = clock.now();
+            let wait = match send_queue.pop(now) {
+                bandwidth::Next::Ready(category, Frame(mut msg)) => {
+                    let len = msg.len();
+                    // TODO(gprusak): sending a too large message should probably be treated as a bug,
+                    // since dropping messages may lead to hard-to-debug high-level issues.
+                    if len > NETWORK_MESSAGE_MAX_SIZE_BYTES {
+                        metrics::MessageDropped::InputTooLong.inc_unknown_msg();
+                    } else {
+                        if let Some(cipher) = &mut cipher {
+                            cipher.seal(&mut msg).map_err(io::Error::other)?;
+                        }
+                        writer.write_u32_le(msg.len() as u32).await?;
+                        writer.write_all(&msg[..]).await?;
+                        send_queue.charge(now, category, msg.len());
+                    }
+                    stats.messages_to_send.fetch_sub(1, Ordering::Release);
+                    stats.bytes_to_send.fetch_sub(len as u64, Ordering::Release);
+                    buf_size_metric.sub(len as i64);
+                    continue;
+                }
+                bandwidth::Next::Wait(wait) => Some(wait),
+                bandwidth::Next::Empty => {
+                    if let Some(it) = pending_cipher.take() {
+                        writer.write_u32_le(encryption::ENCRYPTION_MARKER.len() as u32).await?;
+                        writer.write_all(encryption::ENCRYPTION_MARKER).await?;
+                        cipher = Some(it);
+                        continue;
+                    }
+                    None
+                }
+            };
+            // Nothing more can be sent right away.
             // This is an unconditional flush, which means that even if new messages
             // will be added to the queue in the meantime, we will wait for the buffer
             // to be flushed before sending them. This is suboptimal in case messages are small
@@ -209,7 +356,19 @@ where
             // we would need to put writer.flush() and queue_recv.recv() into a tokio::select
             // and make sure that both are cancellation-safe.
             writer.flush().await?;
+            if closed && wait.is_none() {
+                return Ok(());
+            }
+            tokio::select! {
+                cmd = queue_recv.recv(), if !closed && pending_cipher.is_none() => match cmd {
+                    Some(Command::Send(category, frame)) => {
+                        send_queue.push(clock.now(), category, frame)
+                    }
+                    Some(Command::StartEncryption(it)) => pending_cipher = Some(it),
+                    None => closed = true,
+                },
+                () = clock.sleep(wait.unwrap_or(time::Duration::ZERO)), if wait.is_some() => {}
+            }
         }
-        Ok(())
     }
 }
@@ -1,4 +1,6 @@
 use crate::actix::ActixSystem;
+use crate::bandwidth;
+use crate::concurrency::rate;
 use crate::network_protocol::testonly as data;
 use crate::peer::stream;
 use crate::tcp;
@@ -8,6 +10,7 @@ use actix::ActorContext as _;
 use rand::Rng as _;
 use std::sync::Arc;
 use tokio::sync::mpsc;
+use unc_async::time;
 
 struct Actor {
     stream: stream::FramedStream<Actor>,
@@ -20,12 +23,12 @@ impl actix::Actor for Actor {
 
 #[derive(actix::Message)]
 #[rtype("()")]
-struct SendFrame(stream::Frame);
+struct SendFrame(bandwidth::Category, stream::Frame);
 
 impl actix::Handler<SendFrame> for Actor {
     type Result = ();
-    fn handle(&mut self, SendFrame(frame): SendFrame, _ctx: &mut Self::Context) {
-        self.stream.send(frame);
+    fn handle(&mut self, SendFrame(category, frame): SendFrame, _ctx: &mut Self::Context) {
+        self.stream.send(category, frame);
     }
 }
 
@@ -49,13 +52,16 @@ struct Handler {
 }
 
 impl Actor {
-    async fn spawn(s: tcp::Stream) -> Handler {
+    async fn spawn(s: tcp::Stream, bandwidth: bandwidth::Config) -> Handler {
         let (queue_send, queue_recv) = mpsc::unbounded_channel();
         Handler {
             queue_recv,
             system: ActixSystem::spawn(|| {
                 Actor::create(|ctx| {
-                    let stream = stream::FramedStream::spawn(ctx, s, Arc::default());
+                    let clock = time::Clock::real();
+                    let bandwidth = Arc::new(bandwidth::Global::new(&clock, bandwidth));
+                    let stream =
+                        stream::FramedStream::spawn(ctx, clock, s, Arc::default(), bandwidth);
                     Self { stream, queue_send }
                 })
             })
@@ -68,8 +74,8 @@ impl Actor {
 async fn send_recv() {
     let mut rng = make_rng(98324532);
     let (s1, s2) = tcp::Stream::loopback(data::make_peer_id(&mut rng), tcp::Tier::T2).await;
-    let a1 = Actor::spawn(s1).await;
-    let mut a2 = Actor::spawn(s2).await;
+    let a1 = Actor::spawn(s1, bandwidth::Config::default()).await;
+    let mut a2 = Actor::spawn(s2, bandwidth::Config::default()).await;
 
     for _ in 0..5 {
         let n = rng.gen_range(1..10);
@@ -82,7 +88,8 @@ async fn send_recv() {
             })
             .collect();
         for msg in &msgs {
-            a1.system.addr.send(SendFrame(msg.clone())).await.unwrap();
+            let category = bandwidth::Category::Control;
+            a1.system.addr.send(SendFrame(category, msg.clone())).await.unwrap();
         }
         for want in &msgs {
             let got = a2.queue_recv.recv().await.unwrap();
@@ -90,3 +97,33 @@ async fn send_recv() {
         }
     }
 }
+
+#[tokio::test]
+async fn consensus_overtakes_throttled_frames() {
+    let mut rng = make_rng(98324532);
+    let (s1, s2) = tcp::Stream::loopback(data::make_peer_id(&mut rng), tcp::Tier::T2).await;
+    let config = bandwidth::Config {
+        peer_upload: Some(rate::Limit { qps: 10000., burst: 10000 }),
+        ..bandwidth::Config::default()
+    };
+    let a1 = Actor::spawn(s1, config).await;
+    let mut a2 = Actor::spawn(s2, bandwidth::Config::default()).await;
+
+    // The first 2 frames exhaust the upload limit, so the third one has to wait for 1s.
+    let state_parts: Vec<_> = (0..3u8).map(|i| stream::Frame(vec![i; 10000])).collect();
+    for frame in &state_parts {
+        a1.system
+            .addr
+            .send(SendFrame(bandwidth::Category::StateSync, frame.clone()))
+            .await
+            .unwrap();
+    }
+    let approval = stream::Frame(vec![42; 100]);
+    a1.system.addr.send(SendFrame(bandwidth::Category::Consensus, approval.clone())).await.unwrap();
+
+    let want = [&state_parts[0], &state_parts[1], &approval, &state_parts[2]];
+    for want in want {
+        let got = a2.queue_recv.recv().await.unwrap();
+        assert_eq!(&got, want);
+    }
+}
@@ -62,6 +62,8 @@ pub(crate) struct Stats {
     pub received_bytes_per_sec: AtomicU64,
     /// Avg sent bytes/s, based on the last few minutes of traffic.
     pub sent_bytes_per_sec: AtomicU64,
+    /// Number of dropped duplicated routed messages since the last reset of the counter.
+    pub duplicate_routed_messages: AtomicU64,
 
     /// Number of messages in the buffer to send.
     pub messages_to_send: AtomicU64,
@@ -90,6 +92,8 @@ pub(crate) struct Connection {
 
     /// Who started connection. Inbound (other) or Outbound (us).
     pub peer_type: PeerType,
+    /// Whether the frames sent over the connection are encrypted, see `peer::encryption`.
+    pub encrypted: bool,
     /// Time where the connection was established.
     pub established_time: time::Instant,
 
@@ -113,6 +117,7 @@ impl fmt::Debug for Connection {
         f.debug_struct("Connection")
             .field("peer_info", &self.peer_info)
             .field("peer_type", &self.peer_type)
+            .field("encrypted", &self.encrypted)
             .field("established_time", &self.established_time)
             .finish()
     }
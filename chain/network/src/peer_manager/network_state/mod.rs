@@ -1,5 +1,6 @@
 use crate::accounts_data::{AccountDataCache, AccountDataError};
 use crate::announce_accounts::AnnounceAccountCache;
+use crate::bandwidth;
 use crate::client;
 use crate::concurrency::demux;
 use crate::concurrency::runtime::Runtime;
@@ -14,6 +15,7 @@ use crate::peer_manager::connection;
 use crate::peer_manager::connection_store;
 use crate::peer_manager::peer_manager_actor::Event;
 use crate::peer_manager::peer_store;
+use crate::peer_manager::reputation;
 use crate::private_actix::RegisterPeerError;
 use crate::routing::route_back_cache::RouteBackCache;
 use crate::routing::NetworkTopologyChange;
@@ -22,7 +24,7 @@ use crate::snapshot_hosts::{SnapshotHostInfoError, SnapshotHostsCache};
 use crate::stats::metrics;
 use crate::store;
 use crate::tcp;
-use crate::types::{ChainInfo, PeerType, ReasonForBan};
+use crate::types::{ChainInfo, Misbehavior, PeerType, ReasonForBan};
 use anyhow::Context;
 use arc_swap::ArcSwap;
 use parking_lot::Mutex;
@@ -114,6 +116,10 @@ pub(crate) struct NetworkState {
     pub inbound_handshake_permits: Arc<tokio::sync::Semaphore>,
     /// Peer store that provides read/write access to peers.
     pub peer_store: peer_store::PeerStore,
+    /// Reputation of peers, based on their reported misbehavior.
+    pub reputation: reputation::Reputation,
+    /// Bandwidth limits shared by all the connections.
+    pub bandwidth: Arc<bandwidth::Global>,
     /// Information about state snapshots hosted by network peers.
     pub snapshot_hosts: Arc<SnapshotHostsCache>,
     /// Connection store that provides read/write access to stored connections.
@@ -192,6 +198,12 @@ impl NetworkState {
             tier1: connection::Pool::new(config.node_id()),
             inbound_handshake_permits: Arc::new(tokio::sync::Semaphore::new(LIMIT_PENDING_PEERS)),
             peer_store,
+            reputation: reputation::Reputation::new(
+                clock,
+                config.reputation.clone(),
+                store.clone(),
+            ),
+            bandwidth: Arc::new(bandwidth::Global::new(clock, config.bandwidth.clone())),
             snapshot_hosts: Arc::new(SnapshotHostsCache::new(config.snapshot_hosts.clone())),
             connection_store: connection_store::ConnectionStore::new(store.clone()).unwrap(),
             pending_reconnect: Mutex::new(Vec::<PeerInfo>::new()),
@@ -246,6 +258,18 @@ impl NetworkState {
         }
     }
 
+    /// Lowers the reputation of the peer. If the peer gets banned as a result,
+    /// the TIER1 and TIER2 connections to it are closed.
+    pub fn report_peer(&self, clock: &time::Clock, peer_id: &PeerId, misbehavior: Misbehavior) {
+        if self.reputation.report(clock, peer_id, misbehavior) {
+            for pool in [&self.tier1, &self.tier2] {
+                if let Some(peer) = pool.load().ready.get(peer_id) {
+                    peer.stop(None);
+                }
+            }
+        }
+    }
+
     /// is_peer_whitelisted checks whether a peer is a whitelisted node.
     /// whitelisted nodes are allowed to connect, even if the inbound connections limit has
     /// been reached. This predicate should be evaluated AFTER the Handshake.
@@ -300,6 +324,10 @@ impl NetworkState {
                 tracing::debug!(target: "network", id = ?peer_info.id, "Dropping connection from banned peer");
                 return Err(RegisterPeerError::Banned);
             }
+            if this.reputation.is_banned(&peer_info.id) {
+                tracing::debug!(target: "network", id = ?peer_info.id, "Dropping connection from peer with bad reputation");
+                return Err(RegisterPeerError::Banned);
+            }
 
             match conn.tier {
                 tcp::Tier::T1 => {
@@ -458,16 +458,17 @@ impl PeerManagerActor {
     }
 
     /// Check if the number of connections (excluding whitelisted ones) exceeds ideal_connections_hi.
-    /// If so, constructs a safe set of peers and selects one random peer outside of that set
-    /// and sends signal to stop connection to it gracefully.
+    /// If so, constructs a safe set of peers and selects one peer outside of that set
+    /// and sends signal to stop connection to it gracefully. The selected peer is the one
+    /// with the worst reputation among the downranked peers, or a random one if there are none.
     ///
     /// Safe set contruction process:
     /// 1. Add all whitelisted peers to the safe set.
     /// 2. If the number of outbound connections is less or equal than minimum_outbound_connections,
     ///    add all outbound connections to the safe set.
-    /// 3. Find all peers who sent us a message within the last peer_recent_time_window,
-    ///    and add them one by one to the safe_set (starting from earliest connection time)
-    ///    until safe set has safe_set_size elements.
+    /// 3. Find all peers who sent us a message within the last peer_recent_time_window
+    ///    and are not downranked, and add them one by one to the safe_set
+    ///    (starting from earliest connection time) until safe set has safe_set_size elements.
     fn maybe_stop_active_connection(&self) {
         let tier2 = self.state.tier2.load();
         let filter_peers = |predicate: &dyn Fn(&connection::Connection) -> bool| -> Vec<_> {
@@ -517,6 +518,7 @@ impl PeerManagerActor {
             .filter(|p| {
                 now - p.last_time_received_message.load()
                     < self.state.config.peer_recent_time_window
+                    && !self.state.reputation.is_downranked(&self.clock, &p.peer_info.id)
             })
             .cloned()
             .collect();
@@ -533,8 +535,16 @@ impl PeerManagerActor {
         }
 
         // Build valid candidate list to choose the peer to be removed. All peers outside the safe set.
-        let candidates = tier2.ready.values().filter(|p| !safe_set.contains(&p.peer_info.id));
-        if let Some(p) = candidates.choose(&mut rand::thread_rng()) {
+        let candidates: Vec<_> =
+            tier2.ready.values().filter(|p| !safe_set.contains(&p.peer_info.id)).collect();
+        // Prefer dropping the downranked peer with the worst reputation, otherwise pick at random.
+        let worst = candidates
+            .iter()
+            .map(|p| (self.state.reputation.score(&self.clock, &p.peer_info.id), p))
+            .filter(|(score, _)| *score >= self.state.config.reputation.downrank_threshold)
+            .max_by(|a, b| a.0.total_cmp(&b.0))
+            .map(|(_, p)| *p);
+        if let Some(p) = worst.or_else(|| candidates.choose(&mut rand::thread_rng()).copied()) {
             tracing::debug!(target: "network", id = ?p.peer_info.id,
                 tier2_len = tier2.ready.len(),
                 ideal_connections_hi = self.state.config.ideal_connections_hi,
@@ -548,6 +558,7 @@ impl PeerManagerActor {
     ///  - request new peers from connected peers,
     ///  - bootstrap outbound connections from known peers,
     ///  - unban peers that have been banned for awhile,
+    ///  - forget the decayed reputation scores,
     ///  - remove expired peers,
     ///
     /// # Arguments:
@@ -567,6 +578,7 @@ impl PeerManagerActor {
             metrics::PEER_MANAGER_TRIGGER_TIME.with_label_values(&["monitor_peers"]).start_timer();
 
         self.state.peer_store.update(&self.clock);
+        self.state.reputation.update(&self.clock);
 
         if self.is_outbound_bootstrap_needed() {
             let tier2 = self.state.tier2.load();
@@ -580,6 +592,9 @@ impl PeerManagerActor {
                     || self.state.config.node_addr.as_ref().map(|a|**a) == peer_state.peer_info.addr
                     // Or to peers we are currently trying to connect to
                     || tier2.outbound_handshakes.contains(&peer_state.peer_info.id)
+                    // Or to peers with bad reputation
+                    || self.state.reputation.is_banned(&peer_state.peer_info.id)
+                    || self.state.reputation.is_downranked(&self.clock, &peer_state.peer_info.id)
                 },
                 prefer_previously_connected_peer,
             ) {
@@ -664,6 +679,7 @@ impl PeerManagerActor {
                 Some(e) => e.nonce(),
                 None => 0,
             },
+            reputation_score: self.state.reputation.score(&self.clock, &cp.peer_info.id),
         };
         NetworkInfo {
             connected_peers: tier2.ready.values().map(connected_peer).collect(),
@@ -822,6 +838,10 @@ impl PeerManagerActor {
                 self.state.disconnect_and_ban(&self.clock, &peer_id, ban_reason);
                 NetworkResponses::NoResponse
             }
+            NetworkRequests::ReportPeer { peer_id, misbehavior } => {
+                self.state.report_peer(&self.clock, &peer_id, misbehavior);
+                NetworkResponses::NoResponse
+            }
             NetworkRequests::AnnounceAccount(announce_account) => {
                 let state = self.state.clone();
                 ctx.spawn(wrap_future(async move {
@@ -1063,6 +1083,7 @@ impl actix::Handler<GetDebugStatus> for PeerManagerActor {
     fn handle(&mut self, msg: GetDebugStatus, _ctx: &mut actix::Context<Self>) -> Self::Result {
         match msg {
             GetDebugStatus::PeerStore => {
+                let reputation = self.state.reputation.load(&self.clock);
                 let mut peer_states_view = self
                     .state
                     .peer_store
@@ -1083,6 +1104,13 @@ impl actix::Handler<GetDebugStatus> for PeerManagerActor {
                                 (attempt_time.unix_timestamp(), foo)
                             },
                         ),
+                        reputation_score: reputation
+                            .get(peer_id)
+                            .map_or(0, |r| r.score.round() as u64),
+                        banned_until: reputation
+                            .get(peer_id)
+                            .and_then(|r| r.banned_until)
+                            .map(|t| t.unix_timestamp()),
                     })
                     .collect::<Vec<_>>();
 
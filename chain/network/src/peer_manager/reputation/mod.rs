@@ -0,0 +1,161 @@
+use crate::stats::metrics;
+use crate::store;
+use crate::types::Misbehavior;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use unc_async::time;
+use unc_primitives::network::PeerId;
+
+#[cfg(test)]
+mod tests;
+
+/// Scores which decayed below this value are forgotten.
+const MIN_SCORE: f64 = 0.01;
+
+/// Reputation keeps track of misbehavior of peers, which is not severe enough
+/// to ban them right away (see `ReasonForBan` for these), but which we don't want
+/// to tolerate when it keeps happening:
+///     - every reported `Misbehavior` adds its penalty to the score of the peer.
+///     - the score decays exponentially over time, so that occasional misbehavior is forgiven.
+///     - peers whose score reaches `Config::downrank_threshold` are the last ones
+///       we connect to and the first ones we disconnect from.
+///     - peers whose score reaches `Config::ban_threshold` are disconnected and banned
+///       for `Config::ban_duration`.
+///
+/// The bans are persisted in the DB, so that they survive a node restart.
+/// The scores are kept only in memory.
+#[derive(Clone, Debug)]
+pub struct Config {
+    /// Time after which a penalty loses half of its weight.
+    pub decay_half_life: time::Duration,
+    /// Score at which the peer gets downranked.
+    pub downrank_threshold: f64,
+    /// Score at which the peer gets banned.
+    pub ban_threshold: f64,
+    /// Duration of the ban.
+    pub ban_duration: time::Duration,
+}
+
+/// Reputation of a single peer, as exposed in the debug views.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PeerReputation {
+    pub score: f64,
+    pub banned_until: Option<time::Utc>,
+}
+
+#[derive(Clone, Copy)]
+struct Score {
+    value: f64,
+    updated: time::Instant,
+}
+
+impl Score {
+    /// Returns the value of the score decayed up to `now`.
+    fn decayed(&self, half_life: time::Duration, now: time::Instant) -> f64 {
+        let elapsed = (now - self.updated).as_seconds_f64().max(0.);
+        self.value * 0.5f64.powf(elapsed / half_life.as_seconds_f64())
+    }
+}
+
+struct Inner {
+    config: Config,
+    store: store::Store,
+    scores: HashMap<PeerId, Score>,
+    banned: HashMap<PeerId, time::Utc>,
+}
+
+impl Inner {
+    fn score(&self, now: time::Instant, peer_id: &PeerId) -> f64 {
+        self.scores.get(peer_id).map_or(0., |s| s.decayed(self.config.decay_half_life, now))
+    }
+
+    fn save_banned(&mut self) {
+        let banned = self.banned.iter().map(|(k, v)| (k.clone(), *v)).collect();
+        if let Err(err) = self.store.set_banned_peers(&banned) {
+            tracing::error!(target: "network", ?err, "Failed to save banned peers");
+        }
+    }
+}
+
+pub(crate) struct Reputation(Mutex<Inner>);
+
+impl Reputation {
+    pub fn new(clock: &time::Clock, config: Config, store: store::Store) -> Self {
+        let now = clock.now_utc();
+        let banned =
+            store.get_banned_peers().into_iter().filter(|(_, until)| *until > now).collect();
+        Self(Mutex::new(Inner { config, store, scores: HashMap::new(), banned }))
+    }
+
+    /// Adds the penalty of `misbehavior` to the score of the peer.
+    /// Returns true iff the peer got banned as a result.
+    pub fn report(&self, clock: &time::Clock, peer_id: &PeerId, misbehavior: Misbehavior) -> bool {
+        metrics::PEER_MISBEHAVIOR_REPORTS.with_label_values(&[misbehavior.into()]).inc();
+        let mut inner = self.0.lock();
+        if inner.banned.contains_key(peer_id) {
+            return false;
+        }
+        let now = clock.now();
+        let value = inner.score(now, peer_id) + misbehavior.penalty();
+        if value < inner.config.ban_threshold {
+            inner.scores.insert(peer_id.clone(), Score { value, updated: now });
+            return false;
+        }
+        tracing::info!(target: "network", ?peer_id, ?misbehavior, score = value, "Banning peer with bad reputation");
+        metrics::PEER_REPUTATION_BANS.inc();
+        // The peer starts with a clean slate once the ban expires.
+        inner.scores.remove(peer_id);
+        let banned_until = clock.now_utc() + inner.config.ban_duration;
+        inner.banned.insert(peer_id.clone(), banned_until);
+        inner.save_banned();
+        true
+    }
+
+    /// Current score of the peer. 0 means that the peer behaves well.
+    pub fn score(&self, clock: &time::Clock, peer_id: &PeerId) -> f64 {
+        self.0.lock().score(clock.now(), peer_id)
+    }
+
+    pub fn is_banned(&self, peer_id: &PeerId) -> bool {
+        self.0.lock().banned.contains_key(peer_id)
+    }
+
+    pub fn is_downranked(&self, clock: &time::Clock, peer_id: &PeerId) -> bool {
+        let inner = self.0.lock();
+        inner.score(clock.now(), peer_id) >= inner.config.downrank_threshold
+    }
+
+    /// Lifts the expired bans and forgets the scores which decayed to nothing.
+    pub fn update(&self, clock: &time::Clock) {
+        let mut inner = self.0.lock();
+        let now = clock.now();
+        let half_life = inner.config.decay_half_life;
+        inner.scores.retain(|_, s| s.decayed(half_life, now) >= MIN_SCORE);
+        let now_utc = clock.now_utc();
+        let banned = inner.banned.len();
+        inner.banned.retain(|_, until| *until > now_utc);
+        if inner.banned.len() != banned {
+            inner.save_banned();
+        }
+    }
+
+    /// Returns the reputation of all peers which have a non-zero score or are banned.
+    pub fn load(&self, clock: &time::Clock) -> HashMap<PeerId, PeerReputation> {
+        let inner = self.0.lock();
+        let now = clock.now();
+        let mut res: HashMap<_, _> = inner
+            .scores
+            .keys()
+            .map(|peer_id| {
+                let score = inner.score(now, peer_id);
+                (peer_id.clone(), PeerReputation { score, banned_until: None })
+            })
+            .collect();
+        for (peer_id, until) in &inner.banned {
+            res.entry(peer_id.clone())
+                .or_insert(PeerReputation { score: 0., banned_until: None })
+                .banned_until = Some(*until);
+        }
+        res
+    }
+}
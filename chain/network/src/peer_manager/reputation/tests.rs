@@ -0,0 +1,105 @@
+use crate::network_protocol::testonly::make_peer_id;
+use crate::peer_manager::reputation::{Config, PeerReputation, Reputation};
+use crate::store;
+use crate::testonly::make_rng;
+use crate::types::Misbehavior;
+use unc_async::time;
+
+fn make_config() -> Config {
+    Config {
+        decay_half_life: time::Duration::minutes(10),
+        downrank_threshold: 15.,
+        ban_threshold: 30.,
+        ban_duration: time::Duration::hours(1),
+    }
+}
+
+#[test]
+fn test_score_decay() {
+    let mut rng = make_rng(921853233);
+    let rng = &mut rng;
+    let clock = time::FakeClock::default();
+    let store = store::Store::from(unc_store::db::TestDB::new());
+    let reputation = Reputation::new(&clock.clock(), make_config(), store);
+    let peer_id = make_peer_id(rng);
+
+    tracing::debug!(target:"test", "two reports downrank the peer");
+    assert!(!reputation.report(&clock.clock(), &peer_id, Misbehavior::SlowStatePart));
+    assert!(!reputation.report(&clock.clock(), &peer_id, Misbehavior::SlowStatePart));
+    assert_eq!(20., reputation.score(&clock.clock(), &peer_id));
+    assert!(reputation.is_downranked(&clock.clock(), &peer_id));
+
+    tracing::debug!(target:"test", "the score halves every half life");
+    clock.advance(time::Duration::minutes(10));
+    assert_eq!(10., reputation.score(&clock.clock(), &peer_id));
+    assert!(!reputation.is_downranked(&clock.clock(), &peer_id));
+
+    tracing::debug!(target:"test", "decayed scores are forgotten");
+    clock.advance(time::Duration::hours(10));
+    reputation.update(&clock.clock());
+    assert!(reputation.load(&clock.clock()).is_empty());
+}
+
+#[test]
+fn test_ban() {
+    let mut rng = make_rng(921853233);
+    let rng = &mut rng;
+    let clock = time::FakeClock::default();
+    let store = store::Store::from(unc_store::db::TestDB::new());
+    let reputation = Reputation::new(&clock.clock(), make_config(), store);
+    let peer_id = make_peer_id(rng);
+    let other_peer_id = make_peer_id(rng);
+
+    tracing::debug!(target:"test", "penalties accumulate until the peer gets banned");
+    assert!(!reputation.report(&clock.clock(), &peer_id, Misbehavior::SlowStatePart));
+    assert!(!reputation.report(&clock.clock(), &other_peer_id, Misbehavior::SlowStatePart));
+    assert!(!reputation.report(&clock.clock(), &peer_id, Misbehavior::SlowStatePart));
+    assert!(reputation.report(&clock.clock(), &peer_id, Misbehavior::SlowStatePart));
+    assert!(reputation.is_banned(&peer_id));
+    assert!(!reputation.is_banned(&other_peer_id));
+
+    tracing::debug!(target:"test", "further reports don't extend the ban");
+    assert!(!reputation.report(&clock.clock(), &peer_id, Misbehavior::SlowStatePart));
+    let banned_until = clock.now_utc() + time::Duration::hours(1);
+    assert_eq!(
+        Some(&PeerReputation { score: 0., banned_until: Some(banned_until) }),
+        reputation.load(&clock.clock()).get(&peer_id)
+    );
+
+    tracing::debug!(target:"test", "the ban expires");
+    clock.advance(time::Duration::minutes(59));
+    reputation.update(&clock.clock());
+    assert!(reputation.is_banned(&peer_id));
+    clock.advance(time::Duration::minutes(1));
+    reputation.update(&clock.clock());
+    assert!(!reputation.is_banned(&peer_id));
+    assert_eq!(0., reputation.score(&clock.clock(), &peer_id));
+}
+
+#[test]
+fn test_reload_bans_from_storage() {
+    let mut rng = make_rng(921853233);
+    let rng = &mut rng;
+    let clock = time::FakeClock::default();
+    let store = store::Store::from(unc_store::db::TestDB::new());
+    let peer_id = make_peer_id(rng);
+    let mut config = make_config();
+    config.ban_threshold = 1.;
+
+    {
+        tracing::debug!(target:"test", "ban the peer");
+        let reputation = Reputation::new(&clock.clock(), config.clone(), store.clone());
+        assert!(reputation.report(&clock.clock(), &peer_id, Misbehavior::UselessRoutedMessage));
+    }
+    {
+        tracing::debug!(target:"test", "the ban is loaded from storage");
+        let reputation = Reputation::new(&clock.clock(), config.clone(), store.clone());
+        assert!(reputation.is_banned(&peer_id));
+    }
+    {
+        tracing::debug!(target:"test", "expired bans are not loaded");
+        clock.advance(time::Duration::hours(2));
+        let reputation = Reputation::new(&clock.clock(), config, store);
+        assert!(!reputation.is_banned(&peer_id));
+    }
+}
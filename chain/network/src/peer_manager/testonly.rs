@@ -17,8 +17,8 @@ use crate::test_utils;
 use crate::testonly::actix::ActixSystem;
 use crate::testonly::fake_client;
 use crate::types::{
-    AccountKeys, ChainInfo, KnownPeerStatus, NetworkRequests, PeerManagerMessageRequest,
-    ReasonForBan,
+    AccountKeys, ChainInfo, KnownPeerStatus, Misbehavior, NetworkRequests,
+    PeerManagerMessageRequest, ReasonForBan,
 };
 use crate::PeerManagerActor;
 use std::collections::HashSet;
@@ -386,6 +386,22 @@ impl ActorHandler {
         self.with_state(move |s| async move { s.peer_store.update(&clock) }).await;
     }
 
+    pub async fn report_peer(
+        &self,
+        clock: &time::Clock,
+        peer_id: &PeerId,
+        misbehavior: Misbehavior,
+    ) {
+        let clock = clock.clone();
+        let peer_id = peer_id.clone();
+        self.with_state(move |s| async move { s.report_peer(&clock, &peer_id, misbehavior) }).await
+    }
+
+    pub async fn reputation_update(&self, clock: &time::Clock) {
+        let clock = clock.clone();
+        self.with_state(move |s| async move { s.reputation.update(&clock) }).await;
+    }
+
     pub async fn send_ping(&self, clock: &time::Clock, nonce: u64, target: PeerId) {
         let clock = clock.clone();
         self.with_state(move |s| async move {
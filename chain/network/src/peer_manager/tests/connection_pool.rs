@@ -103,6 +103,8 @@ async fn loop_connection() {
                 &pm.cfg.node_key,
             ),
             owned_account: None,
+            transport_key: None,
+            compression: None,
         }))
         .await;
     let reason = events
@@ -171,6 +173,8 @@ async fn owned_account_mismatch() {
                 }
                 .sign(vc.signer.as_ref()),
             ),
+            transport_key: None,
+            compression: None,
         }))
         .await;
     let reason = events
@@ -284,6 +288,8 @@ async fn invalid_edge() {
                     }
                     .sign(vc.signer.as_ref()),
                 ),
+                transport_key: None,
+                compression: None,
             };
             let handshake = match tier {
                 tcp::Tier::T1 => PeerMessage::Tier1Handshake(handshake),
@@ -0,0 +1,236 @@
+use crate::network_protocol::testonly as data;
+use crate::network_protocol::{
+    Encoding, Handshake, PartialEdgeInfo, PeerMessage, Ping, Pong, TransportKey,
+};
+use crate::peer::peer_actor::ClosingReason;
+use crate::peer_manager;
+use crate::peer_manager::peer_manager_actor::Event as PME;
+use crate::peer_manager::testonly::{ActorHandler, Event};
+use crate::peer_manager::tests::routing::{wait_for_ping, wait_for_pong};
+use crate::tcp;
+use crate::testonly::make_rng;
+use crate::testonly::stream::Stream;
+use crate::types::ReasonForBan;
+use std::sync::Arc;
+use unc_async::time;
+use unc_crypto::SecretKey;
+use unc_o11y::testonly::init_test_logger;
+use unc_primitives::network::PeerId;
+use unc_primitives::version::{ProtocolFeature, ProtocolVersion, PROTOCOL_VERSION};
+
+fn encryption_supported() -> bool {
+    ProtocolFeature::EncryptedPeerTransport.protocol_version() <= PROTOCOL_VERSION
+}
+
+async fn is_encrypted(pm: &ActorHandler, peer_id: PeerId) -> bool {
+    pm.with_state(move |s| async move { s.tier2.load().ready.get(&peer_id).unwrap().encrypted })
+        .await
+}
+
+/// Newest protocol version which predates the encryption of the connections.
+fn unencrypted_protocol_version() -> ProtocolVersion {
+    PROTOCOL_VERSION.min(ProtocolFeature::EncryptedPeerTransport.protocol_version() - 1)
+}
+
+/// Connects to `pm` over a raw stream and sends a handshake offering `transport_key`.
+async fn raw_handshake(
+    chain: &data::Chain,
+    pm: &ActorHandler,
+    peer_key: &SecretKey,
+    protocol_version: ProtocolVersion,
+    transport_key: Option<TransportKey>,
+) -> (tcp::StreamId, Stream) {
+    let stream = tcp::Stream::connect(&pm.peer_info(), tcp::Tier::T2).await.unwrap();
+    let stream_id = stream.id();
+    let mut stream = Stream::new(Some(Encoding::Proto), stream);
+    let peer_id = PeerId::new(peer_key.public_key());
+    stream
+        .write(&PeerMessage::Tier2Handshake(Handshake {
+            protocol_version,
+            oldest_supported_version: protocol_version,
+            sender_peer_id: peer_id.clone(),
+            target_peer_id: pm.cfg.node_id(),
+            sender_listen_port: Some(24567),
+            sender_chain_info: chain.get_peer_chain_info(),
+            partial_edge_info: PartialEdgeInfo::new(&peer_id, &pm.cfg.node_id(), 1, peer_key),
+            owned_account: None,
+            transport_key,
+        }))
+        .await;
+    (stream_id, stream)
+}
+
+async fn wait_for_closing_reason(
+    events: &mut crate::broadcast::Receiver<Event>,
+    stream_id: tcp::StreamId,
+) -> ClosingReason {
+    events
+        .recv_until(|ev| match ev {
+            Event::PeerManager(PME::ConnectionClosed(ev)) if ev.stream_id == stream_id => {
+                Some(ev.reason)
+            }
+            Event::PeerManager(PME::HandshakeCompleted(ev)) if ev.stream_id == stream_id => {
+                panic!("PeerManager accepted the handshake")
+            }
+            _ => None,
+        })
+        .await
+}
+
+// Two peer managers encrypt the connection whenever the protocol version allows it,
+// and can talk to each other over it.
+#[tokio::test]
+async fn encrypted_connection() {
+    init_test_logger();
+    let mut rng = make_rng(921853233);
+    let rng = &mut rng;
+    let mut clock = time::FakeClock::default();
+    let chain = Arc::new(data::Chain::make(&mut clock, rng, 10));
+
+    let pm0 = peer_manager::testonly::start(
+        clock.clock(),
+        unc_store::db::TestDB::new(),
+        chain.make_config(rng),
+        chain.clone(),
+    )
+    .await;
+    let pm1 = peer_manager::testonly::start(
+        clock.clock(),
+        unc_store::db::TestDB::new(),
+        chain.make_config(rng),
+        chain.clone(),
+    )
+    .await;
+    let id0 = pm0.cfg.node_id();
+    let id1 = pm1.cfg.node_id();
+
+    tracing::info!(target:"test", "connect pm0 to pm1");
+    pm0.connect_to(&pm1.peer_info(), tcp::Tier::T2).await;
+    pm1.wait_for_direct_connection(id0.clone()).await;
+    assert_eq!(encryption_supported(), is_encrypted(&pm0, id1.clone()).await);
+    assert_eq!(encryption_supported(), is_encrypted(&pm1, id0.clone()).await);
+
+    tracing::info!(target:"test", "exchange messages over the connection");
+    pm0.wait_for_routing_table(&[(id1.clone(), vec![id1.clone()])]).await;
+    let mut pm0_ev = pm0.events.from_now();
+    let mut pm1_ev = pm1.events.from_now();
+    pm0.send_ping(&clock.clock(), 0, id1.clone()).await;
+    wait_for_ping(&mut pm1_ev, Ping { nonce: 0, source: id0.clone() }).await;
+    wait_for_pong(&mut pm0_ev, Pong { nonce: 0, source: id1.clone() }).await;
+}
+
+// A peer running a protocol version without encryption gets a plaintext connection.
+#[tokio::test]
+async fn unencrypted_fallback() {
+    init_test_logger();
+    let mut rng = make_rng(921853233);
+    let rng = &mut rng;
+    let mut clock = time::FakeClock::default();
+    let chain = Arc::new(data::Chain::make(&mut clock, rng, 10));
+
+    let pm = peer_manager::testonly::start(
+        clock.clock(),
+        unc_store::db::TestDB::new(),
+        chain.make_config(rng),
+        chain.clone(),
+    )
+    .await;
+    let peer_key = data::make_secret_key(rng);
+    let (_, mut stream) =
+        raw_handshake(&chain, &pm, &peer_key, unencrypted_protocol_version(), None).await;
+    match stream.read().await {
+        Ok(PeerMessage::Tier2Handshake(handshake)) => assert_eq!(None, handshake.transport_key),
+        got => panic!("got = {got:?}, want Handshake"),
+    }
+    assert!(!is_encrypted(&pm, PeerId::new(peer_key.public_key())).await);
+}
+
+// A peer which supports encryption but doesn't offer a transport key is rejected,
+// so that the connection can't be downgraded to plaintext.
+#[cfg(feature = "nightly_protocol")]
+#[tokio::test]
+async fn missing_transport_key() {
+    init_test_logger();
+    let mut rng = make_rng(921853233);
+    let rng = &mut rng;
+    let mut clock = time::FakeClock::default();
+    let chain = Arc::new(data::Chain::make(&mut clock, rng, 10));
+
+    let pm = peer_manager::testonly::start(
+        clock.clock(),
+        unc_store::db::TestDB::new(),
+        chain.make_config(rng),
+        chain.clone(),
+    )
+    .await;
+    let mut events = pm.events.from_now();
+    let peer_key = data::make_secret_key(rng);
+    let (stream_id, _stream) = raw_handshake(&chain, &pm, &peer_key, PROTOCOL_VERSION, None).await;
+    assert_eq!(
+        ClosingReason::EncryptionRequired,
+        wait_for_closing_reason(&mut events, stream_id).await
+    );
+}
+
+// A node requiring encryption rejects peers running a protocol version without it.
+// Requiring encryption is allowed only once the feature is enabled in the binary.
+#[cfg(feature = "nightly_protocol")]
+#[tokio::test]
+async fn encryption_required() {
+    init_test_logger();
+    let mut rng = make_rng(921853233);
+    let rng = &mut rng;
+    let mut clock = time::FakeClock::default();
+    let chain = Arc::new(data::Chain::make(&mut clock, rng, 10));
+
+    let mut cfg = chain.make_config(rng);
+    cfg.require_encrypted_transport = true;
+    let pm = peer_manager::testonly::start(
+        clock.clock(),
+        unc_store::db::TestDB::new(),
+        cfg,
+        chain.clone(),
+    )
+    .await;
+    let mut events = pm.events.from_now();
+    let peer_key = data::make_secret_key(rng);
+    let (stream_id, _stream) =
+        raw_handshake(&chain, &pm, &peer_key, unencrypted_protocol_version(), None).await;
+    assert_eq!(
+        ClosingReason::EncryptionRequired,
+        wait_for_closing_reason(&mut events, stream_id).await
+    );
+}
+
+// A transport key which is not signed by the sender of the handshake is rejected.
+#[cfg(feature = "nightly_protocol")]
+#[tokio::test]
+async fn invalid_transport_key() {
+    init_test_logger();
+    let mut rng = make_rng(921853233);
+    let rng = &mut rng;
+    let mut clock = time::FakeClock::default();
+    let chain = Arc::new(data::Chain::make(&mut clock, rng, 10));
+
+    let pm = peer_manager::testonly::start(
+        clock.clock(),
+        unc_store::db::TestDB::new(),
+        chain.make_config(rng),
+        chain.clone(),
+    )
+    .await;
+    let mut events = pm.events.from_now();
+    let peer_key = data::make_secret_key(rng);
+    let transport_key = TransportKey::new(
+        &data::make_secret_key(rng),
+        &PeerId::new(peer_key.public_key()),
+        &pm.cfg.node_id(),
+        [7; 32],
+    );
+    let (stream_id, _stream) =
+        raw_handshake(&chain, &pm, &peer_key, PROTOCOL_VERSION, Some(transport_key)).await;
+    assert_eq!(
+        ClosingReason::Ban(ReasonForBan::InvalidSignature),
+        wait_for_closing_reason(&mut events, stream_id).await
+    );
+}
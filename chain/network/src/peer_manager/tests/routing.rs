@@ -16,7 +16,7 @@ use crate::store;
 use crate::tcp;
 use crate::testonly::{abort_on_panic, make_rng, Rng};
 use crate::types::PeerMessage;
-use crate::types::{PeerInfo, ReasonForBan};
+use crate::types::{Misbehavior, PeerInfo, ReasonForBan};
 use pretty_assertions::assert_eq;
 use rand::seq::IteratorRandom;
 use rand::Rng as _;
@@ -1385,6 +1385,56 @@ async fn connect_to_unbanned_peer() {
     drop(pm1);
 }
 
+/// Check that a peer gets disconnected once its reputation drops too low,
+/// and that it can connect again after the ban expires.
+#[tokio::test]
+async fn reputation_ban() {
+    abort_on_panic();
+    let mut rng = make_rng(921853233);
+    let rng = &mut rng;
+    let mut clock = time::FakeClock::default();
+    let chain = Arc::new(data::Chain::make(&mut clock, rng, 10));
+
+    let mut pm0 =
+        start_pm(clock.clock(), TestDB::new(), chain.make_config(rng), chain.clone()).await;
+    let mut pm1 =
+        start_pm(clock.clock(), TestDB::new(), chain.make_config(rng), chain.clone()).await;
+
+    tracing::info!(target:"test", "pm0 connects to pm1");
+    let stream_id = pm0.connect_to(&pm1.peer_info(), tcp::Tier::T2).await;
+
+    tracing::info!(target:"test", "pm1 reports pm0 until its reputation is too low");
+    let id0 = pm0.cfg.node_id();
+    let misbehavior = Misbehavior::SlowStatePart;
+    let reports = (pm1.cfg.reputation.ban_threshold / misbehavior.penalty()).ceil() as usize;
+    for _ in 0..reports {
+        pm1.report_peer(&clock.clock(), &id0, misbehavior).await;
+    }
+    wait_for_stream_closed(&mut pm0.events, stream_id).await;
+    assert_eq!(
+        ClosingReason::PeerManagerRequest,
+        wait_for_stream_closed(&mut pm1.events, stream_id).await
+    );
+
+    tracing::info!(target:"test", "pm0 fails to reconnect to pm1");
+    let got_reason = pm1
+        .start_inbound(chain.clone(), pm0.cfg.clone())
+        .await
+        .manager_fail_handshake(&clock.clock())
+        .await;
+    assert_eq!(ClosingReason::RejectedByPeerManager(RegisterPeerError::Banned), got_reason);
+
+    tracing::info!(target:"test", "the ban expires");
+    clock.advance(pm1.cfg.reputation.ban_duration);
+    pm1.reputation_update(&clock.clock()).await;
+
+    tracing::info!(target:"test", "pm0 reconnects to pm1");
+    pm0.connect_to(&pm1.peer_info(), tcp::Tier::T2).await;
+
+    drop(pm0);
+    drop(pm1);
+}
+
 /// Awaits a DistanceVector message from a given `peer_id`
 async fn wait_for_distance_vector(events: &mut broadcast::Receiver<Event>, peer_id: PeerId) {
     events
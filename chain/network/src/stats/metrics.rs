@@ -6,7 +6,7 @@ use once_cell::sync::Lazy;
 use unc_async::time;
 use unc_o11y::metrics::prometheus;
 use unc_o11y::metrics::{
-    exponential_buckets, try_create_histogram, try_create_histogram_vec,
+    exponential_buckets, linear_buckets, try_create_histogram, try_create_histogram_vec,
     try_create_histogram_with_buckets, try_create_int_counter, try_create_int_counter_vec,
     try_create_int_gauge, try_create_int_gauge_vec, Histogram, HistogramVec, IntCounter,
     IntCounterVec, IntGauge, IntGaugeVec, MetricVec, MetricVecBuilder,
@@ -296,6 +296,69 @@ pub(crate) static ROUTED_MESSAGE_DROPPED: Lazy<IntCounterVec> = Lazy::new(|| {
     .unwrap()
 });
 
+pub(crate) static PEER_MISBEHAVIOR_REPORTS: Lazy<IntCounterVec> = Lazy::new(|| {
+    try_create_int_counter_vec(
+        "unc_peer_misbehavior_reports",
+        "Number of misbehavior reports lowering the reputation of peers, by misbehavior type",
+        &["misbehavior"],
+    )
+    .unwrap()
+});
+pub(crate) static PEER_REPUTATION_BANS: Lazy<IntCounter> = Lazy::new(|| {
+    try_create_int_counter(
+        "unc_peer_reputation_bans",
+        "Number of peers banned because their reputation score reached the ban threshold",
+    )
+    .unwrap()
+});
+
+pub(crate) static PEER_MESSAGE_COMPRESSION_RATIO: Lazy<HistogramVec> = Lazy::new(|| {
+    try_create_histogram_vec(
+        "unc_peer_message_compression_ratio",
+        "Size of compressed messages sent to peers relative to their uncompressed size, by message type",
+        &["type"],
+        Some(linear_buckets(0.05, 0.05, 20).unwrap()),
+    )
+    .unwrap()
+});
+
+pub(crate) static PEER_SENT_BYTES_BY_CATEGORY: Lazy<IntCounterVec> = Lazy::new(|| {
+    try_create_int_counter_vec(
+        "unc_peer_sent_bytes_by_category",
+        "Total data written to peer connections, by bandwidth category",
+        &["category"],
+    )
+    .unwrap()
+});
+
+pub(crate) static PEER_SEND_QUEUE_LATENCY: Lazy<HistogramVec> = Lazy::new(|| {
+    try_create_histogram_vec(
+        "unc_peer_send_queue_latency",
+        "Time that a message spends in the send queue of a connection, by bandwidth category",
+        &["category"],
+        Some(exponential_buckets(0.0001, 2., 20).unwrap()),
+    )
+    .unwrap()
+});
+
+pub(crate) static PEER_UPLOAD_THROTTLED_TOTAL: Lazy<IntCounterVec> = Lazy::new(|| {
+    try_create_int_counter_vec(
+        "unc_peer_upload_throttled_total",
+        "Number of times a connection had to wait for upload bandwidth, by bandwidth category",
+        &["category"],
+    )
+    .unwrap()
+});
+
+pub(crate) static PEER_DOWNLOAD_THROTTLE_DELAY: Lazy<Histogram> = Lazy::new(|| {
+    try_create_histogram_with_buckets(
+        "unc_peer_download_throttle_delay",
+        "Time that a connection stops reading from the socket because of download bandwidth limits",
+        exponential_buckets(0.0001, 2., 20).unwrap(),
+    )
+    .unwrap()
+});
+
 pub(crate) static PEER_REACHABLE: Lazy<IntGauge> = Lazy::new(|| {
     try_create_int_gauge(
         "unc_peer_reachable",
@@ -6,6 +6,7 @@ use crate::types::ConnectionInfo;
 use std::collections::HashSet;
 use std::sync::Arc;
 use tracing::debug;
+use unc_async::time;
 use unc_primitives::network::{AnnounceAccount, PeerId};
 use unc_primitives::types::AccountId;
 
@@ -137,6 +138,19 @@ impl Store {
     }
 }
 
+// Reputation storage.
+impl Store {
+    pub fn set_banned_peers(&mut self, banned: &Vec<(PeerId, time::Utc)>) -> Result<(), Error> {
+        let mut update = self.0.new_update();
+        update.set::<schema::BannedPeers>(&(), banned);
+        self.0.commit(update).map_err(Error)
+    }
+
+    pub fn get_banned_peers(&self) -> Vec<(PeerId, time::Utc)> {
+        self.0.get::<schema::BannedPeers>(&()).unwrap_or(Some(vec![])).unwrap_or(vec![])
+    }
+}
+
 impl From<Arc<dyn unc_store::db::Database>> for Store {
     fn from(store: Arc<dyn unc_store::db::Database>) -> Self {
         Self(schema::Store::from(store))
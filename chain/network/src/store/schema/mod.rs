@@ -59,6 +59,28 @@ impl BorshRepr for ConnectionInfoRepr {
     }
 }
 
+/// A Borsh representation of a temporary peer ban.
+#[derive(BorshSerialize, BorshDeserialize)]
+pub(super) struct BannedPeerRepr {
+    peer_id: PeerId,
+    /// UNIX timestamp in nanos.
+    banned_until: u64,
+}
+
+impl BorshRepr for BannedPeerRepr {
+    type T = (PeerId, time::Utc);
+    fn to_repr(s: &(PeerId, time::Utc)) -> Self {
+        Self { peer_id: s.0.clone(), banned_until: s.1.unix_timestamp_nanos() as u64 }
+    }
+
+    fn from_repr(s: Self) -> Result<(PeerId, time::Utc), Error> {
+        Ok((
+            s.peer_id,
+            time::Utc::from_unix_timestamp_nanos(s.banned_until as i128).map_err(invalid_data)?,
+        ))
+    }
+}
+
 #[derive(BorshSerialize, BorshDeserialize)]
 pub(super) struct EdgeRepr {
     key: (PeerId, PeerId),
@@ -103,6 +125,13 @@ impl Column for RecentOutboundConnections {
     type Value = Vec<ConnectionInfoRepr>;
 }
 
+pub(super) struct BannedPeers;
+impl Column for BannedPeers {
+    const COL: DBCol = DBCol::BannedPeers;
+    type Key = Borsh<()>;
+    type Value = Vec<BannedPeerRepr>;
+}
+
 pub(super) struct PeerComponent;
 impl Column for PeerComponent {
     const COL: DBCol = DBCol::PeerComponent;
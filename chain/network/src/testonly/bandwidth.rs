@@ -0,0 +1,69 @@
+//! Deterministic simulation of the send loops of connections, which allows to test
+//! bandwidth shaping without real sockets.
+use crate::bandwidth::{Category, Global, Next, SendQueue, UploadLimiter};
+use std::sync::Arc;
+use unc_async::time;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Sent {
+    pub at: time::Instant,
+    pub category: Category,
+    pub bytes: usize,
+}
+
+/// Simulated connection, which sends frames of the given sizes.
+pub(crate) struct Link {
+    queue: SendQueue<usize>,
+    pub sent: Vec<Sent>,
+}
+
+impl Link {
+    pub fn new(clock: &time::FakeClock, global: Arc<Global>) -> Self {
+        Self { queue: SendQueue::new(UploadLimiter::new(&clock.clock(), global)), sent: vec![] }
+    }
+
+    pub fn push(&mut self, clock: &time::FakeClock, category: Category, bytes: usize) {
+        self.queue.push(clock.now(), category, bytes);
+    }
+
+    /// Total number of bytes of the given category sent so far.
+    pub fn bytes_sent(&self, category: Category) -> usize {
+        self.sent.iter().filter(|s| s.category == category).map(|s| s.bytes).sum()
+    }
+
+    /// Time at which the last frame of the given category has been sent.
+    pub fn last_sent(&self, category: Category) -> Option<time::Instant> {
+        self.sent.iter().filter(|s| s.category == category).map(|s| s.at).last()
+    }
+}
+
+/// Sends the frames queued in `links`, advancing `clock` whenever all the links have to
+/// wait for bandwidth. Frames are sent instantly, so the bandwidth is limited only by
+/// the shaping. Returns once all the queues are empty, or once `deadline` is reached.
+pub(crate) fn run(clock: &time::FakeClock, links: &mut [&mut Link], deadline: time::Instant) {
+    loop {
+        let now = clock.now();
+        let mut progress = false;
+        let mut wait: Option<time::Duration> = None;
+        for link in links.iter_mut() {
+            match link.queue.pop(now) {
+                Next::Ready(category, bytes) => {
+                    link.queue.charge(now, category, bytes);
+                    link.sent.push(Sent { at: now, category, bytes });
+                    progress = true;
+                }
+                Next::Wait(d) => wait = Some(wait.map_or(d, |w| w.min(d))),
+                Next::Empty => {}
+            }
+        }
+        if progress {
+            continue;
+        }
+        let Some(wait) = wait else { return };
+        if now + wait > deadline {
+            clock.advance_until(deadline);
+            return;
+        }
+        clock.advance(wait);
+    }
+}
@@ -6,6 +6,7 @@ use std::hash::Hash;
 use unc_o11y::testonly::init_test_logger;
 
 pub use super::actix;
+pub mod bandwidth;
 pub mod fake_client;
 pub mod stream;
 
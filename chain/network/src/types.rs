@@ -71,6 +71,28 @@ pub enum ReasonForBan {
     ProvidedNotEnoughHeaders = 15,
 }
 
+/// Misbehavior which is not severe enough to ban the peer right away,
+/// but which lowers its reputation, see `peer_manager::reputation`.
+#[derive(Debug, Clone, PartialEq, Eq, Copy, strum::IntoStaticStr)]
+pub enum Misbehavior {
+    /// Peer kept sending routed messages which we have already seen, at a rate above
+    /// `peer::peer_actor::MAX_DUPLICATE_ROUTED_MESSAGES_PER_SEC`. An occasional duplicate is
+    /// expected, as messages can reach us over several routes.
+    UselessRoutedMessage,
+    /// Peer didn't respond to a state part request in time.
+    SlowStatePart,
+}
+
+impl Misbehavior {
+    /// Amount added to the peer's reputation score on each occurrence.
+    pub fn penalty(&self) -> f64 {
+        match self {
+            Misbehavior::UselessRoutedMessage => 5.,
+            Misbehavior::SlowStatePart => 10.,
+        }
+    }
+}
+
 /// Banning signal sent from Peer instance to PeerManager
 /// just before Peer instance is stopped.
 #[derive(actix::Message, Debug)]
@@ -231,6 +253,8 @@ pub enum NetworkRequests {
     StateRequestPart { shard_id: ShardId, sync_hash: CryptoHash, part_id: u64, peer_id: PeerId },
     /// Ban given peer.
     BanPeer { peer_id: PeerId, ban_reason: ReasonForBan },
+    /// Lower the reputation of the given peer.
+    ReportPeer { peer_id: PeerId, misbehavior: Misbehavior },
     /// Announce account
     AnnounceAccount(AnnounceAccount),
     /// Broadcast information about a hosted snapshot.
@@ -343,6 +367,8 @@ pub struct ConnectedPeerInfo {
     pub peer_type: PeerType,
     /// Nonce used for the connection with the peer.
     pub nonce: u64,
+    /// Reputation score of the peer, see `peer_manager::reputation`.
+    pub reputation_score: f64,
 }
 
 #[derive(Debug, Clone, actix::MessageResponse)]
@@ -1,15 +1,19 @@
 use std::collections::btree_map::Entry;
-use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
 
 use crate::types::{PoolIterator, PoolKey, TransactionGroup};
 
 use std::ops::Bound;
+use unc_chain_configs::{
+    MutableConfigValue, TransactionPoolEviction, TransactionPoolOrdering, TransactionPoolPolicy,
+    TransactionPoolQuotas,
+};
 use unc_crypto::PublicKey;
 use unc_o11y::metrics::prometheus::core::{AtomicI64, GenericGauge};
 use unc_primitives::epoch_manager::RngSeed;
 use unc_primitives::hash::{hash, CryptoHash};
 use unc_primitives::transaction::SignedTransaction;
-use unc_primitives::types::AccountId;
+use unc_primitives::types::{AccountId, Balance};
 
 mod metrics;
 pub mod types;
@@ -22,6 +26,64 @@ pub enum InsertTransactionResult {
     Duplicate,
     /// Not enough space to fit the transaction.
     NoSpaceLeft,
+    /// A transaction with the same nonce and a priority at least as high is already in the pool.
+    ReplacementRejected,
+    /// The signer or the access key of the transaction already has as many transactions in the
+    /// pool as its quota allows.
+    QuotaExceeded,
+}
+
+/// Priority of the transaction in the pool: the price per gas the signer pays on top of the base
+/// gas price. Depending on the pool policy, transactions with a higher priority are pulled from
+/// the pool first and are evicted last.
+///
+/// Transactions don't carry a tip yet, so every transaction pays exactly the base gas price and
+/// has zero priority. Ties are broken by the randomized group order and the insertion order.
+pub fn transaction_priority(_signed_transaction: &SignedTransaction) -> Balance {
+    0
+}
+
+/// Label of the eviction policy in the pool metrics.
+fn eviction_label(eviction: TransactionPoolEviction) -> &'static str {
+    match eviction {
+        TransactionPoolEviction::Reject => "reject",
+        TransactionPoolEviction::Oldest => "oldest",
+        TransactionPoolEviction::LowestPriority => "lowest_priority",
+    }
+}
+
+/// Position of a transaction in the eviction order, the smallest one is evicted first.
+/// It's a pair of the transaction priority (zero unless the lowest priority transactions are
+/// evicted) and the sequence number of the insertion.
+type EvictionKey = (Balance, u64);
+
+/// Number and total size of transactions in the pool.
+#[derive(Clone, Copy, Default)]
+struct PoolUsage {
+    count: u64,
+    size: u64,
+}
+
+/// Bookkeeping of a single transaction in the pool.
+struct PoolEntry {
+    /// Key of the group the transaction belongs to.
+    key: PoolKey,
+    signer_id: AccountId,
+    priority: Balance,
+    size: u64,
+    eviction_key: EvictionKey,
+}
+
+/// Takes a transaction of the given size off the usage of `owner`.
+fn release_usage<K: std::hash::Hash + Eq>(usage: &mut HashMap<K, PoolUsage>, owner: K, size: u64) {
+    if let std::collections::hash_map::Entry::Occupied(mut entry) = usage.entry(owner) {
+        let usage = entry.get_mut();
+        usage.count -= 1;
+        usage.size -= size;
+        if usage.count == 0 {
+            entry.remove();
+        }
+    }
 }
 
 /// Transaction pool: keeps track of transactions that were not yet accepted into the block chain.
@@ -30,17 +92,31 @@ pub struct TransactionPool {
     /// NOTE: It's more efficient on average to keep transactions unsorted and with potentially
     /// conflicting nonce than to create a BTreeMap for every transaction.
     transactions: BTreeMap<PoolKey, Vec<SignedTransaction>>,
-    /// Set of all hashes to quickly check if the given transaction is in the pool.
-    unique_transactions: HashSet<CryptoHash>,
+    /// All hashes to quickly check if the given transaction is in the pool.
+    unique_transactions: HashMap<CryptoHash, PoolEntry>,
+    /// Transactions in the order they are evicted in when the pool is full.
+    eviction_order: BTreeSet<(EvictionKey, CryptoHash)>,
+    /// Sequence number of the next inserted transaction.
+    next_sequence_number: u64,
+    /// Usage of the pool by every signer with transactions in the pool.
+    signer_usage: HashMap<AccountId, PoolUsage>,
+    /// Usage of the pool by every access key, i.e. every group.
+    access_key_usage: HashMap<PoolKey, PoolUsage>,
     /// A uniquely generated key seed to randomize PoolKey order.
     key_seed: RngSeed,
     /// The key after which the pool iterator starts. Doesn't have to be present in the pool.
     last_used_key: PoolKey,
-    /// If set, new transactions that bring the size of the pool over this limit will be rejected.
+    /// If set, new transactions that bring the size of the pool over this limit will be rejected
+    /// or will evict other transactions, depending on the policy.
     total_transaction_size_limit: Option<u64>,
     /// Total size of transactions in the pool measured in bytes.
     total_transaction_size: u64,
+    /// Ordering, eviction and replacement policy.
+    policy: TransactionPoolPolicy,
+    /// Per-signer and per-access-key limits, can be updated while the node is running.
+    quotas: MutableConfigValue<TransactionPoolQuotas>,
     /// Metrics tracked for transaction pool.
+    metrics_label: String,
     transaction_pool_count_metric: GenericGauge<AtomicI64>,
     transaction_pool_size_metric: GenericGauge<AtomicI64>,
 }
@@ -49,6 +125,8 @@ impl TransactionPool {
     pub fn new(
         key_seed: RngSeed,
         total_transaction_size_limit: Option<u64>,
+        policy: TransactionPoolPolicy,
+        quotas: MutableConfigValue<TransactionPoolQuotas>,
         metrics_label: &str,
     ) -> Self {
         let transaction_pool_count_metric =
@@ -62,10 +140,17 @@ impl TransactionPool {
         Self {
             key_seed,
             transactions: BTreeMap::new(),
-            unique_transactions: HashSet::new(),
+            unique_transactions: HashMap::new(),
+            eviction_order: BTreeSet::new(),
+            next_sequence_number: 0,
+            signer_usage: HashMap::new(),
+            access_key_usage: HashMap::new(),
             last_used_key: CryptoHash::default(),
             total_transaction_size_limit,
             total_transaction_size: 0,
+            policy,
+            quotas,
+            metrics_label: metrics_label.to_string(),
             transaction_pool_count_metric,
             transaction_pool_size_metric,
         }
@@ -79,42 +164,219 @@ impl TransactionPool {
     }
 
     /// Inserts a signed transaction that passed validation into the pool.
+    ///
+    /// `priority` is the price per gas the transaction pays on top of the base gas price, see
+    /// `transaction_priority`.
+    /// Depending on the pool policy, transactions with a higher priority are pulled from the pool
+    /// first, are evicted last and replace transactions with the same nonce.
     #[must_use]
     pub fn insert_transaction(
         &mut self,
         signed_transaction: SignedTransaction,
+        priority: Balance,
     ) -> InsertTransactionResult {
-        if !self.unique_transactions.insert(signed_transaction.get_hash()) {
+        let tx_hash = signed_transaction.get_hash();
+        if self.unique_transactions.contains_key(&tx_hash) {
             // The hash of this transaction was already seen, skip it.
+            self.record_rejection("duplicate");
             return InsertTransactionResult::Duplicate;
         }
+        let signer_id = &signed_transaction.transaction.signer_id;
+        let signer_public_key = &signed_transaction.transaction.public_key;
+        let key = self.key(signer_id, signer_public_key);
+        let size = signed_transaction.get_size();
+
+        let replaced_hash = if self.policy.replace_by_nonce {
+            let nonce = signed_transaction.transaction.nonce;
+            match self
+                .transactions
+                .get(&key)
+                .and_then(|group| group.iter().find(|tx| tx.transaction.nonce == nonce))
+            {
+                Some(tx) if self.unique_transactions[&tx.get_hash()].priority >= priority => {
+                    self.record_rejection("replacement_rejected");
+                    return InsertTransactionResult::ReplacementRejected;
+                }
+                Some(tx) => Some(tx.get_hash()),
+                None => None,
+            }
+        } else {
+            None
+        };
+        let replaced_size = replaced_hash.map_or(0, |hash| self.unique_transactions[&hash].size);
+        let replaced = PoolUsage { count: replaced_hash.is_some() as u64, size: replaced_size };
+        if let Some(quota) = self.exceeded_quota(signer_id, &key, size, replaced) {
+            self.record_rejection(quota);
+            return InsertTransactionResult::QuotaExceeded;
+        }
+
         // We never expect the total size to go over `u64` during real operation as that would
         // be more than 10^9 GiB of RAM consumed for transaction pool, so panicing here is intended
         // to catch a logic error in estimation of transaction size.
         let new_total_transaction_size = self
             .total_transaction_size
-            .checked_add(signed_transaction.get_size())
-            .expect("Total transaction size is too large");
+            .checked_add(size)
+            .expect("Total transaction size is too large")
+            - replaced_size;
+        let mut evicted_hashes = vec![];
         if let Some(limit) = self.total_transaction_size_limit {
             if new_total_transaction_size > limit {
-                return InsertTransactionResult::NoSpaceLeft;
+                let Some(hashes) = self.eviction_candidates(
+                    new_total_transaction_size - limit,
+                    priority,
+                    replaced_hash,
+                ) else {
+                    self.record_rejection("no_space_left");
+                    return InsertTransactionResult::NoSpaceLeft;
+                };
+                evicted_hashes = hashes;
             }
         }
 
         // At this point transaction is accepted to the pool.
-        self.total_transaction_size = new_total_transaction_size;
-        let signer_id = &signed_transaction.transaction.signer_id;
-        let signer_public_key = &signed_transaction.transaction.public_key;
-        self.transactions
-            .entry(self.key(signer_id, signer_public_key))
-            .or_insert_with(Vec::new)
-            .push(signed_transaction);
+        if let Some(replaced_hash) = replaced_hash {
+            self.remove_transaction(&replaced_hash);
+            metrics::TRANSACTION_POOL_REPLACED.with_label_values(&[&self.metrics_label]).inc();
+        }
+        if !evicted_hashes.is_empty() {
+            for evicted_hash in &evicted_hashes {
+                self.remove_transaction(evicted_hash);
+            }
+            metrics::TRANSACTION_POOL_EVICTED
+                .with_label_values(&[&self.metrics_label, eviction_label(self.policy.eviction)])
+                .inc_by(evicted_hashes.len() as u64);
+        }
+
+        let sequence_number = self.next_sequence_number;
+        self.next_sequence_number += 1;
+        let eviction_key = match self.policy.eviction {
+            TransactionPoolEviction::LowestPriority => (priority, sequence_number),
+            TransactionPoolEviction::Reject | TransactionPoolEviction::Oldest => {
+                (0, sequence_number)
+            }
+        };
+        self.eviction_order.insert((eviction_key, tx_hash));
+        let signer_usage = self.signer_usage.entry(signer_id.clone()).or_default();
+        signer_usage.count += 1;
+        signer_usage.size += size;
+        let access_key_usage = self.access_key_usage.entry(key).or_default();
+        access_key_usage.count += 1;
+        access_key_usage.size += size;
+        self.unique_transactions.insert(
+            tx_hash,
+            PoolEntry { key, signer_id: signer_id.clone(), priority, size, eviction_key },
+        );
+        self.total_transaction_size += size;
+        self.transactions.entry(key).or_insert_with(Vec::new).push(signed_transaction);
 
-        self.transaction_pool_count_metric.inc();
+        self.transaction_pool_count_metric.set(self.unique_transactions.len() as i64);
         self.transaction_pool_size_metric.set(self.total_transaction_size as i64);
         InsertTransactionResult::Success
     }
 
+    /// Picks transactions to evict so that at least `needed_size` bytes are freed for a new
+    /// transaction with the given priority. The transaction being replaced by the new one is
+    /// skipped since its space is already accounted for.
+    ///
+    /// Returns `None` if the policy doesn't allow to free enough space, in which case nothing
+    /// should be evicted.
+    fn eviction_candidates(
+        &self,
+        needed_size: u64,
+        priority: Balance,
+        replaced_hash: Option<CryptoHash>,
+    ) -> Option<Vec<CryptoHash>> {
+        if self.policy.eviction == TransactionPoolEviction::Reject {
+            return None;
+        }
+        let mut freed_size = 0;
+        let mut candidates = vec![];
+        for (_, tx_hash) in &self.eviction_order {
+            if freed_size >= needed_size {
+                break;
+            }
+            if Some(*tx_hash) == replaced_hash {
+                continue;
+            }
+            let entry = &self.unique_transactions[tx_hash];
+            if self.policy.eviction == TransactionPoolEviction::LowestPriority
+                && entry.priority >= priority
+            {
+                return None;
+            }
+            freed_size += entry.size;
+            candidates.push(*tx_hash);
+        }
+        (freed_size >= needed_size).then_some(candidates)
+    }
+
+    /// Checks the quotas of the signer and of the access key against a new transaction of the
+    /// given size, which replaces `replaced` transactions of the same access key. Returns the
+    /// label of the exceeded quota, if any.
+    fn exceeded_quota(
+        &self,
+        signer_id: &AccountId,
+        key: &PoolKey,
+        size: u64,
+        replaced: PoolUsage,
+    ) -> Option<&'static str> {
+        let quotas = self.quotas.get();
+        let exceeds = |usage: Option<&PoolUsage>, max_count: Option<u64>, max_size: Option<u64>| {
+            let usage = usage.copied().unwrap_or_default();
+            let count = usage.count + 1 - replaced.count;
+            let size = usage.size + size - replaced.size;
+            max_count.is_some_and(|max| count > max) || max_size.is_some_and(|max| size > max)
+        };
+        if exceeds(
+            self.signer_usage.get(signer_id),
+            quotas.max_transactions_per_signer,
+            quotas.max_bytes_per_signer,
+        ) {
+            Some("signer_quota")
+        } else if exceeds(
+            self.access_key_usage.get(key),
+            quotas.max_transactions_per_access_key,
+            quotas.max_bytes_per_access_key,
+        ) {
+            Some("access_key_quota")
+        } else {
+            None
+        }
+    }
+
+    fn record_rejection(&self, reason: &str) {
+        metrics::TRANSACTION_POOL_REJECTED.with_label_values(&[&self.metrics_label, reason]).inc();
+    }
+
+    /// Removes the transaction with the given hash from its group and from the bookkeeping.
+    fn remove_transaction(&mut self, tx_hash: &CryptoHash) {
+        let Some(entry) = self.forget_transaction(tx_hash) else {
+            return;
+        };
+        if let Entry::Occupied(mut group) = self.transactions.entry(entry.key) {
+            group.get_mut().retain(|tx| tx.get_hash() != *tx_hash);
+            if group.get().is_empty() {
+                group.remove_entry();
+            }
+        }
+        // See the comment in `insert_transaction` where we increase the size for reasoning why
+        // panicing here catches a logic error.
+        self.total_transaction_size = self
+            .total_transaction_size
+            .checked_sub(entry.size)
+            .expect("Total transaction size dropped below zero");
+    }
+
+    /// Removes the transaction with the given hash from the bookkeeping only, the caller is
+    /// responsible for the transaction itself and for the total size.
+    fn forget_transaction(&mut self, tx_hash: &CryptoHash) -> Option<PoolEntry> {
+        let entry = self.unique_transactions.remove(tx_hash)?;
+        self.eviction_order.remove(&(entry.eviction_key, *tx_hash));
+        release_usage(&mut self.signer_usage, entry.signer_id.clone(), entry.size);
+        release_usage(&mut self.access_key_usage, entry.key, entry.size);
+        Some(entry)
+    }
+
     /// Returns a pool iterator wrapper that implements an iterator-like trait to iterate over
     /// transaction groups in the proper order defined by the protocol.
     /// When the iterator is dropped, all remaining groups are inserted back into the pool.
@@ -130,14 +392,12 @@ impl TransactionPool {
         let mut grouped_transactions = HashMap::new();
         for tx in transactions {
             // If transaction is not present in the pool, skip it.
-            if !self.unique_transactions.remove(&tx.get_hash()) {
+            let Some(entry) = self.forget_transaction(&tx.get_hash()) else {
                 continue;
-            }
+            };
 
-            let signer_id = &tx.transaction.signer_id;
-            let signer_public_key = &tx.transaction.public_key;
             grouped_transactions
-                .entry(self.key(signer_id, signer_public_key))
+                .entry(entry.key)
                 .or_insert_with(HashSet::new)
                 .insert(tx.get_hash());
         }
@@ -166,6 +426,21 @@ impl TransactionPool {
         self.transaction_pool_size_metric.set(self.total_transaction_size as i64);
     }
 
+    /// Takes all transactions out of the pool together with their priorities.
+    pub fn take_transactions(&mut self) -> Vec<(SignedTransaction, Balance)> {
+        let priorities: HashMap<CryptoHash, Balance> =
+            self.unique_transactions.iter().map(|(hash, entry)| (*hash, entry.priority)).collect();
+        let mut transactions = vec![];
+        let mut iter = self.pool_iterator();
+        while let Some(group) = iter.next() {
+            while let Some(tx) = group.next() {
+                let priority = priorities[&tx.get_hash()];
+                transactions.push((tx, priority));
+            }
+        }
+        transactions
+    }
+
     /// Returns the number of unique transactions in the pool.
     pub fn len(&self) -> usize {
         self.unique_transactions.len()
@@ -186,18 +461,72 @@ pub struct PoolIteratorWrapper<'a> {
 
     /// Queue of transaction groups. Each group there is sorted by nonce.
     sorted_groups: VecDeque<TransactionGroup>,
+
+    /// Keys of the groups in the pool, from the highest priority to the lowest. Only used with
+    /// the priority ordering.
+    priority_order: VecDeque<PoolKey>,
 }
 
 impl<'a> PoolIteratorWrapper<'a> {
     pub fn new(pool: &'a mut TransactionPool) -> Self {
-        Self { pool, sorted_groups: Default::default() }
+        let priority_order = match pool.policy.ordering {
+            TransactionPoolOrdering::Random => VecDeque::new(),
+            TransactionPoolOrdering::Priority => {
+                // The priority of a group is the highest priority of its transactions. Ties are
+                // broken by the randomized key.
+                let mut groups: Vec<_> = pool
+                    .transactions
+                    .iter()
+                    .map(|(key, transactions)| {
+                        let priority = transactions
+                            .iter()
+                            .map(|tx| pool.unique_transactions[&tx.get_hash()].priority)
+                            .max()
+                            .unwrap_or(0);
+                        (priority, *key)
+                    })
+                    .collect();
+                groups.sort_unstable_by(|a, b| b.cmp(a));
+                groups.into_iter().map(|(_, key)| key).collect()
+            }
+        };
+        Self { pool, sorted_groups: Default::default(), priority_order }
+    }
+
+    /// Returns the key of the next group to pull from the pool, which must not be empty.
+    fn next_key(&mut self) -> PoolKey {
+        match self.pool.policy.ordering {
+            TransactionPoolOrdering::Random => {
+                let key = *self
+                    .pool
+                    .transactions
+                    .range((Bound::Excluded(self.pool.last_used_key), Bound::Unbounded))
+                    .next()
+                    .map(|(k, _v)| k)
+                    .unwrap_or_else(|| {
+                        self.pool
+                            .transactions
+                            .keys()
+                            .next()
+                            .expect("we've just checked that the map is not empty")
+                    });
+                self.pool.last_used_key = key;
+                key
+            }
+            // Groups are only taken out of the pool while the iterator exists, so every group
+            // left in the pool is still in the queue.
+            TransactionPoolOrdering::Priority => {
+                self.priority_order.pop_front().expect("every group in the pool is queued")
+            }
+        }
     }
 }
 
 /// The iterator works with the following algorithm:
 /// On next(), the iterator tries to get a transaction group from the pool, sorts transactions in
 /// it, and add it to the back of the sorted groups queue.
-/// Remembers the last used key, so it can continue from the next key.
+/// With the random ordering, remembers the last used key, so it can continue from the next key.
+/// With the priority ordering, groups are taken from the pool in the order of their priority.
 ///
 /// If the pool is empty, the iterator gets the group from the front of the sorted groups queue.
 ///
@@ -214,20 +543,7 @@ impl<'a> PoolIteratorWrapper<'a> {
 impl<'a> PoolIterator for PoolIteratorWrapper<'a> {
     fn next(&mut self) -> Option<&mut TransactionGroup> {
         if !self.pool.transactions.is_empty() {
-            let key = *self
-                .pool
-                .transactions
-                .range((Bound::Excluded(self.pool.last_used_key), Bound::Unbounded))
-                .next()
-                .map(|(k, _v)| k)
-                .unwrap_or_else(|| {
-                    self.pool
-                        .transactions
-                        .keys()
-                        .next()
-                        .expect("we've just checked that the map is not empty")
-                });
-            self.pool.last_used_key = key;
+            let key = self.next_key();
             let mut transactions =
                 self.pool.transactions.remove(&key).expect("just checked existence");
             transactions.sort_by_key(|st| std::cmp::Reverse(st.transaction.nonce));
@@ -242,7 +558,7 @@ impl<'a> PoolIterator for PoolIteratorWrapper<'a> {
             while let Some(sorted_group) = self.sorted_groups.pop_front() {
                 if sorted_group.transactions.is_empty() {
                     for hash in sorted_group.removed_transaction_hashes {
-                        self.pool.unique_transactions.remove(&hash);
+                        self.pool.forget_transaction(&hash);
                     }
                     // See the comment in `insert_transaction` where we increase the size for reasoning
                     // why panicing here catches a logic error.
@@ -273,7 +589,7 @@ impl<'a> Drop for PoolIteratorWrapper<'a> {
     fn drop(&mut self) {
         for group in self.sorted_groups.drain(..) {
             for hash in group.removed_transaction_hashes {
-                self.pool.unique_transactions.remove(&hash);
+                self.pool.forget_transaction(&hash);
             }
             // See the comment in `insert_transaction` where we increase the size for reasoning
             // why panicing here catches a logic error.
@@ -308,6 +624,10 @@ mod tests {
 
     const TEST_SEED: RngSeed = [3; 32];
 
+    fn no_quotas() -> MutableConfigValue<TransactionPoolQuotas> {
+        MutableConfigValue::new(TransactionPoolQuotas::default(), "transaction_pool_quotas")
+    }
+
     fn generate_transactions(
         signer_id: &str,
         signer_seed: &str,
@@ -335,11 +655,17 @@ mod tests {
         mut transactions: Vec<SignedTransaction>,
         expected_weight: u32,
     ) -> (Vec<u64>, TransactionPool) {
-        let mut pool = TransactionPool::new(TEST_SEED, None, "");
+        let mut pool = TransactionPool::new(
+            TEST_SEED,
+            None,
+            TransactionPoolPolicy::default(),
+            no_quotas(),
+            "",
+        );
         let mut rng = thread_rng();
         transactions.shuffle(&mut rng);
         for tx in transactions {
-            assert_eq!(pool.insert_transaction(tx), InsertTransactionResult::Success);
+            assert_eq!(pool.insert_transaction(tx, 0), InsertTransactionResult::Success);
         }
         (
             prepare_transactions(&mut pool, expected_weight)
@@ -446,12 +772,18 @@ mod tests {
             })
             .collect::<Vec<_>>();
 
-        let mut pool = TransactionPool::new(TEST_SEED, None, "");
+        let mut pool = TransactionPool::new(
+            TEST_SEED,
+            None,
+            TransactionPoolPolicy::default(),
+            no_quotas(),
+            "",
+        );
         let mut rng = thread_rng();
         transactions.shuffle(&mut rng);
         for tx in transactions.clone() {
             println!("{:?}", tx);
-            assert_eq!(pool.insert_transaction(tx), InsertTransactionResult::Success);
+            assert_eq!(pool.insert_transaction(tx, 0), InsertTransactionResult::Success);
         }
         assert_eq!(pool.len(), n as usize);
 
@@ -507,7 +839,7 @@ mod tests {
 
         for tx in transactions {
             assert!(matches!(
-                pool.insert_transaction(tx),
+                pool.insert_transaction(tx, 0),
                 InsertTransactionResult::Success | InsertTransactionResult::Duplicate
             ));
         }
@@ -546,7 +878,7 @@ mod tests {
 
         for tx in transactions {
             assert!(matches!(
-                pool.insert_transaction(tx),
+                pool.insert_transaction(tx, 0),
                 InsertTransactionResult::Success | InsertTransactionResult::Duplicate
             ));
         }
@@ -561,13 +893,19 @@ mod tests {
 
     #[test]
     fn test_transaction_pool_size() {
-        let mut pool = TransactionPool::new(TEST_SEED, None, "");
+        let mut pool = TransactionPool::new(
+            TEST_SEED,
+            None,
+            TransactionPoolPolicy::default(),
+            no_quotas(),
+            "",
+        );
         let transactions = generate_transactions("alice.unc", "alice.unc", 1, 100);
         let mut total_transaction_size = 0;
         // Adding transactions increases the size.
         for tx in transactions.clone() {
             total_transaction_size += tx.get_size();
-            assert_eq!(pool.insert_transaction(tx), InsertTransactionResult::Success);
+            assert_eq!(pool.insert_transaction(tx, 0), InsertTransactionResult::Success);
             assert_eq!(pool.transaction_size(), total_transaction_size);
         }
         // Removing transactions decreases the size.
@@ -585,13 +923,223 @@ mod tests {
         // Each transaction is at least 1 byte in size, so the last transaction will not fit.
         let pool_size_limit =
             transactions.iter().map(|tx| tx.get_size()).sum::<u64>().checked_sub(1).unwrap();
-        let mut pool = TransactionPool::new(TEST_SEED, Some(pool_size_limit), "");
+        let mut pool = TransactionPool::new(
+            TEST_SEED,
+            Some(pool_size_limit),
+            TransactionPoolPolicy::default(),
+            no_quotas(),
+            "",
+        );
         for (i, tx) in transactions.iter().cloned().enumerate() {
             if i + 1 < transactions.len() {
-                assert_eq!(pool.insert_transaction(tx), InsertTransactionResult::Success);
+                assert_eq!(pool.insert_transaction(tx, 0), InsertTransactionResult::Success);
             } else {
-                assert_eq!(pool.insert_transaction(tx), InsertTransactionResult::NoSpaceLeft);
+                assert_eq!(pool.insert_transaction(tx, 0), InsertTransactionResult::NoSpaceLeft);
             }
         }
     }
+
+    /// A transaction told apart from others by its deposit, which is set to its priority.
+    fn prioritized(signer_id: &str, nonce: u64, priority: Balance) -> (SignedTransaction, Balance) {
+        let signer_id: AccountId = signer_id.parse().unwrap();
+        let signer =
+            InMemorySigner::from_seed(signer_id.clone(), KeyType::ED25519, signer_id.as_ref());
+        let tx = SignedTransaction::send_money(
+            nonce,
+            signer_id,
+            "bob.unc".parse().unwrap(),
+            &signer,
+            priority,
+            CryptoHash::default(),
+        );
+        (tx, priority)
+    }
+
+    fn priorities_of(transactions: &[SignedTransaction]) -> Vec<Balance> {
+        transactions
+            .iter()
+            .map(|tx| {
+                tx.transaction.actions.iter().map(|action| action.get_deposit_balance()).sum()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_priority_ordering() {
+        let policy = TransactionPoolPolicy {
+            ordering: TransactionPoolOrdering::Priority,
+            ..TransactionPoolPolicy::default()
+        };
+        let mut pool = TransactionPool::new(TEST_SEED, None, policy, no_quotas(), "");
+        for (tx, priority) in [
+            prioritized("user_1", 1, 10),
+            prioritized("user_2", 1, 30),
+            prioritized("user_3", 1, 20),
+            // The group goes first thanks to its second transaction, but nonces are kept in order.
+            prioritized("user_4", 1, 5),
+            prioritized("user_4", 2, 40),
+        ] {
+            assert_eq!(pool.insert_transaction(tx, priority), InsertTransactionResult::Success);
+        }
+        let transactions = prepare_transactions(&mut pool, 6);
+        assert_eq!(priorities_of(&transactions), vec![5, 30, 20, 10, 40]);
+    }
+
+    #[test]
+    fn test_evict_oldest() {
+        let transactions: Vec<_> =
+            (1..=4).map(|i| prioritized("user_1", i, i as Balance)).collect();
+        let pool_size_limit = transactions[..3].iter().map(|(tx, _)| tx.get_size()).sum::<u64>();
+        let policy = TransactionPoolPolicy {
+            eviction: TransactionPoolEviction::Oldest,
+            ..TransactionPoolPolicy::default()
+        };
+        let mut pool =
+            TransactionPool::new(TEST_SEED, Some(pool_size_limit), policy, no_quotas(), "");
+        for (tx, priority) in transactions {
+            assert_eq!(pool.insert_transaction(tx, priority), InsertTransactionResult::Success);
+        }
+        assert_eq!(pool.len(), 3);
+        assert_eq!(pool.transaction_size(), pool_size_limit);
+        let nonces: Vec<_> =
+            prepare_transactions(&mut pool, 4).iter().map(|tx| tx.transaction.nonce).collect();
+        assert_eq!(nonces, vec![2, 3, 4]);
+    }
+
+    #[test]
+    fn test_evict_lowest_priority() {
+        let pool_size_limit = 2 * prioritized("user_1", 1, 10).0.get_size();
+        let policy = TransactionPoolPolicy {
+            eviction: TransactionPoolEviction::LowestPriority,
+            ..TransactionPoolPolicy::default()
+        };
+        let mut pool =
+            TransactionPool::new(TEST_SEED, Some(pool_size_limit), policy, no_quotas(), "");
+        for (tx, priority) in [prioritized("user_1", 1, 10), prioritized("user_2", 1, 30)] {
+            assert_eq!(pool.insert_transaction(tx, priority), InsertTransactionResult::Success);
+        }
+        // Evicts the transaction with priority 10.
+        let (tx, priority) = prioritized("user_3", 1, 20);
+        assert_eq!(pool.insert_transaction(tx, priority), InsertTransactionResult::Success);
+        // Nothing has a lower priority than the new transaction.
+        let (tx, priority) = prioritized("user_4", 1, 20);
+        assert_eq!(pool.insert_transaction(tx, priority), InsertTransactionResult::NoSpaceLeft);
+        assert_eq!(pool.len(), 2);
+        let mut priorities = priorities_of(&prepare_transactions(&mut pool, 3));
+        priorities.sort();
+        assert_eq!(priorities, vec![20, 30]);
+        assert_eq!(pool.transaction_size(), 0);
+    }
+
+    #[test]
+    fn test_replace_by_nonce() {
+        let policy =
+            TransactionPoolPolicy { replace_by_nonce: true, ..TransactionPoolPolicy::default() };
+        let mut pool = TransactionPool::new(TEST_SEED, None, policy, no_quotas(), "");
+        let (original, priority) = prioritized("user_1", 1, 10);
+        let original_size = original.get_size();
+        assert_eq!(pool.insert_transaction(original, priority), InsertTransactionResult::Success);
+        let (tx, priority) = prioritized("user_1", 1, 5);
+        assert_eq!(
+            pool.insert_transaction(tx, priority),
+            InsertTransactionResult::ReplacementRejected
+        );
+        let (tx, priority) = prioritized("user_1", 1, 20);
+        assert_eq!(pool.insert_transaction(tx, priority), InsertTransactionResult::Success);
+        assert_eq!(pool.len(), 1);
+        assert_eq!(pool.transaction_size(), original_size);
+        assert_eq!(priorities_of(&prepare_transactions(&mut pool, 2)), vec![20]);
+    }
+
+    #[test]
+    fn test_take_transactions() {
+        let policy = TransactionPoolPolicy {
+            ordering: TransactionPoolOrdering::Priority,
+            ..TransactionPoolPolicy::default()
+        };
+        let mut pool = TransactionPool::new(TEST_SEED, None, policy, no_quotas(), "");
+        let transactions = vec![prioritized("user_1", 1, 10), prioritized("user_2", 1, 30)];
+        for (tx, priority) in transactions.clone() {
+            assert_eq!(pool.insert_transaction(tx, priority), InsertTransactionResult::Success);
+        }
+        let mut taken = pool.take_transactions();
+        taken.sort_by_key(|(_, priority)| *priority);
+        assert_eq!(taken, transactions);
+        assert_eq!(pool.len(), 0);
+        assert_eq!(pool.transaction_size(), 0);
+    }
+
+    #[test]
+    fn test_signer_quota() {
+        let quotas = no_quotas();
+        let mut pool = TransactionPool::new(
+            TEST_SEED,
+            None,
+            TransactionPoolPolicy::default(),
+            quotas.clone(),
+            "",
+        );
+        // Two access keys of the same signer share its quota.
+        let mut transactions = generate_transactions("alice.unc", "alice.unc", 1, 2);
+        transactions.extend(generate_transactions("alice.unc", "bob.unc", 3, 4));
+        quotas.update(TransactionPoolQuotas {
+            max_transactions_per_signer: Some(3),
+            ..TransactionPoolQuotas::default()
+        });
+        for (i, tx) in transactions.iter().cloned().enumerate() {
+            let expected = if i < 3 {
+                InsertTransactionResult::Success
+            } else {
+                InsertTransactionResult::QuotaExceeded
+            };
+            assert_eq!(pool.insert_transaction(tx, 0), expected);
+        }
+        // Other signers are not affected.
+        for tx in generate_transactions("bob.unc", "bob.unc", 1, 3) {
+            assert_eq!(pool.insert_transaction(tx, 0), InsertTransactionResult::Success);
+        }
+        // Transactions leaving the pool free the quota.
+        pool.remove_transactions(&transactions[..1]);
+        assert_eq!(
+            pool.insert_transaction(transactions[3].clone(), 0),
+            InsertTransactionResult::Success
+        );
+        // Quotas can be updated while the pool is in use.
+        quotas.update(TransactionPoolQuotas::default());
+        for tx in generate_transactions("alice.unc", "alice.unc", 5, 10) {
+            assert_eq!(pool.insert_transaction(tx, 0), InsertTransactionResult::Success);
+        }
+    }
+
+    #[test]
+    fn test_access_key_quota() {
+        let transactions = generate_transactions("alice.unc", "alice.unc", 1, 3);
+        let max_bytes = transactions[..2].iter().map(|tx| tx.get_size()).sum::<u64>();
+        let quotas = MutableConfigValue::new(
+            TransactionPoolQuotas {
+                max_bytes_per_access_key: Some(max_bytes),
+                ..TransactionPoolQuotas::default()
+            },
+            "transaction_pool_quotas",
+        );
+        let mut pool =
+            TransactionPool::new(TEST_SEED, None, TransactionPoolPolicy::default(), quotas, "");
+        for tx in transactions[..2].iter().cloned() {
+            assert_eq!(pool.insert_transaction(tx, 0), InsertTransactionResult::Success);
+        }
+        assert_eq!(
+            pool.insert_transaction(transactions[2].clone(), 0),
+            InsertTransactionResult::QuotaExceeded
+        );
+        // Another access key of the same signer has its own quota.
+        for tx in generate_transactions("alice.unc", "bob.unc", 1, 2) {
+            assert_eq!(pool.insert_transaction(tx, 0), InsertTransactionResult::Success);
+        }
+        // Pulling transactions from the pool frees the quota.
+        assert_eq!(prepare_transactions(&mut pool, 4).len(), 4);
+        assert_eq!(
+            pool.insert_transaction(transactions[2].clone(), 0),
+            InsertTransactionResult::Success
+        );
+    }
 }
@@ -1,5 +1,5 @@
 use once_cell::sync::Lazy;
-use unc_o11y::metrics::IntGaugeVec;
+use unc_o11y::metrics::{IntCounterVec, IntGaugeVec};
 
 pub static TRANSACTION_POOL_COUNT: Lazy<IntGaugeVec> = Lazy::new(|| {
     unc_o11y::metrics::try_create_int_gauge_vec(
@@ -18,3 +18,30 @@ pub static TRANSACTION_POOL_SIZE: Lazy<IntGaugeVec> = Lazy::new(|| {
     )
     .unwrap()
 });
+
+pub static TRANSACTION_POOL_EVICTED: Lazy<IntCounterVec> = Lazy::new(|| {
+    unc_o11y::metrics::try_create_int_counter_vec(
+        "unc_transaction_pool_evicted_total",
+        "Number of transactions evicted from a given shard pool to make room for new ones",
+        &["shard_id", "policy"],
+    )
+    .unwrap()
+});
+
+pub static TRANSACTION_POOL_REPLACED: Lazy<IntCounterVec> = Lazy::new(|| {
+    unc_o11y::metrics::try_create_int_counter_vec(
+        "unc_transaction_pool_replaced_total",
+        "Number of transactions in a given shard pool replaced by a transaction with the same nonce",
+        &["shard_id"],
+    )
+    .unwrap()
+});
+
+pub static TRANSACTION_POOL_REJECTED: Lazy<IntCounterVec> = Lazy::new(|| {
+    unc_o11y::metrics::try_create_int_counter_vec(
+        "unc_transaction_pool_rejected_total",
+        "Number of transactions rejected by a given shard pool",
+        &["shard_id", "reason"],
+    )
+    .unwrap()
+});
@@ -3,10 +3,13 @@ use crate::ExternalStorageLocation::GCS;
 use crate::MutableConfigValue;
 use std::cmp::{max, min};
 use std::path::PathBuf;
-use std::sync::atomic::AtomicBool;
+use std::sync::atomic::{AtomicBool, AtomicU64};
 use std::sync::Arc;
 use std::time::Duration;
-use unc_primitives::types::{BlockHeight, BlockHeightDelta, Gas, NumBlocks, NumSeats, ShardId};
+use unc_crypto::PublicKey;
+use unc_primitives::types::{
+    AccountId, BlockHeight, BlockHeightDelta, Gas, NumBlocks, NumSeats, ShardId,
+};
 use unc_primitives::version::Version;
 
 pub const TEST_STATE_SYNC_TIMEOUT: u64 = 5;
@@ -25,6 +28,10 @@ pub const MIN_GC_NUM_EPOCHS_TO_KEEP: u64 = 3;
 /// Default number of epochs for which we keep store data
 pub const DEFAULT_GC_NUM_EPOCHS_TO_KEEP: u64 = 5;
 
+/// Default maximum number of blocks kept by a `GCHoldHandle` on top of the
+/// regular GC horizon, about a day of blocks.
+pub const DEFAULT_GC_HOLD_MAX_BLOCKS: NumBlocks = 86_400;
+
 /// Default number of concurrent requests to external storage to fetch state parts.
 pub const DEFAULT_STATE_SYNC_NUM_CONCURRENT_REQUESTS_EXTERNAL: u32 = 25;
 pub const DEFAULT_STATE_SYNC_NUM_CONCURRENT_REQUESTS_ON_CATCHUP_EXTERNAL: u32 = 5;
@@ -43,6 +50,12 @@ pub struct GCConfig {
 
     /// Number of epochs for which we keep store data.
     pub gc_num_epochs_to_keep: u64,
+
+    /// Maximum number of blocks an external consumer, such as the indexer, can
+    /// keep from being garbage collected on top of the regular GC horizon, see
+    /// `GCHoldHandle`. Past it the blocks are collected anyway, so that a stuck
+    /// consumer doesn't fill up the disk.
+    pub gc_hold_max_blocks: NumBlocks,
 }
 
 impl Default for GCConfig {
@@ -51,6 +64,7 @@ impl Default for GCConfig {
             gc_blocks_limit: 2,
             gc_fork_clean_step: 100,
             gc_num_epochs_to_keep: DEFAULT_GC_NUM_EPOCHS_TO_KEEP,
+            gc_hold_max_blocks: DEFAULT_GC_HOLD_MAX_BLOCKS,
         }
     }
 }
@@ -81,6 +95,15 @@ pub struct ExternalStorageConfig {
     /// to reduce the performance impact of state sync.
     #[serde(default = "default_num_concurrent_requests_during_catchup")]
     pub num_concurrent_requests_during_catchup: u32,
+    /// Before downloading the state parts of a shard, download the manifest of
+    /// the dump and check every part against it. Requires the dump to be
+    /// written with `DumpConfig::state_parts_manifest`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub verify_state_parts_manifest: bool,
+    /// Node keys of the dumping nodes whose manifests are trusted, tried in
+    /// this order. Manifests signed by other keys are never downloaded.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub trusted_state_dumper_keys: Vec<PublicKey>,
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
@@ -90,6 +113,10 @@ pub enum ExternalStorageLocation {
         bucket: String,
         /// Data may only be available in certain locations.
         region: String,
+        /// Endpoint of an S3-compatible storage, such as MinIO, e.g.
+        /// `http://localhost:9000`. Objects are then addressed path-style.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        endpoint: Option<String>,
     },
     Filesystem {
         root_dir: PathBuf,
@@ -97,6 +124,23 @@ pub enum ExternalStorageLocation {
     GCS {
         bucket: String,
     },
+    /// Read-only storage served over HTTP(S), e.g. state dumps mirrored behind
+    /// a CDN or nginx. Parts are fetched
+    /// from `<url>/<location of the part>`.
+    HTTP {
+        url: String,
+    },
+    /// Container of an Azure Storage account. Requests are authorized with a
+    /// shared access signature, see `DumpConfig::credentials_file`.
+    Azure {
+        /// Name of the storage account.
+        account: String,
+        container: String,
+        /// Endpoint of the Blob service, e.g. `http://127.0.0.1:10000/devstoreaccount1`
+        /// for the Azurite emulator. Defaults to `https://<account>.blob.core.windows.net`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        endpoint: Option<String>,
+    },
 }
 
 /// Configures how to dump state to external storage.
@@ -113,8 +157,14 @@ pub struct DumpConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub iteration_delay: Option<Duration>,
     /// Location of a json file with credentials allowing write access to the bucket.
+    /// For Azure, it contains a shared access signature allowing to write and list
+    /// the blobs: `{"sas_token": "..."}`.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub credentials_file: Option<PathBuf>,
+    /// Once all state parts of an epoch are dumped, write a manifest of them
+    /// signed by the node key.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub state_parts_manifest: bool,
 }
 
 /// Configures how to fetch state parts during state sync.
@@ -170,6 +220,36 @@ impl ReshardingHandle {
     }
 }
 
+// A handle that allows an external consumer of the chain data, such as the
+// indexer, to stop garbage collection from removing blocks it hasn't processed
+// yet. Blocks at or above the held height are kept until the hold is moved
+// forward or released, but at most `GCConfig::gc_hold_max_blocks` blocks past
+// the regular GC horizon.
+#[derive(Clone)]
+pub struct GCHoldHandle {
+    hold_height: Arc<AtomicU64>,
+}
+
+impl GCHoldHandle {
+    pub fn new() -> Self {
+        Self { hold_height: Arc::new(AtomicU64::new(BlockHeight::MAX)) }
+    }
+
+    /// Returns the lowest height garbage collection must keep, if any.
+    pub fn get(&self) -> Option<BlockHeight> {
+        let height = self.hold_height.load(std::sync::atomic::Ordering::Relaxed);
+        (height != BlockHeight::MAX).then_some(height)
+    }
+
+    pub fn hold_from(&self, height: BlockHeight) {
+        self.hold_height.store(height, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    pub fn release(&self) {
+        self.hold_height.store(BlockHeight::MAX, std::sync::atomic::Ordering::Relaxed);
+    }
+}
+
 /// Configuration for resharding.
 #[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, PartialEq)]
 #[serde(default)]
@@ -215,6 +295,77 @@ impl Default for ReshardingConfig {
     }
 }
 
+/// Order in which transaction groups are pulled from the transaction pool when
+/// producing a chunk.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TransactionPoolOrdering {
+    /// Round robin over groups in a randomized order.
+    #[default]
+    Random,
+    /// Groups with the highest priority transaction go first, see
+    /// `unc_pool::transaction_priority`.
+    Priority,
+}
+
+/// Which transactions are evicted to make room for a new one when the
+/// transaction pool is full.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TransactionPoolEviction {
+    /// Nothing is evicted, the new transaction is rejected.
+    #[default]
+    Reject,
+    /// The transactions which were inserted first are evicted.
+    Oldest,
+    /// The transactions with the lowest priority are evicted, but only if
+    /// their priority is lower than the one of the new transaction.
+    LowestPriority,
+}
+
+/// Configuration of the transaction pool policy.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct TransactionPoolPolicy {
+    pub ordering: TransactionPoolOrdering,
+    pub eviction: TransactionPoolEviction,
+    /// If set, a transaction with the same signer, public key and nonce as a
+    /// transaction already in the pool replaces it when its priority is
+    /// higher, and is rejected otherwise.
+    pub replace_by_nonce: bool,
+}
+
+/// Limits on the transactions of a single signer account and of a single access key in the
+/// transaction pool of a shard, so that a single account can't fill the pool. Unset limits are
+/// unbounded.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct TransactionPoolQuotas {
+    /// Max number of transactions of a single signer account.
+    pub max_transactions_per_signer: Option<u64>,
+    /// Max total size in bytes of transactions of a single signer account.
+    pub max_bytes_per_signer: Option<u64>,
+    /// Max number of transactions signed with a single access key.
+    pub max_transactions_per_access_key: Option<u64>,
+    /// Max total size in bytes of transactions signed with a single access key.
+    pub max_bytes_per_access_key: Option<u64>,
+}
+
+/// Shards tracked by a node in addition to the shards it has to track because
+/// of its validator duties.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
+pub enum TrackedConfig {
+    /// Tracks all shards.
+    AllShards,
+    /// Tracks the given shards.
+    Shards(Vec<ShardId>),
+    /// Tracks the shards containing the given accounts.
+    Accounts(Vec<AccountId>),
+    /// Rotates between sets of shards. In the epoch of height `h` the node
+    /// tracks the shards of `schedule[h % schedule.len()]`.
+    Schedule(Vec<Vec<ShardId>>),
+}
+
 pub fn default_header_sync_initial_timeout() -> Duration {
     Duration::from_secs(10)
 }
@@ -255,6 +406,8 @@ pub fn default_state_sync() -> Option<StateSyncConfig> {
             num_concurrent_requests: DEFAULT_STATE_SYNC_NUM_CONCURRENT_REQUESTS_EXTERNAL,
             num_concurrent_requests_during_catchup:
                 DEFAULT_STATE_SYNC_NUM_CONCURRENT_REQUESTS_ON_CATCHUP_EXTERNAL,
+            verify_state_parts_manifest: false,
+            trusted_state_dumper_keys: vec![],
         }),
     })
 }
@@ -360,6 +513,8 @@ pub struct ClientConfig {
     pub gc: GCConfig,
     /// Not clear old data, set `true` for archive nodes.
     pub archive: bool,
+    /// Shards tracked by the node in addition to the shards of its validator duties.
+    pub tracked_shards_config: TrackedConfig,
     /// save_trie_changes should be set to true iff
     /// - archive if false - non-archivale nodes need trie changes to perform garbage collection
     /// - archive is true, cold_store is configured and migration to split_storage is finished - node
@@ -391,6 +546,10 @@ pub struct ClientConfig {
     /// Limit of the size of per-shard transaction pool measured in bytes. If not set, the size
     /// will be unbounded.
     pub transaction_pool_size_limit: Option<u64>,
+    /// Ordering, eviction and replacement policy of the transaction pool.
+    pub transaction_pool_policy: TransactionPoolPolicy,
+    /// Per-signer and per-access-key limits of the transaction pool.
+    pub transaction_pool_quotas: MutableConfigValue<TransactionPoolQuotas>,
     // Allows more detailed logging, for example a list of orphaned blocks.
     pub enable_multiline_logging: bool,
     // Configuration for resharding.
@@ -458,6 +617,7 @@ impl ClientConfig {
             block_header_fetch_horizon: 50,
             gc: GCConfig { gc_blocks_limit: 100, ..GCConfig::default() },
             archive,
+            tracked_shards_config: TrackedConfig::AllShards,
             save_trie_changes,
             log_summary_style: LogSummaryStyle::Colored,
             view_client_threads: 1,
@@ -471,6 +631,11 @@ impl ClientConfig {
             state_sync_enabled,
             state_sync: StateSyncConfig::default(),
             transaction_pool_size_limit: None,
+            transaction_pool_policy: TransactionPoolPolicy::default(),
+            transaction_pool_quotas: MutableConfigValue::new(
+                TransactionPoolQuotas::default(),
+                "transaction_pool_quotas",
+            ),
             enable_multiline_logging: false,
             resharding_config: MutableConfigValue::new(
                 ReshardingConfig::default(),
@@ -72,6 +72,10 @@ fn default_use_production_config() -> bool {
     false
 }
 
+fn default_power_reward_rate() -> Rational32 {
+    Rational32::new(1, 2)
+}
+
 fn default_max_kickout_pledge_threshold() -> u8 {
     100
 }
@@ -176,6 +180,11 @@ pub struct GenesisConfig {
     /// in AllEpochConfig, and we want to have a way to test that code path. This flag is for that.
     /// If set to true, the node will use the same config override path as mainnet and testnet.
     pub use_production_config: bool,
+    /// Share of the validator reward distributed by power rather than by pledge.
+    /// Only used once `ProtocolFeature::PowerWeightedReward` is enabled.
+    #[serde(default = "default_power_reward_rate")]
+    #[default(default_power_reward_rate())]
+    pub power_reward_rate: Rational32,
 }
 
 impl GenesisConfig {
@@ -757,6 +766,8 @@ pub struct ProtocolConfigView {
     pub transaction_validity_period: NumBlocks,
     /// Protocol treasury rate
     pub protocol_reward_rate: Rational32,
+    /// Share of the validator reward distributed by power rather than by pledge.
+    pub power_reward_rate: Rational32,
     /// Maximum inflation on the total supply every epoch.
     pub max_inflation_rate: Rational32,
     /// Expected number of blocks per year
@@ -812,6 +823,7 @@ impl From<ProtocolConfig> for ProtocolConfigView {
             runtime_config: RuntimeConfigView::from(runtime_config),
             transaction_validity_period: genesis_config.transaction_validity_period,
             protocol_reward_rate: genesis_config.protocol_reward_rate,
+            power_reward_rate: genesis_config.power_reward_rate,
             max_inflation_rate: genesis_config.max_inflation_rate,
             num_blocks_per_year: genesis_config.num_blocks_per_year,
             protocol_treasury_account: genesis_config.protocol_treasury_account,
@@ -31,6 +31,7 @@ struct GenesisValidator<'a> {
     account_ids: HashSet<AccountId>,
     access_key_account_ids: HashSet<AccountId>,
     contract_account_ids: HashSet<AccountId>,
+    rsa2048_keys_account_ids: HashSet<AccountId>,
     validation_errors: &'a mut ValidationErrors,
 }
 
@@ -46,6 +47,7 @@ impl<'a> GenesisValidator<'a> {
             account_ids: HashSet::new(),
             access_key_account_ids: HashSet::new(),
             contract_account_ids: HashSet::new(),
+            rsa2048_keys_account_ids: HashSet::new(),
             validation_errors,
         }
     }
@@ -75,6 +77,9 @@ impl<'a> GenesisValidator<'a> {
                 }
                 self.contract_account_ids.insert(account_id.clone());
             }
+            StateRecord::Rsa2048Keys { account_id, .. } => {
+                self.rsa2048_keys_account_ids.insert(account_id.clone());
+            }
             _ => {}
         }
     }
@@ -131,6 +136,13 @@ impl<'a> GenesisValidator<'a> {
             }
         }
 
+        for account_id in &self.rsa2048_keys_account_ids {
+            if !self.account_ids.contains(account_id) {
+                let error_message = format!("rsa2048 keys account {} does not exist", account_id);
+                self.validation_errors.push_genesis_semantics_error(error_message)
+            }
+        }
+
         if self.genesis_config.online_max_threshold <= self.genesis_config.online_min_threshold {
             let error_message = format!(
                 "Online max threshold {} smaller than min threshold {}",
@@ -183,6 +195,16 @@ impl<'a> GenesisValidator<'a> {
             let error_message = format!("Epoch Length must be greater than 0");
             self.validation_errors.push_genesis_semantics_error(error_message)
         }
+
+        if self.genesis_config.power_reward_rate < Rational32::from_integer(0)
+            || self.genesis_config.power_reward_rate > Rational32::from_integer(1)
+        {
+            let error_message = format!(
+                "Power reward rate must be between 0 and 1, value in config is {}",
+                self.genesis_config.power_reward_rate
+            );
+            self.validation_errors.push_genesis_semantics_error(error_message)
+        }
     }
 
     fn result_with_full_error(&self) -> Result<(), ValidationError> {
@@ -202,6 +224,7 @@ mod test {
     use crate::GenesisRecords;
     use unc_crypto::{KeyType, PublicKey};
     use unc_primitives::account::{AccessKey, Account};
+    use unc_primitives::action::{RegisterRsa2048KeysAction, Rsa2048KeysOperation};
     use unc_primitives::types::AccountInfo;
 
     const VALID_ED25519_RISTRETTO_KEY: &str = "ed25519:KuTCtARNzxZQ3YvXDeLjx83FDqxv2SdQTSbiq876zR7";
@@ -300,6 +323,34 @@ mod test {
         validate_genesis(genesis).unwrap();
     }
 
+    #[test]
+    #[should_panic(expected = "rsa2048 keys account test1 does not exist")]
+    fn test_rsa2048_keys_with_nonexistent_account() {
+        let mut config = GenesisConfig::default();
+        config.validators = vec![AccountInfo {
+            account_id: "test".parse().unwrap(),
+            public_key: VALID_ED25519_RISTRETTO_KEY.parse().unwrap(),
+            power: 10,
+            pledging: 10,
+        }];
+        config.total_supply = 110;
+        let public_key = PublicKey::empty(KeyType::ED25519);
+        let records = GenesisRecords(vec![
+            StateRecord::Account { account_id: "test".parse().unwrap(), account: create_account() },
+            StateRecord::Rsa2048Keys {
+                account_id: "test1".parse().unwrap(),
+                public_key: public_key.clone(),
+                rsa2048_keys: RegisterRsa2048KeysAction {
+                    public_key,
+                    operation_type: Rsa2048KeysOperation::AddKeys as u8,
+                    args: vec![],
+                },
+            },
+        ]);
+        let genesis = &Genesis::new(config, records).unwrap();
+        validate_genesis(genesis).unwrap();
+    }
+
     #[test]
     #[should_panic(expected = "account test has more than one contract deployed")]
     fn test_more_than_one_contract() {
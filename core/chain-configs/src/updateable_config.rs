@@ -3,7 +3,7 @@ use std::sync::{Arc, Mutex};
 use std::{fmt::Debug, time::Duration};
 use unc_primitives::types::BlockHeight;
 
-use crate::ReshardingConfig;
+use crate::{ReshardingConfig, TransactionPoolQuotas};
 
 /// A wrapper for a config value that can be updated while the node is running.
 /// When initializing sub-objects (e.g. `ShardsManager`), please make sure to
@@ -96,4 +96,7 @@ pub struct UpdateableClientConfig {
 
     /// Time limit for adding transactions in produce_chunk()
     pub produce_chunk_add_transactions_time_limit: Option<Duration>,
+
+    /// Per-signer and per-access-key limits of the transaction pool.
+    pub transaction_pool_quotas: TransactionPoolQuotas,
 }
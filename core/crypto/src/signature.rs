@@ -709,7 +709,10 @@ impl Signature {
                 SECP256K1.verify_ecdsa(&message, &sig, &pub_key).is_ok()
             }
             (Signature::RSA(signature), PublicKey::RSA(public_key)) => {
-                let pk = rsa::RsaPublicKey::from_public_key_der(&public_key.0).unwrap();
+                let pk = match rsa::RsaPublicKey::from_public_key_der(&public_key.0) {
+                    Ok(pk) => pk,
+                    Err(_) => return false,
+                };
                 match pk.verify(Pkcs1v15Sign::new_unprefixed(), &data, signature.0.as_ref()) {
                     Ok(_) => true,
                     Err(_) => false,
@@ -23,6 +23,8 @@ pub struct RuntimeConfig {
     pub wasm_config: crate::vm::Config,
     /// Config that defines rules for account creation.
     pub account_creation_config: AccountCreationConfig,
+    /// Config that defines who may register RSA2048 chip keys.
+    pub chip_registry_config: ChipRegistryConfig,
 }
 
 impl RuntimeConfig {
@@ -45,6 +47,7 @@ impl RuntimeConfig {
             fees: RuntimeFeesConfig::test(),
             wasm_config,
             account_creation_config: AccountCreationConfig::default(),
+            chip_registry_config: ChipRegistryConfig::default(),
         }
     }
 
@@ -57,6 +60,7 @@ impl RuntimeConfig {
             fees: RuntimeFeesConfig::free(),
             wasm_config,
             account_creation_config: AccountCreationConfig::default(),
+            chip_registry_config: ChipRegistryConfig::default(),
         }
     }
 
@@ -83,3 +87,23 @@ impl Default for AccountCreationConfig {
         }
     }
 }
+
+/// The structure describes configuration for the RSA2048 chip registry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChipRegistryConfig {
+    /// Accounts allowed to register and revoke RSA2048 chip keys. Unclaimed chip keys are
+    /// stored under one of these accounts until a miner claims them with a challenge.
+    pub registrar_account_ids: Vec<AccountId>,
+}
+
+impl ChipRegistryConfig {
+    pub fn is_registrar(&self, account_id: &AccountId) -> bool {
+        self.registrar_account_ids.contains(account_id)
+    }
+}
+
+impl Default for ChipRegistryConfig {
+    fn default() -> Self {
+        Self { registrar_account_ids: vec!["unc".parse().unwrap()] }
+    }
+}
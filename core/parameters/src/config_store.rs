@@ -40,6 +40,10 @@ static CONFIG_DIFFS: &[(ProtocolVersion, &str)] = &[
     (129, include_config!("129.yaml")),
     // Introduce ETH-implicit accounts.
     (138, include_config!("138.yaml")),
+    // Contracts can register chips and create RSA2048 challenges.
+    (141, include_config!("141.yaml")),
+    // Contracts can verify RSA2048 and secp256k1 signatures.
+    (142, include_config!("142.yaml")),
 ];
 
 /// Testnet parameters for versions <= 29, which (incorrectly) differed from mainnet parameters
@@ -153,11 +157,13 @@ impl RuntimeConfigStore {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::ChipRegistryConfig;
     use crate::cost::{ActionCosts, ExtCosts};
     use std::collections::HashSet;
     use unc_primitives_core::version::ProtocolFeature::{
         LowerDataReceiptAndEcrecoverBaseCost, LowerStorageCost, LowerStorageKeyLimit,
     };
+    use unc_primitives_core::version::PROTOCOL_VERSION;
 
     const GENESIS_PROTOCOL_VERSION: ProtocolVersion = 29;
     const RECEIPTS_DEPTH: u64 = 63;
@@ -230,6 +236,16 @@ mod tests {
         assert_eq!(new_cfg.account_creation_config.min_allowed_top_level_account_length, 0);
     }
 
+    #[test]
+    fn test_chip_registrars() {
+        let store = RuntimeConfigStore::new(None);
+        for protocol_version in [GENESIS_PROTOCOL_VERSION, PROTOCOL_VERSION] {
+            let cfg = store.get_config(protocol_version);
+            assert_eq!(cfg.chip_registry_config, ChipRegistryConfig::default());
+            assert!(cfg.chip_registry_config.is_registrar(&"unc".parse().unwrap()));
+        }
+    }
+
     #[test]
     fn test_lower_data_receipt_cost() {
         let store = RuntimeConfigStore::new(None);
@@ -319,7 +335,6 @@ mod tests {
     #[cfg(not(feature = "calimero_zero_storage"))]
     fn test_json_unchanged() {
         use crate::view::RuntimeConfigView;
-        use unc_primitives_core::version::PROTOCOL_VERSION;
 
         let store = RuntimeConfigStore::new(None);
         let mut any_failure = false;
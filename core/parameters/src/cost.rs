@@ -132,6 +132,9 @@ impl ExtCostsConfig {
             ExtCosts::alt_bn128_g1_sum_element => 5_000_000_000,
             ExtCosts::validator_power_base => SAFETY_MULTIPLIER * 3_000_000_000,
             ExtCosts::validator_total_power_base => SAFETY_MULTIPLIER * 3_000_000_000,
+            ExtCosts::rsa2048_verify_base => SAFETY_MULTIPLIER * 5_000_000_000,
+            ExtCosts::rsa2048_verify_byte => SAFETY_MULTIPLIER * 7157035,
+            ExtCosts::secp256k1_verify_base => SAFETY_MULTIPLIER * 4_000_000_000,
         }
         .map(|_, value| ParameterCost { gas: value, compute: value * factor });
         ExtCostsConfig { costs }
@@ -225,6 +228,9 @@ pub enum ExtCosts {
     ed25519_verify_byte = 60,
     validator_power_base = 61,
     validator_total_power_base = 62,
+    rsa2048_verify_base = 63,
+    rsa2048_verify_byte = 64,
+    secp256k1_verify_base = 65,
 }
 
 // Type of an action, used in fees logic.
@@ -261,6 +267,8 @@ pub enum ActionCosts {
     delegate = 15,
     register_rsa2048_keys = 16,
     create_rsa2048_challenge = 17,
+    register_rsa2048_keys_byte = 18,
+    create_rsa2048_challenge_byte = 19,
 }
 
 impl ExtCosts {
@@ -337,6 +345,9 @@ impl ExtCosts {
             ExtCosts::alt_bn128_g1_sum_element => Parameter::WasmAltBn128G1SumElement,
             ExtCosts::validator_power_base => Parameter::WasmValidatorPledgeBase,
             ExtCosts::validator_total_power_base => Parameter::WasmValidatorTotalPledgeBase,
+            ExtCosts::rsa2048_verify_base => Parameter::WasmRsa2048VerifyBase,
+            ExtCosts::rsa2048_verify_byte => Parameter::WasmRsa2048VerifyByte,
+            ExtCosts::secp256k1_verify_base => Parameter::WasmSecp256k1VerifyBase,
         }
     }
 }
@@ -470,6 +481,16 @@ impl RuntimeFeesConfig {
                     send_not_sir: 115123062500,
                     execution: 115123062500,
                 },
+                ActionCosts::register_rsa2048_keys_byte => Fee {
+                    send_sir: 2235934,
+                    send_not_sir: 2235934,
+                    execution: 2235934,
+                },
+                ActionCosts::create_rsa2048_challenge_byte => Fee {
+                    send_sir: 2235934,
+                    send_not_sir: 2235934,
+                    execution: 2235934,
+                },
             },
         }
     }
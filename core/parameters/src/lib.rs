@@ -6,7 +6,7 @@ pub mod parameter_table;
 pub mod view;
 pub mod vm;
 
-pub use config::{AccountCreationConfig, RuntimeConfig};
+pub use config::{AccountCreationConfig, ChipRegistryConfig, RuntimeConfig};
 pub use config_store::RuntimeConfigStore;
 pub use cost::{
     transfer_exec_fee, transfer_send_fee, ActionCosts, ExtCosts, ExtCostsConfig, Fee,
@@ -24,6 +24,9 @@ pub enum Parameter {
     MinAllowedTopLevelAccountLength,
     RegistrarAccountId,
 
+    // Chip registry config
+    ChipRegistrarAccountIds,
+
     // Storage usage config
     StorageAmountPerByte,
     StorageNumBytesAccount,
@@ -80,6 +83,9 @@ pub enum Parameter {
     WasmEcrecoverBase,
     WasmEd25519VerifyBase,
     WasmEd25519VerifyByte,
+    WasmRsa2048VerifyBase,
+    WasmRsa2048VerifyByte,
+    WasmSecp256k1VerifyBase,
     WasmLogBase,
     WasmLogByte,
     WasmStorageWriteBase,
@@ -159,9 +165,13 @@ pub enum Parameter {
     FunctionCallWeight,
     VmKind,
     EthAccounts,
+    Rsa2048HostFunctions,
+    Rsa2048Secp256k1Verify,
 
     ActionRegisterRSA2048Keys,
     ActionCreateRSA2048Challenge,
+    ActionRegisterRSA2048KeysPerByte,
+    ActionCreateRSA2048ChallengePerByte,
 }
 
 #[derive(
@@ -196,6 +206,8 @@ pub enum FeeParameter {
     ActionDelegate,
     ActionRegisterRSA2048Keys,
     ActionCreateRSA2048Challenge,
+    ActionRegisterRSA2048KeysPerByte,
+    ActionCreateRSA2048ChallengePerByte,
 }
 
 impl Parameter {
@@ -257,6 +269,10 @@ impl From<ActionCosts> for FeeParameter {
             ActionCosts::new_data_receipt_byte => Self::DataReceiptCreationPerByte,
             ActionCosts::register_rsa2048_keys => Self::ActionRegisterRSA2048Keys,
             ActionCosts::create_rsa2048_challenge => Self::ActionCreateRSA2048Challenge,
+            ActionCosts::register_rsa2048_keys_byte => Self::ActionRegisterRSA2048KeysPerByte,
+            ActionCosts::create_rsa2048_challenge_byte => {
+                Self::ActionCreateRSA2048ChallengePerByte
+            }
         }
     }
 }
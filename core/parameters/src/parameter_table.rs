@@ -1,4 +1,4 @@
-use super::config::{AccountCreationConfig, RuntimeConfig};
+use super::config::{AccountCreationConfig, ChipRegistryConfig, RuntimeConfig};
 use crate::cost::{
     ActionCosts, ExtCostsConfig, Fee, ParameterCost, RuntimeFeesConfig, StorageUsageConfig,
 };
@@ -166,6 +166,23 @@ impl TryFrom<&ParameterValue> for AccountId {
     }
 }
 
+impl TryFrom<&ParameterValue> for Vec<AccountId> {
+    type Error = ValueConversionError;
+
+    fn try_from(value: &ParameterValue) -> Result<Self, Self::Error> {
+        let value: &str = value.try_into()?;
+        value
+            .split(',')
+            .map(str::trim)
+            .map(|account_id| {
+                account_id.parse().map_err(|err| {
+                    ValueConversionError::ParseAccountId(err, account_id.to_string())
+                })
+            })
+            .collect()
+    }
+}
+
 impl TryFrom<&ParameterValue> for VMKind {
     type Error = ValueConversionError;
 
@@ -325,12 +342,17 @@ impl TryFrom<&ParameterTable> for RuntimeConfig {
                 alt_bn128: params.get(Parameter::AltBn128)?,
                 function_call_weight: params.get(Parameter::FunctionCallWeight)?,
                 eth_accounts: params.get(Parameter::EthAccounts)?,
+                rsa2048_host_functions: params.get(Parameter::Rsa2048HostFunctions)?,
+                rsa2048_secp256k1_verify: params.get(Parameter::Rsa2048Secp256k1Verify)?,
             },
             account_creation_config: AccountCreationConfig {
                 min_allowed_top_level_account_length: params
                     .get(Parameter::MinAllowedTopLevelAccountLength)?,
                 registrar_account_id: params.get(Parameter::RegistrarAccountId)?,
             },
+            chip_registry_config: ChipRegistryConfig {
+                registrar_account_ids: params.get(Parameter::ChipRegistrarAccountIds)?,
+            },
         })
     }
 }
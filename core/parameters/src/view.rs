@@ -221,6 +221,10 @@ pub struct VMConfigView {
     pub function_call_weight: bool,
     /// See [`VMConfig::eth_accounts`].
     pub eth_accounts: bool,
+    /// See [`VMConfig::rsa2048_host_functions`].
+    pub rsa2048_host_functions: bool,
+    /// See [`VMConfig::rsa2048_secp256k1_verify`].
+    pub rsa2048_secp256k1_verify: bool,
 
     /// Describes limits for VM and Runtime.
     ///
@@ -246,6 +250,8 @@ impl From<crate::vm::Config> for VMConfigView {
             function_call_weight: config.function_call_weight,
             vm_kind: config.vm_kind,
             eth_accounts: config.eth_accounts,
+            rsa2048_host_functions: config.rsa2048_host_functions,
+            rsa2048_secp256k1_verify: config.rsa2048_secp256k1_verify,
         }
     }
 }
@@ -267,6 +273,8 @@ impl From<VMConfigView> for crate::vm::Config {
             function_call_weight: view.function_call_weight,
             vm_kind: view.vm_kind,
             eth_accounts: view.eth_accounts,
+            rsa2048_host_functions: view.rsa2048_host_functions,
+            rsa2048_secp256k1_verify: view.rsa2048_secp256k1_verify,
         }
     }
 }
@@ -337,6 +345,12 @@ pub struct ExtCostsConfigView {
     pub ed25519_verify_base: Gas,
     /// Cost of getting ed25519 per byte
     pub ed25519_verify_byte: Gas,
+    /// Cost of verifying a RSA2048 signature
+    pub rsa2048_verify_base: Gas,
+    /// Cost of verifying a RSA2048 signature per message byte
+    pub rsa2048_verify_byte: Gas,
+    /// Cost of verifying a secp256k1 signature
+    pub secp256k1_verify_base: Gas,
 
     /// Cost of calling ecrecover
     pub ecrecover_base: Gas,
@@ -470,6 +484,9 @@ impl From<crate::ExtCostsConfig> for ExtCostsConfigView {
             ripemd160_block: config.gas_cost(ExtCosts::ripemd160_block),
             ed25519_verify_base: config.gas_cost(ExtCosts::ed25519_verify_base),
             ed25519_verify_byte: config.gas_cost(ExtCosts::ed25519_verify_byte),
+            rsa2048_verify_base: config.gas_cost(ExtCosts::rsa2048_verify_base),
+            rsa2048_verify_byte: config.gas_cost(ExtCosts::rsa2048_verify_byte),
+            secp256k1_verify_base: config.gas_cost(ExtCosts::secp256k1_verify_base),
             ecrecover_base: config.gas_cost(ExtCosts::ecrecover_base),
             log_base: config.gas_cost(ExtCosts::log_base),
             log_byte: config.gas_cost(ExtCosts::log_byte),
@@ -547,6 +564,9 @@ impl From<ExtCostsConfigView> for crate::ExtCostsConfig {
                 ExtCosts::ripemd160_block => view.ripemd160_block,
                 ExtCosts::ed25519_verify_base => view.ed25519_verify_base,
                 ExtCosts::ed25519_verify_byte => view.ed25519_verify_byte,
+                ExtCosts::rsa2048_verify_base => view.rsa2048_verify_base,
+                ExtCosts::rsa2048_verify_byte => view.rsa2048_verify_byte,
+                ExtCosts::secp256k1_verify_base => view.secp256k1_verify_base,
                 ExtCosts::ecrecover_base => view.ecrecover_base,
                 ExtCosts::log_base => view.log_base,
                 ExtCosts::log_byte => view.log_byte,
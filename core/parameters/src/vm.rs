@@ -183,6 +183,12 @@ pub struct Config {
     /// Enable the `EthAccounts` protocol feature.
     pub eth_accounts: bool,
 
+    /// Enable the host functions added by the `Rsa2048HostFunctions` protocol feature.
+    pub rsa2048_host_functions: bool,
+
+    /// Enable the host functions added by the `Rsa2048Secp256k1Verify` protocol feature.
+    pub rsa2048_secp256k1_verify: bool,
+
     /// Describes limits for VM and Runtime.
     pub limit_config: LimitConfig,
 }
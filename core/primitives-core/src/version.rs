@@ -130,6 +130,25 @@ pub enum ProtocolFeature {
     /// NEP: https://github.com/Utility/UEPs/pull/509
     ChunkValidation,
     EthAccounts,
+    /// Chip keys must be registered with a borsh-serialized `ChipCertificate`.
+    /// Before this feature the certificate was free-form JSON, which remains
+    /// decodable for chips registered earlier. Chip challenges must carry
+    /// args signed by the chip that match its certificate.
+    ChipCertificate,
+    /// Validator rewards are split by a combination of pledge and power instead
+    /// of by pledge alone, see `GenesisConfig::power_reward_rate`.
+    PowerWeightedReward,
+    /// Contracts can register chip keys and create RSA2048 challenges through
+    /// `promise_batch_action_register_rsa2048_keys` and
+    /// `promise_batch_action_create_rsa2048_challenge`.
+    Rsa2048HostFunctions,
+    /// Contracts can verify RSA2048 and secp256k1 signatures through
+    /// `rsa2048_verify` and `secp256k1_verify`.
+    Rsa2048Secp256k1Verify,
+    /// Peers encrypt the connection with the ephemeral keys exchanged in the
+    /// handshake. This isn't a change of the chain protocol, the network
+    /// protocol is just versioned together with it.
+    EncryptedPeerTransport,
 }
 
 impl ProtocolFeature {
@@ -186,6 +205,11 @@ impl ProtocolFeature {
             ProtocolFeature::RejectBlocksWithOutdatedProtocolVersions => 132,
             ProtocolFeature::ChunkValidation => 137,
             ProtocolFeature::EthAccounts => 138,
+            ProtocolFeature::ChipCertificate => 139,
+            ProtocolFeature::PowerWeightedReward => 140,
+            ProtocolFeature::Rsa2048HostFunctions => 141,
+            ProtocolFeature::Rsa2048Secp256k1Verify => 142,
+            ProtocolFeature::EncryptedPeerTransport => 143,
         }
     }
 }
@@ -198,7 +222,7 @@ const STABLE_PROTOCOL_VERSION: ProtocolVersion = 64;
 /// Largest protocol version supported by the current binary.
 pub const PROTOCOL_VERSION: ProtocolVersion = if cfg!(feature = "nightly_protocol") {
     // On nightly, pick big enough version to support all features.
-    139
+    144
 } else {
     // Enable all stable features.
     STABLE_PROTOCOL_VERSION
@@ -1,4 +1,5 @@
 pub mod delegate;
+pub mod rsa2048;
 
 use borsh::{BorshDeserialize, BorshSerialize};
 use serde_with::base64::Base64;
@@ -152,6 +153,19 @@ pub struct TransferAction {
     pub deposit: Balance,
 }
 
+/// Operation performed by a `RegisterRsa2048KeysAction`.
+///
+/// The action carries it as the raw `operation_type` byte, see
+/// `RegisterRsa2048KeysAction::operation`.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[repr(u8)]
+pub enum Rsa2048KeysOperation {
+    /// Registers an unclaimed chip key under the registrar account.
+    AddKeys = 0,
+    /// Revokes a chip key, whether it is still unclaimed or already claimed by a miner.
+    DeleteKeys = 1,
+}
+
 #[serde_as]
 #[derive(
     BorshSerialize, BorshDeserialize, serde::Serialize, serde::Deserialize, PartialEq, Eq, Clone,
@@ -160,13 +174,27 @@ pub struct RegisterRsa2048KeysAction {
     /// this only can be used by the owner of root account
     /// Public key used to sign this rsa keys action.
     pub public_key: PublicKey,
-    /// addkeys or deletekeys
+    /// addkeys or deletekeys, see `Rsa2048KeysOperation`
     pub operation_type: u8,
     /// attach args such as Miner id, sequence number，power，etc.
     #[serde_as(as = "Base64")]
     pub args: Vec<u8>,
 }
 
+impl RegisterRsa2048KeysAction {
+    /// Typed `operation_type`, `None` if it is neither `0` nor `1`.
+    ///
+    /// Before `ProtocolFeature::ChipCertificate` every value added keys, and since then
+    /// action validation rejects values that aren't an operation.
+    pub fn operation(&self) -> Option<Rsa2048KeysOperation> {
+        match self.operation_type {
+            0 => Some(Rsa2048KeysOperation::AddKeys),
+            1 => Some(Rsa2048KeysOperation::DeleteKeys),
+            _ => None,
+        }
+    }
+}
+
 impl fmt::Debug for RegisterRsa2048KeysAction {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("RegisterRsa2048KeysAction")
@@ -314,3 +342,41 @@ impl From<CreateRsa2048ChallengeAction> for Action {
         Self::CreateRsa2048Challenge(Box::new(create_rsa2048_challenge_action))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use unc_crypto::KeyType;
+
+    #[test]
+    fn test_register_rsa2048_keys_operation_type_layout() {
+        let public_key = PublicKey::empty(KeyType::RSA2048);
+        // Encoding of the action from before `Rsa2048KeysOperation` existed.
+        let encode = |operation_type: u8| {
+            let mut bytes = borsh::to_vec(&public_key).unwrap();
+            bytes.push(operation_type);
+            bytes.extend(borsh::to_vec(&vec![1u8, 2, 3]).unwrap());
+            bytes
+        };
+
+        for (operation_type, operation) in [
+            (0, Some(Rsa2048KeysOperation::AddKeys)),
+            (1, Some(Rsa2048KeysOperation::DeleteKeys)),
+            (7, None),
+        ] {
+            let bytes = encode(operation_type);
+            let action = RegisterRsa2048KeysAction::try_from_slice(&bytes).unwrap();
+            assert_eq!(action.operation_type, operation_type);
+            assert_eq!(action.operation(), operation);
+            assert_eq!(borsh::to_vec(&action).unwrap(), bytes);
+        }
+
+        let action = RegisterRsa2048KeysAction {
+            public_key,
+            operation_type: Rsa2048KeysOperation::DeleteKeys as u8,
+            args: vec![],
+        };
+        let json = serde_json::to_value(&action).unwrap();
+        assert_eq!(json["operation_type"], serde_json::json!(1));
+    }
+}
@@ -0,0 +1,230 @@
+//! Structured arguments carried by RSA2048 chip actions.
+//!
+//! A chip is registered by the foundation together with a `ChipCertificate`.
+//! A chip challenge is only accepted when its arguments are signed by the
+//! chip's registered RSA2048 key and match that certificate.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+use unc_crypto::{PublicKey, Signature};
+use unc_primitives_core::hash::{hash, CryptoHash};
+use unc_primitives_core::serialize::dec_format;
+use unc_primitives_core::types::{AccountId, Nonce, Power};
+
+/// The message a chip signs in order to be claimed by a miner.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, PartialEq, Eq, Clone, Debug)]
+pub struct Rsa2048ChallengeMessage {
+    /// Account that claims the chip and receives its power.
+    pub miner_id: AccountId,
+    /// Serial number of the chip.
+    pub sn: String,
+    /// Arbitrary value chosen by the miner to make the signed message unique.
+    pub nonce: Nonce,
+    /// Power attached to the chip by its registration.
+    #[serde(with = "dec_format")]
+    pub power: Power,
+}
+
+impl Rsa2048ChallengeMessage {
+    /// Hash of the borsh-serialized message. This is what the chip signs.
+    pub fn get_hash(&self) -> CryptoHash {
+        hash(&borsh::to_vec(self).expect("Failed to serialize"))
+    }
+}
+
+/// JSON payload of `CreateRsa2048ChallengeAction::args`.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Debug)]
+pub struct Rsa2048ChallengeArgs {
+    #[serde(flatten)]
+    pub message: Rsa2048ChallengeMessage,
+    /// Signature of `message` by the registered RSA2048 chip key.
+    #[serde(default)]
+    pub signature: Option<Signature>,
+}
+
+impl Rsa2048ChallengeArgs {
+    pub fn try_from_slice(args: &[u8]) -> Result<Self, serde_json::Error> {
+        serde_json::from_slice(args)
+    }
+
+    /// Checks that the arguments are signed by the given chip key.
+    /// Unsigned arguments never verify.
+    pub fn verify(&self, public_key: &PublicKey) -> bool {
+        match &self.signature {
+            Some(signature) => signature.verify(self.message.get_hash().as_ref(), public_key),
+            None => false,
+        }
+    }
+}
+
+/// Certificate the foundation attaches to a chip key in
+/// `RegisterRsa2048KeysAction::args`, borsh-serialized.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, PartialEq, Eq, Clone, Debug)]
+pub enum ChipCertificate {
+    V1(ChipCertificateV1),
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, PartialEq, Eq, Clone, Debug)]
+pub struct ChipCertificateV1 {
+    /// Identifier of the miner the chip was shipped to.
+    pub miner_id: String,
+    /// Serial number of the chip.
+    pub sn: String,
+    /// Power the chip contributes once it is claimed.
+    #[serde(with = "dec_format")]
+    pub power: Power,
+    /// Bus the chip is attached to on the mining board.
+    pub bus_id: String,
+    /// Secondary key of the chip.
+    pub p2key: String,
+}
+
+/// Free-form JSON certificate used before `ChipCertificate` was introduced.
+/// `power` was written both as a number and as a string.
+#[derive(Deserialize)]
+struct LegacyChipCertificate {
+    #[serde(default)]
+    miner_id: String,
+    sn: String,
+    #[serde(with = "dec_format")]
+    power: Power,
+    #[serde(default)]
+    bus_id: String,
+    #[serde(default)]
+    p2key: String,
+}
+
+impl From<LegacyChipCertificate> for ChipCertificate {
+    fn from(legacy: LegacyChipCertificate) -> Self {
+        let LegacyChipCertificate { miner_id, sn, power, bus_id, p2key } = legacy;
+        ChipCertificate::V1(ChipCertificateV1 { miner_id, sn, power, bus_id, p2key })
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum ChipCertificateError {
+    #[error("chip certificate is not valid borsh: {0}")]
+    Borsh(std::io::Error),
+    #[error("chip certificate is not valid legacy JSON: {0}")]
+    LegacyJson(serde_json::Error),
+}
+
+impl ChipCertificate {
+    pub fn new(miner_id: String, sn: String, power: Power, bus_id: String, p2key: String) -> Self {
+        ChipCertificate::V1(ChipCertificateV1 { miner_id, sn, power, bus_id, p2key })
+    }
+
+    /// Decodes the certificate from registered args.
+    ///
+    /// Legacy JSON certificates are only accepted when `allow_legacy_json` is
+    /// set, see `ProtocolFeature::ChipCertificate`.
+    pub fn try_from_args(
+        args: &[u8],
+        allow_legacy_json: bool,
+    ) -> Result<Self, ChipCertificateError> {
+        match borsh::from_slice::<ChipCertificate>(args) {
+            Ok(certificate) => Ok(certificate),
+            Err(_) if allow_legacy_json => serde_json::from_slice::<LegacyChipCertificate>(args)
+                .map(Into::into)
+                .map_err(ChipCertificateError::LegacyJson),
+            Err(err) => Err(ChipCertificateError::Borsh(err)),
+        }
+    }
+
+    pub fn to_args(&self) -> Vec<u8> {
+        borsh::to_vec(self).expect("Failed to serialize")
+    }
+
+    pub fn miner_id(&self) -> &str {
+        match self {
+            ChipCertificate::V1(v1) => &v1.miner_id,
+        }
+    }
+
+    pub fn sn(&self) -> &str {
+        match self {
+            ChipCertificate::V1(v1) => &v1.sn,
+        }
+    }
+
+    pub fn power(&self) -> Power {
+        match self {
+            ChipCertificate::V1(v1) => v1.power,
+        }
+    }
+
+    pub fn bus_id(&self) -> &str {
+        match self {
+            ChipCertificate::V1(v1) => &v1.bus_id,
+        }
+    }
+
+    pub fn p2key(&self) -> &str {
+        match self {
+            ChipCertificate::V1(v1) => &v1.p2key,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::chip_certificate;
+    use unc_crypto::{KeyType, SecretKey};
+
+    fn message() -> Rsa2048ChallengeMessage {
+        Rsa2048ChallengeMessage {
+            miner_id: "miner.unc".parse().unwrap(),
+            sn: "sn-0001".to_string(),
+            nonce: 7,
+            power: 100,
+        }
+    }
+
+    #[test]
+    fn test_challenge_args_verify() {
+        let chip_key = SecretKey::from_seed(KeyType::RSA2048, "chip");
+        let other_key = SecretKey::from_seed(KeyType::ED25519, "other");
+        let message = message();
+        let signature = chip_key.sign(message.get_hash().as_ref());
+        let args = Rsa2048ChallengeArgs { message, signature: Some(signature) };
+
+        let encoded = serde_json::to_vec(&args).unwrap();
+        let decoded = Rsa2048ChallengeArgs::try_from_slice(&encoded).unwrap();
+        assert_eq!(decoded, args);
+        assert!(decoded.verify(&chip_key.public_key()));
+        assert!(!decoded.verify(&other_key.public_key()));
+
+        let mut tampered = decoded.clone();
+        tampered.message.power += 1;
+        assert!(!tampered.verify(&chip_key.public_key()));
+
+        let unsigned = Rsa2048ChallengeArgs { signature: None, ..decoded };
+        assert!(!unsigned.verify(&chip_key.public_key()));
+    }
+
+    #[test]
+    fn test_chip_certificate_borsh_roundtrip() {
+        let certificate = chip_certificate("miner", "sn-0001", 100);
+        let args = certificate.to_args();
+        assert_eq!(ChipCertificate::try_from_args(&args, false).unwrap(), certificate);
+        assert_eq!(ChipCertificate::try_from_args(&args, true).unwrap(), certificate);
+    }
+
+    #[test]
+    fn test_chip_certificate_legacy_json() {
+        let from_str = br#"{"miner_id":"m","sn":"a","power":"12","bus_id":"b","p2key":"p"}"#;
+        let from_num = br#"{"miner_id":"m","sn":"a","power":12,"bus_id":"b","p2key":"p"}"#;
+        let expected = ChipCertificate::new(
+            "m".to_string(),
+            "a".to_string(),
+            12,
+            "b".to_string(),
+            "p".to_string(),
+        );
+        assert_eq!(ChipCertificate::try_from_args(from_str, true).unwrap(), expected);
+        assert_eq!(ChipCertificate::try_from_args(from_num, true).unwrap(), expected);
+        assert!(ChipCertificate::try_from_args(from_str, false).is_err());
+        assert!(ChipCertificate::try_from_args(br#"{"sn":"a"}"#, true).is_err());
+    }
+}
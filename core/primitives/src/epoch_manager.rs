@@ -1145,6 +1145,33 @@ impl BlockInfoV1 {
 )]
 pub struct ValidatorWeight(ValidatorId, u64);
 
+/// Split of the validator reward of an epoch between pledge and power.
+///
+/// Every validator gets `pledge` parts of its pledge share and `power` parts of its power share
+/// of the reward, out of `pledge + power` parts.
+#[derive(
+    BorshSerialize,
+    BorshDeserialize,
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub struct RewardWeights {
+    pub pledge: u32,
+    pub power: u32,
+}
+
+impl Default for RewardWeights {
+    /// Split by pledge alone, as before `PowerWeightedReward`.
+    fn default() -> Self {
+        Self { pledge: 1, power: 0 }
+    }
+}
+
 pub mod epoch_info {
     use crate::epoch_manager::ValidatorWeight;
     use crate::types::validator_power::ValidatorPower;
@@ -1175,6 +1202,7 @@ pub mod epoch_info {
     };
 
     pub use super::EpochInfoV1;
+    use super::RewardWeights;
 
     /// Information per epoch.
     #[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq, serde::Serialize)]
@@ -1183,6 +1211,7 @@ pub mod epoch_info {
         V2(EpochInfoV2),
         V3(EpochInfoV3),
         V4(EpochInfoV4),
+        V5(EpochInfoV5),
     }
 
     impl Default for EpochInfo {
@@ -1308,6 +1337,44 @@ pub mod epoch_info {
         validator_mandates: ValidatorMandates,
     }
 
+    // V4 -> V5: Record the weights the validator reward was split with.
+    #[derive(
+        SmartDefault,
+        BorshSerialize,
+        BorshDeserialize,
+        Clone,
+        Debug,
+        PartialEq,
+        Eq,
+        serde::Serialize,
+    )]
+    pub struct EpochInfoV5 {
+        pub epoch_height: EpochHeight,
+        pub validators: Vec<ValidatorPowerAndPledge>,
+        pub validator_to_index: HashMap<AccountId, ValidatorId>,
+        pub block_producers_settlement: Vec<ValidatorId>,
+        pub chunk_producers_settlement: Vec<Vec<ValidatorId>>,
+        pub hidden_validators_settlement: Vec<ValidatorWeight>,
+        pub fishermen: Vec<ValidatorPowerAndPledge>,
+        pub fishermen_to_index: HashMap<AccountId, ValidatorId>,
+        pub power_change: BTreeMap<AccountId, Power>,
+        pub pledge_change: BTreeMap<AccountId, Balance>,
+        pub validator_reward: HashMap<AccountId, Balance>,
+        /// Weights `validator_reward` was split with.
+        pub reward_weights: RewardWeights,
+        pub validator_kickout: HashMap<AccountId, ValidatorKickoutReason>,
+        pub minted_amount: Balance,
+        pub seat_price: Balance,
+        #[default(PROTOCOL_VERSION)]
+        pub protocol_version: ProtocolVersion,
+        // stuff for selecting validators at each height
+        rng_seed: RngSeed,
+        block_producers_sampler: WeightedIndex,
+        chunk_producers_sampler: Vec<WeightedIndex>,
+        /// Contains the epoch's validator mandates. Used to sample chunk validators.
+        validator_mandates: ValidatorMandates,
+    }
+
     impl EpochInfo {
         pub fn new(
             epoch_height: EpochHeight,
@@ -1321,6 +1388,7 @@ pub mod epoch_info {
             power_change: BTreeMap<AccountId, Power>,
             pledge_change: BTreeMap<AccountId, Balance>,
             validator_reward: HashMap<AccountId, Balance>,
+            reward_weights: RewardWeights,
             validator_kickout: HashMap<AccountId, ValidatorKickoutReason>,
             minted_amount: Balance,
             seat_price: Balance,
@@ -1340,7 +1408,30 @@ pub mod epoch_info {
                 let block_producers_sampler = power_weights(&block_producers_settlement);
                 let chunk_producers_sampler =
                     chunk_producers_settlement.iter().map(|vs| power_weights(vs)).collect();
-                if checked_feature!("stable", ChunkValidation, protocol_version) {
+                if checked_feature!("stable", PowerWeightedReward, protocol_version) {
+                    Self::V5(EpochInfoV5 {
+                        epoch_height,
+                        validators,
+                        fishermen,
+                        validator_to_index,
+                        block_producers_settlement,
+                        chunk_producers_settlement,
+                        hidden_validators_settlement,
+                        power_change,
+                        pledge_change,
+                        validator_reward,
+                        reward_weights,
+                        validator_kickout,
+                        fishermen_to_index,
+                        minted_amount,
+                        seat_price,
+                        protocol_version,
+                        rng_seed,
+                        block_producers_sampler,
+                        chunk_producers_sampler,
+                        validator_mandates,
+                    })
+                } else if checked_feature!("stable", ChunkValidation, protocol_version) {
                     Self::V4(EpochInfoV4 {
                         epoch_height,
                         validators,
@@ -1449,6 +1540,7 @@ pub mod epoch_info {
                 Self::V2(v2) => &mut v2.epoch_height,
                 Self::V3(v3) => &mut v3.epoch_height,
                 Self::V4(v4) => &mut v4.epoch_height,
+                Self::V5(v5) => &mut v5.epoch_height,
             }
         }
 
@@ -1459,6 +1551,7 @@ pub mod epoch_info {
                 Self::V2(v2) => v2.epoch_height,
                 Self::V3(v3) => v3.epoch_height,
                 Self::V4(v4) => v4.epoch_height,
+                Self::V5(v5) => v5.epoch_height,
             }
         }
 
@@ -1469,6 +1562,7 @@ pub mod epoch_info {
                 Self::V2(v2) => v2.seat_price,
                 Self::V3(v3) => v3.seat_price,
                 Self::V4(v4) => v4.seat_price,
+                Self::V5(v5) => v5.seat_price,
             }
         }
 
@@ -1479,6 +1573,7 @@ pub mod epoch_info {
                 Self::V2(v2) => v2.minted_amount,
                 Self::V3(v3) => v3.minted_amount,
                 Self::V4(v4) => v4.minted_amount,
+                Self::V5(v5) => v5.minted_amount,
             }
         }
 
@@ -1489,6 +1584,7 @@ pub mod epoch_info {
                 Self::V2(v2) => &v2.block_producers_settlement,
                 Self::V3(v3) => &v3.block_producers_settlement,
                 Self::V4(v4) => &v4.block_producers_settlement,
+                Self::V5(v5) => &v5.block_producers_settlement,
             }
         }
 
@@ -1499,6 +1595,7 @@ pub mod epoch_info {
                 Self::V2(v2) => &v2.chunk_producers_settlement,
                 Self::V3(v3) => &v3.chunk_producers_settlement,
                 Self::V4(v4) => &v4.chunk_producers_settlement,
+                Self::V5(v5) => &v5.chunk_producers_settlement,
             }
         }
 
@@ -1509,6 +1606,7 @@ pub mod epoch_info {
                 Self::V2(v2) => &v2.validator_kickout,
                 Self::V3(v3) => &v3.validator_kickout,
                 Self::V4(v4) => &v4.validator_kickout,
+                Self::V5(v5) => &v5.validator_kickout,
             }
         }
 
@@ -1519,6 +1617,7 @@ pub mod epoch_info {
                 Self::V2(v2) => v2.protocol_version,
                 Self::V3(v3) => v3.protocol_version,
                 Self::V4(v4) => v4.protocol_version,
+                Self::V5(v5) => v5.protocol_version,
             }
         }
 
@@ -1529,6 +1628,7 @@ pub mod epoch_info {
                 Self::V2(v2) => &v2.pledge_change,
                 Self::V3(v3) => &v3.pledge_change,
                 Self::V4(v4) => &v4.pledge_change,
+                Self::V5(v5) => &v5.pledge_change,
             }
         }
 
@@ -1539,6 +1639,7 @@ pub mod epoch_info {
                 Self::V2(v2) => &v2.power_change,
                 Self::V3(v3) => &v3.power_change,
                 Self::V4(v4) => &v4.power_change,
+                Self::V5(v5) => &v5.power_change,
             }
         }
 
@@ -1549,6 +1650,17 @@ pub mod epoch_info {
                 Self::V2(v2) => &v2.validator_reward,
                 Self::V3(v3) => &v3.validator_reward,
                 Self::V4(v4) => &v4.validator_reward,
+                Self::V5(v5) => &v5.validator_reward,
+            }
+        }
+
+        /// Weights `validator_reward` was split with. Epoch infos from before
+        /// `PowerWeightedReward` carry rewards split by pledge alone.
+        #[inline]
+        pub fn reward_weights(&self) -> RewardWeights {
+            match self {
+                Self::V1(_) | Self::V2(_) | Self::V3(_) | Self::V4(_) => RewardWeights::default(),
+                Self::V5(v5) => v5.reward_weights,
             }
         }
 
@@ -1559,6 +1671,7 @@ pub mod epoch_info {
                 Self::V2(v2) => ValidatorPowerAndPledgeIter::new(&v2.validators),
                 Self::V3(v3) => ValidatorPowerAndPledgeIter::new(&v3.validators),
                 Self::V4(v4) => ValidatorPowerAndPledgeIter::new(&v4.validators),
+                Self::V5(v5) => ValidatorPowerAndPledgeIter::new(&v5.validators),
             }
         }
 
@@ -1569,6 +1682,7 @@ pub mod epoch_info {
                 Self::V2(v2) => ValidatorPowerAndPledgeIter::new(&v2.fishermen),
                 Self::V3(v3) => ValidatorPowerAndPledgeIter::new(&v3.fishermen),
                 Self::V4(v4) => ValidatorPowerAndPledgeIter::new(&v4.fishermen),
+                Self::V5(v5) => ValidatorPowerAndPledgeIter::new(&v5.fishermen),
             }
         }
 
@@ -1579,6 +1693,7 @@ pub mod epoch_info {
                 Self::V2(v2) => v2.validators[validator_id as usize].power(),
                 Self::V3(v3) => v3.validators[validator_id as usize].power(),
                 Self::V4(v4) => v4.validators[validator_id as usize].power(),
+                Self::V5(v5) => v5.validators[validator_id as usize].power(),
             }
         }
 
@@ -1589,6 +1704,7 @@ pub mod epoch_info {
                 Self::V2(v2) => v2.validators[validator_id as usize].pledge(),
                 Self::V3(v3) => v3.validators[validator_id as usize].pledge(),
                 Self::V4(v4) => v4.validators[validator_id as usize].pledge(),
+                Self::V5(v5) => v5.validators[validator_id as usize].pledge(),
             }
         }
 
@@ -1599,6 +1715,7 @@ pub mod epoch_info {
                 Self::V2(v2) => v2.validators[validator_id as usize].account_id(),
                 Self::V3(v3) => v3.validators[validator_id as usize].account_id(),
                 Self::V4(v4) => v4.validators[validator_id as usize].account_id(),
+                Self::V5(v5) => v5.validators[validator_id as usize].account_id(),
             }
         }
 
@@ -1609,6 +1726,7 @@ pub mod epoch_info {
                 Self::V2(v2) => v2.validator_to_index.contains_key(account_id),
                 Self::V3(v3) => v3.validator_to_index.contains_key(account_id),
                 Self::V4(v4) => v4.validator_to_index.contains_key(account_id),
+                Self::V5(v5) => v5.validator_to_index.contains_key(account_id),
             }
         }
 
@@ -1618,6 +1736,7 @@ pub mod epoch_info {
                 Self::V2(v2) => v2.validator_to_index.get(account_id),
                 Self::V3(v3) => v3.validator_to_index.get(account_id),
                 Self::V4(v4) => v4.validator_to_index.get(account_id),
+                Self::V5(v5) => v5.validator_to_index.get(account_id),
             }
         }
 
@@ -1641,6 +1760,10 @@ pub mod epoch_info {
                     .validator_to_index
                     .get(account_id)
                     .map(|validator_id| v4.validators[*validator_id as usize].clone()),
+                Self::V5(v5) => v5
+                    .validator_to_index
+                    .get(account_id)
+                    .map(|validator_id| v5.validators[*validator_id as usize].clone()),
             }
         }
 
@@ -1653,6 +1776,7 @@ pub mod epoch_info {
                 Self::V2(v2) => v2.validators[validator_id as usize].clone(),
                 Self::V3(v3) => v3.validators[validator_id as usize].clone(),
                 Self::V4(v4) => v4.validators[validator_id as usize].clone(),
+                Self::V5(v5) => v5.validators[validator_id as usize].clone(),
             }
         }
 
@@ -1663,6 +1787,7 @@ pub mod epoch_info {
                 Self::V2(v2) => v2.fishermen_to_index.contains_key(account_id),
                 Self::V3(v3) => v3.fishermen_to_index.contains_key(account_id),
                 Self::V4(v4) => v4.fishermen_to_index.contains_key(account_id),
+                Self::V5(v5) => v5.fishermen_to_index.contains_key(account_id),
             }
         }
 
@@ -1686,6 +1811,10 @@ pub mod epoch_info {
                     .fishermen_to_index
                     .get(account_id)
                     .map(|validator_id| v4.fishermen[*validator_id as usize].clone()),
+                Self::V5(v5) => v5
+                    .fishermen_to_index
+                    .get(account_id)
+                    .map(|validator_id| v5.fishermen[*validator_id as usize].clone()),
             }
         }
 
@@ -1698,6 +1827,7 @@ pub mod epoch_info {
                 Self::V2(v2) => v2.fishermen[fisherman_id as usize].clone(),
                 Self::V3(v3) => v3.fishermen[fisherman_id as usize].clone(),
                 Self::V4(v4) => v4.fishermen[fisherman_id as usize].clone(),
+                Self::V5(v5) => v5.fishermen[fisherman_id as usize].clone(),
             }
         }
 
@@ -1708,6 +1838,7 @@ pub mod epoch_info {
                 Self::V2(v2) => v2.validators.len(),
                 Self::V3(v3) => v3.validators.len(),
                 Self::V4(v4) => v4.validators.len(),
+                Self::V5(v5) => v5.validators.len(),
             }
         }
 
@@ -1733,6 +1864,10 @@ pub mod epoch_info {
                     let seed = Self::block_produce_seed(height, &v4.rng_seed);
                     v4.block_producers_settlement[v4.block_producers_sampler.sample(seed)]
                 }
+                Self::V5(v5) => {
+                    let seed = Self::block_produce_seed(height, &v5.rng_seed);
+                    v5.block_producers_settlement[v5.block_producers_sampler.sample(seed)]
+                }
             }
         }
 
@@ -1764,6 +1899,14 @@ pub mod epoch_info {
                     let sample = v4.chunk_producers_sampler[shard_id].sample(seed);
                     v4.chunk_producers_settlement[shard_id][sample]
                 }
+                Self::V5(v5) => {
+                    let protocol_version = self.protocol_version();
+                    let seed =
+                        Self::chunk_produce_seed(protocol_version, &v5.rng_seed, height, shard_id);
+                    let shard_id = shard_id as usize;
+                    let sample = v5.chunk_producers_sampler[shard_id].sample(seed);
+                    v5.chunk_producers_settlement[shard_id][sample]
+                }
             }
         }
 
@@ -1777,6 +1920,10 @@ pub mod epoch_info {
                     let mut rng = Self::chunk_validate_rng(&v4.rng_seed, height);
                     v4.validator_mandates.sample(&mut rng)
                 }
+                Self::V5(v5) => {
+                    let mut rng = Self::chunk_validate_rng(&v5.rng_seed, height);
+                    v5.validator_mandates.sample(&mut rng)
+                }
             }
         }
 
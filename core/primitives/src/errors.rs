@@ -176,6 +176,8 @@ pub enum InvalidTxError {
     ActionsValidation(ActionsValidationError),
     /// The size of serialized transaction exceeded the limit.
     TransactionSizeExceeded { size: u64, limit: u64 },
+    /// Only chip registrars can sign transactions that register or revoke RSA2048 chip keys.
+    ChipRegistrarRequired { signer_id: AccountId },
 }
 
 impl std::error::Error for InvalidTxError {}
@@ -259,6 +261,10 @@ pub enum ActionsValidationError {
     /// `ProtocolFeature` here because we don't want to leak the internals of
     /// that type into observable borsh serialization.
     UnsupportedProtocolFeature { protocol_feature: String, version: ProtocolVersion },
+    /// The args of a RegisterRsa2048Keys action are not a valid chip certificate.
+    InvalidChipCertificate { public_key: Box<PublicKey> },
+    /// The `operation_type` of a RegisterRsa2048Keys action is neither add nor delete.
+    UnknownRsa2048KeysOperation { operation_type: u8 },
 }
 
 /// Describes the error for validating a receipt.
@@ -393,6 +399,16 @@ impl Display for ActionsValidationError {
                     protocol_feature,
                     version,
             ),
+            ActionsValidationError::InvalidChipCertificate { public_key } => write!(
+                f,
+                "The args of rsa key {} are not a valid chip certificate",
+                public_key,
+            ),
+            ActionsValidationError::UnknownRsa2048KeysOperation { operation_type } => write!(
+                f,
+                "The rsa keys operation type {} is neither add (0) nor delete (1)",
+                operation_type,
+            ),
         }
     }
 }
@@ -504,6 +520,16 @@ pub enum ActionErrorKind {
 
     /// The public key used for an  not existed  rsa key
     RsaKeysNotFound { account_id: AccountId, public_key: Box<PublicKey> },
+    /// The registered args of the rsa key can't be parsed
+    Rsa2048KeysArgsMalformed { account_id: AccountId, public_key: Box<PublicKey> },
+    /// The args of a rsa challenge can't be parsed
+    Rsa2048ChallengeArgsMalformed { account_id: AccountId, public_key: Box<PublicKey> },
+    /// The args of a rsa challenge don't carry a signature
+    Rsa2048ChallengeNotSigned { account_id: AccountId, public_key: Box<PublicKey> },
+    /// The signature of a rsa challenge doesn't match the registered rsa key
+    Rsa2048ChallengeInvalidSignature { account_id: AccountId, public_key: Box<PublicKey> },
+    /// The args of a rsa challenge don't match the miner or the registered rsa key
+    Rsa2048ChallengeArgsMismatch { account_id: AccountId, public_key: Box<PublicKey> },
 }
 
 impl From<ActionErrorKind> for ActionError {
@@ -565,6 +591,9 @@ impl Display for InvalidTxError {
             InvalidTxError::TransactionSizeExceeded { size, limit } => {
                 write!(f, "Size of serialized transaction {} exceeded the limit {}", size, limit)
             }
+            InvalidTxError::ChipRegistrarRequired { signer_id } => {
+                write!(f, "Signer {} is not allowed to register or revoke chip keys", signer_id)
+            }
         }
     }
 }
@@ -828,6 +857,31 @@ impl Display for ActionErrorKind {
                 "The public key {:?} is doesn't exist rsa key",
                 public_key
             ),
+            ActionErrorKind::Rsa2048KeysArgsMalformed { public_key, .. } => write!(
+                f,
+                "The registered args of rsa key {:?} are malformed",
+                public_key
+            ),
+            ActionErrorKind::Rsa2048ChallengeArgsMalformed { public_key, .. } => write!(
+                f,
+                "The challenge args for rsa key {:?} are malformed",
+                public_key
+            ),
+            ActionErrorKind::Rsa2048ChallengeNotSigned { public_key, .. } => write!(
+                f,
+                "The challenge args for rsa key {:?} are not signed",
+                public_key
+            ),
+            ActionErrorKind::Rsa2048ChallengeInvalidSignature { public_key, .. } => write!(
+                f,
+                "The challenge args are not signed with the rsa key {:?}",
+                public_key
+            ),
+            ActionErrorKind::Rsa2048ChallengeArgsMismatch { account_id, public_key } => write!(
+                f,
+                "The challenge args for rsa key {:?} don't match miner {:?} or the registered args",
+                public_key, account_id
+            ),
         }
     }
 }
@@ -1219,6 +1273,15 @@ pub enum HostError {
     /// Invalid input to ed25519 signature verification function (e.g. signature cannot be
     /// derived from bytes).
     Ed25519VerifyInvalidInput { msg: String },
+    /// `operation_type` passed to `promise_batch_action_register_rsa2048_keys` is neither add
+    /// nor delete.
+    InvalidRsa2048KeysOperation { operation_type: u64 },
+    /// Invalid input to RSA2048 signature verification function (e.g. signature or public key
+    /// of a wrong length).
+    Rsa2048VerifyInvalidInput { msg: String },
+    /// Invalid input to secp256k1 signature verification function (e.g. message which isn't a
+    /// 32 bytes hash).
+    Secp256k1VerifyInvalidInput { msg: String },
 }
 
 #[derive(
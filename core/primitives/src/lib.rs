@@ -15,6 +15,7 @@ pub mod epoch_sync;
 pub mod errors;
 pub mod merkle;
 pub mod network;
+pub mod producer_selection;
 pub mod rand;
 pub mod receipt;
 pub mod runtime;
@@ -0,0 +1,248 @@
+//! Power weighted block producer selection driven by the block VRF output.
+//!
+//! The epoch manager picks a producer by interpreting the block random value as a big-endian
+//! integer, reducing it modulo the total power of the block validators and walking the
+//! validators in order until the cumulative power exceeds that target. The same routine is
+//! exposed here so that light clients can replay the choice from a
+//! [`ProducerSelectionView`] without access to the epoch manager.
+//!
+//! Replaying only checks that the data is self-consistent. The VRF output is authenticated by
+//! the producer key, but the power table is taken as given since no header commits to it.
+use crate::hash::{hash, CryptoHash};
+use crate::types::AccountId;
+use crate::views::ProducerSelectionView;
+use primitive_types::U256;
+use unc_crypto::key_conversion::convert_public_key;
+use unc_crypto::PublicKey;
+use unc_primitives_core::types::Power;
+
+/// Outcome of the cumulative power walk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProducerSelection {
+    /// Position of the selected validator in the power table.
+    pub index: usize,
+    /// Sum of the power of all validators in the table.
+    pub total_power: U256,
+    /// `random_value % total_power`, the point on the cumulative power line that was hit.
+    pub target: U256,
+}
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum ProducerSelectionError {
+    #[error("Total power of the validator table is zero")]
+    ZeroTotalPower,
+    #[error("Random value is not the hash of the VRF value")]
+    RandomValueMismatch,
+    #[error("Producer key of type {0} can not be used to verify a VRF output")]
+    UnsupportedVrfKey(String),
+    #[error("VRF proof does not verify against the parent block producer key")]
+    InvalidVrfProof,
+    #[error("Claimed total power {claimed} does not match the power table sum {computed}")]
+    TotalPowerMismatch { claimed: U256, computed: U256 },
+    #[error("Claimed target {claimed} does not match the recomputed target {computed}")]
+    TargetMismatch { claimed: U256, computed: U256 },
+    #[error("Claimed producer {claimed} does not match the recomputed producer {computed}")]
+    ProducerMismatch { claimed: AccountId, computed: AccountId },
+    #[error("Block author {author} is not the selected producer {selected}")]
+    AuthorNotSelected { author: AccountId, selected: AccountId },
+}
+
+/// Chooses a validator out of `powers` using `random_value`.
+pub fn select_producer<I>(
+    powers: I,
+    random_value: &CryptoHash,
+) -> Result<ProducerSelection, ProducerSelectionError>
+where
+    I: IntoIterator<Item = Power>,
+    I::IntoIter: Clone,
+{
+    let powers = powers.into_iter();
+    let total_power =
+        powers.clone().fold(U256::zero(), |total, power| total.saturating_add(U256::from(power)));
+    if total_power.is_zero() {
+        return Err(ProducerSelectionError::ZeroTotalPower);
+    }
+
+    let target = U256::from_big_endian(random_value.as_ref()) % total_power;
+    let mut cumulative_power = U256::zero();
+    for (index, power) in powers.enumerate() {
+        cumulative_power = cumulative_power.saturating_add(U256::from(power));
+        if target < cumulative_power {
+            return Ok(ProducerSelection { index, total_power, target });
+        }
+    }
+    unreachable!("target is always below the total power")
+}
+
+/// Replays every step of a producer selection.
+///
+/// Checks that `random_value` is derived from the VRF value, that the VRF output was produced
+/// by the parent block producer over the VRF input, that the power walk over the provided table
+/// lands on the claimed target and producer, and that this producer authored the block.
+pub fn replay_producer_selection(
+    view: &ProducerSelectionView,
+) -> Result<(), ProducerSelectionError> {
+    if hash(&view.vrf_value.0) != view.random_value {
+        return Err(ProducerSelectionError::RandomValueMismatch);
+    }
+
+    let vrf_key = match &view.vrf_public_key {
+        PublicKey::ED25519(key) => convert_public_key(key),
+        _ => None,
+    }
+    .ok_or_else(|| {
+        ProducerSelectionError::UnsupportedVrfKey(view.vrf_public_key.key_type().to_string())
+    })?;
+    if !vrf_key.is_vrf_valid(&view.vrf_input.as_ref(), &view.vrf_value, &view.vrf_proof) {
+        return Err(ProducerSelectionError::InvalidVrfProof);
+    }
+
+    let selection = select_producer(view.validators.iter().map(|v| v.power()), &view.random_value)?;
+    if selection.total_power != view.total_power {
+        return Err(ProducerSelectionError::TotalPowerMismatch {
+            claimed: view.total_power,
+            computed: selection.total_power,
+        });
+    }
+    if selection.target != view.target {
+        return Err(ProducerSelectionError::TargetMismatch {
+            claimed: view.target,
+            computed: selection.target,
+        });
+    }
+    let selected = view.validators[selection.index].account_id();
+    if selected != &view.selected {
+        return Err(ProducerSelectionError::ProducerMismatch {
+            claimed: view.selected.clone(),
+            computed: selected.clone(),
+        });
+    }
+    if selected != &view.author {
+        return Err(ProducerSelectionError::AuthorNotSelected {
+            author: view.author.clone(),
+            selected: selected.clone(),
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::validator_power::ValidatorPower;
+    use crate::views::validator_power_view::ValidatorPowerView;
+    use unc_crypto::{KeyType, SecretKey};
+
+    fn random_value_for(value: u64) -> CryptoHash {
+        let mut bytes = [0u8; 32];
+        bytes[24..].copy_from_slice(&value.to_be_bytes());
+        CryptoHash(bytes)
+    }
+
+    #[test]
+    fn test_select_producer_walks_cumulative_power() {
+        let powers = [10, 0, 30, 60];
+        let pick = |value| select_producer(powers, &random_value_for(value)).unwrap();
+        assert_eq!(pick(0).index, 0);
+        assert_eq!(pick(9).index, 0);
+        assert_eq!(pick(10).index, 2);
+        assert_eq!(pick(39).index, 2);
+        assert_eq!(pick(40).index, 3);
+        assert_eq!(pick(99).index, 3);
+        let wrapped = pick(145);
+        assert_eq!(wrapped.index, 3);
+        assert_eq!(wrapped.target, U256::from(45));
+        assert_eq!(wrapped.total_power, U256::from(100));
+    }
+
+    #[test]
+    fn test_select_producer_zero_power() {
+        assert_eq!(
+            select_producer([0, 0], &random_value_for(1)),
+            Err(ProducerSelectionError::ZeroTotalPower)
+        );
+        assert_eq!(
+            select_producer([], &random_value_for(1)),
+            Err(ProducerSelectionError::ZeroTotalPower)
+        );
+    }
+
+    fn make_selection_view() -> ProducerSelectionView {
+        let vrf_key = SecretKey::from_seed(KeyType::ED25519, "test0");
+        let vrf_input = hash(b"prev");
+        let signer = match &vrf_key {
+            SecretKey::ED25519(key) => unc_crypto::key_conversion::convert_secret_key(key),
+            _ => unreachable!(),
+        };
+        let (vrf_value, vrf_proof) = signer.compute_vrf_with_proof(&vrf_input.as_ref());
+        let random_value = hash(&vrf_value.0);
+        let validators: Vec<ValidatorPowerView> = ["test0", "test1", "test2"]
+            .iter()
+            .zip([5, 7, 11])
+            .map(|(account_id, power)| {
+                ValidatorPower::new_v1(
+                    account_id.parse().unwrap(),
+                    SecretKey::from_seed(KeyType::ED25519, account_id).public_key(),
+                    power,
+                )
+                .into()
+            })
+            .collect();
+        let selection =
+            select_producer(validators.iter().map(|v| v.power()), &random_value).unwrap();
+        let selected = validators[selection.index].account_id().clone();
+        ProducerSelectionView {
+            block_hash: CryptoHash::default(),
+            block_height: 2,
+            author: selected.clone(),
+            prev_block_hash: CryptoHash::default(),
+            vrf_public_key: vrf_key.public_key(),
+            vrf_input,
+            vrf_value,
+            vrf_proof,
+            random_value,
+            selected,
+            validators,
+            total_power: selection.total_power,
+            target: selection.target,
+        }
+    }
+
+    #[test]
+    fn test_replay_producer_selection() {
+        let view = make_selection_view();
+        assert_eq!(replay_producer_selection(&view), Ok(()));
+
+        let mut bad = view.clone();
+        bad.vrf_input = hash(b"other");
+        assert_eq!(replay_producer_selection(&bad), Err(ProducerSelectionError::InvalidVrfProof));
+
+        let mut bad = view.clone();
+        bad.random_value = hash(b"other");
+        assert_eq!(
+            replay_producer_selection(&bad),
+            Err(ProducerSelectionError::RandomValueMismatch)
+        );
+
+        let mut bad = view.clone();
+        bad.target = bad.target + 1;
+        assert!(matches!(
+            replay_producer_selection(&bad),
+            Err(ProducerSelectionError::TargetMismatch { .. })
+        ));
+
+        let mut bad = view.clone();
+        bad.selected = "test9".parse().unwrap();
+        assert!(matches!(
+            replay_producer_selection(&bad),
+            Err(ProducerSelectionError::ProducerMismatch { .. })
+        ));
+
+        let mut bad = view;
+        bad.author = "test9".parse().unwrap();
+        assert!(matches!(
+            replay_producer_selection(&bad),
+            Err(ProducerSelectionError::AuthorNotSelected { .. })
+        ));
+    }
+}
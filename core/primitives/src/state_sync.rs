@@ -6,6 +6,7 @@ use crate::sharding::{
 use crate::types::{BlockHeight, EpochId, ShardId, StateRoot, StateRootNode};
 use borsh::{BorshDeserialize, BorshSerialize};
 use std::sync::Arc;
+use unc_crypto::{PublicKey, SecretKey, Signature};
 use unc_primitives_core::types::EpochHeight;
 
 #[derive(PartialEq, Eq, Clone, Debug, BorshSerialize, BorshDeserialize)]
@@ -278,9 +279,74 @@ pub enum StateSyncDumpProgress {
     },
 }
 
+/// Size and hash of a single state part dumped to external storage.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct StatePartManifestEntry {
+    pub part_id: u64,
+    pub size: u64,
+    pub hash: CryptoHash,
+}
+
+impl StatePartManifestEntry {
+    pub fn new(part_id: u64, state_part: &[u8]) -> Self {
+        Self { part_id, size: state_part.len() as u64, hash: CryptoHash::hash_bytes(state_part) }
+    }
+}
+
+/// Lists all state parts dumped to external storage for an epoch and a shard.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct StatePartsManifest {
+    pub epoch_id: EpochId,
+    pub epoch_height: EpochHeight,
+    pub shard_id: ShardId,
+    /// Block hash of the first block of the epoch.
+    pub sync_hash: CryptoHash,
+    pub state_root: StateRoot,
+    /// Entries ordered by `part_id`, one per part.
+    pub parts: Vec<StatePartManifestEntry>,
+}
+
+impl StatePartsManifest {
+    pub fn num_parts(&self) -> u64 {
+        self.parts.len() as u64
+    }
+
+    pub fn part(&self, part_id: u64) -> Option<&StatePartManifestEntry> {
+        self.parts.get(part_id as usize).filter(|entry| entry.part_id == part_id)
+    }
+}
+
+/// Manifest signed by the key of the node that dumped the state parts.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct SignedStatePartsManifest {
+    pub manifest: StatePartsManifest,
+    pub public_key: PublicKey,
+    /// Signature of the hash of the borsh-serialized `manifest`.
+    pub signature: Signature,
+}
+
+impl SignedStatePartsManifest {
+    pub fn new(manifest: StatePartsManifest, secret_key: &SecretKey) -> Self {
+        let hash = CryptoHash::hash_borsh(&manifest);
+        let signature = secret_key.sign(hash.as_ref());
+        Self { manifest, public_key: secret_key.public_key(), signature }
+    }
+
+    pub fn verify_signature(&self) -> bool {
+        let hash = CryptoHash::hash_borsh(&self.manifest);
+        self.signature.verify(hash.as_ref(), &self.public_key)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::state_sync::{get_num_state_parts, STATE_PART_MEMORY_LIMIT};
+    use crate::hash::CryptoHash;
+    use crate::state_sync::{
+        get_num_state_parts, SignedStatePartsManifest, StatePartManifestEntry, StatePartsManifest,
+        STATE_PART_MEMORY_LIMIT,
+    };
+    use crate::types::EpochId;
+    use unc_crypto::{KeyType, SecretKey};
 
     #[test]
     fn test_get_num_state_parts() {
@@ -291,4 +357,37 @@ mod tests {
         assert_eq!(get_num_state_parts(STATE_PART_MEMORY_LIMIT.as_u64() * 100), 100);
         assert_eq!(get_num_state_parts(STATE_PART_MEMORY_LIMIT.as_u64() * 100 + 1), 101);
     }
+
+    #[test]
+    fn test_signed_state_parts_manifest() {
+        let manifest = StatePartsManifest {
+            epoch_id: EpochId::default(),
+            epoch_height: 1,
+            shard_id: 0,
+            sync_hash: CryptoHash::hash_bytes(b"sync"),
+            state_root: CryptoHash::hash_bytes(b"root"),
+            parts: (0..3)
+                .map(|part_id| StatePartManifestEntry {
+                    part_id,
+                    size: 10,
+                    hash: CryptoHash::hash_bytes(&[part_id as u8]),
+                })
+                .collect(),
+        };
+        assert_eq!(manifest.num_parts(), 3);
+        assert_eq!(manifest.part(1).unwrap().part_id, 1);
+        assert!(manifest.part(3).is_none());
+
+        let secret_key = SecretKey::from_seed(KeyType::ED25519, "node");
+        let signed = SignedStatePartsManifest::new(manifest, &secret_key);
+        assert!(signed.verify_signature());
+
+        let mut tampered = signed.clone();
+        tampered.manifest.parts[2].size = 11;
+        assert!(!tampered.verify_signature());
+
+        let mut resigned = signed;
+        resigned.public_key = SecretKey::from_seed(KeyType::ED25519, "other").public_key();
+        assert!(!resigned.verify_signature());
+    }
 }
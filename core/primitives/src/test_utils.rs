@@ -6,6 +6,7 @@ use unc_primitives_core::account::id::AccountIdRef;
 use unc_primitives_core::types::{Power, ProtocolVersion};
 
 use crate::account::{AccessKey, AccessKeyPermission, Account};
+use crate::action::rsa2048::ChipCertificate;
 use crate::block::Block;
 use crate::block::BlockV3;
 use crate::block_header::BlockHeader;
@@ -551,6 +552,18 @@ impl EpochInfoProvider for MockEpochInfoProvider {
     fn minimum_pledge(&self, _prev_block_hash: &CryptoHash) -> Result<Balance, EpochError> {
         Ok(0)
     }
+
+    fn validator_public_key(
+        &self,
+        _epoch_id: &EpochId,
+        _last_block_hash: &CryptoHash,
+        account_id: &AccountId,
+    ) -> Result<Option<PublicKey>, EpochError> {
+        Ok(self
+            .validators
+            .contains_key(account_id)
+            .then(|| PublicKey::from_seed(KeyType::ED25519, account_id.as_ref())))
+    }
 }
 
 /// Encode array of `u64` to be passed as a smart contract argument.
@@ -560,6 +573,17 @@ pub fn encode(xs: &[u64]) -> Vec<u8> {
 
 // Helper function that creates a new signer for a given account, that uses the account name as seed.
 // Should be used only in tests.
+/// Chip certificate for `sn` with placeholder bus and secondary key.
+pub fn chip_certificate(miner_id: &str, sn: &str, power: Power) -> ChipCertificate {
+    ChipCertificate::new(
+        miner_id.to_string(),
+        sn.to_string(),
+        power,
+        "bus".to_string(),
+        "p2key".to_string(),
+    )
+}
+
 pub fn create_test_signer(account_name: &str) -> InMemoryValidatorSigner {
     InMemoryValidatorSigner::from_seed(
         account_name.parse().unwrap(),
@@ -17,7 +17,7 @@ use unc_vm_runner::{ProfileDataV2, ProfileDataV3};
 pub use crate::action::{
     Action, AddKeyAction, CreateAccountAction, CreateRsa2048ChallengeAction, DeleteAccountAction,
     DeleteKeyAction, DeployContractAction, FunctionCallAction, PledgeAction,
-    RegisterRsa2048KeysAction, TransferAction,
+    RegisterRsa2048KeysAction, Rsa2048KeysOperation, TransferAction,
 };
 
 pub type LogEntry = String;
@@ -48,7 +48,7 @@ pub mod col {
 
     pub const RSA2048_KEY: u8 = 10;
     /// All columns
-    pub const NON_DELAYED_RECEIPT_COLUMNS: [(u8, &str); 8] = [
+    pub const NON_DELAYED_RECEIPT_COLUMNS: [(u8, &str); 9] = [
         (ACCOUNT, "Account"),
         (CONTRACT_CODE, "ContractCode"),
         (ACCESS_KEY, "AccessKey"),
@@ -57,6 +57,7 @@ pub mod col {
         (PENDING_DATA_COUNT, "PendingDataCount"),
         (POSTPONED_RECEIPT, "PostponedReceipt"),
         (CONTRACT_DATA, "ContractData"),
+        (RSA2048_KEY, "Rsa2048Keys"),
     ];
 }
 
@@ -391,6 +392,7 @@ pub mod trie_key_parsers {
                 col::ACCOUNT => parse_account_id_from_account_key(raw_key)?,
                 col::CONTRACT_CODE => parse_account_id_from_contract_code_key(raw_key)?,
                 col::ACCESS_KEY => parse_account_id_from_access_key_key(raw_key)?,
+                col::RSA2048_KEY => parse_account_id_from_rsa_key_key(raw_key)?,
                 _ => parse_account_id_from_trie_key_with_separator(col, raw_key, col_name)?,
             };
             return Ok(Some(account_id));
@@ -582,6 +584,27 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_key_for_rsa2048_keys_consistency() {
+        let public_key = PublicKey::empty(KeyType::ED25519);
+        for account_id in OK_ACCOUNT_IDS.iter().map(|x| x.parse::<AccountId>().unwrap()) {
+            let key = TrieKey::Rsa2048Keys {
+                account_id: account_id.clone(),
+                public_key: public_key.clone(),
+            };
+            let raw_key = key.to_vec();
+            assert_eq!(raw_key.len(), key.len());
+            assert_eq!(
+                trie_key_parsers::parse_trie_key_rsa_key_from_raw_key(&raw_key).unwrap(),
+                key
+            );
+            assert_eq!(
+                trie_key_parsers::parse_account_id_from_raw_key(&raw_key).unwrap().unwrap(),
+                account_id
+            );
+        }
+    }
+
     #[test]
     fn test_key_for_data_consistency() {
         let data_key = b"0123456789" as &[u8];
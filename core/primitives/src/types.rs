@@ -418,14 +418,14 @@ impl StateChanges {
                         |RawStateChange { cause, data }| StateChangeWithCause {
                             cause,
                             value: if let Some(change_data) = data {
-                                StateChangeValue::AccessKeyUpdate {
+                                StateChangeValue::RsaKeyUpdate {
                                     account_id: account_id.clone(),
                                     public_key: public_key.clone(),
-                                    access_key: <_>::try_from_slice(&change_data)
-                                        .expect("Failed to parse internally stored access key"),
+                                    rsa_key: <_>::try_from_slice(&change_data)
+                                        .expect("Failed to parse internally stored rsa key"),
                                 }
                             } else {
-                                StateChangeValue::AccessKeyDeletion {
+                                StateChangeValue::RsaKeyDeletion {
                                     account_id: account_id.clone(),
                                     public_key: public_key.clone(),
                                 }
@@ -1480,6 +1480,15 @@ pub trait EpochInfoProvider {
     ) -> Result<Balance, EpochError>;
 
     fn minimum_pledge(&self, prev_block_hash: &CryptoHash) -> Result<Balance, EpochError>;
+
+    /// Get the public key a validator pledged with in the given epoch.
+    /// If the account is not a validator, returns `None`.
+    fn validator_public_key(
+        &self,
+        epoch_id: &EpochId,
+        last_block_hash: &CryptoHash,
+        account_id: &AccountId,
+    ) -> Result<Option<PublicKey>, EpochError>;
 }
 
 /// Mode of the trie cache.
@@ -13,6 +13,8 @@ use crate::block_header::{
 use crate::block_header::{BlockHeaderInnerRestV4, BlockHeaderV4};
 use crate::challenge::{Challenge, ChallengesResult};
 use crate::checked_feature;
+use crate::epoch_manager::epoch_info::EpochInfo;
+use crate::epoch_manager::RewardWeights;
 use crate::errors::TxExecutionError;
 use crate::hash::{hash, CryptoHash};
 use crate::merkle::{combine_hash, MerklePath};
@@ -36,6 +38,7 @@ use crate::types::{
     ValidatorKickoutReason,
 };
 
+use crate::action::rsa2048::ChipCertificate;
 use crate::action::{CreateRsa2048ChallengeAction, RegisterRsa2048KeysAction};
 use crate::types::validator_power_and_pledge::{
     ValidatorPowerAndPledge, ValidatorPowerAndPledgeIter,
@@ -45,9 +48,10 @@ use crate::views::validator_pledge_view::ValidatorPledgeView;
 use crate::views::validator_power_and_pledge_view::ValidatorPowerAndPledgeView;
 use borsh::{BorshDeserialize, BorshSerialize};
 use chrono::DateTime;
+use primitive_types::U256;
 use serde_with::base64::Base64;
 use serde_with::serde_as;
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::fmt;
 use std::ops::Range;
 use std::sync::Arc;
@@ -288,6 +292,29 @@ impl FromIterator<ChipView> for ChipsList {
     }
 }
 
+/// Number of chips returned by [`QueryRequest::ViewChips`] when no limit is given.
+pub const DEFAULT_CHIPS_PAGE_LIMIT: u32 = 100;
+/// Upper bound on the number of chips returned in a single page.
+pub const MAX_CHIPS_PAGE_LIMIT: u32 = 1000;
+/// Upper bound on the number of trie entries inspected for a single page.
+///
+/// Filters may skip most entries, so without it a filtered query over a large
+/// account would still walk the whole prefix.
+pub const MAX_CHIPS_PAGE_SCAN: u32 = 10_000;
+
+/// A page of chips returned by [`QueryRequest::ViewChips`].
+#[serde_as]
+#[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct ChipsPage {
+    pub chips: Vec<ChipView>,
+    /// Public key to pass as `cursor` to fetch the next page, `None` once the
+    /// whole prefix has been visited.
+    pub next_cursor: Option<PublicKey>,
+    #[serde_as(as = "Vec<Base64>")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub proof: Vec<Arc<[u8]>>,
+}
+
 #[cfg_attr(feature = "deepsize_feature", derive(deepsize::DeepSizeOf))]
 #[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq, Eq, Clone)]
 pub struct KnownPeerStateView {
@@ -297,6 +324,10 @@ pub struct KnownPeerStateView {
     pub first_seen: i64,
     pub last_seen: i64,
     pub last_attempt: Option<(i64, String)>,
+    /// Reputation score of the peer, rounded.
+    pub reputation_score: u64,
+    /// UNIX timestamp until which the peer is banned for its bad reputation.
+    pub banned_until: Option<i64>,
 }
 
 #[cfg_attr(feature = "deepsize_feature", derive(deepsize::DeepSizeOf))]
@@ -327,6 +358,7 @@ pub enum QueryResponseKind {
     AccessKey(AccessKeyView),
     AccessKeyList(AccessKeyList),
     ChipList(ChipsList),
+    ChipsPage(ChipsPage),
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq, Eq, Clone)]
@@ -355,6 +387,23 @@ pub enum QueryRequest {
     ViewChipList {
         account_id: AccountId,
     },
+    /// Chips registered to `account_id`, in public key order.
+    ///
+    /// `miner_id` and `sn` only keep matching chips; `cursor` is the
+    /// `next_cursor` of the previous page.
+    ViewChips {
+        account_id: AccountId,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        miner_id: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        sn: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        cursor: Option<PublicKey>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        limit: Option<u32>,
+        #[serde(default, skip_serializing_if = "is_false")]
+        include_proof: bool,
+    },
     CallFunction {
         account_id: AccountId,
         method_name: String,
@@ -413,6 +462,8 @@ pub struct PeerInfoView {
     pub is_outbound_peer: bool,
     /// Connection nonce.
     pub nonce: u64,
+    /// Reputation score of the peer, rounded. 0 means that the peer behaves well.
+    pub reputation_score: u64,
 }
 
 /// Information about a Producer: its account name, peer_id and a list of connected peers that
@@ -772,6 +823,19 @@ pub struct ChipView {
     pub bus_id: String,
     pub p2key: String,
 }
+
+impl ChipView {
+    pub fn new(public_key: &PublicKey, certificate: &ChipCertificate) -> Self {
+        ChipView {
+            miner_id: certificate.miner_id().to_string(),
+            public_key: public_key.to_string(),
+            power: certificate.power(),
+            sn: certificate.sn().to_string(),
+            bus_id: certificate.bus_id().to_string(),
+            p2key: certificate.p2key().to_string(),
+        }
+    }
+}
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
 pub struct BlockHeaderView {
     pub height: BlockHeight,
@@ -1200,36 +1264,139 @@ impl From<ChunkHeaderView> for ShardChunkHeader {
 pub struct AllMinersView {
     pub total_power: Power,
     pub miners: Vec<ValidatorPowerView>,
+    /// Epoch the role and shard breakdowns are taken from.
+    pub epoch_id: EpochId,
+    pub block_producers: MinerSetView,
+    pub chunk_producers: MinerSetView,
+    pub shards: Vec<ShardMinersView>,
+}
+
+impl AllMinersView {
+    /// Builds the view from `miners` and the producer assignments of `epoch_info`.
+    pub fn new(
+        miners: ValidatorPowerAndPledgeIter,
+        epoch_id: EpochId,
+        epoch_info: &EpochInfo,
+    ) -> Self {
+        let mut total_power = 0;
+        let miners = miners
+            .map(|miner| {
+                total_power += miner.power();
+                ValidatorPowerView::V1(ValidatorPowerViewV1 {
+                    account_id: miner.account_id().clone(),
+                    public_key: miner.public_key().clone(),
+                    power: miner.power(),
+                })
+            })
+            .collect();
+
+        let block_producers = epoch_info
+            .block_producers_settlement()
+            .iter()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .map(|id| epoch_info.get_validator(*id))
+            .collect();
+        let chunk_producers = epoch_info
+            .chunk_producers_settlement()
+            .iter()
+            .flatten()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .map(|id| epoch_info.get_validator(*id))
+            .collect();
+        let shards = epoch_info
+            .chunk_producers_settlement()
+            .iter()
+            .enumerate()
+            .map(|(shard_id, validator_ids)| ShardMinersView {
+                shard_id: shard_id as ShardId,
+                chunk_producers: validator_ids
+                    .iter()
+                    .map(|id| epoch_info.get_validator(*id))
+                    .collect(),
+            })
+            .collect();
+
+        AllMinersView { total_power, miners, epoch_id, block_producers, chunk_producers, shards }
+    }
 }
 
-impl From<ValidatorPowerAndPledgeIter<'_>> for AllMinersView {
-    fn from(iter: ValidatorPowerAndPledgeIter) -> Self {
-        let mut total_power = 0; // Initialize total power
-        let mut miners = Vec::new(); // Initialize the vector to store ValidatorPowerView instances
-
-        // Iterate over each ValidatorPowerAndPledge
-        for validator in iter {
-            match validator {
-                // Assuming we only deal with V1 here, similar logic could apply for other versions
-                ValidatorPowerAndPledge::V1(v) => {
-                    total_power += v.power; // Accumulate the total power
-                                            // Convert ValidatorPowerAndPledge to ValidatorPowerView
-                    let miner = ValidatorPowerView::V1(ValidatorPowerViewV1 {
-                        account_id: v.account_id,
-                        public_key: v.public_key,
-                        power: v.power,
-                    });
-                    miners.push(miner); // Add the converted ValidatorPowerView to the miners vector
-                } // Handle other variants as needed
-            }
-        }
+/// A set of miners along with their combined power and pledge.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Default)]
+pub struct MinerSetView {
+    pub total_power: Power,
+    #[serde(with = "dec_format")]
+    pub total_pledge: Balance,
+    pub miners: Vec<ValidatorPowerAndPledgeView>,
+}
 
-        // Create an instance of AllMinersView with the aggregated data
-        AllMinersView { total_power, miners }
+impl FromIterator<ValidatorPowerAndPledge> for MinerSetView {
+    fn from_iter<I: IntoIterator<Item = ValidatorPowerAndPledge>>(iter: I) -> Self {
+        let mut view = MinerSetView::default();
+        for miner in iter {
+            view.total_power += miner.power();
+            view.total_pledge += miner.pledge();
+            view.miners.push(miner.into());
+        }
+        view
     }
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Debug)]
+pub struct ShardMinersView {
+    pub shard_id: ShardId,
+    pub chunk_producers: MinerSetView,
+}
+
+/// Everything needed to replay the VRF driven choice of a block's producer.
+///
+/// The choice is seeded by the VRF output of the parent block, so the VRF fields describe
+/// `prev_block_hash` while `author` is the producer of `block_hash`. The power table is not
+/// committed to by any block header and has to be trusted from the serving node.
+///
+/// See [`crate::producer_selection::replay_producer_selection`].
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ProducerSelectionView {
+    pub block_hash: CryptoHash,
+    pub block_height: BlockHeight,
+    /// Producer of the block, which the selection has to land on.
+    pub author: AccountId,
+    /// Parent block whose VRF output seeds the selection.
+    pub prev_block_hash: CryptoHash,
+    /// Key of the parent block producer, which produced the VRF output.
+    pub vrf_public_key: PublicKey,
+    /// Random value of the block before the parent, used as the VRF input.
+    pub vrf_input: CryptoHash,
+    pub vrf_value: unc_crypto::vrf::Value,
+    pub vrf_proof: unc_crypto::vrf::Proof,
+    /// Hash of `vrf_value`, the random value of the parent block.
+    pub random_value: CryptoHash,
+    /// Validators of the parent block in the order the power walk visits them.
+    pub validators: Vec<ValidatorPowerView>,
+    #[serde(with = "u256_dec_format")]
+    pub total_power: U256,
+    /// `random_value` read as a big-endian integer modulo `total_power`.
+    #[serde(with = "u256_dec_format")]
+    pub target: U256,
+    pub selected: AccountId,
+}
+
+mod u256_dec_format {
+    use primitive_types::U256;
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &U256, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&value.to_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<U256, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        U256::from_dec_str(&s).map_err(de::Error::custom)
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
 pub struct BlockView {
     pub author: AccountId,
     pub header: BlockHeaderView,
@@ -2015,7 +2182,7 @@ pub mod validator_power_view {
     use crate::types::validator_power::ValidatorPower;
     use borsh::{BorshDeserialize, BorshSerialize};
     use serde::Deserialize;
-    use unc_primitives_core::types::AccountId;
+    use unc_primitives_core::types::{AccountId, Power};
 
     #[derive(
         BorshSerialize, BorshDeserialize, serde::Serialize, Deserialize, Debug, Clone, Eq, PartialEq,
@@ -2043,6 +2210,13 @@ pub mod validator_power_view {
                 Self::V1(v1) => &v1.account_id,
             }
         }
+
+        #[inline]
+        pub fn power(&self) -> Power {
+            match self {
+                Self::V1(v1) => v1.power,
+            }
+        }
     }
 
     impl From<ValidatorPower> for ValidatorPowerView {
@@ -2321,6 +2495,12 @@ pub struct EpochValidatorInfo {
     pub current_pledge_proposals: Vec<ValidatorPledgeView>,
     /// Kickout in the previous epoch
     pub prev_epoch_kickout: Vec<ValidatorKickoutView>,
+    /// Rewards for the previous epoch
+    #[serde(default)]
+    pub prev_epoch_reward: Vec<ValidatorRewardView>,
+    /// Weights the rewards for the previous epoch were split by pledge and power with
+    #[serde(default)]
+    pub prev_epoch_reward_weights: RewardWeights,
     /// Epoch start block height
     pub epoch_start_height: BlockHeight,
     /// Epoch height
@@ -2342,6 +2522,13 @@ pub struct ValidatorKickoutView {
     pub reason: ValidatorKickoutReason,
 }
 
+#[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct ValidatorRewardView {
+    pub account_id: AccountId,
+    #[serde(with = "dec_format")]
+    pub reward: Balance,
+}
+
 #[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq, Eq, Clone)]
 pub struct CurrentEpochValidatorInfo {
     pub account_id: AccountId,
@@ -2520,7 +2707,7 @@ impl From<StateChangeKind> for StateChangeKindView {
 pub type StateChangesKindsView = Vec<StateChangeKindView>;
 
 /// See crate::types::StateChangeCause for details.
-#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "snake_case", tag = "type")]
 pub enum StateChangeCauseView {
     NotWritableToDisk,
@@ -2565,7 +2752,7 @@ impl From<StateChangeCause> for StateChangeCauseView {
 }
 
 #[serde_as]
-#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "snake_case", tag = "type", content = "change")]
 pub enum StateChangeValueView {
     AccountUpdate {
@@ -2654,7 +2841,24 @@ impl From<StateChangeValue> for StateChangeValueView {
     }
 }
 
-#[derive(Debug, serde::Serialize, serde::Deserialize)]
+impl StateChangeValueView {
+    pub fn affected_account_id(&self) -> &AccountId {
+        match &self {
+            StateChangeValueView::AccountUpdate { account_id, .. }
+            | StateChangeValueView::AccountDeletion { account_id }
+            | StateChangeValueView::AccessKeyUpdate { account_id, .. }
+            | StateChangeValueView::AccessKeyDeletion { account_id, .. }
+            | StateChangeValueView::DataUpdate { account_id, .. }
+            | StateChangeValueView::DataDeletion { account_id, .. }
+            | StateChangeValueView::ContractCodeUpdate { account_id, .. }
+            | StateChangeValueView::RsaKeyUpdate { account_id, .. }
+            | StateChangeValueView::RsaKeyDeletion { account_id, .. }
+            | StateChangeValueView::ContractCodeDeletion { account_id } => account_id,
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct StateChangeWithCauseView {
     pub cause: StateChangeCauseView,
     #[serde(flatten)]
@@ -2670,6 +2874,21 @@ impl From<StateChangeWithCause> for StateChangeWithCauseView {
 
 pub type StateChangesView = Vec<StateChangeWithCauseView>;
 
+/// A single change of `Account::power` within a block.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct AccountPowerChangeView {
+    pub account_id: AccountId,
+    pub cause: StateChangeCauseView,
+    /// Chip claimed by a `CreateRsa2048Challenge` action or revoked by a
+    /// `RegisterRsa2048Keys` action, if one caused the change.
+    pub chip_public_key: Option<PublicKey>,
+    pub power_before: Power,
+    /// Total power of the account after the change.
+    pub power_after: Power,
+}
+
+pub type PowerChangesView = Vec<AccountPowerChangeView>;
+
 /// Maintenance windows view are a vector of maintenance window.
 pub type MaintenanceWindowsView = Vec<Range<BlockHeight>>;
 
@@ -2685,8 +2904,9 @@ pub struct SplitStorageInfoView {
 
 #[cfg(test)]
 mod tests {
-    use super::ExecutionMetadataView;
+    use super::{AllMinersView, EpochInfo, ExecutionMetadataView};
     use crate::transaction::ExecutionMetadata;
+    use crate::types::EpochId;
     use unc_vm_runner::{ProfileDataV2, ProfileDataV3};
 
     /// The JSON representation used in RPC responses must not remove or rename
@@ -2721,6 +2941,16 @@ mod tests {
         insta::assert_json_snapshot!(view);
     }
 
+    /// `AllMinersView` returned by the `all_miners` RPC should not change.
+    #[test]
+    #[cfg_attr(feature = "nightly", ignore)]
+    fn test_all_miners_view() {
+        let epoch_info = EpochInfo::v1_test();
+        let view =
+            AllMinersView::new(epoch_info.validators_iter(), EpochId::default(), &epoch_info);
+        insta::assert_json_snapshot!(view);
+    }
+
     /// `ExecutionMetadataView` with profile V3 displayed on the RPC should not change.
     #[test]
     #[cfg_attr(feature = "nightly", ignore)]
@@ -279,6 +279,12 @@ pub enum DBCol {
     /// - *Rows*: arbitrary string, see `crate::db::FLAT_STATE_VALUES_INLINING_MIGRATION_STATUS_KEY` for example
     /// - *Column type*: arbitrary bytes
     Misc,
+    /// Peers temporarily banned because their reputation score crossed the ban threshold.
+    /// We keep the bans across restarts, so that a misbehaving peer cannot reset its
+    /// ban by waiting for us to restart.
+    /// - *Rows*: single row (empty row name)
+    /// - *Content type*: Vec of (PeerId, ban expiry as UNIX timestamp in nanos)
+    BannedPeers,
     /// Column to store data for Epoch Sync.
     /// Does not contain data for genesis epoch.
     /// - *Rows*: `epoch_id`
@@ -458,6 +464,7 @@ impl DBCol {
             | DBCol::BlockHeight
             | DBCol::_Peers
             | DBCol::RecentOutboundConnections
+            | DBCol::BannedPeers
             | DBCol::BlockMerkleTree
             | DBCol::AccountAnnouncements
             | DBCol::EpochLightClientBlocks
@@ -505,6 +512,7 @@ impl DBCol {
             DBCol::IncomingReceipts => &[DBKeyType::BlockHash, DBKeyType::ShardId],
             DBCol::_Peers => &[DBKeyType::PeerId],
             DBCol::RecentOutboundConnections => &[DBKeyType::Empty],
+            DBCol::BannedPeers => &[DBKeyType::Empty],
             DBCol::EpochInfo => &[DBKeyType::EpochId],
             DBCol::BlockInfo => &[DBKeyType::BlockHash],
             DBCol::Chunks => &[DBKeyType::ChunkHash],
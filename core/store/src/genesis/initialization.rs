@@ -96,7 +96,6 @@ fn genesis_state_from_genesis(store: Store, genesis: &Genesis) -> Vec<StateRoot>
     }
     let runtime_config_store = RuntimeConfigStore::for_chain_id(&genesis.config.chain_id);
     let runtime_config = runtime_config_store.get_config(genesis.config.protocol_version);
-    let storage_usage_config = &runtime_config.fees.storage_usage_config;
     let initial_epoch_config = EpochConfig::from(&genesis.config);
     let shard_layout = initial_epoch_config.shard_layout;
     let shard_ids: Vec<_> = shard_layout.shard_ids().collect();
@@ -150,7 +149,7 @@ fn genesis_state_from_genesis(store: Store, genesis: &Genesis) -> Vec<StateRoot>
                 tries.clone(),
                 shard_id,
                 &validators,
-                storage_usage_config,
+                runtime_config,
                 genesis,
                 shard_account_ids[shard_id as usize].clone(),
             )
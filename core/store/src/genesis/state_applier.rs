@@ -1,14 +1,15 @@
 use crate::flat::FlatStateChanges;
 use crate::{
     get_account, get_received_data, set, set_access_key, set_account, set_code,
-    set_delayed_receipt, set_postponed_receipt, set_received_data, ShardTries, TrieUpdate,
+    set_delayed_receipt, set_postponed_receipt, set_received_data, set_rsa2048_keys, ShardTries,
+    TrieUpdate,
 };
 
 use std::collections::{HashMap, HashSet};
 use std::sync::atomic;
 use unc_chain_configs::Genesis;
 use unc_crypto::PublicKey;
-use unc_parameters::StorageUsageConfig;
+use unc_parameters::{ChipRegistryConfig, RuntimeConfig, StorageUsageConfig};
 use unc_primitives::account::{AccessKey, Account};
 use unc_primitives::receipt::{DelayedReceiptIndices, Receipt, ReceiptEnum, ReceivedData};
 use unc_primitives::shard_layout::ShardUId;
@@ -25,11 +26,18 @@ struct StorageComputer<'a> {
     result: HashMap<AccountId, u64>,
     /// Configuration that keeps information like 'how many bytes should accountId consume' etc.
     config: &'a StorageUsageConfig,
+    /// Chip registrars. They pay for the whole chip registration, while miners that claimed a
+    /// chip only pay for its public key.
+    chip_registry_config: &'a ChipRegistryConfig,
 }
 
 impl<'a> StorageComputer<'a> {
-    fn new(config: &'a StorageUsageConfig) -> Self {
-        Self { result: HashMap::new(), config: &config }
+    fn new(config: &'a RuntimeConfig) -> Self {
+        Self {
+            result: HashMap::new(),
+            config: &config.fees.storage_usage_config,
+            chip_registry_config: &config.chip_registry_config,
+        }
     }
 
     /// Updates user's storage info based on the StateRecord.
@@ -59,6 +67,16 @@ impl<'a> StorageComputer<'a> {
             StateRecord::PostponedReceipt(_) => None,
             StateRecord::ReceivedData { .. } => None,
             StateRecord::DelayedReceipt(_) => None,
+            StateRecord::Rsa2048Keys { account_id, public_key, rsa2048_keys } => {
+                // Mirrors the accounting of the runtime when registering and claiming chips.
+                let record_len = if self.chip_registry_config.is_registrar(account_id) {
+                    borsh::object_length(rsa2048_keys).unwrap()
+                } else {
+                    borsh::object_length(public_key).unwrap()
+                };
+                let storage_usage = self.config.num_extra_bytes_record + record_len as u64;
+                Some((account_id.clone(), storage_usage))
+            }
         };
         if let Some((account_id, storage_usage)) = account_and_storage {
             *self.result.entry(account_id).or_default() += storage_usage;
@@ -165,7 +183,7 @@ impl GenesisStateApplier {
         delayed_receipts_indices: &mut DelayedReceiptIndices,
         shard_uid: ShardUId,
         validators: &[(AccountId, PublicKey, Balance, Power)],
-        config: &StorageUsageConfig,
+        config: &RuntimeConfig,
         genesis: &Genesis,
         account_ids: HashSet<AccountId>,
     ) {
@@ -243,6 +261,15 @@ impl GenesisStateApplier {
                 StateRecord::DelayedReceipt(receipt) => storage.modify(|state_update| {
                     set_delayed_receipt(state_update, delayed_receipts_indices, &*receipt);
                 }),
+                StateRecord::Rsa2048Keys { account_id, public_key, rsa2048_keys } => storage
+                    .modify(|state_update| {
+                        set_rsa2048_keys(
+                            state_update,
+                            account_id.clone(),
+                            public_key.clone(),
+                            rsa2048_keys,
+                        );
+                    }),
             }
         });
 
@@ -341,7 +368,7 @@ impl GenesisStateApplier {
         tries: ShardTries,
         shard_id: ShardId,
         validators: &[(AccountId, PublicKey, Balance, Power)],
-        config: &StorageUsageConfig,
+        config: &RuntimeConfig,
         genesis: &Genesis,
         shard_account_ids: HashSet<AccountId>,
     ) -> StateRoot {
@@ -369,7 +396,7 @@ impl GenesisStateApplier {
 /// Computes the expected storage per account for a given set of StateRecord(s).
 pub fn compute_storage_usage(
     records: &[StateRecord],
-    config: &StorageUsageConfig,
+    config: &RuntimeConfig,
 ) -> HashMap<AccountId, u64> {
     let mut storage_computer = StorageComputer::new(config);
     storage_computer.process_records(records);
@@ -379,7 +406,7 @@ pub fn compute_storage_usage(
 /// Compute the expected storage per account for genesis records.
 pub fn compute_genesis_storage_usage(
     genesis: &Genesis,
-    config: &StorageUsageConfig,
+    config: &RuntimeConfig,
 ) -> HashMap<AccountId, u64> {
     let mut storage_computer = StorageComputer::new(config);
     genesis.for_each_record(|record| {
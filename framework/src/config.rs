@@ -21,7 +21,8 @@ use unc_chain_configs::{
     default_trie_viewer_state_size_limit, default_tx_routing_height_horizon,
     default_view_client_threads, default_view_client_throttle_period, get_initial_supply,
     ClientConfig, GCConfig, Genesis, GenesisConfig, GenesisValidationMode, LogSummaryStyle,
-    MutableConfigValue, ReshardingConfig, StateSyncConfig,
+    MutableConfigValue, ReshardingConfig, StateSyncConfig, TrackedConfig, TransactionPoolPolicy,
+    TransactionPoolQuotas,
 };
 use unc_config_utils::{ValidationError, ValidationErrors};
 use unc_crypto::{InMemorySigner, KeyFile, KeyType, PublicKey, Signer};
@@ -289,7 +290,15 @@ pub struct Config {
     pub telemetry: TelemetryConfig,
     pub network: unc_network::config_json::Config,
     pub consensus: Consensus,
+    /// Legacy option, the node tracks all shards whatever its value. Validators
+    /// of mainnet and testnet must still set it to a non-empty list.
+    /// Ignored if `tracked_shards_config` is set.
     pub tracked_shards: Vec<ShardId>,
+    /// Shards tracked in addition to the shards of the validator duties, e.g.
+    /// `"AllShards"`, `{"Shards": [0]}`, `{"Accounts": ["alice.unc"]}` or
+    /// `{"Schedule": [[0], [1, 2]]}`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tracked_shards_config: Option<TrackedConfig>,
     #[serde(skip_serializing_if = "is_false")]
     pub archive: bool,
     /// If save_trie_changes is not set it will get inferred from the `archive` field as follows:
@@ -338,6 +347,13 @@ pub struct Config {
     /// Setting this value too low (<1MB) on the validator might lead to production of smaller
     /// chunks and underutilizing the capacity of the network.
     pub transaction_pool_size_limit: Option<u64>,
+    /// Ordering of the transaction pool, which transactions are evicted when it's full and
+    /// whether a transaction can replace another one with the same nonce.
+    pub transaction_pool_policy: TransactionPoolPolicy,
+    /// Limits on the number and the total size of transactions of a single signer account and of
+    /// a single access key in the transaction pool of a shard.
+    /// Can be updated while the node is running.
+    pub transaction_pool_quotas: TransactionPoolQuotas,
     // Configuration for resharding.
     pub resharding_config: ReshardingConfig,
     /// If the node is not a chunk producer within that many blocks, then route
@@ -366,6 +382,7 @@ impl Default for Config {
             network: Default::default(),
             consensus: Consensus::default(),
             tracked_shards: vec![0],
+            tracked_shards_config: None,
             archive: false,
             save_trie_changes: None,
             log_summary_style: LogSummaryStyle::Colored,
@@ -382,6 +399,8 @@ impl Default for Config {
             state_sync: default_state_sync(),
             state_sync_enabled: default_state_sync_enabled(),
             transaction_pool_size_limit: default_transaction_pool_size_limit(),
+            transaction_pool_policy: TransactionPoolPolicy::default(),
+            transaction_pool_quotas: TransactionPoolQuotas::default(),
             enable_multiline_logging: default_enable_multiline_logging(),
             resharding_config: ReshardingConfig::default(),
             tx_routing_height_horizon: default_tx_routing_height_horizon(),
@@ -483,6 +502,12 @@ impl Config {
         crate::config_validate::validate_config(self)
     }
 
+    /// Shards tracked by the node. Nodes without `tracked_shards_config` track
+    /// all shards, as they did before the tracking modes were configurable.
+    pub fn tracked_config(&self) -> TrackedConfig {
+        self.tracked_shards_config.clone().unwrap_or(TrackedConfig::AllShards)
+    }
+
     pub fn write_to_file(&self, path: &Path) -> std::io::Result<()> {
         let mut file = File::create(path)?;
         let str = serde_json::to_string_pretty(self)?;
@@ -664,6 +689,7 @@ impl UncConfig {
                 chunk_request_retry_period: config.consensus.chunk_request_retry_period,
                 doosmslug_step_period: config.consensus.doomslug_step_period,
                 archive: config.archive,
+                tracked_shards_config: config.tracked_config(),
                 save_trie_changes: config.save_trie_changes.unwrap_or(!config.archive),
                 log_summary_style: config.log_summary_style,
                 gc: config.gc,
@@ -678,6 +704,11 @@ impl UncConfig {
                 state_sync_enabled: config.state_sync_enabled,
                 state_sync: config.state_sync.unwrap_or_default(),
                 transaction_pool_size_limit: config.transaction_pool_size_limit,
+                transaction_pool_policy: config.transaction_pool_policy,
+                transaction_pool_quotas: MutableConfigValue::new(
+                    config.transaction_pool_quotas,
+                    "transaction_pool_quotas",
+                ),
                 enable_multiline_logging: config.enable_multiline_logging.unwrap_or(true),
                 resharding_config: MutableConfigValue::new(
                     config.resharding_config,
@@ -1414,11 +1445,16 @@ pub fn load_config(
                     genesis.config.chain_id.as_ref(),
                     unc_primitives::chains::MAINNET | unc_primitives::chains::TESTNET
                 )
-                && config.tracked_shards.is_empty()
             {
                 // Make sure validators tracks all shards
-                let error_message = "The `chain_id` field specified in genesis is among mainnet/betanet/testnet, so validator must track all shards. Please change `tracked_shards` field in config.json to be any non-empty vector";
-                validation_errors.push_cross_file_semantics_error(error_message.to_string());
+                if config.tracked_config() != TrackedConfig::AllShards {
+                    let error_message = "The `chain_id` field specified in genesis is among mainnet/betanet/testnet, so validator must track all shards. Please set `tracked_shards_config` field in config.json to \"AllShards\"";
+                    validation_errors.push_cross_file_semantics_error(error_message.to_string());
+                } else if config.tracked_shards_config.is_none() && config.tracked_shards.is_empty()
+                {
+                    let error_message = "The `chain_id` field specified in genesis is among mainnet/betanet/testnet, so validator must track all shards. Please change `tracked_shards` field in config.json to be any non-empty vector";
+                    validation_errors.push_cross_file_semantics_error(error_message.to_string());
+                }
             }
             Some(genesis)
         }
@@ -1598,9 +1634,19 @@ fn test_config_from_file_skip_validation() {
         // values is probably not worth it but there may be some other defaults
         // we want to ensure that they happen.
         let want_gc = if has_gc {
-            GCConfig { gc_blocks_limit: 42, gc_fork_clean_step: 420, gc_num_epochs_to_keep: 24 }
+            GCConfig {
+                gc_blocks_limit: 42,
+                gc_fork_clean_step: 420,
+                gc_num_epochs_to_keep: 24,
+                gc_hold_max_blocks: 86_400,
+            }
         } else {
-            GCConfig { gc_blocks_limit: 2, gc_fork_clean_step: 100, gc_num_epochs_to_keep: 5 }
+            GCConfig {
+                gc_blocks_limit: 2,
+                gc_fork_clean_step: 100,
+                gc_num_epochs_to_keep: 5,
+                gc_hold_max_blocks: 86_400,
+            }
         };
         assert_eq!(want_gc, config.gc);
 
@@ -1611,6 +1657,17 @@ fn test_config_from_file_skip_validation() {
     }
 }
 
+#[test]
+fn test_tracked_config_legacy_tracked_shards() {
+    let mut config = Config::default();
+    for tracked_shards in [vec![], vec![0], vec![1, 2]] {
+        config.tracked_shards = tracked_shards;
+        assert_eq!(config.tracked_config(), TrackedConfig::AllShards);
+    }
+    config.tracked_shards_config = Some(TrackedConfig::Shards(vec![]));
+    assert_eq!(config.tracked_config(), TrackedConfig::Shards(vec![]));
+}
+
 #[test]
 fn test_create_testnet_configs() {
     let num_shards = 4;
@@ -1,6 +1,6 @@
 use std::collections::HashSet;
 use std::path::Path;
-use unc_chain_configs::{ExternalStorageLocation, SyncConfig};
+use unc_chain_configs::{ExternalStorageLocation, SyncConfig, TrackedConfig};
 use unc_config_utils::{ValidationError, ValidationErrors};
 
 use crate::config::Config;
@@ -79,6 +79,13 @@ impl<'a> ConfigValidator<'a> {
             self.validation_errors.push_config_semantics_error(error_message);
         }
 
+        if let Some(TrackedConfig::Schedule(schedule)) = &self.config.tracked_shards_config {
+            if schedule.is_empty() {
+                let error_message = format!("'config.tracked_shards_config.Schedule' needs to list at least one set of shards.");
+                self.validation_errors.push_config_semantics_error(error_message);
+            }
+        }
+
         if let Some(state_sync) = &self.config.state_sync {
             if let Some(dump_config) = &state_sync.dump {
                 if let Some(restart_dump_for_shards) = &dump_config.restart_dump_for_shards {
@@ -90,7 +97,7 @@ impl<'a> ConfigValidator<'a> {
                 }
 
                 match &dump_config.location {
-                    ExternalStorageLocation::S3 { bucket, region } => {
+                    ExternalStorageLocation::S3 { bucket, region, .. } => {
                         if bucket.is_empty() || region.is_empty() {
                             let error_message = format!("'config.state_sync.dump.location.S3.bucket' and 'config.state_sync.dump.location.S3.region' need to be specified when 'config.state_sync.dump.location.S3' is present.");
                             self.validation_errors.push_config_semantics_error(error_message);
@@ -108,6 +115,16 @@ impl<'a> ConfigValidator<'a> {
                             self.validation_errors.push_config_semantics_error(error_message);
                         }
                     }
+                    ExternalStorageLocation::HTTP { .. } => {
+                        let error_message = format!("'config.state_sync.dump.location.HTTP' is read-only and can't be used for dumping state.");
+                        self.validation_errors.push_config_semantics_error(error_message);
+                    }
+                    ExternalStorageLocation::Azure { account, container, .. } => {
+                        if account.is_empty() || container.is_empty() {
+                            let error_message = format!("'config.state_sync.dump.location.Azure.account' and 'config.state_sync.dump.location.Azure.container' need to be specified when 'config.state_sync.dump.location.Azure' is present.");
+                            self.validation_errors.push_config_semantics_error(error_message);
+                        }
+                    }
                 }
 
                 if let Some(credentials_file) = &dump_config.credentials_file {
@@ -121,7 +138,7 @@ impl<'a> ConfigValidator<'a> {
                 SyncConfig::Peers => {}
                 SyncConfig::ExternalStorage(config) => {
                     match &config.location {
-                        ExternalStorageLocation::S3 { bucket, region } => {
+                        ExternalStorageLocation::S3 { bucket, region, .. } => {
                             if bucket.is_empty() || region.is_empty() {
                                 let error_message = format!("'config.state_sync.sync.ExternalStorage.location.S3.bucket' and 'config.state_sync.sync.ExternalStorage.location.S3.region' need to be specified when 'config.state_sync.sync.ExternalStorage.location.S3' is present.");
                                 self.validation_errors.push_config_semantics_error(error_message);
@@ -139,6 +156,24 @@ impl<'a> ConfigValidator<'a> {
                                 self.validation_errors.push_config_semantics_error(error_message);
                             }
                         }
+                        ExternalStorageLocation::HTTP { url } => {
+                            if !url.starts_with("http://") && !url.starts_with("https://") {
+                                let error_message = format!("'config.state_sync.sync.ExternalStorage.location.HTTP.url' needs to be an http:// or https:// URL when 'config.state_sync.sync.ExternalStorage.location.HTTP' is present.");
+                                self.validation_errors.push_config_semantics_error(error_message);
+                            }
+                        }
+                        ExternalStorageLocation::Azure { account, container, .. } => {
+                            if account.is_empty() || container.is_empty() {
+                                let error_message = format!("'config.state_sync.sync.ExternalStorage.location.Azure.account' and 'config.state_sync.sync.ExternalStorage.location.Azure.container' need to be specified when 'config.state_sync.sync.ExternalStorage.location.Azure' is present.");
+                                self.validation_errors.push_config_semantics_error(error_message);
+                            }
+                        }
+                    }
+                    if config.verify_state_parts_manifest
+                        && config.trusted_state_dumper_keys.is_empty()
+                    {
+                        let error_message = format!("'config.state_sync.sync.ExternalStorage.trusted_state_dumper_keys' needs to list at least one key when 'config.state_sync.sync.ExternalStorage.verify_state_parts_manifest' is enabled.");
+                        self.validation_errors.push_config_semantics_error(error_message);
                     }
                     if config.num_concurrent_requests == 0 {
                         let error_message = format!("'config.state_sync.sync.ExternalStorage.num_concurrent_requests' needs to be greater than 0");
@@ -172,6 +207,7 @@ impl<'a> ConfigValidator<'a> {
 #[cfg(test)]
 mod test {
     use super::*;
+    use unc_chain_configs::{DumpConfig, ExternalStorageConfig, StateSyncConfig};
 
     #[test]
     #[should_panic(expected = "gc config values should all be greater than 0")]
@@ -250,4 +286,75 @@ mod test {
         config.tx_routing_height_horizon = 1_000_000_000;
         validate_config(&config).unwrap();
     }
+
+    #[test]
+    #[should_panic(
+        expected = "\\nconfig.json semantic issue: 'config.state_sync.dump.location.HTTP' is read-only and can't be used for dumping state."
+    )]
+    fn test_state_sync_dump_to_http() {
+        let mut config = Config::default();
+        config.state_sync = Some(StateSyncConfig {
+            dump: Some(DumpConfig {
+                location: ExternalStorageLocation::HTTP { url: "https://localhost".to_string() },
+                restart_dump_for_shards: None,
+                iteration_delay: None,
+                credentials_file: None,
+                state_parts_manifest: false,
+            }),
+            sync: SyncConfig::Peers,
+        });
+        validate_config(&config).unwrap();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "\\nconfig.json semantic issue: 'config.state_sync.dump.location.Azure.account' and 'config.state_sync.dump.location.Azure.container' need to be specified when 'config.state_sync.dump.location.Azure' is present."
+    )]
+    fn test_state_sync_dump_to_azure_without_container() {
+        let mut config = Config::default();
+        config.state_sync = Some(StateSyncConfig {
+            dump: Some(DumpConfig {
+                location: ExternalStorageLocation::Azure {
+                    account: "account".to_string(),
+                    container: String::new(),
+                    endpoint: None,
+                },
+                restart_dump_for_shards: None,
+                iteration_delay: None,
+                credentials_file: None,
+                state_parts_manifest: false,
+            }),
+            sync: SyncConfig::Peers,
+        });
+        validate_config(&config).unwrap();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "\\nconfig.json semantic issue: 'config.state_sync.sync.ExternalStorage.trusted_state_dumper_keys' needs to list at least one key when 'config.state_sync.sync.ExternalStorage.verify_state_parts_manifest' is enabled."
+    )]
+    fn test_state_sync_verify_manifest_without_trusted_keys() {
+        let mut config = Config::default();
+        config.state_sync = Some(StateSyncConfig {
+            dump: None,
+            sync: SyncConfig::ExternalStorage(ExternalStorageConfig {
+                location: ExternalStorageLocation::GCS { bucket: "state-parts".to_string() },
+                num_concurrent_requests: 4,
+                num_concurrent_requests_during_catchup: 4,
+                verify_state_parts_manifest: true,
+                trusted_state_dumper_keys: vec![],
+            }),
+        });
+        validate_config(&config).unwrap();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "'config.tracked_shards_config.Schedule' needs to list at least one set of shards"
+    )]
+    fn test_tracked_shards_empty_schedule() {
+        let mut config = Config::default();
+        config.tracked_shards_config = Some(TrackedConfig::Schedule(vec![]));
+        validate_config(&config).unwrap();
+    }
 }
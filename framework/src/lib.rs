@@ -21,8 +21,8 @@ use unc_chain::state_snapshot_actor::{
 };
 use unc_chain::types::RuntimeAdapter;
 use unc_chain::{Chain, ChainGenesis};
-use unc_chain_configs::ReshardingHandle;
 use unc_chain_configs::SyncConfig;
+use unc_chain_configs::{GCHoldHandle, ReshardingHandle};
 use unc_chunks::shards_manager_actor::start_shards_manager;
 use unc_client::sync::adapter::SyncAdapter;
 use unc_client::{start_client, start_view_client, ClientActor, ConfigUpdater, ViewClientActor};
@@ -217,6 +217,9 @@ pub struct UncNode {
     // A handle that allows the main process to interrupt resharding if needed.
     // This typically happens when the main process is interrupted.
     pub resharding_handle: ReshardingHandle,
+    /// A handle that allows an external consumer, such as the indexer, to keep
+    /// garbage collection from removing blocks it hasn't processed yet.
+    pub gc_hold_handle: GCHoldHandle,
 }
 
 pub fn start_with_config(home_dir: &Path, config: UncConfig) -> anyhow::Result<UncNode> {
@@ -253,7 +256,10 @@ pub fn start_with_config_and_synchronization(
 
     let epoch_manager =
         EpochManager::new_arc_handle(storage.get_hot_store(), &config.genesis.config);
-    let shard_tracker = ShardTracker::new(epoch_manager.clone());
+    let shard_tracker = ShardTracker::new(
+        config.client_config.tracked_shards_config.clone(),
+        epoch_manager.clone(),
+    );
     let runtime = NightshadeRuntime::from_config(
         home_dir,
         storage.get_hot_store(),
@@ -268,7 +274,10 @@ pub fn start_with_config_and_synchronization(
         if let Some(split_store) = &split_store {
             let view_epoch_manager =
                 EpochManager::new_arc_handle(split_store.clone(), &config.genesis.config);
-            let view_shard_tracker = ShardTracker::new(epoch_manager.clone());
+            let view_shard_tracker = ShardTracker::new(
+                config.client_config.tracked_shards_config.clone(),
+                epoch_manager.clone(),
+            );
             let view_runtime = NightshadeRuntime::from_config(
                 home_dir,
                 split_store.clone(),
@@ -329,7 +338,7 @@ pub fn start_with_config_and_synchronization(
         get_make_snapshot_callback(state_snapshot_actor, runtime.get_flat_storage_manager());
     let snapshot_callbacks = SnapshotCallbacks { make_snapshot_callback, delete_snapshot_callback };
 
-    let (client_actor, client_arbiter_handle, resharding_handle) = start_client(
+    let (client_actor, client_arbiter_handle, resharding_handle, gc_hold_handle) = start_client(
         config.client_config.clone(),
         chain_genesis.clone(),
         epoch_manager.clone(),
@@ -375,6 +384,7 @@ pub fn start_with_config_and_synchronization(
         shard_tracker,
         runtime,
         config.validator_signer.as_ref().map(|signer| signer.validator_id().clone()),
+        config.network_config.node_key.clone(),
     )?;
 
     let hot_store = storage.get_hot_store();
@@ -400,6 +410,8 @@ pub fn start_with_config_and_synchronization(
             runtime: view_runtime,
             store: hot_store,
         };
+        let streamer_feed =
+            unc_jsonrpc::StreamerFeed::new(rpc_config.subscriptions_config.streamer_buffer_size);
         rpc_servers.extend(unc_jsonrpc::start_http(
             rpc_config,
             config.genesis.config.clone(),
@@ -407,6 +419,7 @@ pub fn start_with_config_and_synchronization(
             view_client.clone(),
             Some(network_actor),
             Arc::new(entity_debug_handler),
+            streamer_feed,
         ));
     }
 
@@ -429,6 +442,7 @@ pub fn start_with_config_and_synchronization(
         state_sync_dump_handle,
         flat_state_migration_handle,
         resharding_handle,
+        gc_hold_handle,
     })
 }
 
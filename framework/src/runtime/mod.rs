@@ -36,8 +36,8 @@ use unc_primitives::types::{
 };
 use unc_primitives::version::ProtocolVersion;
 use unc_primitives::views::{
-    AccessKeyInfoView, CallResult, ChipView, QueryRequest, QueryResponse, QueryResponseKind,
-    ViewApplyState, ViewStateResult,
+    AccessKeyInfoView, CallResult, ChipView, ChipsPage, QueryRequest, QueryResponse,
+    QueryResponseKind, ViewApplyState, ViewStateResult,
 };
 use unc_store::config::StateSnapshotType;
 use unc_store::flat::FlatStorageManager;
@@ -1058,6 +1058,31 @@ impl RuntimeAdapter for NightshadeRuntime {
                     block_hash: *block_hash,
                 })
             }
+            QueryRequest::ViewChips { account_id, miner_id, sn, cursor, limit, include_proof } => {
+                let chips_page = self
+                    .view_chips(
+                        &shard_uid,
+                        *state_root,
+                        account_id,
+                        miner_id.as_deref(),
+                        sn.as_deref(),
+                        cursor.as_ref(),
+                        *limit,
+                        *include_proof,
+                    )
+                    .map_err(|err| {
+                        unc_chain::unc_chain_primitives::error::QueryError::from_view_chip_error(
+                            err,
+                            block_height,
+                            *block_hash,
+                        )
+                    })?;
+                Ok(QueryResponse {
+                    kind: QueryResponseKind::ChipsPage(chips_page),
+                    block_height,
+                    block_hash: *block_hash,
+                })
+            }
             QueryRequest::ViewAccessKey { account_id, public_key } => {
                 let access_key = self
                     .view_access_key(&shard_uid, *state_root, account_id, public_key)
@@ -1365,6 +1390,29 @@ impl node_runtime::adapter::ViewRuntimeAdapter for NightshadeRuntime {
         self.trie_viewer.view_chip_list(&state_update, account_id)
     }
 
+    fn view_chips(
+        &self,
+        shard_uid: &ShardUId,
+        state_root: MerkleHash,
+        account_id: &AccountId,
+        miner_id: Option<&str>,
+        sn: Option<&str>,
+        cursor: Option<&PublicKey>,
+        limit: Option<u32>,
+        include_proof: bool,
+    ) -> Result<ChipsPage, node_runtime::state_viewer::errors::ViewChipError> {
+        let state_update = self.tries.new_trie_update_view(*shard_uid, state_root);
+        self.trie_viewer.view_chips(
+            &state_update,
+            account_id,
+            miner_id,
+            sn,
+            cursor,
+            limit,
+            include_proof,
+        )
+    }
+
     fn view_state(
         &self,
         shard_uid: &ShardUId,
@@ -848,6 +848,8 @@ fn test_get_validator_info() {
             )
             .into()],
             prev_epoch_kickout: Default::default(),
+            prev_epoch_reward: Default::default(),
+            prev_epoch_reward_weights: Default::default(),
             epoch_start_height: 1,
             epoch_height: 1,
         }
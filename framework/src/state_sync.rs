@@ -1,24 +1,31 @@
 use crate::metrics;
 
+use anyhow::Context;
 use rand::{thread_rng, Rng};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use unc_chain::types::RuntimeAdapter;
 use unc_chain::{Chain, ChainGenesis, ChainStoreAccess, DoomslugThresholdMode, Error};
 use unc_chain_configs::{ClientConfig, ExternalStorageLocation};
-use unc_client::sync::external::{create_bucket_readwrite, external_storage_location};
 use unc_client::sync::external::{
-    external_storage_location_directory, get_part_id_from_filename, is_part_filename,
-    ExternalConnection,
+    create_azure_connection, create_bucket_readwrite, external_storage_location,
+};
+use unc_client::sync::external::{
+    external_storage_location_directory, external_storage_manifest_location,
+    get_part_id_from_filename, is_part_filename, ExternalConnection,
 };
 use unc_client::sync::state::{StateSync, STATE_DUMP_ITERATION_TIME_LIMIT_SECS};
+use unc_crypto::SecretKey;
 use unc_epoch_manager::shard_tracker::ShardTracker;
 use unc_epoch_manager::EpochManagerAdapter;
 use unc_primitives::hash::CryptoHash;
 use unc_primitives::state_part::PartId;
-use unc_primitives::state_sync::{StatePartKey, StateSyncDumpProgress};
+use unc_primitives::state_sync::{
+    SignedStatePartsManifest, StatePartKey, StatePartManifestEntry, StatePartsManifest,
+    StateSyncDumpProgress,
+};
 use unc_primitives::types::{AccountId, EpochHeight, EpochId, ShardId, StateRoot};
 use unc_store::DBCol;
 
@@ -31,6 +38,7 @@ pub fn spawn_state_sync_dump(
     shard_tracker: ShardTracker,
     runtime: Arc<dyn RuntimeAdapter>,
     account_id: Option<AccountId>,
+    node_key: SecretKey,
 ) -> anyhow::Result<Option<StateSyncDumpHandle>> {
     let dump_config = if let Some(dump_config) = client_config.state_sync.dump.clone() {
         dump_config
@@ -42,8 +50,8 @@ pub fn spawn_state_sync_dump(
     tracing::info!(target: "state_sync_dump", "Spawning the state sync dump loop");
 
     let external = match dump_config.location {
-        ExternalStorageLocation::S3 { bucket, region } => ExternalConnection::S3{
-            bucket: Arc::new(create_bucket_readwrite(&bucket, &region, Duration::from_secs(30), dump_config.credentials_file).expect(
+        ExternalStorageLocation::S3 { bucket, region, endpoint } => ExternalConnection::S3{
+            bucket: Arc::new(create_bucket_readwrite(&bucket, &region, endpoint.as_deref(), Duration::from_secs(30), dump_config.credentials_file).expect(
                 "Failed to authenticate connection to S3. Please either provide AWS_ACCESS_KEY_ID and AWS_SECRET_ACCESS_KEY in the environment, or create a credentials file and link it in config.json as 's3_credentials_file'."))
         },
         ExternalStorageLocation::Filesystem { root_dir } => ExternalConnection::Filesystem { root_dir },
@@ -62,6 +70,13 @@ pub fn spawn_state_sync_dump(
                 bucket
             }
         },
+        ExternalStorageLocation::HTTP { .. } => {
+            anyhow::bail!("State can't be dumped to HTTP external storage, it's read-only");
+        }
+        ExternalStorageLocation::Azure { account, container, endpoint } => {
+            create_azure_connection(&account, &container, endpoint.as_deref(), dump_config.credentials_file).context(
+                "Failed to read the Azure credentials file. It should contain a shared access signature: {\"sas_token\": \"...\"}.")?
+        }
     };
 
     // Determine how many threads to start.
@@ -81,6 +96,7 @@ pub fn spawn_state_sync_dump(
     }?;
 
     let chain_id = client_config.chain_id.clone();
+    let manifest_key = dump_config.state_parts_manifest.then_some(node_key);
     let keep_running = Arc::new(AtomicBool::new(true));
     // Start a thread for each shard.
     let handles = shard_ids
@@ -109,6 +125,7 @@ pub fn spawn_state_sync_dump(
                 external.clone(),
                 dump_config.iteration_delay.unwrap_or(Duration::from_secs(10)),
                 account_id.clone(),
+                manifest_key.clone(),
                 keep_running.clone(),
             )));
             arbiter_handle
@@ -158,6 +175,7 @@ async fn get_missing_part_ids_for_epoch(
     if !file_names.is_empty() {
         let existing_nums: HashSet<_> = file_names
             .iter()
+            .filter(|file_name| is_part_filename(file_name))
             .map(|file_name| extract_part_id_from_part_file_name(file_name))
             .collect();
         let missing_nums: Vec<u64> =
@@ -230,6 +248,7 @@ async fn state_sync_dump(
     external: ExternalConnection,
     iteration_delay: Duration,
     account_id: Option<AccountId>,
+    manifest_key: Option<SecretKey>,
     keep_running: Arc<AtomicBool>,
 ) {
     tracing::info!(target: "state_sync_dump", shard_id, "Running StateSyncDump loop");
@@ -238,6 +257,9 @@ async fn state_sync_dump(
         tracing::debug!(target: "state_sync_dump", shard_id, "Dropped existing progress");
         chain.chain_store().set_state_sync_dump_progress(shard_id, None).unwrap();
     }
+    // Sizes and hashes of the state parts uploaded by this node, by sync hash
+    // and part id.
+    let mut dumped_parts = HashMap::new();
 
     // Stop if the node is stopped.
     // Note that without this check the state dumping thread is unstoppable, i.e. non-interruptable.
@@ -257,6 +279,7 @@ async fn state_sync_dump(
             }
             Ok(None) => None,
             Ok(Some((epoch_id, epoch_height, sync_hash))) => {
+                dumped_parts.retain(|(hash, _), _| *hash == sync_hash);
                 let in_progress_data = get_in_progress_data(shard_id, sync_hash, &chain);
                 match in_progress_data {
                     Err(err) => {
@@ -286,7 +309,29 @@ async fn state_sync_dump(
                                     num_parts,
                                     num_parts,
                                 );
-                                Some(StateSyncDumpProgress::AllDumped { epoch_id, epoch_height })
+                                match dump_state_parts_manifest(
+                                    shard_id,
+                                    &chain_id,
+                                    &epoch_id,
+                                    epoch_height,
+                                    sync_hash,
+                                    &state_root,
+                                    num_parts,
+                                    &dumped_parts,
+                                    &external,
+                                    manifest_key.as_ref(),
+                                )
+                                .await
+                                {
+                                    Ok(()) => Some(StateSyncDumpProgress::AllDumped {
+                                        epoch_id,
+                                        epoch_height,
+                                    }),
+                                    Err(err) => {
+                                        tracing::warn!(target: "state_sync_dump", shard_id, epoch_height, ?err, "Failed to dump the state parts manifest. Will retry.");
+                                        None
+                                    }
+                                }
                             }
                             Ok(missing_parts) => {
                                 let mut parts_to_dump = missing_parts.clone();
@@ -346,6 +391,10 @@ async fn state_sync_dump(
                                         continue;
                                     }
 
+                                    dumped_parts.insert(
+                                        (sync_hash, part_id),
+                                        StatePartManifestEntry::new(part_id, &state_part),
+                                    );
                                     // Remove the dumped part from parts_to_dump so that we draw without replacement.
                                     parts_to_dump.swap_remove(selected_idx);
                                     update_dumped_size_and_cnt_metrics(
@@ -358,10 +407,34 @@ async fn state_sync_dump(
                                     dumped_any_state_part = true;
                                 }
                                 if parts_to_dump.is_empty() {
-                                    Some(StateSyncDumpProgress::AllDumped {
-                                        epoch_id,
+                                    match dump_state_parts_manifest(
+                                        shard_id,
+                                        &chain_id,
+                                        &epoch_id,
                                         epoch_height,
-                                    })
+                                        sync_hash,
+                                        &state_root,
+                                        num_parts,
+                                        &dumped_parts,
+                                        &external,
+                                        manifest_key.as_ref(),
+                                    )
+                                    .await
+                                    {
+                                        Ok(()) => Some(StateSyncDumpProgress::AllDumped {
+                                            epoch_id,
+                                            epoch_height,
+                                        }),
+                                        Err(err) => {
+                                            // All parts are dumped, the manifest will be retried in the next iteration.
+                                            tracing::warn!(target: "state_sync_dump", shard_id, epoch_height, ?err, "Failed to dump the state parts manifest. Will retry.");
+                                            Some(StateSyncDumpProgress::InProgress {
+                                                epoch_id,
+                                                epoch_height,
+                                                sync_hash,
+                                            })
+                                        }
+                                    }
                                 } else if dumped_any_state_part {
                                     Some(StateSyncDumpProgress::InProgress {
                                         epoch_id,
@@ -474,6 +547,68 @@ fn obtain_and_store_state_part(
     Ok(state_part)
 }
 
+/// Writes a manifest listing sizes and hashes of all state parts of the epoch,
+/// signed by `manifest_key`, if the dump is configured to write one.
+/// Must be called only after all state parts are dumped.
+/// Parts uploaded by this node are listed as uploaded. The others, dumped by
+/// another node or before a restart, are read back from the external storage.
+async fn dump_state_parts_manifest(
+    shard_id: ShardId,
+    chain_id: &str,
+    epoch_id: &EpochId,
+    epoch_height: EpochHeight,
+    sync_hash: CryptoHash,
+    state_root: &StateRoot,
+    num_parts: u64,
+    dumped_parts: &HashMap<(CryptoHash, u64), StatePartManifestEntry>,
+    external: &ExternalConnection,
+    manifest_key: Option<&SecretKey>,
+) -> Result<(), anyhow::Error> {
+    let Some(manifest_key) = manifest_key else {
+        return Ok(());
+    };
+    let mut parts = Vec::with_capacity(num_parts as usize);
+    for part_id in 0..num_parts {
+        let entry = match dumped_parts.get(&(sync_hash, part_id)) {
+            Some(entry) => entry.clone(),
+            None => {
+                let location = external_storage_location(
+                    chain_id,
+                    epoch_id,
+                    epoch_height,
+                    shard_id,
+                    part_id,
+                    num_parts,
+                );
+                let state_part = external.get_part(shard_id, &location).await?;
+                StatePartManifestEntry::new(part_id, &state_part)
+            }
+        };
+        parts.push(entry);
+    }
+    let manifest = SignedStatePartsManifest::new(
+        StatePartsManifest {
+            epoch_id: epoch_id.clone(),
+            epoch_height,
+            shard_id,
+            sync_hash,
+            state_root: *state_root,
+            parts,
+        },
+        manifest_key,
+    );
+    let location = external_storage_manifest_location(
+        chain_id,
+        epoch_id,
+        epoch_height,
+        shard_id,
+        &manifest.public_key,
+    );
+    external.put_state_part(&borsh::to_vec(&manifest)?, shard_id, &location).await?;
+    tracing::debug!(target: "state_sync_dump", shard_id, epoch_height, num_parts, public_key = %manifest.public_key, ?location, "Wrote the state parts manifest");
+    Ok(())
+}
+
 fn cares_about_shard(
     chain: &Chain,
     shard_id: &ShardId,
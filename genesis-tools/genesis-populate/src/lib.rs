@@ -178,10 +178,11 @@ impl GenesisBuilder {
         let mut state_update =
             self.state_updates.remove(&shard_idx).expect("State updates are always available");
         let protocol_config = self.runtime.get_protocol_config(&EpochId::default())?;
-        let storage_usage_config = protocol_config.runtime_config.fees.storage_usage_config;
 
         // Compute storage usage and update accounts.
-        for (account_id, storage_usage) in compute_storage_usage(&records, &storage_usage_config) {
+        for (account_id, storage_usage) in
+            compute_storage_usage(&records, &protocol_config.runtime_config)
+        {
             let mut account =
                 get_account(&state_update, &account_id)?.expect("We should've created account");
             account.set_storage_usage(storage_usage);
@@ -1,5 +1,5 @@
 use tempfile::tempdir;
-use unc_epoch_manager::shard_tracker::ShardTracker;
+use unc_epoch_manager::shard_tracker::{ShardTracker, TrackedConfig};
 use unc_epoch_manager::EpochManager;
 use unc_store::genesis::initialize_genesis_state;
 
@@ -23,7 +23,7 @@ pub fn genesis_header(genesis: &Genesis) -> BlockHeader {
     initialize_genesis_state(store.clone(), genesis, None);
     let chain_genesis = ChainGenesis::new(genesis);
     let epoch_manager = EpochManager::new_arc_handle(store.clone(), &genesis.config);
-    let shard_tracker = ShardTracker::new_empty(epoch_manager.clone());
+    let shard_tracker = ShardTracker::new(TrackedConfig::AllShards, epoch_manager.clone());
     let runtime =
         NightshadeRuntime::test(dir.path(), store, &genesis.config, epoch_manager.clone());
     let chain = Chain::new(
@@ -46,7 +46,7 @@ pub fn genesis_block(genesis: &Genesis) -> Block {
     initialize_genesis_state(store.clone(), genesis, None);
     let chain_genesis = ChainGenesis::new(genesis);
     let epoch_manager = EpochManager::new_arc_handle(store.clone(), &genesis.config);
-    let shard_tracker = ShardTracker::new_empty(epoch_manager.clone());
+    let shard_tracker = ShardTracker::new(TrackedConfig::AllShards, epoch_manager.clone());
     let runtime =
         NightshadeRuntime::test(dir.path(), store, &genesis.config, epoch_manager.clone());
     let chain = Chain::new(
@@ -985,6 +985,7 @@ fn client_sync_headers() {
                     connection_established_time: unc_async::time::Instant::now(),
                     peer_type: PeerType::Outbound,
                     nonce: 1,
+                    reputation_score: 0.,
                 }],
                 num_connected_peers: 1,
                 peer_max_count: 1,
@@ -1697,7 +1698,7 @@ fn test_gc_after_state_sync() {
     // mimic what we do in possible_targets
     assert!(env.clients[1].epoch_manager.get_epoch_id_from_prev_block(&prev_block_hash).is_ok());
     let tries = env.clients[1].runtime_adapter.get_tries();
-    env.clients[1].chain.clear_data(tries, &Default::default()).unwrap();
+    env.clients[1].chain.clear_data(tries, &Default::default(), None).unwrap();
 }
 
 #[test]
@@ -1,4 +1,5 @@
 use assert_matches::assert_matches;
+use borsh::BorshDeserialize;
 
 use framework::config::GenesisExt;
 use framework::state_sync::spawn_state_sync_dump;
@@ -11,17 +12,17 @@ use unc_chain::unc_chain_primitives::error::QueryError;
 use unc_chain::{ChainGenesis, ChainStoreAccess, Provenance};
 use unc_chain_configs::ExternalStorageLocation::Filesystem;
 use unc_chain_configs::{DumpConfig, Genesis};
-use unc_client::sync::external::external_storage_location;
+use unc_client::sync::external::{external_storage_location, external_storage_manifest_location};
 use unc_client::test_utils::TestEnv;
 use unc_client::ProcessTxResponse;
-use unc_crypto::{InMemorySigner, KeyType, Signer};
+use unc_crypto::{InMemorySigner, KeyType, SecretKey, Signer};
 use unc_network::test_utils::wait_or_timeout;
 use unc_o11y::testonly::init_test_logger;
 use unc_primitives::block::Tip;
 use unc_primitives::shard_layout::ShardUId;
 use unc_primitives::state::FlatStateValue;
 use unc_primitives::state_part::PartId;
-use unc_primitives::state_sync::StatePartKey;
+use unc_primitives::state_sync::{SignedStatePartsManifest, StatePartKey};
 use unc_primitives::transaction::SignedTransaction;
 use unc_primitives::types::BlockHeight;
 use unc_primitives::views::{QueryRequest, QueryResponseKind};
@@ -59,8 +60,10 @@ fn test_state_dump() {
             restart_dump_for_shards: None,
             iteration_delay: Some(Duration::ZERO),
             credentials_file: None,
+            state_parts_manifest: true,
         });
 
+        let node_key = SecretKey::from_seed(KeyType::ED25519, "test0");
         let _state_sync_dump_handle = spawn_state_sync_dump(
             &config,
             chain_genesis,
@@ -68,6 +71,7 @@ fn test_state_dump() {
             shard_tracker,
             runtime,
             Some("test0".parse().unwrap()),
+            node_key.clone(),
         )
         .unwrap();
 
@@ -103,6 +107,25 @@ fn test_state_dump() {
                         all_parts_present = false;
                     }
                 }
+                let path = root_dir.path().join(external_storage_manifest_location(
+                    "unittest",
+                    &epoch_id,
+                    epoch_height,
+                    shard_id,
+                    &node_key.public_key(),
+                ));
+                match std::fs::read(&path) {
+                    Ok(data) => {
+                        let manifest = SignedStatePartsManifest::try_from_slice(&data).unwrap();
+                        assert!(manifest.verify_signature());
+                        assert_eq!(manifest.public_key, node_key.public_key());
+                        assert_eq!(manifest.manifest.num_parts(), num_parts);
+                    }
+                    Err(_) => {
+                        tracing::info!("Missing {:?}", path);
+                        all_parts_present = false;
+                    }
+                }
             }
             if all_parts_present {
                 ControlFlow::Break(())
@@ -164,6 +187,7 @@ fn run_state_sync_with_dumped_parts(
             restart_dump_for_shards: None,
             iteration_delay: Some(Duration::ZERO),
             credentials_file: None,
+            state_parts_manifest: false,
         });
         let _state_sync_dump_handle = spawn_state_sync_dump(
             &config,
@@ -172,6 +196,7 @@ fn run_state_sync_with_dumped_parts(
             shard_tracker,
             runtime,
             Some("test0".parse().unwrap()),
+            SecretKey::from_seed(KeyType::ED25519, "test0"),
         )
         .unwrap();
 
@@ -441,7 +441,9 @@ fn sync_state_dump() {
                 restart_dump_for_shards: None,
                 iteration_delay: Some(Duration::from_millis(500)),
                 credentials_file: None,
+                state_parts_manifest: true,
             });
+            let dumper_key = unc1.network_config.node_key.public_key();
             unc1.config.store.state_snapshot_enabled = true;
             unc1.config.store.state_snapshot_compaction_enabled = false;
 
@@ -489,6 +491,8 @@ fn sync_state_dump() {
                                         },
                                         num_concurrent_requests: 1,
                                         num_concurrent_requests_during_catchup: 1,
+                                        verify_state_parts_manifest: true,
+                                        trusted_state_dumper_keys: vec![dumper_key.clone()],
                                     });
 
                                 let framework::UncNode {
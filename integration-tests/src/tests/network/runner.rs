@@ -16,7 +16,7 @@ use unc_chain::{Chain, ChainGenesis};
 use unc_chain_configs::ClientConfig;
 use unc_chunks::shards_manager_actor::start_shards_manager;
 use unc_client::{start_client, start_view_client, SyncAdapter};
-use unc_epoch_manager::shard_tracker::ShardTracker;
+use unc_epoch_manager::shard_tracker::{ShardTracker, TrackedConfig};
 use unc_network::actix::ActixSystem;
 use unc_network::blacklist;
 use unc_network::config;
@@ -53,7 +53,7 @@ fn setup_network_node(
 
     let vs = ValidatorSchedule::new().block_producers_per_epoch(vec![validators]);
     let epoch_manager = MockEpochManager::new_with_validators(store.get_hot_store(), vs, 5);
-    let shard_tracker = ShardTracker::new_empty(epoch_manager.clone());
+    let shard_tracker = ShardTracker::new(TrackedConfig::AllShards, epoch_manager.clone());
     let runtime = KeyValueRuntime::new(store.get_hot_store(), epoch_manager.as_ref());
     let signer = Arc::new(create_test_signer(account_id.as_str()));
     let telemetry_actor = TelemetryActor::new(TelemetryConfig::default()).start();
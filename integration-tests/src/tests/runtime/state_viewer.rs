@@ -6,6 +6,8 @@ use crate::runtime_utils::{get_runtime_and_trie, get_test_trie_viewer, TEST_SHAR
 use node_runtime::state_viewer::errors;
 use node_runtime::state_viewer::*;
 use testlib::runtime_utils::alice_account;
+use unc_crypto::{KeyType, PublicKey};
+use unc_primitives::transaction::{RegisterRsa2048KeysAction, Rsa2048KeysOperation};
 use unc_primitives::{
     account::Account,
     hash::hash as sha256,
@@ -16,12 +18,12 @@ use unc_primitives::{
     views::{StateItem, ViewApplyState},
 };
 use unc_primitives::{
-    test_utils::MockEpochInfoProvider,
+    test_utils::{chip_certificate, MockEpochInfoProvider},
     trie_key::TrieKey,
     types::{EpochId, StateChangeCause},
     version::PROTOCOL_VERSION,
 };
-use unc_store::{set_account, NibbleSlice, RawTrieNode, RawTrieNodeWithSize};
+use unc_store::{set_account, set_rsa2048_keys, NibbleSlice, RawTrieNode, RawTrieNodeWithSize};
 
 struct ProofVerifier {
     nodes: HashMap<CryptoHash, RawTrieNodeWithSize>,
@@ -383,6 +385,136 @@ fn test_view_state_with_large_contract() {
     assert!(result.is_ok());
 }
 
+#[test]
+fn test_view_chips() {
+    let (_, tries, root) = get_runtime_and_trie();
+    let shard_uid = TEST_SHARD_UID;
+    let mut state_update = tries.new_trie_update(shard_uid, root);
+    let register = |state_update: &mut unc_store::TrieUpdate,
+                    account_id: AccountId,
+                    public_key: PublicKey,
+                    miner_id: &str,
+                    sn: &str| {
+        let certificate = chip_certificate(miner_id, sn, 1);
+        let action = RegisterRsa2048KeysAction {
+            public_key: public_key.clone(),
+            operation_type: Rsa2048KeysOperation::AddKeys as u8,
+            args: certificate.to_args(),
+        };
+        set_rsa2048_keys(state_update, account_id, public_key, &action);
+    };
+    let mut public_keys = (0..5)
+        .map(|i| PublicKey::from_seed(KeyType::ED25519, &format!("chip{i}")))
+        .collect::<Vec<_>>();
+    for (i, public_key) in public_keys.iter().enumerate() {
+        let miner_id = if i % 2 == 0 { "miner-a" } else { "miner-b" };
+        register(
+            &mut state_update,
+            alice_account(),
+            public_key.clone(),
+            miner_id,
+            &format!("sn{i}"),
+        );
+    }
+    register(
+        &mut state_update,
+        "alina".parse().unwrap(),
+        PublicKey::from_seed(KeyType::ED25519, "alina-chip"),
+        "miner-a",
+        "sn-alina",
+    );
+    state_update.commit(StateChangeCause::InitialState);
+    let trie_changes = state_update.finalize().unwrap().1;
+    let mut db_changes = tries.store_update();
+    let new_root = tries.apply_all(&trie_changes, shard_uid, &mut db_changes);
+    db_changes.commit().unwrap();
+
+    let state_update = tries.new_trie_update(shard_uid, new_root);
+    let trie_viewer = TrieViewer::default();
+    let alice = alice_account();
+
+    // Walking the pages yields every chip of the account exactly once, in
+    // public key order.
+    public_keys.sort_by_key(|public_key| borsh::to_vec(public_key).unwrap());
+    let mut seen = vec![];
+    let mut cursor = None;
+    loop {
+        let page = trie_viewer
+            .view_chips(&state_update, &alice, None, None, cursor.as_ref(), Some(2), false)
+            .unwrap();
+        assert!(page.chips.len() <= 2);
+        assert!(page.proof.is_empty());
+        seen.extend(page.chips.into_iter().map(|chip| chip.public_key));
+        cursor = page.next_cursor;
+        if cursor.is_none() {
+            break;
+        }
+    }
+    assert_eq!(seen, public_keys.iter().map(ToString::to_string).collect::<Vec<_>>());
+
+    let page = trie_viewer
+        .view_chips(&state_update, &alice, Some("miner-a"), None, None, None, true)
+        .unwrap();
+    assert_eq!(page.chips.len(), 3);
+    assert!(page.chips.iter().all(|chip| chip.miner_id == "miner-a"));
+    assert_eq!(page.next_cursor, None);
+    assert!(!page.proof.is_empty());
+
+    let page = trie_viewer
+        .view_chips(&state_update, &alice, None, Some("sn3"), None, None, false)
+        .unwrap();
+    assert_eq!(page.chips.len(), 1);
+    assert_eq!(page.chips[0].sn, "sn3");
+}
+
+/// A chip whose certificate can't be decoded is skipped instead of failing
+/// the whole query.
+#[test]
+fn test_view_chips_skips_undecodable_certificates() {
+    let (_, tries, root) = get_runtime_and_trie();
+    let shard_uid = TEST_SHARD_UID;
+    let mut state_update = tries.new_trie_update(shard_uid, root);
+    let register = |state_update: &mut unc_store::TrieUpdate, seed: &str, args: Vec<u8>| {
+        let public_key = PublicKey::from_seed(KeyType::ED25519, seed);
+        let action = RegisterRsa2048KeysAction {
+            public_key: public_key.clone(),
+            operation_type: Rsa2048KeysOperation::AddKeys as u8,
+            args,
+        };
+        set_rsa2048_keys(state_update, alice_account(), public_key, &action);
+    };
+    register(&mut state_update, "chip", chip_certificate("miner-a", "sn0", 1).to_args());
+    register(&mut state_update, "broken-chip", b"not a certificate".to_vec());
+    register(
+        &mut state_update,
+        "legacy-chip",
+        br#"{"miner_id": "miner-a", "sn": "sn1", "power": "1", "public_key": "legacy"}"#.to_vec(),
+    );
+    state_update.commit(StateChangeCause::InitialState);
+    let trie_changes = state_update.finalize().unwrap().1;
+    let mut db_changes = tries.store_update();
+    let new_root = tries.apply_all(&trie_changes, shard_uid, &mut db_changes);
+    db_changes.commit().unwrap();
+
+    let state_update = tries.new_trie_update(shard_uid, new_root);
+    let trie_viewer = TrieViewer::default();
+    let mut expected =
+        vec![PublicKey::from_seed(KeyType::ED25519, "chip").to_string(), "legacy".to_string()];
+    expected.sort();
+
+    let page = trie_viewer
+        .view_chips(&state_update, &alice_account(), Some("miner-a"), None, None, None, false)
+        .unwrap();
+    let mut public_keys = page.chips.into_iter().map(|chip| chip.public_key).collect::<Vec<_>>();
+    public_keys.sort();
+    assert_eq!(public_keys, expected);
+
+    let chips = trie_viewer.view_chip_list(&state_update, &alice_account()).unwrap();
+    let mut public_keys = chips.into_iter().map(|chip| chip.public_key).collect::<Vec<_>>();
+    public_keys.sort();
+    assert_eq!(public_keys, expected);
+}
+
 #[test]
 fn test_log_when_panic() {
     let (viewer, root) = get_test_trie_viewer();
@@ -5,12 +5,13 @@ mod runtime;
 
 use assert_matches::assert_matches;
 use framework::config::{TESTING_INIT_BALANCE, TESTING_INIT_PLEDGE, UNC_BASE};
-use unc_crypto::{InMemorySigner, KeyType, PublicKey};
+use unc_crypto::{InMemorySigner, KeyType, PublicKey, SecretKey};
 use unc_jsonrpc_primitives::errors::ServerError;
 use unc_parameters::{ActionCosts, ExtCosts};
 use unc_primitives::account::{
     id::AccountType, AccessKey, AccessKeyPermission, FunctionCallPermission,
 };
+use unc_primitives::checked_feature;
 use unc_primitives::errors::{
     ActionError, ActionErrorKind, FunctionCallError, InvalidAccessKeyError, InvalidTxError,
     MethodResolveError, TxExecutionError,
@@ -30,9 +31,14 @@ use testlib::runtime_utils::{
     alice_account, bob_account, eve_dot_alice_account, x_dot_y_dot_alice_account,
 };
 use unc_parameters::RuntimeConfig;
+use unc_primitives::action::rsa2048::{Rsa2048ChallengeArgs, Rsa2048ChallengeMessage};
 use unc_primitives::receipt::{ActionReceipt, Receipt, ReceiptEnum};
 use unc_primitives::test_utils;
-use unc_primitives::transaction::{Action, DeployContractAction, FunctionCallAction};
+use unc_primitives::transaction::{
+    Action, CreateRsa2048ChallengeAction, DeployContractAction, FunctionCallAction,
+    RegisterRsa2048KeysAction, Rsa2048KeysOperation,
+};
+use unc_primitives::version::PROTOCOL_VERSION;
 
 /// The amount to send with function call.
 const FUNCTION_CALL_AMOUNT: Balance = TESTING_INIT_BALANCE / 10;
@@ -1192,6 +1198,309 @@ pub fn test_unpledge_while_not_pledged(node: impl Node) {
     assert_eq!(transaction_result.receipts_outcome.len(), 1);
 }
 
+const CHIP_SN: &str = "sn-0001";
+const CHIP_POWER: u64 = 1000;
+
+/// Registers a chip under the node account, which must be the chip registrar `unc`.
+fn register_rsa2048_chip(node: &impl Node, chip_key: &PublicKey) {
+    let args = test_utils::chip_certificate("miner", CHIP_SN, CHIP_POWER).to_args();
+    register_rsa2048_chip_with_args(node, chip_key, args);
+}
+
+fn register_rsa2048_chip_with_args(node: &impl Node, chip_key: &PublicKey, args: Vec<u8>) {
+    let registrar_id = node.account_id().unwrap();
+    let transaction_result = node
+        .user()
+        .sign_and_commit_actions(
+            registrar_id.clone(),
+            registrar_id,
+            vec![Action::RegisterRsa2048Keys(Box::new(RegisterRsa2048KeysAction {
+                public_key: chip_key.clone(),
+                operation_type: Rsa2048KeysOperation::AddKeys as u8,
+                args,
+            }))],
+        )
+        .unwrap();
+    assert_eq!(transaction_result.status, FinalExecutionStatus::SuccessValue(Vec::new()));
+}
+
+/// Revokes a chip held by `owner_id`, signed by the node account.
+fn revoke_rsa2048_chip(
+    node: &impl Node,
+    owner_id: AccountId,
+    chip_key: &PublicKey,
+) -> FinalExecutionOutcomeView {
+    node.user()
+        .sign_and_commit_actions(
+            node.account_id().unwrap(),
+            owner_id,
+            vec![Action::RegisterRsa2048Keys(Box::new(RegisterRsa2048KeysAction {
+                public_key: chip_key.clone(),
+                operation_type: Rsa2048KeysOperation::DeleteKeys as u8,
+                args: vec![],
+            }))],
+        )
+        .unwrap()
+}
+
+/// Sends a chip challenge from alice with the given raw args.
+fn create_rsa2048_challenge(
+    node: &impl Node,
+    chip_key: &PublicKey,
+    args: Vec<u8>,
+) -> FinalExecutionOutcomeView {
+    let mut node_user = node.user();
+    node_user.set_signer(Arc::new(InMemorySigner::from_seed(
+        alice_account(),
+        KeyType::ED25519,
+        alice_account().as_ref(),
+    )));
+    node_user
+        .sign_and_commit_actions(
+            alice_account(),
+            alice_account(),
+            vec![Action::CreateRsa2048Challenge(Box::new(CreateRsa2048ChallengeAction {
+                public_key: chip_key.clone(),
+                challenge_key: node.block_signer().public_key(),
+                args,
+            }))],
+        )
+        .unwrap()
+}
+
+fn rsa2048_challenge_message() -> Rsa2048ChallengeMessage {
+    Rsa2048ChallengeMessage {
+        miner_id: alice_account(),
+        sn: CHIP_SN.to_string(),
+        nonce: 1,
+        power: CHIP_POWER,
+    }
+}
+
+fn signed_rsa2048_challenge_args(
+    chip_secret: &SecretKey,
+    message: Rsa2048ChallengeMessage,
+) -> Vec<u8> {
+    let signature = chip_secret.sign(message.get_hash().as_ref());
+    serde_json::to_vec(&Rsa2048ChallengeArgs { message, signature: Some(signature) }).unwrap()
+}
+
+fn assert_rsa2048_action_failed(
+    transaction_result: &FinalExecutionOutcomeView,
+    kind: ActionErrorKind,
+) {
+    assert_eq!(
+        transaction_result.status,
+        FinalExecutionStatus::Failure(ActionError { index: Some(0), kind }.into())
+    );
+}
+
+pub fn test_rsa2048_challenge_ok(node: impl Node) {
+    let chip_secret = SecretKey::from_seed(KeyType::RSA2048, "chip");
+    let chip_key = chip_secret.public_key();
+    register_rsa2048_chip(&node, &chip_key);
+    let power_before = node.user().view_account(&alice_account()).unwrap().power;
+
+    let args = signed_rsa2048_challenge_args(&chip_secret, rsa2048_challenge_message());
+    let transaction_result = create_rsa2048_challenge(&node, &chip_key, args.clone());
+    assert_eq!(transaction_result.status, FinalExecutionStatus::SuccessValue(Vec::new()));
+    let account = node.user().view_account(&alice_account()).unwrap();
+    assert_eq!(account.power, power_before + CHIP_POWER);
+
+    // The chip now belongs to the miner, so it can't be claimed again.
+    let transaction_result = create_rsa2048_challenge(&node, &chip_key, args);
+    assert_rsa2048_action_failed(
+        &transaction_result,
+        ActionErrorKind::RsaKeysNotFound {
+            account_id: alice_account(),
+            public_key: chip_key.into(),
+        },
+    );
+}
+
+/// Before `ChipCertificate` the challenge args aren't checked, and the power is
+/// taken from the JSON args the chip was registered with.
+pub fn test_rsa2048_challenge_json_args(node: impl Node) {
+    let chip_key = SecretKey::from_seed(KeyType::RSA2048, "chip").public_key();
+    let args = serde_json::to_vec(&serde_json::json!({ "power": CHIP_POWER.to_string() })).unwrap();
+    register_rsa2048_chip_with_args(&node, &chip_key, args);
+    let power_before = node.user().view_account(&alice_account()).unwrap().power;
+
+    let transaction_result = create_rsa2048_challenge(&node, &chip_key, vec![]);
+    assert_eq!(transaction_result.status, FinalExecutionStatus::SuccessValue(Vec::new()));
+    let account = node.user().view_account(&alice_account()).unwrap();
+    assert_eq!(account.power, power_before + CHIP_POWER);
+}
+
+pub fn test_rsa2048_challenge_args_malformed(node: impl Node) {
+    let chip_key = SecretKey::from_seed(KeyType::RSA2048, "chip").public_key();
+    register_rsa2048_chip(&node, &chip_key);
+    let transaction_result = create_rsa2048_challenge(&node, &chip_key, b"not json".to_vec());
+    assert_rsa2048_action_failed(
+        &transaction_result,
+        ActionErrorKind::Rsa2048ChallengeArgsMalformed {
+            account_id: alice_account(),
+            public_key: chip_key.into(),
+        },
+    );
+}
+
+/// Expects the node to hold the chip seeded with `"chip"` under args that
+/// aren't a chip certificate, as registered before `ChipCertificate`.
+pub fn test_rsa2048_keys_args_malformed(node: impl Node) {
+    let chip_secret = SecretKey::from_seed(KeyType::RSA2048, "chip");
+    let chip_key = chip_secret.public_key();
+    let args = signed_rsa2048_challenge_args(&chip_secret, rsa2048_challenge_message());
+    let transaction_result = create_rsa2048_challenge(&node, &chip_key, args);
+    assert_rsa2048_action_failed(
+        &transaction_result,
+        ActionErrorKind::Rsa2048KeysArgsMalformed {
+            account_id: alice_account(),
+            public_key: chip_key.into(),
+        },
+    );
+}
+
+pub fn test_rsa2048_challenge_not_signed(node: impl Node) {
+    let chip_key = SecretKey::from_seed(KeyType::RSA2048, "chip").public_key();
+    register_rsa2048_chip(&node, &chip_key);
+    let args = serde_json::to_vec(&Rsa2048ChallengeArgs {
+        message: rsa2048_challenge_message(),
+        signature: None,
+    })
+    .unwrap();
+    let transaction_result = create_rsa2048_challenge(&node, &chip_key, args);
+    assert_rsa2048_action_failed(
+        &transaction_result,
+        ActionErrorKind::Rsa2048ChallengeNotSigned {
+            account_id: alice_account(),
+            public_key: chip_key.into(),
+        },
+    );
+}
+
+pub fn test_rsa2048_challenge_invalid_signature(node: impl Node) {
+    let chip_key = SecretKey::from_seed(KeyType::RSA2048, "chip").public_key();
+    register_rsa2048_chip(&node, &chip_key);
+    let other_chip_secret = SecretKey::from_seed(KeyType::RSA2048, "other-chip");
+    let args = signed_rsa2048_challenge_args(&other_chip_secret, rsa2048_challenge_message());
+    let transaction_result = create_rsa2048_challenge(&node, &chip_key, args);
+    assert_rsa2048_action_failed(
+        &transaction_result,
+        ActionErrorKind::Rsa2048ChallengeInvalidSignature {
+            account_id: alice_account(),
+            public_key: chip_key.into(),
+        },
+    );
+}
+
+pub fn test_rsa2048_challenge_args_mismatch(node: impl Node) {
+    let chip_secret = SecretKey::from_seed(KeyType::RSA2048, "chip");
+    let chip_key = chip_secret.public_key();
+    register_rsa2048_chip(&node, &chip_key);
+    let mut message = rsa2048_challenge_message();
+    message.power = CHIP_POWER * 2;
+    let args = signed_rsa2048_challenge_args(&chip_secret, message);
+    let transaction_result = create_rsa2048_challenge(&node, &chip_key, args);
+    assert_rsa2048_action_failed(
+        &transaction_result,
+        ActionErrorKind::Rsa2048ChallengeArgsMismatch {
+            account_id: alice_account(),
+            public_key: chip_key.into(),
+        },
+    );
+}
+
+pub fn test_rsa2048_revoke_unclaimed(node: impl Node) {
+    let chip_secret = SecretKey::from_seed(KeyType::RSA2048, "chip");
+    let chip_key = chip_secret.public_key();
+    let registrar_id = node.account_id().unwrap();
+    let storage_before = node.user().view_account(&registrar_id).unwrap().storage_usage;
+    register_rsa2048_chip(&node, &chip_key);
+
+    let transaction_result = revoke_rsa2048_chip(&node, registrar_id.clone(), &chip_key);
+    assert_eq!(transaction_result.status, FinalExecutionStatus::SuccessValue(Vec::new()));
+    let account = node.user().view_account(&registrar_id).unwrap();
+    assert_eq!(account.storage_usage, storage_before);
+
+    let args = signed_rsa2048_challenge_args(&chip_secret, rsa2048_challenge_message());
+    let transaction_result = create_rsa2048_challenge(&node, &chip_key, args);
+    assert_rsa2048_action_failed(
+        &transaction_result,
+        ActionErrorKind::RsaKeysNotFound {
+            account_id: alice_account(),
+            public_key: chip_key.into(),
+        },
+    );
+}
+
+pub fn test_rsa2048_revoke_claimed(node: impl Node) {
+    let chip_secret = SecretKey::from_seed(KeyType::RSA2048, "chip");
+    let chip_key = chip_secret.public_key();
+    register_rsa2048_chip(&node, &chip_key);
+    let account_before = node.user().view_account(&alice_account()).unwrap();
+
+    let args = signed_rsa2048_challenge_args(&chip_secret, rsa2048_challenge_message());
+    let transaction_result = create_rsa2048_challenge(&node, &chip_key, args);
+    assert_eq!(transaction_result.status, FinalExecutionStatus::SuccessValue(Vec::new()));
+
+    let transaction_result = revoke_rsa2048_chip(&node, alice_account(), &chip_key);
+    assert_eq!(transaction_result.status, FinalExecutionStatus::SuccessValue(Vec::new()));
+    let account = node.user().view_account(&alice_account()).unwrap();
+    assert_eq!(account.power, account_before.power);
+    assert_eq!(account.storage_usage, account_before.storage_usage);
+
+    let transaction_result = revoke_rsa2048_chip(&node, alice_account(), &chip_key);
+    assert_rsa2048_action_failed(
+        &transaction_result,
+        ActionErrorKind::RsaKeysNotFound {
+            account_id: alice_account(),
+            public_key: chip_key.into(),
+        },
+    );
+}
+
+pub fn test_rsa2048_revoke_not_permitted(node: impl Node) {
+    let chip_secret = SecretKey::from_seed(KeyType::RSA2048, "chip");
+    let chip_key = chip_secret.public_key();
+    register_rsa2048_chip(&node, &chip_key);
+    let args = signed_rsa2048_challenge_args(&chip_secret, rsa2048_challenge_message());
+    let transaction_result = create_rsa2048_challenge(&node, &chip_key, args);
+    assert_eq!(transaction_result.status, FinalExecutionStatus::SuccessValue(Vec::new()));
+
+    let mut node_user = node.user();
+    node_user.set_signer(Arc::new(InMemorySigner::from_seed(
+        alice_account(),
+        KeyType::ED25519,
+        alice_account().as_ref(),
+    )));
+    let transaction_result = node_user.sign_and_commit_actions(
+        alice_account(),
+        alice_account(),
+        vec![Action::RegisterRsa2048Keys(Box::new(RegisterRsa2048KeysAction {
+            public_key: chip_key,
+            operation_type: Rsa2048KeysOperation::DeleteKeys as u8,
+            args: vec![],
+        }))],
+    );
+    if checked_feature!("stable", ChipCertificate, PROTOCOL_VERSION) {
+        assert_eq!(
+            transaction_result.unwrap_err(),
+            ServerError::TxExecutionError(TxExecutionError::InvalidTxError(
+                InvalidTxError::ChipRegistrarRequired { signer_id: alice_account() }
+            ))
+        );
+    } else {
+        assert_rsa2048_action_failed(
+            &transaction_result.unwrap(),
+            ActionErrorKind::ActorNoPermission {
+                account_id: alice_account(),
+                actor_id: alice_account(),
+            },
+        );
+    }
+}
+
 /// Account must have enough balance to cover storage of the account.
 pub fn test_fail_not_enough_balance_for_storage(node: impl Node) {
     let mut node_user = node.user();
@@ -4,19 +4,24 @@
 use crate::node::{create_nodes_from_seeds, Node, NodeConfig, ThreadNode};
 use crate::test_helpers::heavy_test;
 use crate::tests::standard_cases::*;
+use crate::user::rpc_user::RpcUser;
 use std::thread;
 use std::time::Duration;
 use testlib::runtime_utils::alice_account;
+use unc_crypto::{KeyType, SecretKey};
 use unc_o11y::testonly::init_test_module_logger;
+use unc_primitives::views::FinalExecutionStatus;
 
 fn create_thread_nodes_rpc() -> Vec<ThreadNode> {
+    let nodes =
+        create_thread_nodes_rpc_from_seeds(&["alice.unc", "bob.unc", "carol.unc", "dan.unc"]);
+    assert_eq!(nodes[0].account_id().unwrap(), alice_account());
+    nodes
+}
+
+fn create_thread_nodes_rpc_from_seeds(seeds: &[&str]) -> Vec<ThreadNode> {
     init_test_module_logger("runtime");
-    let nodes = create_nodes_from_seeds(vec![
-        "alice.unc".to_string(),
-        "bob.unc".to_string(),
-        "carol.unc".to_string(),
-        "dan.unc".to_string(),
-    ]);
+    let nodes = create_nodes_from_seeds(seeds.iter().map(|seed| seed.to_string()).collect());
     let mut nodes: Vec<_> = nodes
         .into_iter()
         .map(|cfg| match cfg {
@@ -24,9 +29,6 @@ fn create_thread_nodes_rpc() -> Vec<ThreadNode> {
             _ => unreachable!(),
         })
         .collect();
-    let account_names: Vec<_> = nodes.iter().map(|node| node.account_id().unwrap()).collect();
-
-    assert_eq!(account_names[0], alice_account());
     for i in 0..nodes.len() {
         nodes[i].start();
     }
@@ -215,3 +217,39 @@ fn test_delete_access_key_with_allowance_testnet() {
 fn test_access_key_smart_contract_testnet() {
     run_testnet_test!(test_access_key_smart_contract);
 }
+
+/// The block applying a chip challenge reports the miner power it added.
+#[test]
+#[cfg_attr(not(feature = "expensive_tests"), ignore)]
+fn test_power_changes_testnet() {
+    heavy_test(|| {
+        // The first node is the chip registrar `unc`.
+        let mut nodes =
+            create_thread_nodes_rpc_from_seeds(&["unc", "alice.unc", "bob.unc", "carol.unc"]);
+        let node = nodes.remove(0);
+        let chip_secret = SecretKey::from_seed(KeyType::RSA2048, "chip");
+        let chip_key = chip_secret.public_key();
+        register_rsa2048_chip(&node, &chip_key);
+        let power_before = node.user().view_account(&alice_account()).unwrap().power;
+
+        let args = signed_rsa2048_challenge_args(&chip_secret, rsa2048_challenge_message());
+        let transaction_result = create_rsa2048_challenge(&node, &chip_key, args);
+        assert_eq!(transaction_result.status, FinalExecutionStatus::SuccessValue(Vec::new()));
+
+        let block_hash = transaction_result.receipts_outcome[0].block_hash;
+        let rpc_user = RpcUser::new(
+            &node.config.rpc_addr().unwrap(),
+            node.account_id().unwrap(),
+            node.signer(),
+        );
+        let response = rpc_user.power_changes(block_hash, vec![alice_account()]).unwrap();
+        assert_eq!(response.block_hash, block_hash);
+        let [change] = response.changes.as_slice() else {
+            panic!("expected a single power change, got {:?}", response.changes);
+        };
+        assert_eq!(change.account_id, alice_account());
+        assert_eq!(change.chip_public_key, Some(chip_key));
+        assert_eq!(change.power_before, power_before);
+        assert_eq!(change.power_after, power_before + CHIP_POWER);
+    });
+}
@@ -16,6 +16,32 @@ fn create_free_runtime_node() -> RuntimeNode {
     RuntimeNode::free(&alice_account())
 }
 
+/// Runtime node signing as `unc`, the account holding unclaimed chip keys.
+fn create_runtime_node_with_chip_registrar() -> RuntimeNode {
+    let registrar_id: AccountId = "unc".parse().unwrap();
+    let genesis = Genesis::test(vec![registrar_id.clone(), alice_account(), bob_account()], 2);
+    RuntimeNode::new_from_genesis(&registrar_id, genesis)
+}
+
+/// Same as `create_runtime_node_with_chip_registrar`, with the chip seeded with
+/// `"chip"` already registered under malformed args.
+#[cfg(feature = "nightly")]
+fn create_runtime_node_with_malformed_chip() -> RuntimeNode {
+    let registrar_id: AccountId = "unc".parse().unwrap();
+    let mut genesis = Genesis::test(vec![registrar_id.clone(), alice_account(), bob_account()], 2);
+    let chip_key = SecretKey::from_seed(KeyType::RSA2048, "chip").public_key();
+    genesis.force_read_records().as_mut().push(StateRecord::Rsa2048Keys {
+        account_id: registrar_id.clone(),
+        public_key: chip_key.clone(),
+        rsa2048_keys: RegisterRsa2048KeysAction {
+            public_key: chip_key,
+            operation_type: Rsa2048KeysOperation::AddKeys as u8,
+            args: b"not a certificate".to_vec(),
+        },
+    });
+    RuntimeNode::new_from_genesis(&registrar_id, genesis)
+}
+
 fn create_runtime_with_expensive_storage() -> RuntimeNode {
     let mut genesis =
         Genesis::test(vec![alice_account(), bob_account(), "carol.unc".parse().unwrap()], 1);
@@ -369,3 +395,72 @@ fn test_storage_read_write_costs_runtime() {
     let runtime_config = node.client.as_ref().read().unwrap().runtime_config.clone();
     test_storage_read_write_costs(node, runtime_config);
 }
+
+#[test]
+#[cfg(feature = "nightly")]
+fn test_rsa2048_challenge_ok_runtime() {
+    let node = create_runtime_node_with_chip_registrar();
+    test_rsa2048_challenge_ok(node);
+}
+
+#[test]
+#[cfg(not(feature = "nightly"))]
+fn test_rsa2048_challenge_json_args_runtime() {
+    let node = create_runtime_node_with_chip_registrar();
+    test_rsa2048_challenge_json_args(node);
+}
+
+#[test]
+#[cfg(feature = "nightly")]
+fn test_rsa2048_challenge_args_malformed_runtime() {
+    let node = create_runtime_node_with_chip_registrar();
+    test_rsa2048_challenge_args_malformed(node);
+}
+
+#[test]
+#[cfg(feature = "nightly")]
+fn test_rsa2048_keys_args_malformed_runtime() {
+    let node = create_runtime_node_with_malformed_chip();
+    test_rsa2048_keys_args_malformed(node);
+}
+
+#[test]
+#[cfg(feature = "nightly")]
+fn test_rsa2048_challenge_not_signed_runtime() {
+    let node = create_runtime_node_with_chip_registrar();
+    test_rsa2048_challenge_not_signed(node);
+}
+
+#[test]
+#[cfg(feature = "nightly")]
+fn test_rsa2048_challenge_invalid_signature_runtime() {
+    let node = create_runtime_node_with_chip_registrar();
+    test_rsa2048_challenge_invalid_signature(node);
+}
+
+#[test]
+#[cfg(feature = "nightly")]
+fn test_rsa2048_challenge_args_mismatch_runtime() {
+    let node = create_runtime_node_with_chip_registrar();
+    test_rsa2048_challenge_args_mismatch(node);
+}
+
+#[test]
+#[cfg(feature = "nightly")]
+fn test_rsa2048_revoke_unclaimed_runtime() {
+    let node = create_runtime_node_with_chip_registrar();
+    test_rsa2048_revoke_unclaimed(node);
+}
+
+#[test]
+#[cfg(feature = "nightly")]
+fn test_rsa2048_revoke_claimed_runtime() {
+    let node = create_runtime_node_with_chip_registrar();
+    test_rsa2048_revoke_claimed(node);
+}
+
+#[test]
+fn test_rsa2048_revoke_not_permitted_runtime() {
+    let node = create_runtime_node_with_chip_registrar();
+    test_rsa2048_revoke_not_permitted(node);
+}
@@ -9,6 +9,7 @@ use unc_crypto::{PublicKey, Signer};
 use unc_jsonrpc::client::{new_client, JsonRpcClient};
 use unc_jsonrpc_client::ChunkId;
 use unc_jsonrpc_primitives::errors::ServerError;
+use unc_jsonrpc_primitives::types::changes::{RpcPowerChangesRequest, RpcPowerChangesResponse};
 use unc_jsonrpc_primitives::types::query::{QueryResponseKind, RpcQueryRequest, RpcQueryResponse};
 use unc_jsonrpc_primitives::types::transactions::{RpcTransactionStatusRequest, TransactionInfo};
 use unc_primitives::hash::CryptoHash;
@@ -64,6 +65,20 @@ impl RpcUser {
             client.validators(epoch_id_or_block_id).map_err(|err| err.to_string())
         })
     }
+
+    pub fn power_changes(
+        &self,
+        block_hash: CryptoHash,
+        account_ids: Vec<AccountId>,
+    ) -> Result<RpcPowerChangesResponse, String> {
+        let request = RpcPowerChangesRequest {
+            block_reference: BlockReference::BlockId(BlockId::Hash(block_hash)),
+            account_ids,
+        };
+        self.actix(move |client| {
+            client.EXPERIMENTAL_power_changes(request).map_err(|err| err.to_string())
+        })
+    }
 }
 
 impl User for RpcUser {
@@ -11,8 +11,11 @@ use crate::gas_cost::{GasCost, NonNegativeTolerance};
 use crate::transaction_builder::AccountRequirement;
 use crate::utils::{average_cost, percentiles};
 use std::iter;
-use unc_crypto::{KeyType, PublicKey};
+use unc_crypto::{KeyType, PublicKey, SecretKey};
 use unc_primitives::account::{AccessKey, AccessKeyPermission, FunctionCallPermission};
+use unc_primitives::action::rsa2048::{
+    ChipCertificate, Rsa2048ChallengeArgs, Rsa2048ChallengeMessage,
+};
 use unc_primitives::hash::CryptoHash;
 use unc_primitives::receipt::{ActionReceipt, Receipt};
 use unc_primitives::transaction::Action;
@@ -624,6 +627,142 @@ pub(crate) fn new_action_receipt_exec(ctx: &mut EstimatorContext) -> GasCost {
         .apply_cost(&mut ctx.testbed())
 }
 
+// The chip key actions only execute successfully when sent by a chip registrar
+// for registered chip keys, which the estimator testbed does not provide.
+// Their exec costs are therefore put together from the access key estimations
+// for the storage work and native measurements of the CPU work the runtime
+// does on top: decoding the chip certificate and, for challenges, parsing the
+// challenge arguments and verifying their RSA2048 signature.
+pub(crate) fn register_rsa2048_keys(ctx: &mut EstimatorContext) -> GasCost {
+    register_rsa2048_keys_send_not_sir(ctx) + register_rsa2048_keys_exec(ctx)
+}
+
+pub(crate) fn register_rsa2048_keys_byte(ctx: &mut EstimatorContext) -> GasCost {
+    register_rsa2048_keys_byte_send_not_sir(ctx) + register_rsa2048_keys_byte_exec(ctx)
+}
+
+pub(crate) fn create_rsa2048_challenge(ctx: &mut EstimatorContext) -> GasCost {
+    create_rsa2048_challenge_send_not_sir(ctx) + create_rsa2048_challenge_exec(ctx)
+}
+
+pub(crate) fn create_rsa2048_challenge_byte(ctx: &mut EstimatorContext) -> GasCost {
+    create_rsa2048_challenge_byte_send_not_sir(ctx) + create_rsa2048_challenge_byte_exec(ctx)
+}
+
+pub(crate) fn register_rsa2048_keys_send_not_sir(ctx: &mut EstimatorContext) -> GasCost {
+    ActionEstimation::new(ctx)
+        .add_action(register_rsa2048_keys_action(ActionSize::Min))
+        .verify_cost(&mut ctx.testbed())
+}
+
+pub(crate) fn register_rsa2048_keys_exec(ctx: &mut EstimatorContext) -> GasCost {
+    // Adding keys stores a key record, revoking claimed keys decodes the certificate.
+    let args = chip_certificate_args(ActionSize::Min);
+    let decode = native_cost(ctx, || {
+        ChipCertificate::try_from_args(&args, true).unwrap();
+    });
+    add_full_access_key_exec(ctx) + decode
+}
+
+pub(crate) fn register_rsa2048_keys_byte_send_not_sir(ctx: &mut EstimatorContext) -> GasCost {
+    ActionEstimation::new(ctx)
+        .add_action(register_rsa2048_keys_action(ActionSize::Max))
+        .min_gas(GAS_100_PICOSECONDS)
+        .verify_cost(&mut ctx.testbed())
+        / ActionSize::Max.rsa2048_args()
+}
+
+pub(crate) fn register_rsa2048_keys_byte_exec(ctx: &mut EstimatorContext) -> GasCost {
+    let decode = |ctx: &EstimatorContext, size: ActionSize| {
+        let args = chip_certificate_args(size);
+        native_cost(ctx, || {
+            ChipCertificate::try_from_args(&args, true).unwrap();
+        })
+    };
+    let base = decode(ctx, ActionSize::Min);
+    let total = decode(ctx, ActionSize::Max);
+    // The args are stored with the key like the method names of an access key.
+    add_function_call_key_byte_exec(ctx)
+        + total.saturating_sub(&base, &NonNegativeTolerance::PER_MILLE)
+            / ActionSize::Max.rsa2048_args()
+}
+
+pub(crate) fn create_rsa2048_challenge_send_not_sir(ctx: &mut EstimatorContext) -> GasCost {
+    ActionEstimation::new(ctx)
+        .add_action(create_rsa2048_challenge_action(ActionSize::Min))
+        .verify_cost(&mut ctx.testbed())
+}
+
+pub(crate) fn create_rsa2048_challenge_exec(ctx: &mut EstimatorContext) -> GasCost {
+    // Claiming a chip moves its key record from the registrar to the miner.
+    let storage = delete_key_exec(ctx) + add_full_access_key_exec(ctx);
+    storage + check_rsa2048_challenge_cost(ctx, ActionSize::Min)
+}
+
+pub(crate) fn create_rsa2048_challenge_byte_send_not_sir(ctx: &mut EstimatorContext) -> GasCost {
+    ActionEstimation::new(ctx)
+        .add_action(create_rsa2048_challenge_action(ActionSize::Max))
+        .min_gas(GAS_100_PICOSECONDS)
+        .verify_cost(&mut ctx.testbed())
+        / ActionSize::Max.rsa2048_args()
+}
+
+pub(crate) fn create_rsa2048_challenge_byte_exec(ctx: &mut EstimatorContext) -> GasCost {
+    let base = check_rsa2048_challenge_cost(ctx, ActionSize::Min);
+    let total = check_rsa2048_challenge_cost(ctx, ActionSize::Max);
+    total.saturating_sub(&base, &NonNegativeTolerance::PER_MILLE) / ActionSize::Max.rsa2048_args()
+}
+
+/// Native cost of checking a chip challenge with `size` bytes of args against
+/// the registered certificate, as done by the runtime on execution.
+fn check_rsa2048_challenge_cost(ctx: &EstimatorContext, size: ActionSize) -> GasCost {
+    let chip_key = SecretKey::from_seed(KeyType::RSA2048, "chip-key-seed");
+    let certificate_args = chip_certificate_args(ActionSize::Min);
+    let message = Rsa2048ChallengeMessage {
+        miner_id: genesis_populate::get_account_id(0),
+        sn: padded_chip_sn(size),
+        nonce: 1,
+        power: 1,
+    };
+    let signature = chip_key.sign(message.get_hash().as_ref());
+    let challenge_args =
+        serde_json::to_vec(&Rsa2048ChallengeArgs { message, signature: Some(signature) }).unwrap();
+    let public_key = chip_key.public_key();
+    native_cost(ctx, || {
+        ChipCertificate::try_from_args(&certificate_args, true).unwrap();
+        let args = Rsa2048ChallengeArgs::try_from_slice(&challenge_args).unwrap();
+        assert!(args.verify(&public_key));
+    })
+}
+
+/// Borsh-serialized chip certificate with roughly `size` bytes of payload.
+fn chip_certificate_args(size: ActionSize) -> Vec<u8> {
+    ChipCertificate::new(
+        genesis_populate::get_account_id(0).to_string(),
+        padded_chip_sn(size),
+        1,
+        "bus".to_string(),
+        "p2key".to_string(),
+    )
+    .to_args()
+}
+
+fn padded_chip_sn(size: ActionSize) -> String {
+    format!("sn-{}", "0".repeat(size.rsa2048_args() as usize))
+}
+
+/// Measures the average native cost of `f` outside of any transaction.
+fn native_cost(ctx: &EstimatorContext, mut f: impl FnMut()) -> GasCost {
+    let n_iters = 1000;
+    // warm up caches
+    f();
+    let start = GasCost::measure(ctx.config.metric);
+    for _ in 0..n_iters {
+        f();
+    }
+    start.elapsed() / n_iters
+}
+
 pub(crate) fn delegate_send_sir(ctx: &mut EstimatorContext) -> GasCost {
     let receiver_id: AccountId = "a".repeat(AccountId::MAX_LEN).parse().unwrap();
     let sender_id: AccountId = genesis_populate::get_account_id(0);
@@ -698,6 +837,22 @@ fn delete_account_action() -> Action {
     })
 }
 
+fn register_rsa2048_keys_action(size: ActionSize) -> Action {
+    Action::RegisterRsa2048Keys(Box::new(unc_primitives::action::RegisterRsa2048KeysAction {
+        public_key: PublicKey::from_seed(KeyType::RSA2048, "chip-key-seed"),
+        operation_type: unc_primitives::action::Rsa2048KeysOperation::AddKeys as u8,
+        args: vec![1u8; size.rsa2048_args() as usize],
+    }))
+}
+
+fn create_rsa2048_challenge_action(size: ActionSize) -> Action {
+    Action::CreateRsa2048Challenge(Box::new(unc_primitives::action::CreateRsa2048ChallengeAction {
+        public_key: PublicKey::from_seed(KeyType::RSA2048, "chip-key-seed"),
+        challenge_key: PublicKey::from_seed(KeyType::ED25519, "challenge-key-seed"),
+        args: vec![1u8; size.rsa2048_args() as usize],
+    }))
+}
+
 fn deploy_action(size: ActionSize) -> Action {
     Action::DeployContract(unc_primitives::transaction::DeployContractAction {
         code: unc_test_contracts::sized_contract(size.deploy_contract() as usize),
@@ -804,6 +959,14 @@ impl ActionSize {
         }
     }
 
+    fn rsa2048_args(self) -> u64 {
+        match self {
+            ActionSize::Min => 0,
+            // Miner id, sequence number and power fit comfortably in this.
+            ActionSize::Max => 4096,
+        }
+    }
+
     fn deploy_contract(self) -> u64 {
         match self {
             // small number that still allows to generate a valid contract
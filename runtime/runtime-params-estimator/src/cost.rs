@@ -206,9 +206,47 @@ pub enum Cost {
     ActionDelegateSendSir,
     ActionDelegateExec,
 
-    /// rsa2048 keys are used for signing transactions. The cost of creating
+    /// Estimates `action_creation_config.register_rsa2048_keys` which is
+    /// charged for every `RegisterRsa2048Keys` action.
+    ///
+    /// Estimation: The send cost is measured on a transaction with empty
+    /// `args`. Execution needs a chip registrar, which the testbed does not
+    /// have. It is estimated as adding a full access key plus a native
+    /// measurement of decoding the chip certificate.
     RegisterRsa2048Keys,
+    RegisterRsa2048KeysSendNotSir,
+    RegisterRsa2048KeysExec,
+    /// Estimates `action_creation_config.register_rsa2048_keys_byte` which is
+    /// charged for every byte of the `args` in `RegisterRsa2048KeysAction`.
+    ///
+    /// Estimation: The send cost is measured on a transaction with large
+    /// `args`, divided by the number of bytes. Execution is estimated as the
+    /// per-byte cost of storing access key method names plus the per-byte
+    /// cost of decoding a large chip certificate natively.
+    RegisterRsa2048KeysPerByte,
+    RegisterRsa2048KeysPerByteSendNotSir,
+    RegisterRsa2048KeysPerByteExec,
+    /// Estimates `action_creation_config.create_rsa2048_challenge` which is
+    /// charged for every `CreateRsa2048Challenge` action.
+    ///
+    /// Estimation: The send cost is measured on a transaction with empty
+    /// `args`. Execution needs a registered chip key, which the testbed does
+    /// not have. It is estimated as deleting and adding a key, for moving the
+    /// chip key to the miner, plus a native measurement of decoding the chip
+    /// certificate, parsing the challenge and verifying its RSA2048 signature.
     CreateRsa2048Challenge,
+    CreateRsa2048ChallengeSendNotSir,
+    CreateRsa2048ChallengeExec,
+    /// Estimates `action_creation_config.create_rsa2048_challenge_byte` which
+    /// is charged for every byte of the `args` in
+    /// `CreateRsa2048ChallengeAction`.
+    ///
+    /// Estimation: The send cost is measured like for
+    /// `RegisterRsa2048KeysPerByte`. Execution is the per-byte cost of
+    /// checking a challenge with large `args` natively.
+    CreateRsa2048ChallengePerByte,
+    CreateRsa2048ChallengePerByteSendNotSir,
+    CreateRsa2048ChallengePerByteExec,
     /// Estimates `wasm_config.ext_costs.base` which is intended to be charged
     /// once on every host function call. However, this is currently
     /// inconsistent. First, we do not charge on Math API methods (`sha256`,
@@ -441,6 +479,35 @@ pub enum Cost {
     /// In the end, the cost should be low enough, compared to the base cost,
     /// that it does not matter all that much if we overestimate it a bit.
     Ed25519VerifyByte,
+    /// Estimates `rsa2048_verify_base`, which covers the base cost of the host
+    /// function `rsa2048_verify` to verify a RSA2048 signature.
+    ///
+    /// Estimation: Use a fixed signature of a 32 bytes message embedded in the
+    /// test contract and verify it `N` times in a loop and divide by `N`. The
+    /// verification is dominated by parsing the DER-encoded public key and by
+    /// the modular exponentiation with the public exponent, both of which do
+    /// not depend on the message.
+    Rsa2048VerifyBase,
+    /// Estimates `rsa2048_verify_byte`, the cost charged per message byte in
+    /// calls to the rsa2048_verify host function.
+    ///
+    /// Estimation: Verify a signature of the longest message PKCS#1 v1.5
+    /// allows for a 2048 bits key (245 bytes) many times, subtract the cost
+    /// estimated for the base and divide the remainder by the total bytes of
+    /// the message.
+    ///
+    /// The message is not hashed, so the cost per byte is mostly comparing
+    /// the padded message. It is well below the noise of the base cost, so
+    /// the parameter is bounded by the `Ed25519VerifyByte` estimation instead,
+    /// which hashes every byte of the message.
+    Rsa2048VerifyByte,
+    /// Estimates `secp256k1_verify_base`, which covers the full cost of the
+    /// host function `secp256k1_verify` to verify a secp256k1 ECDSA signature
+    /// of a 32 bytes message hash.
+    ///
+    /// Estimation: Use a fixed signature embedded in the test contract and
+    /// verify it `N` times in a loop and divide by `N`.
+    Secp256k1VerifyBase,
     // `storage_write` records a single key-value pair, initially in the
     // prospective changes in-memory hash map, and then once a full block has
     // been processed, in the on-disk trie. If there was already a value
@@ -38,6 +38,7 @@ pub fn costs_to_runtime_config(cost_table: &CostTable) -> anyhow::Result<Runtime
             ..latest_runtime_config.wasm_config
         },
         account_creation_config: AccountCreationConfig::default(),
+        chip_registry_config: latest_runtime_config.chip_registry_config.clone(),
     };
     Ok(res)
 }
@@ -72,6 +73,8 @@ fn runtime_fees_config(cost_table: &CostTable) -> anyhow::Result<RuntimeFeesConf
             ActionCosts::new_data_receipt_byte => fee(Cost::DataReceiptCreationPerByte)?,
             ActionCosts::register_rsa2048_keys => fee(Cost::RegisterRsa2048Keys)?,
             ActionCosts::create_rsa2048_challenge => fee(Cost::CreateRsa2048Challenge)?,
+            ActionCosts::register_rsa2048_keys_byte => fee(Cost::RegisterRsa2048KeysPerByte)?,
+            ActionCosts::create_rsa2048_challenge_byte => fee(Cost::CreateRsa2048ChallengePerByte)?,
         },
         ..actual_fees_config.clone()
     };
@@ -79,6 +82,9 @@ fn runtime_fees_config(cost_table: &CostTable) -> anyhow::Result<RuntimeFeesConf
 }
 
 fn ext_costs_config(cost_table: &CostTable) -> anyhow::Result<ExtCostsConfig> {
+    #[cfg(not(feature = "nightly"))]
+    let actual_ext_costs_config =
+        RuntimeConfigStore::new(None).get_config(PROTOCOL_VERSION).wasm_config.ext_costs.clone();
     Ok(ExtCostsConfig {
         costs: enum_map::enum_map! {
             // TODO: storage_iter_* operations below are deprecated, so just hardcode zero price,
@@ -94,6 +100,12 @@ fn ext_costs_config(cost_table: &CostTable) -> anyhow::Result<ExtCostsConfig> {
             // TODO: accurately price host functions that expose validator information.
             ExtCosts::validator_pledge_base => 303944908800,
             ExtCosts::validator_total_pledge_base => 303944908800,
+            // Only the nightly estimator contract calls these host functions, so stable builds
+            // keep the configured costs.
+            #[cfg(not(feature = "nightly"))]
+            cost @ (ExtCosts::rsa2048_verify_base
+            | ExtCosts::rsa2048_verify_byte
+            | ExtCosts::secp256k1_verify_base) => actual_ext_costs_config.gas_cost(cost),
             cost => {
                 let estimation = estimation(cost).with_context(|| format!("external WASM cost has no estimation defined: {}", cost))?;
                 cost_table.get(estimation).with_context(|| format!("undefined external WASM cost: {}", cost))?
@@ -128,6 +140,9 @@ fn estimation(cost: ExtCosts) -> Option<Cost> {
         ExtCosts::ecrecover_base => Cost::EcrecoverBase,
         ExtCosts::ed25519_verify_base => Cost::Ed25519VerifyBase,
         ExtCosts::ed25519_verify_byte => Cost::Ed25519VerifyByte,
+        ExtCosts::rsa2048_verify_base => Cost::Rsa2048VerifyBase,
+        ExtCosts::rsa2048_verify_byte => Cost::Rsa2048VerifyByte,
+        ExtCosts::secp256k1_verify_base => Cost::Secp256k1VerifyBase,
         ExtCosts::log_base => Cost::LogBase,
         ExtCosts::log_byte => Cost::LogByte,
         ExtCosts::storage_write_base => Cost::StorageWriteBase,
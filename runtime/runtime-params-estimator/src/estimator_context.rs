@@ -47,6 +47,8 @@ pub(crate) struct CachedCosts {
     pub(crate) apply_block: Option<GasCost>,
     pub(crate) touching_trie_node_write: Option<GasCost>,
     pub(crate) ed25519_verify_base: Option<GasCost>,
+    #[cfg(feature = "nightly")]
+    pub(crate) rsa2048_verify_base: Option<GasCost>,
 }
 
 impl<'c> EstimatorContext<'c> {
@@ -192,6 +192,24 @@ static ALL_COSTS: &[(Cost, fn(&mut EstimatorContext) -> GasCost)] = &[
     (Cost::ActionDelegateSendNotSir, action_costs::delegate_send_not_sir),
     (Cost::ActionDelegateSendSir, action_costs::delegate_send_sir),
     (Cost::ActionDelegateExec, action_costs::delegate_exec),
+    (Cost::RegisterRsa2048Keys, action_costs::register_rsa2048_keys),
+    (Cost::RegisterRsa2048KeysSendNotSir, action_costs::register_rsa2048_keys_send_not_sir),
+    (Cost::RegisterRsa2048KeysExec, action_costs::register_rsa2048_keys_exec),
+    (Cost::RegisterRsa2048KeysPerByte, action_costs::register_rsa2048_keys_byte),
+    (
+        Cost::RegisterRsa2048KeysPerByteSendNotSir,
+        action_costs::register_rsa2048_keys_byte_send_not_sir,
+    ),
+    (Cost::RegisterRsa2048KeysPerByteExec, action_costs::register_rsa2048_keys_byte_exec),
+    (Cost::CreateRsa2048Challenge, action_costs::create_rsa2048_challenge),
+    (Cost::CreateRsa2048ChallengeSendNotSir, action_costs::create_rsa2048_challenge_send_not_sir),
+    (Cost::CreateRsa2048ChallengeExec, action_costs::create_rsa2048_challenge_exec),
+    (Cost::CreateRsa2048ChallengePerByte, action_costs::create_rsa2048_challenge_byte),
+    (
+        Cost::CreateRsa2048ChallengePerByteSendNotSir,
+        action_costs::create_rsa2048_challenge_byte_send_not_sir,
+    ),
+    (Cost::CreateRsa2048ChallengePerByteExec, action_costs::create_rsa2048_challenge_byte_exec),
     (Cost::HostFunctionCall, host_function_call),
     (Cost::WasmInstruction, wasm_instruction),
     (Cost::DataReceiptCreationBase, data_receipt_creation_base),
@@ -221,6 +239,12 @@ static ALL_COSTS: &[(Cost, fn(&mut EstimatorContext) -> GasCost)] = &[
     (Cost::EcrecoverBase, ecrecover_base),
     (Cost::Ed25519VerifyBase, ed25519_verify_base),
     (Cost::Ed25519VerifyByte, ed25519_verify_byte),
+    #[cfg(feature = "nightly")]
+    (Cost::Rsa2048VerifyBase, rsa2048_verify_base),
+    #[cfg(feature = "nightly")]
+    (Cost::Rsa2048VerifyByte, rsa2048_verify_byte),
+    #[cfg(feature = "nightly")]
+    (Cost::Secp256k1VerifyBase, secp256k1_verify_base),
     (Cost::AltBn128G1MultiexpBase, alt_bn128g1_multiexp_base),
     (Cost::AltBn128G1MultiexpElement, alt_bn128g1_multiexp_element),
     (Cost::AltBn128G1SumBase, alt_bn128g1_sum_base),
@@ -1031,6 +1055,33 @@ fn ed25519_verify_byte(ctx: &mut EstimatorContext) -> GasCost {
     byte - base / iteration_bytes
 }
 
+#[cfg(feature = "nightly")]
+fn rsa2048_verify_base(ctx: &mut EstimatorContext) -> GasCost {
+    if ctx.cached.rsa2048_verify_base.is_none() {
+        let cost = fn_cost(ctx, "rsa2048_verify_32b_500", ExtCosts::rsa2048_verify_base, 500);
+        ctx.cached.rsa2048_verify_base = Some(cost);
+    }
+    ctx.cached.rsa2048_verify_base.clone().unwrap()
+}
+
+#[cfg(feature = "nightly")]
+fn rsa2048_verify_byte(ctx: &mut EstimatorContext) -> GasCost {
+    let base = rsa2048_verify_base(ctx);
+    // inside the WASM function, there are 500 calls to `rsa2048_verify`.
+    let base_call_num = 500;
+    // each call checks a message of the maximum size of 245 bytes
+    let iteration_bytes = 245;
+    let total_bytes = base_call_num * iteration_bytes;
+    let byte = fn_cost(ctx, "rsa2048_verify_245b_500", ExtCosts::rsa2048_verify_byte, total_bytes);
+    // need to subtract the base cost, which has already been divided by the number of bytes per iteration
+    byte - base / iteration_bytes
+}
+
+#[cfg(feature = "nightly")]
+fn secp256k1_verify_base(ctx: &mut EstimatorContext) -> GasCost {
+    fn_cost(ctx, "secp256k1_verify_500", ExtCosts::secp256k1_verify_base, 500)
+}
+
 fn alt_bn128g1_multiexp_base(ctx: &mut EstimatorContext) -> GasCost {
     fn_cost(ctx, "alt_bn128_g1_multiexp_1_10", ExtCosts::alt_bn128_g1_multiexp_base, 10)
 }
@@ -1284,3 +1335,19 @@ fn one_nanosecond(ctx: &mut EstimatorContext) -> GasCost {
     eprintln!("Cannot estimate ONE_NANOSECOND like any other cost. The result will only show the constant value currently used in the estimator.");
     GasCost::from_gas(estimator_params::GAS_IN_NS, ctx.config.metric)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::ALL_COSTS;
+    use crate::{costs_to_runtime_config, CostTable};
+
+    /// The costs estimated by this build must be enough to build a runtime config.
+    #[test]
+    fn test_all_costs_to_runtime_config() {
+        let mut cost_table = CostTable::default();
+        for (cost, _) in ALL_COSTS {
+            cost_table.add(*cost, 1);
+        }
+        costs_to_runtime_config(&cost_table).unwrap();
+    }
+}
@@ -10,6 +10,7 @@ use unc_crypto::PublicKey;
 use unc_parameters::{ActionCosts, RuntimeConfig, RuntimeFeesConfig};
 use unc_primitives::account::{AccessKey, AccessKeyPermission, Account};
 use unc_primitives::action::delegate::{DelegateAction, SignedDelegateAction};
+use unc_primitives::action::rsa2048::{ChipCertificate, Rsa2048ChallengeArgs};
 use unc_primitives::checked_feature;
 use unc_primitives::config::ViewConfig;
 use unc_primitives::errors::{ActionError, ActionErrorKind, InvalidAccessKeyError, RuntimeError};
@@ -18,10 +19,10 @@ use unc_primitives::receipt::{ActionReceipt, Receipt, ReceiptEnum};
 use unc_primitives::transaction::{
     Action, AddKeyAction, CreateRsa2048ChallengeAction, DeleteAccountAction, DeleteKeyAction,
     DeployContractAction, FunctionCallAction, PledgeAction, RegisterRsa2048KeysAction,
-    TransferAction,
+    Rsa2048KeysOperation, TransferAction,
 };
 use unc_primitives::types::validator_power::ValidatorPower;
-use unc_primitives::types::{AccountId, BlockHeight, EpochInfoProvider, Gas, TrieCacheMode};
+use unc_primitives::types::{AccountId, BlockHeight, EpochInfoProvider, Gas, Power, TrieCacheMode};
 use unc_primitives::utils::{account_is_valid, create_random_seed};
 use unc_primitives::version::{
     ProtocolFeature, ProtocolVersion, DELETE_KEY_STORAGE_USAGE_PROTOCOL_VERSION,
@@ -667,6 +668,47 @@ pub(crate) fn action_register_rsa2048_keys(
     result: &mut ActionResult,
     account_id: &AccountId,
     register_key: &RegisterRsa2048KeysAction,
+    epoch_info_provider: &dyn EpochInfoProvider,
+) -> Result<(), RuntimeError> {
+    match rsa2048_keys_operation(register_key, apply_state.current_protocol_version) {
+        Rsa2048KeysOperation::AddKeys => {
+            add_rsa2048_keys(apply_state, state_update, account, result, account_id, register_key)?
+        }
+        Rsa2048KeysOperation::DeleteKeys => delete_rsa2048_keys(
+            apply_state,
+            state_update,
+            account,
+            result,
+            account_id,
+            register_key,
+            epoch_info_provider,
+        )?,
+    }
+    Ok(())
+}
+
+/// The operation `register_key` performs at `protocol_version`.
+///
+/// Before `ChipCertificate` every action added keys. Since then validation rejects unknown
+/// operation types, only receipts created before the upgrade can still carry one, and they keep
+/// adding keys.
+fn rsa2048_keys_operation(
+    register_key: &RegisterRsa2048KeysAction,
+    protocol_version: ProtocolVersion,
+) -> Rsa2048KeysOperation {
+    if !checked_feature!("stable", ChipCertificate, protocol_version) {
+        return Rsa2048KeysOperation::AddKeys;
+    }
+    register_key.operation().unwrap_or(Rsa2048KeysOperation::AddKeys)
+}
+
+fn add_rsa2048_keys(
+    apply_state: &ApplyState,
+    state_update: &mut TrieUpdate,
+    account: &mut Account,
+    result: &mut ActionResult,
+    account_id: &AccountId,
+    register_key: &RegisterRsa2048KeysAction,
 ) -> Result<(), StorageError> {
     if get_rsa2048_keys(state_update, account_id, &register_key.public_key)?.is_some() {
         result.result = Err(ActionErrorKind::AddKeyAlreadyExists {
@@ -700,6 +742,83 @@ pub(crate) fn action_register_rsa2048_keys(
     Ok(())
 }
 
+/// Revokes a chip key held by `account_id`.
+///
+/// Unclaimed keys are held by a chip registrar. Keys claimed by a miner also
+/// take the chip power back from the miner. If the miner is a validator, the
+/// reduced power is proposed with the key the miner pledged with.
+fn delete_rsa2048_keys(
+    apply_state: &ApplyState,
+    state_update: &mut TrieUpdate,
+    account: &mut Account,
+    result: &mut ActionResult,
+    account_id: &AccountId,
+    register_key: &RegisterRsa2048KeysAction,
+    epoch_info_provider: &dyn EpochInfoProvider,
+) -> Result<(), RuntimeError> {
+    let Some(registered_keys) =
+        get_rsa2048_keys(state_update, account_id, &register_key.public_key)?
+    else {
+        result.result = Err(ActionErrorKind::RsaKeysNotFound {
+            account_id: account_id.to_owned(),
+            public_key: register_key.public_key.clone().into(),
+        }
+        .into());
+        return Ok(());
+    };
+    let is_claimed = !apply_state.config.chip_registry_config.is_registrar(account_id);
+    let power = if is_claimed {
+        // Chips registered before `ChipCertificate` carry a legacy JSON certificate.
+        let Ok(certificate) = ChipCertificate::try_from_args(&registered_keys.args, true) else {
+            result.result = Err(ActionErrorKind::Rsa2048KeysArgsMalformed {
+                account_id: account_id.to_owned(),
+                public_key: register_key.public_key.clone().into(),
+            }
+            .into());
+            return Ok(());
+        };
+        certificate.power()
+    } else {
+        0
+    };
+
+    // Refund what was charged when the key was stored under this account: the whole
+    // registration for unclaimed keys, only the public key for claimed ones.
+    let storage_config = &apply_state.config.fees.storage_usage_config;
+    let storage_usage = if is_claimed {
+        borsh::object_length(&register_key.public_key).unwrap() as u64
+    } else {
+        borsh::object_length(&registered_keys).unwrap() as u64
+    } + storage_config.num_extra_bytes_record;
+    remove_rsa2048_keys(state_update, account_id.clone(), register_key.public_key.clone());
+    account.set_storage_usage(account.storage_usage().saturating_sub(storage_usage));
+
+    if is_claimed {
+        let total_power = account.power().saturating_sub(power);
+        if let Some(public_key) = epoch_info_provider.validator_public_key(
+            &apply_state.epoch_id,
+            &apply_state.prev_block_hash,
+            account_id,
+        )? {
+            result.validator_power_proposals.push(ValidatorPower::new(
+                account_id.clone(),
+                public_key,
+                total_power,
+            ));
+        }
+        tracing::debug!(
+            target: "runtime",
+            %account_id,
+            original_power = account.power(),
+            power,
+            total_power,
+            "rsa2048 keys revoked"
+        );
+        account.set_power(total_power);
+    }
+    Ok(())
+}
+
 pub(crate) fn action_create_rsa2048_challenge(
     apply_state: &ApplyState,
     state_update: &mut TrieUpdate,
@@ -708,66 +827,104 @@ pub(crate) fn action_create_rsa2048_challenge(
     account_id: &AccountId,
     challenge: &CreateRsa2048ChallengeAction,
 ) -> Result<(), RuntimeError> {
-    //TODO: 从unc 基金会获取的公钥， 匹配验证签名以及附加参数(如算力, 矿工信息, dev_id, 等)
-    let root_id = "unc".parse::<AccountId>().unwrap();
-    if get_rsa2048_keys(state_update, &root_id, &challenge.public_key)?.is_none() {
+    if !checked_feature!("stable", ChipCertificate, apply_state.current_protocol_version) {
+        return create_rsa2048_challenge_from_json_args(
+            apply_state,
+            state_update,
+            account,
+            result,
+            account_id,
+            challenge,
+        );
+    }
+    // Unclaimed chip keys are held by the registrar that registered them.
+    let mut unclaimed = None;
+    for registrar_id in &apply_state.config.chip_registry_config.registrar_account_ids {
+        if let Some(keys) = get_rsa2048_keys(state_update, registrar_id, &challenge.public_key)? {
+            unclaimed = Some((registrar_id.clone(), keys));
+            break;
+        }
+    }
+    let Some((registrar_id, registered_keys)) = unclaimed else {
         result.result = Err(ActionErrorKind::RsaKeysNotFound {
             account_id: account_id.to_owned(),
             public_key: challenge.public_key.clone().into(),
         }
         .into());
         return Ok(());
-    }
+    };
 
-    //FIXME： 计算发起challenge 的nonce 随机数
-    // 直接使用 unc证书里面的args, 如算力
-    let registered_keys = get_rsa2048_keys(state_update, &root_id, &challenge.public_key)?.unwrap();
-    let args = registered_keys.clone().args;
-    match serde_json::from_slice::<serde_json::Value>(&args) {
-        Ok(parsed_args) => {
-            if let Some(power_val) = parsed_args.get("power") {
-                match power_val.as_str() {
-                    Some(power_str) => {
-                        match power_str.parse::<u64>() {
-                            Ok(power) => {
-                                // compute total power
-                                let total_power =
-                                    account.power().checked_add(power).ok_or_else(|| {
-                                        StorageError::StorageInconsistentState(
-                                            "Account power integer overflow".to_string(),
-                                        )
-                                    })?;
-                                // push power to validator proposal
-                                result.validator_power_proposals.push(ValidatorPower::new(
-                                    account_id.clone(),
-                                    challenge.challenge_key.clone().into(),
-                                    total_power.clone(),
-                                ));
-                                // attach power to account
-                                tracing::info!("Account: {:?}, original power is : {}, new power is : {}, total power is : {}", account_id.clone(), account.power(), power, total_power.clone());
-                                account.set_power(total_power);
-                            }
-                            Err(_) => tracing::error!("Power value is not a valid u128 number"),
-                        }
-                    }
-                    None => {
-                        if let Some(power_number) = power_val.as_u64() {
-                            tracing::warn!("Power (as u64, converted from u128): {}", power_number);
-                        } else {
-                            tracing::error!(
-                                "Power value is not a string or a number that fits into u64"
-                            );
-                        }
-                    }
-                }
-            }
+    // Chips registered before `ChipCertificate` carry a legacy JSON certificate.
+    let Ok(certificate) = ChipCertificate::try_from_args(&registered_keys.args, true) else {
+        result.result = Err(ActionErrorKind::Rsa2048KeysArgsMalformed {
+            account_id: account_id.to_owned(),
+            public_key: challenge.public_key.clone().into(),
         }
-        Err(_) => {
-            return Ok(());
+        .into());
+        return Ok(());
+    };
+    let Ok(challenge_args) = Rsa2048ChallengeArgs::try_from_slice(&challenge.args) else {
+        result.result = Err(ActionErrorKind::Rsa2048ChallengeArgsMalformed {
+            account_id: account_id.to_owned(),
+            public_key: challenge.public_key.clone().into(),
         }
+        .into());
+        return Ok(());
     };
-    // remove from unc list and add to the miner list
-    remove_rsa2048_keys(state_update, root_id.clone(), challenge.public_key.clone());
+    if challenge_args.signature.is_none() {
+        result.result = Err(ActionErrorKind::Rsa2048ChallengeNotSigned {
+            account_id: account_id.to_owned(),
+            public_key: challenge.public_key.clone().into(),
+        }
+        .into());
+        return Ok(());
+    }
+    if !challenge_args.verify(&challenge.public_key) {
+        result.result = Err(ActionErrorKind::Rsa2048ChallengeInvalidSignature {
+            account_id: account_id.to_owned(),
+            public_key: challenge.public_key.clone().into(),
+        }
+        .into());
+        return Ok(());
+    }
+    let message = &challenge_args.message;
+    if &message.miner_id != account_id
+        || message.sn != certificate.sn()
+        || message.power != certificate.power()
+    {
+        result.result = Err(ActionErrorKind::Rsa2048ChallengeArgsMismatch {
+            account_id: account_id.to_owned(),
+            public_key: challenge.public_key.clone().into(),
+        }
+        .into());
+        return Ok(());
+    }
+
+    let power = certificate.power();
+    let total_power = account.power().checked_add(power).ok_or_else(|| {
+        StorageError::StorageInconsistentState(format!(
+            "Account power integer overflow for account {}",
+            account_id
+        ))
+    })?;
+    // push power to validator proposal
+    result.validator_power_proposals.push(ValidatorPower::new(
+        account_id.clone(),
+        challenge.challenge_key.clone(),
+        total_power,
+    ));
+    tracing::debug!(
+        target: "runtime",
+        %account_id,
+        original_power = account.power(),
+        power,
+        total_power,
+        "rsa2048 challenge accepted"
+    );
+    account.set_power(total_power);
+
+    // remove from the registrar list and add to the miner list
+    remove_rsa2048_keys(state_update, registrar_id, challenge.public_key.clone());
     set_rsa2048_keys(
         state_update,
         account_id.clone(),
@@ -780,7 +937,7 @@ pub(crate) fn action_create_rsa2048_challenge(
         account
             .storage_usage()
             .checked_add(
-                borsh::object_length(&challenge.public_key.clone()).unwrap() as u64
+                borsh::object_length(&challenge.public_key).unwrap() as u64
                     + storage_config.num_extra_bytes_record,
             )
             .ok_or_else(|| {
@@ -791,7 +948,80 @@ pub(crate) fn action_create_rsa2048_challenge(
             })?,
     );
 
-    return Ok(());
+    Ok(())
+}
+
+/// Challenge before `ProtocolFeature::ChipCertificate`: the chip key is taken from the `unc`
+/// account without checking the challenge args, and the power is read from the `power` string
+/// of the JSON args the chip was registered with. Chips with args that aren't JSON are left
+/// untouched, chips without a readable power are moved to the miner without adding power.
+fn create_rsa2048_challenge_from_json_args(
+    apply_state: &ApplyState,
+    state_update: &mut TrieUpdate,
+    account: &mut Account,
+    result: &mut ActionResult,
+    account_id: &AccountId,
+    challenge: &CreateRsa2048ChallengeAction,
+) -> Result<(), RuntimeError> {
+    let root_id: AccountId = "unc".parse().unwrap();
+    let Some(registered_keys) = get_rsa2048_keys(state_update, &root_id, &challenge.public_key)?
+    else {
+        result.result = Err(ActionErrorKind::RsaKeysNotFound {
+            account_id: account_id.to_owned(),
+            public_key: challenge.public_key.clone().into(),
+        }
+        .into());
+        return Ok(());
+    };
+    let Ok(args) = serde_json::from_slice::<serde_json::Value>(&registered_keys.args) else {
+        return Ok(());
+    };
+    let power = args.get("power").and_then(|power| power.as_str()).map(str::parse::<Power>);
+    match power {
+        Some(Ok(power)) => {
+            let total_power = account.power().checked_add(power).ok_or_else(|| {
+                StorageError::StorageInconsistentState(format!(
+                    "Account power integer overflow for account {}",
+                    account_id
+                ))
+            })?;
+            result.validator_power_proposals.push(ValidatorPower::new(
+                account_id.clone(),
+                challenge.challenge_key.clone(),
+                total_power,
+            ));
+            account.set_power(total_power);
+        }
+        Some(Err(_)) => {
+            tracing::debug!(target: "runtime", %account_id, "chip power isn't a number")
+        }
+        None => tracing::debug!(target: "runtime", %account_id, "chip power isn't a string"),
+    }
+
+    remove_rsa2048_keys(state_update, root_id, challenge.public_key.clone());
+    set_rsa2048_keys(
+        state_update,
+        account_id.clone(),
+        challenge.public_key.clone(),
+        &registered_keys,
+    );
+
+    let storage_config = &apply_state.config.fees.storage_usage_config;
+    account.set_storage_usage(
+        account
+            .storage_usage()
+            .checked_add(
+                borsh::object_length(&challenge.public_key).unwrap() as u64
+                    + storage_config.num_extra_bytes_record,
+            )
+            .ok_or_else(|| {
+                StorageError::StorageInconsistentState(format!(
+                    "Storage usage integer overflow for account {}",
+                    account_id
+                ))
+            })?,
+    );
+    Ok(())
 }
 
 pub(crate) fn apply_delegate_action(
@@ -1008,6 +1238,8 @@ pub(crate) fn check_actor_permissions(
     account: &Option<Account>,
     actor_id: &AccountId,
     account_id: &AccountId,
+    config: &RuntimeConfig,
+    current_protocol_version: ProtocolVersion,
 ) -> Result<(), ActionError> {
     match action {
         Action::DeployContract(_)
@@ -1040,10 +1272,33 @@ pub(crate) fn check_actor_permissions(
         }
         Action::CreateAccount(_) | Action::FunctionCall(_) | Action::Transfer(_) => (),
         Action::Delegate(_) => (),
-        Action::RegisterRsa2048Keys(_) => {
-            // FIXME: 这里是硬编码, 之后RuntimeConfig中添加配置, 只有创世共识账号基金会账号, 类似registrar账号
-            let root_id = "unc".parse::<AccountId>().unwrap();
-            if account_id.clone() != root_id {
+        Action::RegisterRsa2048Keys(_)
+            if !checked_feature!("stable", ChipCertificate, current_protocol_version) =>
+        {
+            // Before `ChipCertificate` chip keys could only be registered to the `unc` account,
+            // by any actor.
+            if account_id.as_str() != "unc" {
+                return Err(ActionErrorKind::ActorNoPermission {
+                    account_id: account_id.clone(),
+                    actor_id: actor_id.clone(),
+                }
+                .into());
+            }
+        }
+        Action::RegisterRsa2048Keys(register_key) => {
+            let registry = &config.chip_registry_config;
+            // Keys are added to the registrar's own account only. A registrar can revoke keys
+            // claimed by miners and its own unclaimed keys, but not another registrar's.
+            let permitted = match rsa2048_keys_operation(register_key, current_protocol_version) {
+                Rsa2048KeysOperation::AddKeys => {
+                    registry.is_registrar(actor_id) && actor_id == account_id
+                }
+                Rsa2048KeysOperation::DeleteKeys => {
+                    registry.is_registrar(actor_id)
+                        && (actor_id == account_id || !registry.is_registrar(account_id))
+                }
+            };
+            if !permitted {
                 return Err(ActionErrorKind::ActorNoPermission {
                     account_id: account_id.clone(),
                     actor_id: actor_id.clone(),
@@ -1251,6 +1506,49 @@ mod tests {
         assert!(action_result.result.is_ok());
     }
 
+    #[test]
+    fn test_register_rsa2048_keys_permissions() {
+        let mut config = RuntimeConfig::test();
+        config.chip_registry_config.registrar_account_ids =
+            vec!["registrar1".parse().unwrap(), "registrar2".parse().unwrap()];
+        let check = |protocol_version: ProtocolVersion,
+                     operation: Rsa2048KeysOperation,
+                     actor_id: &str,
+                     account_id: &str| {
+            let action = Action::RegisterRsa2048Keys(Box::new(RegisterRsa2048KeysAction {
+                public_key: PublicKey::empty(unc_crypto::KeyType::RSA2048),
+                operation_type: operation as u8,
+                args: vec![],
+            }));
+            check_actor_permissions(
+                &action,
+                &None,
+                &actor_id.parse().unwrap(),
+                &account_id.parse().unwrap(),
+                &config,
+                protocol_version,
+            )
+            .is_ok()
+        };
+
+        let v = ProtocolFeature::ChipCertificate.protocol_version();
+        assert!(check(v, Rsa2048KeysOperation::AddKeys, "registrar1", "registrar1"));
+        assert!(check(v, Rsa2048KeysOperation::AddKeys, "registrar2", "registrar2"));
+        assert!(!check(v, Rsa2048KeysOperation::AddKeys, "registrar1", "registrar2"));
+        assert!(!check(v, Rsa2048KeysOperation::AddKeys, "unc", "unc"));
+        assert!(!check(v, Rsa2048KeysOperation::AddKeys, "registrar1", "miner"));
+
+        assert!(check(v, Rsa2048KeysOperation::DeleteKeys, "registrar1", "registrar1"));
+        assert!(check(v, Rsa2048KeysOperation::DeleteKeys, "registrar2", "miner"));
+        assert!(!check(v, Rsa2048KeysOperation::DeleteKeys, "registrar1", "registrar2"));
+        assert!(!check(v, Rsa2048KeysOperation::DeleteKeys, "miner", "miner"));
+
+        // Before `ChipCertificate` keys can be registered to `unc` only, by anyone.
+        let v = ProtocolFeature::ChipCertificate.protocol_version() - 1;
+        assert!(check(v, Rsa2048KeysOperation::AddKeys, "miner", "unc"));
+        assert!(!check(v, Rsa2048KeysOperation::AddKeys, "registrar1", "registrar1"));
+    }
+
     fn test_delete_large_account(
         account_id: &AccountId,
         code_hash: &CryptoHash,
@@ -6,7 +6,7 @@ use unc_primitives::types::{
     AccountId, BlockHeight, EpochHeight, EpochId, EpochInfoProvider, MerkleHash,
 };
 use unc_primitives::version::ProtocolVersion;
-use unc_primitives::views::{ChipView, ViewStateResult};
+use unc_primitives::views::{ChipView, ChipsPage, ViewStateResult};
 use unc_vm_runner::ContractCode;
 
 /// Adapter for querying runtime.
@@ -64,6 +64,18 @@ pub trait ViewRuntimeAdapter {
         account_id: &AccountId,
     ) -> Result<Vec<ChipView>, crate::state_viewer::errors::ViewChipError>;
 
+    fn view_chips(
+        &self,
+        shard_uid: &ShardUId,
+        state_root: MerkleHash,
+        account_id: &AccountId,
+        miner_id: Option<&str>,
+        sn: Option<&str>,
+        cursor: Option<&PublicKey>,
+        limit: Option<u32>,
+        include_proof: bool,
+    ) -> Result<ChipsPage, crate::state_viewer::errors::ViewChipError>;
+
     fn view_state(
         &self,
         shard_uid: &ShardUId,
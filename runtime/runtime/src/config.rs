@@ -135,11 +135,19 @@ pub fn total_send_fees(
                         &delegate_action.receiver_id,
                     )?
             }
-            RegisterRsa2048Keys(_) => {
+            RegisterRsa2048Keys(action) => {
+                let num_bytes = action.args.len() as u64;
                 fees.fee(ActionCosts::register_rsa2048_keys).send_fee(sender_is_receiver)
+                    + fees.fee(ActionCosts::register_rsa2048_keys_byte).send_fee(sender_is_receiver)
+                        * num_bytes
             }
-            CreateRsa2048Challenge(_) => {
+            CreateRsa2048Challenge(action) => {
+                let num_bytes = action.args.len() as u64;
                 fees.fee(ActionCosts::create_rsa2048_challenge).send_fee(sender_is_receiver)
+                    + fees
+                        .fee(ActionCosts::create_rsa2048_challenge_byte)
+                        .send_fee(sender_is_receiver)
+                        * num_bytes
             }
         };
         result = safe_add_gas(result, delta)?;
@@ -222,8 +230,16 @@ pub fn exec_fee(config: &RuntimeConfig, action: &Action, receiver_id: &AccountId
         DeleteKey(_) => fees.fee(ActionCosts::delete_key).exec_fee(),
         DeleteAccount(_) => fees.fee(ActionCosts::delete_account).exec_fee(),
         Delegate(_) => fees.fee(ActionCosts::delegate).exec_fee(),
-        RegisterRsa2048Keys(_) => fees.fee(ActionCosts::register_rsa2048_keys).exec_fee(),
-        CreateRsa2048Challenge(_) => fees.fee(ActionCosts::create_rsa2048_challenge).exec_fee(),
+        RegisterRsa2048Keys(action) => {
+            let num_bytes = action.args.len() as u64;
+            fees.fee(ActionCosts::register_rsa2048_keys).exec_fee()
+                + fees.fee(ActionCosts::register_rsa2048_keys_byte).exec_fee() * num_bytes
+        }
+        CreateRsa2048Challenge(action) => {
+            let num_bytes = action.args.len() as u64;
+            fees.fee(ActionCosts::create_rsa2048_challenge).exec_fee()
+                + fees.fee(ActionCosts::create_rsa2048_challenge_byte).exec_fee() * num_bytes
+        }
     }
 }
 
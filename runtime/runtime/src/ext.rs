@@ -324,6 +324,36 @@ impl<'a> External for RuntimeExt<'a> {
         self.receipt_manager.append_action_delete_account(receipt_index, beneficiary_id)
     }
 
+    fn append_action_register_rsa2048_keys(
+        &mut self,
+        receipt_index: ReceiptIndex,
+        public_key: unc_crypto::PublicKey,
+        operation_type: u8,
+        args: Vec<u8>,
+    ) {
+        self.receipt_manager.append_action_register_rsa2048_keys(
+            receipt_index,
+            public_key,
+            operation_type,
+            args,
+        )
+    }
+
+    fn append_action_create_rsa2048_challenge(
+        &mut self,
+        receipt_index: ReceiptIndex,
+        public_key: unc_crypto::PublicKey,
+        challenge_key: unc_crypto::PublicKey,
+        args: Vec<u8>,
+    ) {
+        self.receipt_manager.append_action_create_rsa2048_challenge(
+            receipt_index,
+            public_key,
+            challenge_key,
+            args,
+        )
+    }
+
     fn get_receipt_receiver(&self, receipt_index: ReceiptIndex) -> &AccountId {
         self.receipt_manager.get_receipt_receiver(receipt_index)
     }
@@ -330,7 +330,14 @@ impl Runtime {
             return Ok(result);
         }
         // Permission validation
-        if let Err(e) = check_actor_permissions(action, account, actor_id, account_id) {
+        if let Err(e) = check_actor_permissions(
+            action,
+            account,
+            actor_id,
+            account_id,
+            &apply_state.config,
+            apply_state.current_protocol_version,
+        ) {
             result.result = Err(e);
             return Ok(result);
         }
@@ -461,6 +468,7 @@ impl Runtime {
                     &mut result,
                     account_id,
                     register_rsa2048_keys,
+                    epoch_info_provider,
                 )?;
             }
             Action::CreateRsa2048Challenge(create_rsa2048_challenge) => {
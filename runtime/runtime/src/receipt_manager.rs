@@ -1,7 +1,8 @@
 use unc_crypto::PublicKey;
 use unc_primitives::action::{
-    Action, AddKeyAction, CreateAccountAction, DeleteAccountAction, DeleteKeyAction,
-    DeployContractAction, FunctionCallAction, PledgeAction, TransferAction,
+    Action, AddKeyAction, CreateAccountAction, CreateRsa2048ChallengeAction, DeleteAccountAction,
+    DeleteKeyAction, DeployContractAction, FunctionCallAction, PledgeAction,
+    RegisterRsa2048KeysAction, TransferAction,
 };
 use unc_primitives::errors::RuntimeError;
 use unc_primitives::receipt::DataReceiver;
@@ -350,6 +351,64 @@ impl ReceiptManager {
         Ok(())
     }
 
+    /// Attach the [`RegisterRsa2048KeysAction`] action to an existing receipt.
+    ///
+    /// # Arguments
+    ///
+    /// * `receipt_index` - an index of Receipt to append an action
+    /// * `public_key` - a chip public key to register or remove
+    /// * `operation_type` - whether the key is added or deleted, see `Rsa2048KeysOperation`
+    /// * `args` - serialized arguments of the operation
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `receipt_index` does not refer to a known receipt.
+    pub(super) fn append_action_register_rsa2048_keys(
+        &mut self,
+        receipt_index: ReceiptIndex,
+        public_key: PublicKey,
+        operation_type: u8,
+        args: Vec<u8>,
+    ) {
+        self.append_action(
+            receipt_index,
+            Action::RegisterRsa2048Keys(Box::new(RegisterRsa2048KeysAction {
+                public_key,
+                operation_type,
+                args,
+            })),
+        );
+    }
+
+    /// Attach the [`CreateRsa2048ChallengeAction`] action to an existing receipt.
+    ///
+    /// # Arguments
+    ///
+    /// * `receipt_index` - an index of Receipt to append an action
+    /// * `public_key` - a registered chip public key
+    /// * `challenge_key` - a public key to bind to the chip
+    /// * `args` - serialized arguments of the challenge
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `receipt_index` does not refer to a known receipt.
+    pub(super) fn append_action_create_rsa2048_challenge(
+        &mut self,
+        receipt_index: ReceiptIndex,
+        public_key: PublicKey,
+        challenge_key: PublicKey,
+        args: Vec<u8>,
+    ) {
+        self.append_action(
+            receipt_index,
+            Action::CreateRsa2048Challenge(Box::new(CreateRsa2048ChallengeAction {
+                public_key,
+                challenge_key,
+                args,
+            })),
+        );
+    }
+
     /// Distribute the provided `gas` between receipts managed by this `ReceiptManager` according
     /// to their assigned weights.
     ///
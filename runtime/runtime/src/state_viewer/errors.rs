@@ -98,6 +98,12 @@ impl From<unc_primitives::errors::StorageError> for ViewAccessKeyError {
     }
 }
 
+impl From<unc_primitives::errors::StorageError> for ViewChipError {
+    fn from(storage_error: unc_primitives::errors::StorageError) -> Self {
+        Self::InternalError { error_message: storage_error.to_string() }
+    }
+}
+
 impl From<unc_primitives::errors::StorageError> for ViewStateError {
     fn from(storage_error: unc_primitives::errors::StorageError) -> Self {
         Self::InternalError { error_message: storage_error.to_string() }
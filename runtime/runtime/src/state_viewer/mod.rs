@@ -7,15 +7,19 @@ use tracing::debug;
 use unc_crypto::{KeyType, PublicKey};
 use unc_parameters::RuntimeConfigStore;
 use unc_primitives::account::{AccessKey, Account};
+use unc_primitives::action::rsa2048::ChipCertificate;
 use unc_primitives::borsh::BorshDeserialize;
 use unc_primitives::hash::CryptoHash;
 use unc_primitives::receipt::ActionReceipt;
 use unc_primitives::runtime::apply_state::ApplyState;
 use unc_primitives::runtime::migration_data::{MigrationData, MigrationFlags};
-use unc_primitives::transaction::FunctionCallAction;
-use unc_primitives::trie_key::trie_key_parsers;
+use unc_primitives::transaction::{FunctionCallAction, RegisterRsa2048KeysAction};
+use unc_primitives::trie_key::{trie_key_parsers, TrieKey};
 use unc_primitives::types::{AccountId, EpochInfoProvider, Gas};
-use unc_primitives::views::{ChipView, StateItem, ViewApplyState, ViewStateResult};
+use unc_primitives::views::{
+    ChipView, ChipsPage, StateItem, ViewApplyState, ViewStateResult, DEFAULT_CHIPS_PAGE_LIMIT,
+    MAX_CHIPS_PAGE_LIMIT, MAX_CHIPS_PAGE_SCAN,
+};
 use unc_primitives_core::config::ViewConfig;
 use unc_store::{get_access_key, get_account, get_code, TrieUpdate};
 use unc_vm_runner::logic::ReturnData;
@@ -23,6 +27,27 @@ use unc_vm_runner::ContractCode;
 
 pub mod errors;
 
+/// Builds the view of a registered chip, or `None` if its certificate can't be
+/// decoded.  Such chips are skipped so that a single bad registration doesn't
+/// hide all the other chips of the account.
+fn chip_view(public_key: &PublicKey, args: &[u8]) -> Option<ChipView> {
+    let certificate = match ChipCertificate::try_from_args(args, true) {
+        Ok(certificate) => certificate,
+        Err(err) => {
+            debug!(target: "runtime", %public_key, %err, "Skipping chip with undecodable certificate");
+            return None;
+        }
+    };
+    let mut chip = ChipView::new(public_key, &certificate);
+    // Legacy JSON certificates may name the public key of the chip themselves.
+    if let Ok(args) = serde_json::from_slice::<serde_json::Value>(args) {
+        if let Some(public_key) = args.get("public_key").and_then(|v| v.as_str()) {
+            chip.public_key = public_key.to_string();
+        }
+    }
+    Some(chip)
+}
+
 pub struct TrieViewer {
     /// Upper bound of the byte size of contract state that is still viewable. None is no limit
     state_size_limit: Option<u64>,
@@ -151,79 +176,83 @@ impl TrieViewer {
                     error_message: "Unexpected missing key from iterator".to_string(),
                 })?;
 
-            match serde_json::from_slice::<serde_json::Value>(&chip_action.args) {
-                Ok(parsed_args) => {
-                    let mut chip_view = ChipView {
-                        miner_id: String::new(),
-                        public_key: String::new(), // Assume initially empty, update if necessary
-                        power: 0,
-                        sn: String::new(),
-                        bus_id: String::new(),
-                        p2key: String::new(),
-                    };
-
-                    // Directly assign 'power'
-                    // if let Some(power_val) = parsed_args.get("power").and_then(|v| v.as_u64()) {
-                    //     chip_view.power = power_val;
-                    // }
-                    // Handle power field with dual-path parsing
-                    if let Some(power_val) = parsed_args.get("power") {
-                        if let Some(power_str) = power_val.as_str() {
-                            chip_view.power = power_str.parse::<u64>().unwrap_or(0);
-                        } else if let Some(power_number) = power_val.as_u64() {
-                            chip_view.power = power_number;
-                        } else {
-                            println!("Power value is not a string or a number that fits into u64");
-                        }
-                    }
-
-                    chip_view.public_key = public_key.to_string();
-
-                    // Extract 'sn' directly
-                    if let Some(sn_val) = parsed_args.get("sn").and_then(|v| v.as_str()) {
-                        chip_view.sn = sn_val.to_string();
-                    }
-
-                    // Extract 'public_key' directly
-                    if let Some(public_key_val) =
-                        parsed_args.get("public_key").and_then(|v| v.as_str())
-                    {
-                        chip_view.public_key = public_key_val.to_string();
-                    }
-
-                    // Extract 'miner_id' directly
-                    if let Some(miner_id_val) = parsed_args.get("miner_id").and_then(|v| v.as_str())
-                    {
-                        chip_view.miner_id = miner_id_val.to_string();
-                    }
-
-                    // Extract 'bus_id' directly
-                    if let Some(bus_id_val) = parsed_args.get("bus_id").and_then(|v| v.as_str()) {
-                        chip_view.bus_id = bus_id_val.to_string();
-                    }
+            chip_views.extend(chip_view(&public_key, &chip_action.args));
+        }
 
-                    // Extract 'p2key' directly
-                    if let Some(p2key_val) = parsed_args.get("p2key").and_then(|v| v.as_str()) {
-                        chip_view.p2key = p2key_val.to_string();
-                    }
+        Ok(chip_views)
+    }
 
-                    // Example: Update public_key or other fields based on key_suffix if applicable
-                    // chip_view.public_key = String::from_utf8_lossy(key_suffix).to_string();
+    /// Returns a single page of chips registered to `account_id`.
+    ///
+    /// Chips are visited in trie key order, i.e. ordered by their borsh
+    /// encoded public key, starting right after `cursor`.
+    pub fn view_chips(
+        &self,
+        state_update: &TrieUpdate,
+        account_id: &AccountId,
+        miner_id: Option<&str>,
+        sn: Option<&str>,
+        cursor: Option<&PublicKey>,
+        limit: Option<u32>,
+        include_proof: bool,
+    ) -> Result<ChipsPage, ViewChipError> {
+        let limit = limit.unwrap_or(DEFAULT_CHIPS_PAGE_LIMIT).clamp(1, MAX_CHIPS_PAGE_LIMIT);
+        let prefix = trie_key_parsers::get_raw_prefix_for_rsa_keys(account_id);
+        let start = match cursor {
+            Some(public_key) => TrieKey::Rsa2048Keys {
+                account_id: account_id.clone(),
+                public_key: public_key.clone(),
+            }
+            .to_vec(),
+            None => prefix.clone(),
+        };
 
-                    // Continue to extract and assign other fields as needed
+        let mut chips = vec![];
+        let mut next_cursor = None;
+        let mut last_key = None;
+        let mut scanned = 0;
+        let mut iter = state_update.trie().iter()?;
+        iter.remember_visited_nodes(include_proof);
+        iter.seek(&start)?;
+        for item in &mut iter {
+            let (key, value) = item?;
+            if !key.starts_with(&prefix) {
+                break;
+            }
+            if cursor.is_some() && key == start {
+                continue;
+            }
+            if chips.len() as u32 == limit || scanned == MAX_CHIPS_PAGE_SCAN {
+                next_cursor = last_key;
+                break;
+            }
+            scanned += 1;
 
-                    chip_views.push(chip_view);
+            let public_key_bytes = &key[prefix.len()..];
+            let public_key = PublicKey::try_from_slice(public_key_bytes).map_err(|_| {
+                ViewChipError::InternalError {
+                    error_message: format!(
+                        "Unexpected invalid public key {:?} received from store",
+                        public_key_bytes
+                    ),
                 }
-                Err(_) => {
-                    // Handle parsing error
-                    return Err(ViewChipError::InternalError {
-                        error_message: "Failed to parse JSON from args".to_string(),
-                    });
+            })?;
+            let chip_action = RegisterRsa2048KeysAction::try_from_slice(&value).map_err(|err| {
+                ViewChipError::InternalError {
+                    error_message: format!("Failed to decode chip registration: {}", err),
+                }
+            })?;
+            if let Some(chip) = chip_view(&public_key, &chip_action.args) {
+                if miner_id.map_or(true, |miner_id| chip.miner_id == miner_id)
+                    && sn.map_or(true, |sn| chip.sn == sn)
+                {
+                    chips.push(chip);
                 }
             }
+            last_key = Some(public_key);
         }
-
-        Ok(chip_views)
+        let proof = iter.into_visited_nodes();
+        Ok(ChipsPage { chips, next_cursor, proof })
     }
 
     pub fn view_state(
@@ -369,11 +398,3 @@ impl TrieViewer {
         }
     }
 }
-
-// Helper function to deserialize ChipView from binary format
-#[allow(dead_code)]
-fn deserialize_chip_view(encoded: &[u8]) -> Result<ChipView, Box<dyn std::error::Error>> {
-    // Directly deserialize the JSON data into ChipView
-    let chip_view = serde_json::from_slice::<ChipView>(encoded)?;
-    Ok(chip_view)
-}
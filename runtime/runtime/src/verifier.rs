@@ -5,6 +5,7 @@ use unc_crypto::key_conversion::{is_valid_challenge_key, is_valid_staking_key};
 use unc_parameters::RuntimeConfig;
 use unc_primitives::account::AccessKeyPermission;
 use unc_primitives::action::delegate::SignedDelegateAction;
+use unc_primitives::action::rsa2048::ChipCertificate;
 use unc_primitives::checked_feature;
 use unc_primitives::errors::{
     ActionsValidationError, InvalidAccessKeyError, InvalidTxError, ReceiptValidationError,
@@ -14,7 +15,7 @@ use unc_primitives::receipt::{ActionReceipt, DataReceipt, Receipt, ReceiptEnum};
 use unc_primitives::transaction::DeleteAccountAction;
 use unc_primitives::transaction::{
     Action, AddKeyAction, CreateRsa2048ChallengeAction, DeployContractAction, FunctionCallAction,
-    PledgeAction, RegisterRsa2048KeysAction, SignedTransaction,
+    PledgeAction, RegisterRsa2048KeysAction, Rsa2048KeysOperation, SignedTransaction,
 };
 use unc_primitives::types::{AccountId, Balance};
 use unc_primitives::types::{BlockHeight, StorageUsage};
@@ -118,6 +119,13 @@ pub fn validate_transaction(
     )
     .map_err(InvalidTxError::ActionsValidation)?;
 
+    if checked_feature!("stable", ChipCertificate, current_protocol_version)
+        && transaction.actions.iter().any(|action| matches!(action, Action::RegisterRsa2048Keys(_)))
+        && !config.chip_registry_config.is_registrar(signer_id)
+    {
+        return Err(InvalidTxError::ChipRegistrarRequired { signer_id: signer_id.clone() }.into());
+    }
+
     let sender_is_receiver = &transaction.receiver_id == signer_id;
 
     tx_cost(&config, transaction, gas_price, sender_is_receiver)
@@ -403,7 +411,9 @@ pub fn validate_action(
         Action::DeleteKey(_) => Ok(()),
         Action::DeleteAccount(a) => validate_delete_action(a),
         Action::Delegate(a) => validate_delegate_action(limit_config, a, current_protocol_version),
-        Action::RegisterRsa2048Keys(a) => validate_register_rsa2048_keys_action(limit_config, a),
+        Action::RegisterRsa2048Keys(a) => {
+            validate_register_rsa2048_keys_action(a, current_protocol_version)
+        }
         Action::CreateRsa2048Challenge(a) => validate_create_rsa2048_challenge_action(a),
     }
 }
@@ -516,10 +526,37 @@ fn validate_add_key_action(
     Ok(())
 }
 
+/// Validates `RegisterRsa2048KeysAction`.
+///
+/// Since `ProtocolFeature::ChipCertificate`, checks that the `operation_type` is known and that
+/// added keys carry a borsh chip certificate with a serial number and a positive power. Before
+/// that the action isn't validated.
 fn validate_register_rsa2048_keys_action(
-    _limit_config: &LimitConfig,
-    _action: &RegisterRsa2048KeysAction,
+    action: &RegisterRsa2048KeysAction,
+    current_protocol_version: ProtocolVersion,
 ) -> Result<(), ActionsValidationError> {
+    if !checked_feature!("stable", ChipCertificate, current_protocol_version) {
+        return Ok(());
+    }
+    match action.operation() {
+        Some(Rsa2048KeysOperation::AddKeys) => {}
+        Some(Rsa2048KeysOperation::DeleteKeys) => return Ok(()),
+        None => {
+            return Err(ActionsValidationError::UnknownRsa2048KeysOperation {
+                operation_type: action.operation_type,
+            })
+        }
+    }
+    let is_valid = match ChipCertificate::try_from_args(&action.args, false) {
+        Ok(certificate) => !certificate.sn().is_empty() && certificate.power() > 0,
+        Err(_) => false,
+    };
+    if !is_valid {
+        return Err(ActionsValidationError::InvalidChipCertificate {
+            public_key: Box::new(action.public_key.clone()),
+        });
+    }
+
     Ok(())
 }
 
@@ -566,7 +603,7 @@ mod tests {
     use unc_primitives::account::{AccessKey, FunctionCallPermission};
     use unc_primitives::action::delegate::{DelegateAction, NonDelegateAction};
     use unc_primitives::hash::{hash, CryptoHash};
-    use unc_primitives::test_utils::account_new;
+    use unc_primitives::test_utils::{account_new, chip_certificate};
     use unc_primitives::transaction::{
         CreateAccountAction, DeleteAccountAction, DeleteKeyAction, PledgeAction, TransferAction,
     };
@@ -1494,6 +1531,42 @@ mod tests {
         .expect("valid transaction");
     }
 
+    #[test]
+    fn test_validate_transaction_chip_registrar_required() {
+        let (signer, _, gas_price) =
+            setup_common(TESTING_INIT_BALANCE, 0, Some(AccessKey::full_access()));
+        let certificate = chip_certificate("miner", "sn-0001", 100);
+        let transaction = SignedTransaction::from_actions(
+            1,
+            alice_account(),
+            alice_account(),
+            &*signer,
+            vec![Action::RegisterRsa2048Keys(Box::new(RegisterRsa2048KeysAction {
+                public_key: PublicKey::empty(KeyType::RSA2048),
+                operation_type: Rsa2048KeysOperation::AddKeys as u8,
+                args: certificate.to_args(),
+            }))],
+            CryptoHash::default(),
+        );
+        let protocol_version = ProtocolFeature::ChipCertificate.protocol_version();
+
+        let mut config = RuntimeConfig::test();
+        validate_transaction(&config, gas_price, &transaction, true, protocol_version - 1)
+            .expect("registrars aren't checked before ChipCertificate");
+        assert_eq!(
+            validate_transaction(&config, gas_price, &transaction, true, protocol_version)
+                .expect_err("expected an error"),
+            RuntimeError::InvalidTxError(InvalidTxError::ChipRegistrarRequired {
+                signer_id: alice_account(),
+            }),
+        );
+
+        config.chip_registry_config.registrar_account_ids =
+            vec!["unc".parse().unwrap(), alice_account()];
+        validate_transaction(&config, gas_price, &transaction, true, protocol_version)
+            .expect("valid transaction");
+    }
+
     // Receipts
 
     #[test]
@@ -1849,6 +1922,61 @@ mod tests {
         .expect("valid action");
     }
 
+    #[test]
+    fn test_validate_action_register_rsa2048_keys() {
+        let public_key = PublicKey::empty(KeyType::RSA2048);
+        let register = |args: Vec<u8>| {
+            Action::RegisterRsa2048Keys(Box::new(RegisterRsa2048KeysAction {
+                public_key: public_key.clone(),
+                operation_type: Rsa2048KeysOperation::AddKeys as u8,
+                args,
+            }))
+        };
+        let certificate = chip_certificate("miner", "sn-0001", 100);
+        let legacy_args = br#"{"sn":"sn-0001","power":"100"}"#.to_vec();
+        let zero_power = chip_certificate("miner", "sn-0001", 0);
+        let protocol_version = ProtocolFeature::ChipCertificate.protocol_version();
+        let invalid = Err(ActionsValidationError::InvalidChipCertificate {
+            public_key: Box::new(public_key.clone()),
+        });
+
+        // Before the feature the args aren't validated at all.
+        for args in
+            [certificate.to_args(), legacy_args.clone(), zero_power.to_args(), vec![1, 2, 3]]
+        {
+            validate_action(&test_limit_config(), &register(args), protocol_version - 1)
+                .expect("valid action");
+        }
+
+        validate_action(&test_limit_config(), &register(certificate.to_args()), protocol_version)
+            .expect("valid action");
+        for args in [legacy_args, zero_power.to_args(), vec![1, 2, 3]] {
+            assert_eq!(
+                validate_action(&test_limit_config(), &register(args), protocol_version),
+                invalid
+            );
+        }
+
+        let delete = Action::RegisterRsa2048Keys(Box::new(RegisterRsa2048KeysAction {
+            public_key: public_key.clone(),
+            operation_type: Rsa2048KeysOperation::DeleteKeys as u8,
+            args: vec![],
+        }));
+        validate_action(&test_limit_config(), &delete, protocol_version).expect("valid action");
+
+        let unknown = Action::RegisterRsa2048Keys(Box::new(RegisterRsa2048KeysAction {
+            public_key,
+            operation_type: 7,
+            args: certificate.to_args(),
+        }));
+        validate_action(&test_limit_config(), &unknown, protocol_version - 1)
+            .expect("valid action");
+        assert_eq!(
+            validate_action(&test_limit_config(), &unknown, protocol_version),
+            Err(ActionsValidationError::UnknownRsa2048KeysOperation { operation_type: 7 })
+        );
+    }
+
     #[test]
     fn test_delegate_action_must_be_only_one() {
         let signed_delegate_action = SignedDelegateAction {
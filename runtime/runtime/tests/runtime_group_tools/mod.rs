@@ -86,7 +86,7 @@ impl StandaloneRuntime {
             tries.clone(),
             0,
             &[],
-            &runtime_config.fees.storage_usage_config,
+            &runtime_config,
             &genesis,
             account_ids,
         );
@@ -62,6 +62,24 @@ extern "C" {
         pub_key_len: u64,
         pub_key_ptr: u64,
     ) -> u64;
+    #[cfg(feature = "nightly")]
+    fn rsa2048_verify(
+        sig_len: u64,
+        sig_ptr: u64,
+        msg_len: u64,
+        msg_ptr: u64,
+        pub_key_len: u64,
+        pub_key_ptr: u64,
+    ) -> u64;
+    #[cfg(feature = "nightly")]
+    fn secp256k1_verify(
+        sig_len: u64,
+        sig_ptr: u64,
+        msg_len: u64,
+        msg_ptr: u64,
+        pub_key_len: u64,
+        pub_key_ptr: u64,
+    ) -> u64;
     // #####################
     // # Miscellaneous API #
     // #####################
@@ -551,6 +569,153 @@ pub unsafe fn ed25519_verify_16kib_64() {
     }
 }
 
+/// Function to measure `rsa2048_verify_base`. Also measures `base`,
+/// `read_memory_base`, and `read_memory_byte`. However `rsa2048_verify_base`
+/// computation is more expensive than reading memory so we are okay
+/// overcharging it.
+#[cfg(feature = "nightly")]
+#[no_mangle]
+pub unsafe fn rsa2048_verify_32b_500() {
+    // 32 bytes message ("kajdlfkjalkfjaklfjdkladjfkljadsk")
+    let message: [u8; 32] = [
+        107, 97, 106, 100, 108, 102, 107, 106, 97, 108, 107, 102, 106, 97, 107, 108, 102, 106, 100,
+        107, 108, 97, 100, 106, 102, 107, 108, 106, 97, 100, 115, 107,
+    ];
+
+    for _ in 0..500 {
+        let result = rsa2048_verify(
+            RSA2048_SIGNATURE_32B.len() as _,
+            RSA2048_SIGNATURE_32B.as_ptr() as _,
+            message.len() as _,
+            message.as_ptr() as _,
+            RSA2048_PUBLIC_KEY.len() as _,
+            RSA2048_PUBLIC_KEY.as_ptr() as _,
+        );
+        // check that result was positive, as negative results could have exited
+        // early and do not reflect the full cost.
+        assert!(result == 1);
+    }
+}
+
+/// Function to measure `rsa2048_verify_byte`.
+#[cfg(feature = "nightly")]
+#[no_mangle]
+pub unsafe fn rsa2048_verify_245b_500() {
+    // 245 bytes message, the longest a 2048 bits key can sign
+    let message = [b'a'; 245];
+
+    for _ in 0..500 {
+        let result = rsa2048_verify(
+            RSA2048_SIGNATURE_245B.len() as _,
+            RSA2048_SIGNATURE_245B.as_ptr() as _,
+            message.len() as _,
+            message.as_ptr() as _,
+            RSA2048_PUBLIC_KEY.len() as _,
+            RSA2048_PUBLIC_KEY.as_ptr() as _,
+        );
+        // check that result was positive, as negative results could have exited
+        // early and do not reflect the full cost.
+        assert!(result == 1);
+    }
+}
+
+/// DER-encoded public key the `RSA2048_SIGNATURE_*` signatures verify with.
+#[cfg(feature = "nightly")]
+const RSA2048_PUBLIC_KEY: [u8; 294] = [
+    48, 130, 1, 34, 48, 13, 6, 9, 42, 134, 72, 134, 247, 13, 1, 1, 1, 5, 0, 3, 130, 1, 15, 0, 48,
+    130, 1, 10, 2, 130, 1, 1, 0, 140, 152, 20, 38, 50, 167, 204, 43, 194, 185, 209, 234, 214, 170,
+    111, 119, 55, 196, 209, 45, 178, 83, 239, 232, 189, 69, 224, 2, 98, 211, 151, 84, 166, 47, 241,
+    221, 243, 184, 231, 181, 229, 197, 152, 114, 33, 243, 134, 16, 68, 224, 67, 86, 157, 38, 38,
+    217, 189, 110, 72, 25, 226, 186, 172, 36, 206, 144, 127, 98, 37, 127, 15, 108, 231, 49, 10,
+    171, 239, 76, 215, 29, 99, 60, 207, 230, 225, 40, 211, 156, 54, 64, 252, 167, 241, 159, 8, 216,
+    0, 129, 86, 53, 88, 114, 61, 68, 57, 47, 240, 144, 128, 153, 46, 55, 220, 199, 195, 55, 237,
+    225, 55, 118, 128, 16, 246, 63, 38, 113, 70, 111, 144, 107, 176, 33, 38, 135, 59, 103, 37, 239,
+    14, 116, 188, 245, 134, 222, 159, 227, 137, 10, 142, 19, 136, 185, 159, 161, 69, 22, 246, 252,
+    154, 201, 182, 223, 118, 145, 162, 73, 239, 161, 119, 245, 58, 214, 85, 96, 46, 237, 181, 121,
+    211, 30, 208, 47, 13, 139, 48, 71, 9, 107, 168, 110, 106, 137, 157, 84, 62, 188, 241, 23, 140,
+    179, 223, 25, 106, 95, 193, 207, 247, 134, 227, 238, 246, 134, 13, 121, 176, 106, 6, 150, 54,
+    119, 120, 86, 237, 185, 218, 107, 162, 179, 191, 145, 226, 2, 248, 16, 233, 197, 46, 205, 64,
+    78, 155, 194, 145, 155, 214, 112, 79, 79, 64, 26, 126, 86, 24, 69, 200, 31, 2, 3, 1, 0, 1,
+];
+
+/// Signature of the "kajdlfkjalkfjaklfjdkladjfkljadsk" message.
+#[cfg(feature = "nightly")]
+const RSA2048_SIGNATURE_32B: [u8; 256] = [
+    135, 244, 248, 233, 48, 81, 171, 14, 50, 162, 65, 227, 51, 253, 108, 65, 159, 127, 110, 246,
+    254, 187, 109, 249, 134, 9, 205, 86, 241, 46, 187, 86, 178, 50, 23, 138, 93, 15, 5, 202, 247,
+    165, 129, 32, 61, 233, 225, 239, 79, 101, 27, 10, 36, 144, 189, 201, 219, 231, 40, 143, 125,
+    117, 42, 108, 212, 107, 38, 197, 244, 168, 168, 162, 103, 240, 21, 146, 104, 148, 79, 180, 195,
+    175, 7, 203, 218, 233, 33, 83, 197, 215, 82, 28, 89, 195, 183, 248, 211, 16, 178, 73, 178, 60,
+    117, 4, 7, 101, 211, 82, 71, 253, 185, 1, 30, 251, 183, 148, 171, 251, 180, 195, 254, 165, 139,
+    148, 195, 254, 88, 240, 86, 97, 59, 64, 211, 240, 214, 41, 33, 25, 255, 60, 143, 103, 137, 101,
+    191, 198, 93, 181, 195, 252, 204, 32, 83, 238, 56, 110, 5, 32, 65, 222, 176, 72, 188, 24, 60,
+    192, 202, 250, 178, 143, 109, 146, 126, 25, 242, 232, 77, 14, 15, 80, 96, 121, 167, 193, 235,
+    16, 178, 175, 188, 84, 216, 26, 47, 118, 84, 92, 222, 245, 8, 6, 107, 26, 52, 241, 132, 27, 7,
+    13, 159, 28, 7, 16, 217, 103, 206, 19, 143, 203, 41, 41, 103, 77, 254, 122, 36, 105, 140, 39,
+    142, 181, 152, 247, 126, 230, 162, 91, 179, 12, 24, 139, 181, 176, 18, 202, 212, 73, 173, 51,
+    241, 122, 234, 130, 142, 7, 201, 215,
+];
+
+/// Signature of the message of 245 `a` bytes.
+#[cfg(feature = "nightly")]
+const RSA2048_SIGNATURE_245B: [u8; 256] = [
+    127, 2, 66, 104, 105, 60, 38, 19, 101, 6, 153, 250, 109, 68, 127, 40, 2, 155, 106, 175, 97, 13,
+    34, 253, 171, 45, 249, 227, 107, 145, 171, 217, 179, 233, 239, 239, 54, 141, 254, 19, 152, 69,
+    105, 223, 158, 161, 190, 174, 75, 231, 221, 25, 158, 243, 18, 72, 241, 176, 6, 153, 93, 3, 87,
+    199, 163, 93, 137, 125, 243, 215, 30, 46, 148, 61, 19, 184, 65, 165, 102, 136, 33, 251, 255,
+    31, 206, 111, 195, 60, 17, 92, 153, 229, 8, 25, 18, 4, 216, 185, 74, 77, 144, 251, 17, 111,
+    115, 149, 163, 0, 69, 25, 116, 78, 83, 149, 227, 49, 252, 236, 161, 62, 43, 237, 112, 136, 163,
+    195, 119, 133, 206, 225, 183, 150, 68, 22, 216, 92, 76, 186, 107, 201, 154, 252, 131, 36, 154,
+    178, 245, 173, 49, 114, 46, 240, 41, 64, 42, 155, 182, 64, 174, 125, 217, 40, 0, 220, 126, 148,
+    101, 97, 40, 3, 233, 254, 204, 34, 204, 172, 79, 238, 91, 109, 122, 232, 2, 152, 122, 31, 143,
+    250, 248, 68, 231, 144, 11, 46, 221, 112, 158, 185, 44, 231, 77, 76, 200, 115, 206, 59, 217,
+    23, 99, 174, 112, 108, 109, 117, 244, 167, 153, 111, 131, 218, 85, 201, 26, 211, 32, 67, 51,
+    202, 159, 173, 193, 35, 255, 208, 104, 200, 106, 52, 238, 229, 11, 67, 33, 158, 244, 132, 13,
+    174, 118, 188, 46, 96, 189, 183, 253, 49,
+];
+
+/// Function to measure `secp256k1_verify_base`. Also measures `base`,
+/// `read_memory_base`, and `read_memory_byte`. However `secp256k1_verify`
+/// computation is more expensive than reading memory so we are okay
+/// overcharging it.
+#[cfg(feature = "nightly")]
+#[no_mangle]
+pub unsafe fn secp256k1_verify_500() {
+    // 32 bytes message hash ("kajdlfkjalkfjaklfjdkladjfkljadsk")
+    let message: [u8; 32] = [
+        107, 97, 106, 100, 108, 102, 107, 106, 97, 108, 107, 102, 106, 97, 107, 108, 102, 106, 100,
+        107, 108, 97, 100, 106, 102, 107, 108, 106, 97, 100, 115, 107,
+    ];
+
+    let public_key: [u8; 64] = [
+        143, 53, 57, 63, 4, 67, 144, 131, 38, 189, 106, 218, 224, 228, 65, 70, 242, 31, 122, 156,
+        32, 27, 139, 130, 32, 135, 49, 107, 211, 205, 173, 211, 187, 130, 38, 49, 171, 66, 114,
+        102, 173, 152, 41, 103, 178, 73, 235, 246, 196, 123, 250, 23, 161, 89, 82, 111, 184, 162,
+        105, 190, 60, 166, 200, 194,
+    ];
+
+    let signature: [u8; 64] = [
+        238, 36, 90, 78, 43, 132, 164, 214, 252, 14, 120, 187, 27, 157, 95, 78, 239, 186, 217, 13,
+        163, 251, 99, 44, 83, 230, 70, 93, 200, 176, 212, 145, 53, 73, 101, 230, 130, 246, 84, 241,
+        89, 206, 202, 151, 255, 128, 148, 143, 172, 101, 148, 136, 130, 180, 167, 12, 225, 203,
+        175, 236, 50, 190, 38, 12,
+    ];
+
+    for _ in 0..500 {
+        let result = secp256k1_verify(
+            signature.len() as _,
+            signature.as_ptr() as _,
+            message.len() as _,
+            message.as_ptr() as _,
+            public_key.len() as _,
+            public_key.as_ptr() as _,
+        );
+        // check that result was positive, as negative results could have exited
+        // early and do not reflect the full cost.
+        assert!(result == 1);
+    }
+}
+
 #[repr(C)]
 struct MultiexpElem([u8; 64], [u8; 32]);
 
@@ -125,6 +125,20 @@ imports! {
         pub_key_len: u64,
         pub_key_ptr: u64
     ] -> [u64]>,
+    #[rsa2048_secp256k1_verify] rsa2048_verify<[sig_len: u64,
+        sig_ptr: u64,
+        msg_len: u64,
+        msg_ptr: u64,
+        pub_key_len: u64,
+        pub_key_ptr: u64
+    ] -> [u64]>,
+    #[rsa2048_secp256k1_verify] secp256k1_verify<[sig_len: u64,
+        sig_ptr: u64,
+        msg_len: u64,
+        msg_ptr: u64,
+        pub_key_len: u64,
+        pub_key_ptr: u64
+    ] -> [u64]>,
     #[math_extension] ripemd160<[value_len: u64, value_ptr: u64, register_id: u64] -> []>,
     #[math_extension] ecrecover<[hash_len: u64, hash_ptr: u64, sign_len: u64, sig_ptr: u64, v: u64, malleability_flag: u64, register_id: u64] -> [u64]>,
     // #####################
@@ -221,6 +235,23 @@ imports! {
         beneficiary_id_len: u64,
         beneficiary_id_ptr: u64
     ] -> []>,
+    #[rsa2048_host_functions] promise_batch_action_register_rsa2048_keys<[
+        promise_index: u64,
+        public_key_len: u64,
+        public_key_ptr: u64,
+        operation_type: u64,
+        args_len: u64,
+        args_ptr: u64
+    ] -> []>,
+    #[rsa2048_host_functions] promise_batch_action_create_rsa2048_challenge<[
+        promise_index: u64,
+        public_key_len: u64,
+        public_key_ptr: u64,
+        challenge_key_len: u64,
+        challenge_key_ptr: u64,
+        args_len: u64,
+        args_ptr: u64
+    ] -> []>,
     // #######################
     // # Promise API results #
     // #######################
@@ -437,6 +437,46 @@ pub trait External {
         beneficiary_id: AccountId,
     ) -> Result<(), VMLogicError>;
 
+    /// Attach the [`RegisterRsa2048KeysAction`] action to an existing receipt.
+    ///
+    /// # Arguments
+    ///
+    /// * `receipt_index` - an index of Receipt to append an action
+    /// * `public_key` - a chip public key to register or remove
+    /// * `operation_type` - `0` to add the keys, `1` to delete them
+    /// * `args` - serialized arguments of the operation
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `receipt_index` does not refer to a known receipt.
+    fn append_action_register_rsa2048_keys(
+        &mut self,
+        receipt_index: ReceiptIndex,
+        public_key: PublicKey,
+        operation_type: u8,
+        args: Vec<u8>,
+    );
+
+    /// Attach the [`CreateRsa2048ChallengeAction`] action to an existing receipt.
+    ///
+    /// # Arguments
+    ///
+    /// * `receipt_index` - an index of Receipt to append an action
+    /// * `public_key` - a registered chip public key
+    /// * `challenge_key` - a public key to bind to the chip
+    /// * `args` - serialized arguments of the challenge
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `receipt_index` does not refer to a known receipt.
+    fn append_action_create_rsa2048_challenge(
+        &mut self,
+        receipt_index: ReceiptIndex,
+        public_key: PublicKey,
+        challenge_key: PublicKey,
+        args: Vec<u8>,
+    );
+
     /// # Panic
     ///
     /// Panics if `ReceiptIndex` is invalid.
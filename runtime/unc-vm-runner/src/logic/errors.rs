@@ -206,6 +206,15 @@ pub enum HostError {
     /// Invalid input to ed25519 signature verification function (e.g. signature cannot be
     /// derived from bytes).
     Ed25519VerifyInvalidInput { msg: String },
+    /// `operation_type` passed to `promise_batch_action_register_rsa2048_keys` is neither add
+    /// nor delete.
+    InvalidRsa2048KeysOperation { operation_type: u64 },
+    /// Invalid input to RSA2048 signature verification function (e.g. signature or public key
+    /// of a wrong length).
+    Rsa2048VerifyInvalidInput { msg: String },
+    /// Invalid input to secp256k1 signature verification function (e.g. message which isn't a
+    /// 32 bytes hash).
+    Secp256k1VerifyInvalidInput { msg: String },
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -435,6 +444,15 @@ impl std::fmt::Display for HostError {
             Ed25519VerifyInvalidInput { msg } => {
                 write!(f, "ED25519 signature verification error: {}", msg)
             }
+            InvalidRsa2048KeysOperation { operation_type } => {
+                write!(f, "Invalid RSA2048 keys operation type {}", operation_type)
+            }
+            Rsa2048VerifyInvalidInput { msg } => {
+                write!(f, "RSA2048 signature verification error: {}", msg)
+            }
+            Secp256k1VerifyInvalidInput { msg } => {
+                write!(f, "SECP256K1 signature verification error: {}", msg)
+            }
         }
     }
 }
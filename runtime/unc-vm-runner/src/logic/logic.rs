@@ -8,7 +8,10 @@ use super::ValuePtr;
 use super::{HostError, VMLogicError};
 use crate::ProfileDataV3;
 use std::mem::size_of;
-use unc_crypto::Secp256K1Signature;
+use unc_crypto::{
+    PublicKey, Rsa2048PublicKey, Rsa2048Signature, Secp256K1PublicKey, Secp256K1Signature,
+    Signature,
+};
 use unc_parameters::vm::{Config, StorageGetMode};
 use unc_parameters::{
     transfer_exec_fee, transfer_send_fee, ActionCosts, ExtCosts, RuntimeFeesConfig,
@@ -1209,6 +1212,141 @@ impl<'a> VMLogic<'a> {
         }
     }
 
+    /// Verify a RSA2048 signature given a message and a public key.
+    ///
+    /// The public key is DER-encoded in the SubjectPublicKeyInfo format, the
+    /// same encoding `Rsa2048PublicKey` uses. The signature is a PKCS#1 v1.5
+    /// signature of the raw message, i.e. the message is not hashed and not
+    /// prefixed with a digest identifier before verification, so it can be at
+    /// most 245 bytes long.
+    ///
+    /// Returns a bool indicating success (1) or failure (0) as a `u64`.
+    ///
+    /// # Errors
+    ///
+    /// * If the public key's size is not equal to 294, or signature size is
+    ///   not equal to 256, returns [HostError::Rsa2048VerifyInvalidInput].
+    /// * If any of the signature, message or public key arguments are out of
+    ///   memory bounds, returns [`HostError::MemoryAccessViolation`]
+    ///
+    /// # Cost
+    ///
+    /// Each input can either be in memory or in a register, see
+    /// [`VMLogic::ed25519_verify`] for the `input_cost` of each of them.
+    ///
+    /// `input_cost(num_bytes_signature) + input_cost(num_bytes_message) +
+    ///  input_cost(num_bytes_public_key) + rsa2048_verify_base +
+    ///  rsa2048_verify_byte * num_bytes_message`
+    pub fn rsa2048_verify(
+        &mut self,
+        signature_len: u64,
+        signature_ptr: u64,
+        message_len: u64,
+        message_ptr: u64,
+        public_key_len: u64,
+        public_key_ptr: u64,
+    ) -> Result<u64> {
+        self.gas_counter.pay_base(rsa2048_verify_base)?;
+
+        let signature = {
+            let vec = get_memory_or_register!(self, signature_ptr, signature_len)?;
+            Rsa2048Signature::try_from(&vec[..]).map_err(|_| {
+                VMLogicError::HostError(HostError::Rsa2048VerifyInvalidInput {
+                    msg: "invalid signature length".to_string(),
+                })
+            })?
+        };
+
+        let message = get_memory_or_register!(self, message_ptr, message_len)?;
+        self.gas_counter.pay_per(rsa2048_verify_byte, message.len() as u64)?;
+
+        let public_key = {
+            let vec = get_memory_or_register!(self, public_key_ptr, public_key_len)?;
+            Rsa2048PublicKey::try_from(&vec[..]).map_err(|_| {
+                VMLogicError::HostError(HostError::Rsa2048VerifyInvalidInput {
+                    msg: "invalid public key length".to_string(),
+                })
+            })?
+        };
+
+        Ok(Signature::RSA(signature).verify(&message, &PublicKey::RSA(public_key)) as u64)
+    }
+
+    /// Verify a secp256k1 ECDSA signature given a message hash and a public key.
+    ///
+    /// The signature is the 64 bytes `r || s`, without the recovery byte
+    /// `ecrecover` takes. The public key is the 64 bytes uncompressed point
+    /// without the `0x04` prefix, the same encoding `Secp256K1PublicKey` uses.
+    /// Signatures in the upper range of `s` are malleable and are rejected.
+    ///
+    /// Returns a bool indicating success (1) or failure (0) as a `u64`.
+    ///
+    /// # Errors
+    ///
+    /// * If the public key's size is not equal to 64, signature size is not
+    ///   equal to 64, or message size is not equal to 32, returns
+    ///   [HostError::Secp256k1VerifyInvalidInput].
+    /// * If any of the signature, message or public key arguments are out of
+    ///   memory bounds, returns [`HostError::MemoryAccessViolation`]
+    ///
+    /// # Cost
+    ///
+    /// Each input can either be in memory or in a register, see
+    /// [`VMLogic::ed25519_verify`] for the `input_cost` of each of them.
+    ///
+    /// `input_cost(num_bytes_signature) + input_cost(num_bytes_message) +
+    ///  input_cost(num_bytes_public_key) + secp256k1_verify_base`
+    pub fn secp256k1_verify(
+        &mut self,
+        signature_len: u64,
+        signature_ptr: u64,
+        message_len: u64,
+        message_ptr: u64,
+        public_key_len: u64,
+        public_key_ptr: u64,
+    ) -> Result<u64> {
+        self.gas_counter.pay_base(secp256k1_verify_base)?;
+
+        let signature = {
+            let vec = get_memory_or_register!(self, signature_ptr, signature_len)?;
+            if vec.len() != 64 {
+                return Err(VMLogicError::HostError(HostError::Secp256k1VerifyInvalidInput {
+                    msg: "invalid signature length".to_string(),
+                }));
+            }
+            // The recovery byte is irrelevant for verification.
+            let mut bytes = [0u8; 65];
+            bytes[0..64].copy_from_slice(&vec);
+            Secp256K1Signature::from(bytes)
+        };
+
+        let message = {
+            let vec = get_memory_or_register!(self, message_ptr, message_len)?;
+            if vec.len() != 32 {
+                return Err(VMLogicError::HostError(HostError::Secp256k1VerifyInvalidInput {
+                    msg: "invalid message length".to_string(),
+                }));
+            }
+            vec
+        };
+
+        let public_key = {
+            let vec = get_memory_or_register!(self, public_key_ptr, public_key_len)?;
+            Secp256K1PublicKey::try_from(&vec[..]).map_err(|_| {
+                VMLogicError::HostError(HostError::Secp256k1VerifyInvalidInput {
+                    msg: "invalid public key length".to_string(),
+                })
+            })?
+        };
+
+        if !signature.check_signature_values(true) {
+            return Ok(false as u64);
+        }
+
+        Ok(Signature::SECP256K1(signature).verify(&message, &PublicKey::SECP256K1(public_key))
+            as u64)
+    }
+
     /// Consume gas. Counts both towards `burnt_gas` and `used_gas`.
     ///
     /// # Errors
@@ -2035,6 +2173,116 @@ impl<'a> VMLogic<'a> {
         Ok(())
     }
 
+    /// Appends `RegisterRsa2048Keys` action to the batch of actions for the given promise pointed
+    /// by `promise_idx`. `operation_type` selects between adding (`0`) and deleting (`1`) the
+    /// chip keys.
+    ///
+    /// # Errors
+    ///
+    /// * If `promise_idx` does not correspond to an existing promise returns `InvalidPromiseIndex`.
+    /// * If the promise pointed by the `promise_idx` is an ephemeral promise created by
+    /// `promise_and` returns `CannotAppendActionToJointPromise`.
+    /// * If the given public key is not a valid (e.g. wrong length) returns `InvalidPublicKey`.
+    /// * If `operation_type` is neither `0` nor `1` returns `InvalidRsa2048KeysOperation`.
+    /// * If `public_key_len + public_key_ptr` or `args_len + args_ptr` points outside the memory
+    /// of the guest or host returns `MemoryAccessViolation`.
+    /// * If called as view function returns `ProhibitedInView`.
+    ///
+    /// # Cost
+    ///
+    /// `burnt_gas := base + dispatch action base fee + dispatch action per byte fee * num bytes + cost of reading public key and args from memory `
+    /// `used_gas := burnt_gas + exec action base fee + exec action per byte fee * num bytes`
+    pub fn promise_batch_action_register_rsa2048_keys(
+        &mut self,
+        promise_idx: u64,
+        public_key_len: u64,
+        public_key_ptr: u64,
+        operation_type: u64,
+        args_len: u64,
+        args_ptr: u64,
+    ) -> Result<()> {
+        self.gas_counter.pay_base(base)?;
+        if self.context.is_view() {
+            return Err(HostError::ProhibitedInView {
+                method_name: "promise_batch_action_register_rsa2048_keys".to_string(),
+            }
+            .into());
+        }
+        let public_key = self.get_public_key(public_key_ptr, public_key_len)?;
+        let operation_type = match operation_type {
+            0 | 1 => operation_type as u8,
+            _ => return Err(HostError::InvalidRsa2048KeysOperation { operation_type }.into()),
+        };
+        let args = get_memory_or_register!(self, args_ptr, args_len)?.into_owned();
+        let args_len = args.len() as u64;
+
+        let (receipt_idx, sir) = self.promise_idx_to_receipt_idx_with_sir(promise_idx)?;
+        self.pay_action_base(ActionCosts::register_rsa2048_keys, sir)?;
+        self.pay_action_per_byte(ActionCosts::register_rsa2048_keys_byte, args_len, sir)?;
+
+        self.ext.append_action_register_rsa2048_keys(
+            receipt_idx,
+            public_key.decode()?,
+            operation_type,
+            args,
+        );
+        Ok(())
+    }
+
+    /// Appends `CreateRsa2048Challenge` action to the batch of actions for the given promise
+    /// pointed by `promise_idx`.
+    ///
+    /// # Errors
+    ///
+    /// * If `promise_idx` does not correspond to an existing promise returns `InvalidPromiseIndex`.
+    /// * If the promise pointed by the `promise_idx` is an ephemeral promise created by
+    /// `promise_and` returns `CannotAppendActionToJointPromise`.
+    /// * If either of the given public keys is not a valid (e.g. wrong length) returns
+    /// `InvalidPublicKey`.
+    /// * If `public_key_len + public_key_ptr`, `challenge_key_len + challenge_key_ptr` or
+    /// `args_len + args_ptr` points outside the memory of the guest or host returns
+    /// `MemoryAccessViolation`.
+    /// * If called as view function returns `ProhibitedInView`.
+    ///
+    /// # Cost
+    ///
+    /// `burnt_gas := base + dispatch action base fee + dispatch action per byte fee * num bytes + cost of reading public keys and args from memory `
+    /// `used_gas := burnt_gas + exec action base fee + exec action per byte fee * num bytes`
+    pub fn promise_batch_action_create_rsa2048_challenge(
+        &mut self,
+        promise_idx: u64,
+        public_key_len: u64,
+        public_key_ptr: u64,
+        challenge_key_len: u64,
+        challenge_key_ptr: u64,
+        args_len: u64,
+        args_ptr: u64,
+    ) -> Result<()> {
+        self.gas_counter.pay_base(base)?;
+        if self.context.is_view() {
+            return Err(HostError::ProhibitedInView {
+                method_name: "promise_batch_action_create_rsa2048_challenge".to_string(),
+            }
+            .into());
+        }
+        let public_key = self.get_public_key(public_key_ptr, public_key_len)?;
+        let challenge_key = self.get_public_key(challenge_key_ptr, challenge_key_len)?;
+        let args = get_memory_or_register!(self, args_ptr, args_len)?.into_owned();
+        let args_len = args.len() as u64;
+
+        let (receipt_idx, sir) = self.promise_idx_to_receipt_idx_with_sir(promise_idx)?;
+        self.pay_action_base(ActionCosts::create_rsa2048_challenge, sir)?;
+        self.pay_action_per_byte(ActionCosts::create_rsa2048_challenge_byte, args_len, sir)?;
+
+        self.ext.append_action_create_rsa2048_challenge(
+            receipt_idx,
+            public_key.decode()?,
+            challenge_key.decode()?,
+            args,
+        );
+        Ok(())
+    }
+
     /// If the current function is invoked by a callback we can access the execution results of the
     /// promises that caused the callback. This function returns the number of complete and
     /// incomplete callbacks.
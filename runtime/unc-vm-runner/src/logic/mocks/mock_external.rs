@@ -62,6 +62,18 @@ pub enum MockAction {
         public_key: unc_crypto::PublicKey,
         nonce: u64,
     },
+    RegisterRsa2048Keys {
+        receipt_index: ReceiptIndex,
+        public_key: unc_crypto::PublicKey,
+        operation_type: u8,
+        args: Vec<u8>,
+    },
+    CreateRsa2048Challenge {
+        receipt_index: ReceiptIndex,
+        public_key: unc_crypto::PublicKey,
+        challenge_key: unc_crypto::PublicKey,
+        args: Vec<u8>,
+    },
 }
 
 #[derive(Default, Clone)]
@@ -280,6 +292,36 @@ impl External for MockedExternal {
         Ok(())
     }
 
+    fn append_action_register_rsa2048_keys(
+        &mut self,
+        receipt_index: ReceiptIndex,
+        public_key: unc_crypto::PublicKey,
+        operation_type: u8,
+        args: Vec<u8>,
+    ) {
+        self.action_log.push(MockAction::RegisterRsa2048Keys {
+            receipt_index,
+            public_key,
+            operation_type,
+            args,
+        });
+    }
+
+    fn append_action_create_rsa2048_challenge(
+        &mut self,
+        receipt_index: ReceiptIndex,
+        public_key: unc_crypto::PublicKey,
+        challenge_key: unc_crypto::PublicKey,
+        args: Vec<u8>,
+    ) {
+        self.action_log.push(MockAction::CreateRsa2048Challenge {
+            receipt_index,
+            public_key,
+            challenge_key,
+            args,
+        });
+    }
+
     fn get_receipt_receiver(&self, receipt_index: ReceiptIndex) -> &AccountId {
         match &self.action_log[receipt_index as usize] {
             MockAction::CreateReceipt { receiver_id, .. } => receiver_id,
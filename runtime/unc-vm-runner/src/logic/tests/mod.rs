@@ -8,6 +8,8 @@ mod logs;
 mod miscs;
 mod promises;
 mod registers;
+mod rsa2048_verify;
+mod secp256k1_verify;
 mod storage_read_write;
 mod storage_usage;
 mod view_method;
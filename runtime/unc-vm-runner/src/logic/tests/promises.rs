@@ -2,6 +2,7 @@ use crate::logic::mocks::mock_external::MockedExternal;
 use crate::logic::tests::helpers::*;
 use crate::logic::tests::vm_logic_builder::VMLogicBuilder;
 use crate::logic::types::PromiseResult;
+use crate::logic::HostError;
 
 use serde_json;
 use unc_crypto::PublicKey;
@@ -437,6 +438,122 @@ fn test_promise_batch_action_pledge() {
     .assert_eq(&serde_json::to_string_pretty(&vm_receipts(&logic_builder.ext)).unwrap());
 }
 
+#[test]
+fn test_promise_batch_action_register_rsa2048_keys() {
+    let mut logic_builder = VMLogicBuilder::default();
+    let mut logic = logic_builder.build();
+    let index = promise_batch_create(&mut logic, "rick.test").expect("should create a promise");
+    let key = borsh::to_vec(
+        &"ed25519:5do5nkAEVhL8iteDvXNgxi4pWK78Y7DDadX11ArFNyrf".parse::<PublicKey>().unwrap(),
+    )
+    .unwrap();
+
+    let key = logic.internal_mem_write(&key);
+    let args = logic.internal_mem_write(b"args");
+
+    logic
+        .promise_batch_action_register_rsa2048_keys(123, key.len, key.ptr, 0, args.len, args.ptr)
+        .expect_err("shouldn't accept not existent promise index");
+    assert_eq!(
+        logic.promise_batch_action_register_rsa2048_keys(
+            index, key.len, key.ptr, 2, args.len, args.ptr
+        ),
+        Err(HostError::InvalidRsa2048KeysOperation { operation_type: 2 }.into())
+    );
+
+    logic
+        .promise_batch_action_register_rsa2048_keys(index, key.len, key.ptr, 1, args.len, args.ptr)
+        .expect("should add an action to register rsa2048 keys");
+    expect_test::expect![[r#"
+        [
+          {
+            "CreateReceipt": {
+              "receipt_indices": [],
+              "receiver_id": "rick.test"
+            }
+          },
+          {
+            "RegisterRsa2048Keys": {
+              "receipt_index": 0,
+              "public_key": "ed25519:5do5nkAEVhL8iteDvXNgxi4pWK78Y7DDadX11ArFNyrf",
+              "operation_type": 1,
+              "args": [
+                97,
+                114,
+                103,
+                115
+              ]
+            }
+          }
+        ]"#]]
+    .assert_eq(&serde_json::to_string_pretty(&vm_receipts(&logic_builder.ext)).unwrap());
+}
+
+#[test]
+fn test_promise_batch_action_create_rsa2048_challenge() {
+    let mut logic_builder = VMLogicBuilder::default();
+    let mut logic = logic_builder.build();
+    let index = promise_batch_create(&mut logic, "rick.test").expect("should create a promise");
+    let key = borsh::to_vec(
+        &"ed25519:5do5nkAEVhL8iteDvXNgxi4pWK78Y7DDadX11ArFNyrf".parse::<PublicKey>().unwrap(),
+    )
+    .unwrap();
+    let challenge_key = borsh::to_vec(
+        &"ed25519:He7QeRuwizNEhBioYG3u4DZ8jWXyETiyNzFD3MkTjDMf".parse::<PublicKey>().unwrap(),
+    )
+    .unwrap();
+
+    let key = logic.internal_mem_write(&key);
+    let challenge_key = logic.internal_mem_write(&challenge_key);
+    let args = logic.internal_mem_write(b"args");
+
+    logic
+        .promise_batch_action_create_rsa2048_challenge(
+            123,
+            key.len,
+            key.ptr,
+            challenge_key.len,
+            challenge_key.ptr,
+            args.len,
+            args.ptr,
+        )
+        .expect_err("shouldn't accept not existent promise index");
+    logic
+        .promise_batch_action_create_rsa2048_challenge(
+            index,
+            key.len,
+            key.ptr,
+            challenge_key.len,
+            challenge_key.ptr,
+            args.len,
+            args.ptr,
+        )
+        .expect("should add an action to create an rsa2048 challenge");
+    expect_test::expect![[r#"
+        [
+          {
+            "CreateReceipt": {
+              "receipt_indices": [],
+              "receiver_id": "rick.test"
+            }
+          },
+          {
+            "CreateRsa2048Challenge": {
+              "receipt_index": 0,
+              "public_key": "ed25519:5do5nkAEVhL8iteDvXNgxi4pWK78Y7DDadX11ArFNyrf",
+              "challenge_key": "ed25519:He7QeRuwizNEhBioYG3u4DZ8jWXyETiyNzFD3MkTjDMf",
+              "args": [
+                97,
+                114,
+                103,
+                115
+              ]
+            }
+          }
+        ]"#]]
+    .assert_eq(&serde_json::to_string_pretty(&vm_receipts(&logic_builder.ext)).unwrap());
+}
+
 #[test]
 fn test_promise_batch_action_add_key_with_function_call() {
     let mut logic_builder = VMLogicBuilder::default();
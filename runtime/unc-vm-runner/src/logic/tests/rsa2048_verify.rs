@@ -0,0 +1,97 @@
+use crate::logic::tests::helpers::*;
+use crate::logic::tests::vm_logic_builder::VMLogicBuilder;
+use crate::logic::HostError;
+use crate::logic::VMLogicError;
+use crate::map;
+use std::collections::HashMap;
+use unc_crypto::{KeyType, SecretKey, Signature};
+use unc_parameters::ExtCosts;
+
+const MESSAGE: &[u8] = b"chip attestation challenge";
+
+/// Returns a signature of `MESSAGE` and the public key it verifies with.
+fn sign_message() -> ([u8; 256], Vec<u8>) {
+    let secret_key = SecretKey::from_random(KeyType::RSA2048);
+    let signature = match secret_key.sign(MESSAGE) {
+        Signature::RSA(signature) => signature.into(),
+        _ => unreachable!(),
+    };
+    (signature, secret_key.public_key().key_data().to_vec())
+}
+
+#[track_caller]
+fn check_rsa2048_verify(
+    signature: &[u8],
+    message: &[u8],
+    public_key: &[u8],
+    want: Result<u64, HostError>,
+    want_costs: HashMap<ExtCosts, u64>,
+) {
+    let mut logic_builder = VMLogicBuilder::default();
+    let mut logic = logic_builder.build();
+
+    let signature_ptr = logic.internal_mem_write(signature).ptr;
+    let message_ptr = logic.internal_mem_write(message).ptr;
+    let public_key_ptr = logic.internal_mem_write(public_key).ptr;
+
+    let result = logic.rsa2048_verify(
+        signature.len() as u64,
+        signature_ptr,
+        message.len() as u64,
+        message_ptr,
+        public_key.len() as u64,
+        public_key_ptr,
+    );
+
+    let want = want.map_err(VMLogicError::HostError);
+    assert_eq!(want, result);
+    assert_costs(want_costs);
+}
+
+#[test]
+fn test_rsa2048_verify_behavior_and_errors() {
+    let (signature, public_key) = sign_message();
+    let costs = map! {
+        ExtCosts::read_memory_byte: 256 + MESSAGE.len() as u64 + 294,
+        ExtCosts::read_memory_base: 3,
+        ExtCosts::rsa2048_verify_base: 1,
+        ExtCosts::rsa2048_verify_byte: MESSAGE.len() as u64,
+    };
+
+    check_rsa2048_verify(&signature, MESSAGE, &public_key, Ok(1), costs.clone());
+    check_rsa2048_verify(
+        &signature,
+        b"another message entirely!!",
+        &public_key,
+        Ok(0),
+        costs.clone(),
+    );
+
+    let (_, other_public_key) = sign_message();
+    check_rsa2048_verify(&signature, MESSAGE, &other_public_key, Ok(0), costs);
+
+    check_rsa2048_verify(
+        &signature[..255],
+        MESSAGE,
+        &public_key,
+        Err(HostError::Rsa2048VerifyInvalidInput { msg: "invalid signature length".to_string() }),
+        map! {
+            ExtCosts::read_memory_byte: 255,
+            ExtCosts::read_memory_base: 1,
+            ExtCosts::rsa2048_verify_base: 1,
+        },
+    );
+
+    check_rsa2048_verify(
+        &signature,
+        MESSAGE,
+        &public_key[..32],
+        Err(HostError::Rsa2048VerifyInvalidInput { msg: "invalid public key length".to_string() }),
+        map! {
+            ExtCosts::read_memory_byte: 256 + MESSAGE.len() as u64 + 32,
+            ExtCosts::read_memory_base: 3,
+            ExtCosts::rsa2048_verify_base: 1,
+            ExtCosts::rsa2048_verify_byte: MESSAGE.len() as u64,
+        },
+    );
+}
@@ -0,0 +1,109 @@
+use crate::logic::tests::helpers::*;
+use crate::logic::tests::vm_logic_builder::VMLogicBuilder;
+use crate::logic::HostError;
+use crate::logic::VMLogicError;
+use crate::map;
+use std::collections::HashMap;
+use unc_crypto::{KeyType, SecretKey, Signature};
+use unc_parameters::ExtCosts;
+
+// 32 bytes message hash
+const MESSAGE: [u8; 32] = [
+    107, 97, 106, 100, 108, 102, 107, 106, 97, 108, 107, 102, 106, 97, 107, 108, 102, 106, 100,
+    107, 108, 97, 100, 106, 102, 107, 108, 106, 97, 100, 115, 107,
+];
+
+/// Returns a `r || s` signature of `MESSAGE` and the public key it verifies
+/// with.
+fn sign_message() -> ([u8; 64], Vec<u8>) {
+    let secret_key = SecretKey::from_random(KeyType::SECP256K1);
+    let signature: [u8; 65] = match secret_key.sign(&MESSAGE) {
+        Signature::SECP256K1(signature) => signature.into(),
+        _ => unreachable!(),
+    };
+    (signature[..64].try_into().unwrap(), secret_key.public_key().key_data().to_vec())
+}
+
+#[track_caller]
+fn check_secp256k1_verify(
+    signature: &[u8],
+    message: &[u8],
+    public_key: &[u8],
+    want: Result<u64, HostError>,
+    want_costs: HashMap<ExtCosts, u64>,
+) {
+    let mut logic_builder = VMLogicBuilder::default();
+    let mut logic = logic_builder.build();
+
+    let signature_ptr = logic.internal_mem_write(signature).ptr;
+    let message_ptr = logic.internal_mem_write(message).ptr;
+    let public_key_ptr = logic.internal_mem_write(public_key).ptr;
+
+    let result = logic.secp256k1_verify(
+        signature.len() as u64,
+        signature_ptr,
+        message.len() as u64,
+        message_ptr,
+        public_key.len() as u64,
+        public_key_ptr,
+    );
+
+    let want = want.map_err(VMLogicError::HostError);
+    assert_eq!(want, result);
+    assert_costs(want_costs);
+}
+
+#[test]
+fn test_secp256k1_verify_behavior_and_errors() {
+    let (signature, public_key) = sign_message();
+    let costs = map! {
+        ExtCosts::read_memory_byte: 160,
+        ExtCosts::read_memory_base: 3,
+        ExtCosts::secp256k1_verify_base: 1,
+    };
+
+    check_secp256k1_verify(&signature, &MESSAGE, &public_key, Ok(1), costs.clone());
+    check_secp256k1_verify(&signature, &[1; 32], &public_key, Ok(0), costs.clone());
+    check_secp256k1_verify(&[1; 64], &MESSAGE, &public_key, Ok(0), costs.clone());
+
+    let (_, other_public_key) = sign_message();
+    check_secp256k1_verify(&signature, &MESSAGE, &other_public_key, Ok(0), costs);
+
+    check_secp256k1_verify(
+        &[0; 65],
+        &MESSAGE,
+        &public_key,
+        Err(HostError::Secp256k1VerifyInvalidInput { msg: "invalid signature length".to_string() }),
+        map! {
+            ExtCosts::read_memory_byte: 65,
+            ExtCosts::read_memory_base: 1,
+            ExtCosts::secp256k1_verify_base: 1,
+        },
+    );
+
+    check_secp256k1_verify(
+        &signature,
+        &MESSAGE[..31],
+        &public_key,
+        Err(HostError::Secp256k1VerifyInvalidInput { msg: "invalid message length".to_string() }),
+        map! {
+            ExtCosts::read_memory_byte: 95,
+            ExtCosts::read_memory_base: 2,
+            ExtCosts::secp256k1_verify_base: 1,
+        },
+    );
+
+    check_secp256k1_verify(
+        &signature,
+        &MESSAGE,
+        &public_key[..33],
+        Err(HostError::Secp256k1VerifyInvalidInput {
+            msg: "invalid public key length".to_string(),
+        }),
+        map! {
+            ExtCosts::read_memory_byte: 129,
+            ExtCosts::read_memory_base: 3,
+            ExtCosts::secp256k1_verify_base: 1,
+        },
+    );
+}
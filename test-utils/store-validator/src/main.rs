@@ -37,7 +37,10 @@ fn main() {
     .unwrap()
     .get_hot_store();
     let epoch_manager = EpochManager::new_arc_handle(store.clone(), &unc_config.genesis.config);
-    let shard_tracker = ShardTracker::new(epoch_manager.clone());
+    let shard_tracker = ShardTracker::new(
+        unc_config.client_config.tracked_shards_config.clone(),
+        epoch_manager.clone(),
+    );
     let runtime = framework::NightshadeRuntime::from_config(
         home_dir,
         store.clone(),
@@ -8,6 +8,8 @@ use std::io::{BufReader, BufWriter};
 use std::path::Path;
 use unc_chain_configs::{Genesis, GenesisValidationMode};
 use unc_crypto::PublicKey;
+use unc_parameters::{ChipRegistryConfig, RuntimeConfigStore};
+use unc_primitives::action::RegisterRsa2048KeysAction;
 use unc_primitives::hash::CryptoHash;
 use unc_primitives::shard_layout::ShardLayout;
 use unc_primitives::state_record::StateRecord;
@@ -34,6 +36,7 @@ struct AccountRecords {
     // given there
     amount_needed: bool,
     keys: HashMap<PublicKey, AccessKey>,
+    rsa2048_keys: HashMap<PublicKey, RegisterRsa2048KeysAction>,
     // code state records must appear after the account state record. So for accounts we're
     // modifying/adding keys for, we will remember any code records (there really should only be one),
     // and add them to the output only after we write the account record
@@ -114,6 +117,7 @@ impl AccountRecords {
         seq: &mut S,
         total_supply: &mut Balance,
         num_extra_bytes_record: u64,
+        chip_registry_config: &ChipRegistryConfig,
     ) -> anyhow::Result<()>
     where
         <S as SerializeSeq>::Error: Send + Sync + 'static,
@@ -133,6 +137,25 @@ impl AccountRecords {
                         access_key,
                     })?;
                 }
+                // registrars pay for the whole chip registration, miners that claimed a chip
+                // only pay for its public key
+                let is_registrar = chip_registry_config.is_registrar(&account_id);
+                for (public_key, rsa2048_keys) in self.rsa2048_keys {
+                    let record_len = if is_registrar {
+                        borsh::object_length(&rsa2048_keys).unwrap() as u64
+                    } else {
+                        borsh::object_length(&public_key).unwrap() as u64
+                    };
+                    let storage_usage =
+                        account.storage_usage() + record_len + num_extra_bytes_record;
+                    account.set_storage_usage(storage_usage);
+
+                    seq.serialize_element(&StateRecord::Rsa2048Keys {
+                        account_id: account_id.clone(),
+                        public_key,
+                        rsa2048_keys,
+                    })?;
+                }
                 if self.amount_needed {
                     account.set_amount(10_000 * framework::config::UNC_BASE);
                 }
@@ -143,7 +166,7 @@ impl AccountRecords {
                 }
             }
             None => {
-                tracing::warn!("access keys for {} were included in --extra-records, but no Account record was found. Not adding them to the output", &account_id);
+                tracing::warn!("access keys or rsa2048 keys for {} were included in --extra-records, but no Account record was found. Not adding them to the output", &account_id);
             }
         }
         Ok(())
@@ -224,9 +247,12 @@ fn parse_extra_records(
             StateRecord::AccessKey { account_id, public_key, access_key } => {
                 records.entry(account_id).or_default().keys.insert(public_key, access_key);
             }
+            StateRecord::Rsa2048Keys { account_id, public_key, rsa2048_keys } => {
+                records.entry(account_id).or_default().rsa2048_keys.insert(public_key, rsa2048_keys);
+            }
             _ => {
                 result = Err(anyhow::anyhow!(
-                    "FIXME: only Account and AccessKey records are supported in --extra-records"
+                    "FIXME: only Account, AccessKey and Rsa2048Keys records are supported in --extra-records"
                 ));
             }
         };
@@ -256,6 +282,7 @@ fn wanted_records(
                         validator_records.amount_needed = false;
                     }
                     validator_records.keys.extend(account_records.keys);
+                    validator_records.rsa2048_keys.extend(account_records.rsa2048_keys);
                 }
                 hash_map::Entry::Vacant(e) => {
                     e.insert(account_records);
@@ -330,6 +357,14 @@ pub fn amend_genesis(
                 }
                 records_seq.serialize_element(&r).unwrap();
             }
+            StateRecord::Rsa2048Keys { account_id, public_key, rsa2048_keys } => {
+                if let Some(a) = wanted.get_mut(account_id) {
+                    if let Some(k) = a.rsa2048_keys.remove(public_key) {
+                        *rsa2048_keys = k;
+                    }
+                }
+                records_seq.serialize_element(&r).unwrap();
+            }
             StateRecord::Account { account_id, account } => {
                 if let Some(acc) = wanted.get_mut(account_id) {
                     acc.update_from_existing(account);
@@ -355,12 +390,19 @@ pub fn amend_genesis(
         };
     })?;
 
+    // the registrars come from the runtime config of the amended genesis
+    let chain_id = genesis_changes.chain_id.as_ref().unwrap_or(&genesis.config.chain_id);
+    let protocol_version =
+        genesis_changes.protocol_version.unwrap_or(genesis.config.protocol_version);
+    let runtime_config =
+        RuntimeConfigStore::for_chain_id(chain_id).get_config(protocol_version).clone();
     for (account_id, records) in wanted {
         records.write_out(
             account_id,
             &mut records_seq,
             &mut total_supply,
             num_extra_bytes_record,
+            &runtime_config.chip_registry_config,
         )?;
     }
 
@@ -106,7 +106,10 @@ impl ValidateEpochSyncInfoCmd {
 
         let epoch_manager =
             EpochManager::new_arc_handle(storage.get_hot_store(), &config.genesis.config);
-        let shard_tracker = ShardTracker::new(epoch_manager.clone());
+        let shard_tracker = ShardTracker::new(
+            config.client_config.tracked_shards_config.clone(),
+            epoch_manager.clone(),
+        );
         let runtime = NightshadeRuntime::from_config(
             home_dir,
             storage.get_hot_store(),
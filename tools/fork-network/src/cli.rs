@@ -495,6 +495,7 @@ impl ForkNetworkCommand {
         let mut postponed_receipts_updated = 0;
         let mut delayed_receipts_updated = 0;
         let mut received_data_updated = 0;
+        let mut rsa2048_keys_updated = 0;
         let mut fake_block_height = block_height + 1;
         for item in store_helper::iter_flat_state_entries(shard_uid, &store, None, None) {
             let (key, value) = match item {
@@ -577,6 +578,19 @@ impl ForkNetworkCommand {
                             received_data_updated += 1;
                         }
                     }
+                    StateRecord::Rsa2048Keys { account_id, public_key, rsa2048_keys } => {
+                        // TODO(eth-implicit) Change back to is_implicit() when ETH-implicit accounts are supported.
+                        if account_id.get_account_type() == AccountType::UtilityAccount {
+                            let new_account_id = map_account(&account_id, None);
+                            storage_mutator.delete_rsa2048_keys(account_id, public_key.clone())?;
+                            storage_mutator.set_rsa2048_keys(
+                                new_account_id,
+                                public_key,
+                                rsa2048_keys,
+                            )?;
+                            rsa2048_keys_updated += 1;
+                        }
+                    }
                     StateRecord::DelayedReceipt(receipt) => {
                         // TODO(eth-implicit) Change back to is_implicit() when ETH-implicit accounts are supported.
                         if receipt.predecessor_id.get_account_type() == AccountType::UtilityAccount
@@ -611,7 +625,8 @@ impl ForkNetworkCommand {
                         + contract_code_updated
                         + postponed_receipts_updated
                         + delayed_receipts_updated
-                        + received_data_updated,
+                        + received_data_updated
+                        + rsa2048_keys_updated,
                 );
                 let state_root = storage_mutator.commit(&shard_uid, fake_block_height)?;
                 fake_block_height += 1;
@@ -631,6 +646,7 @@ impl ForkNetworkCommand {
             postponed_receipts_updated,
             delayed_receipts_updated,
             received_data_updated,
+            rsa2048_keys_updated,
             num_has_full_key = has_full_key.len(),
             "Pass 1 done"
         );
@@ -809,6 +825,7 @@ impl ForkNetworkCommand {
             min_gas_price: original_config.min_gas_price,
             num_blocks_per_year: original_config.num_blocks_per_year,
             protocol_reward_rate: original_config.protocol_reward_rate,
+            power_reward_rate: original_config.power_reward_rate,
             protocol_treasury_account: original_config.protocol_treasury_account.clone(),
             total_supply: original_config.total_supply,
             transaction_validity_period: original_config.transaction_validity_period,
@@ -2,6 +2,7 @@ use framework::NightshadeRuntime;
 use unc_chain::types::RuntimeAdapter;
 use unc_crypto::PublicKey;
 use unc_primitives::account::{AccessKey, Account};
+use unc_primitives::action::RegisterRsa2048KeysAction;
 use unc_primitives::borsh;
 use unc_primitives::hash::CryptoHash;
 use unc_primitives::receipt::Receipt;
@@ -63,6 +64,23 @@ impl SingleShardStorageMutator {
         self.remove(TrieKey::AccessKey { account_id, public_key })
     }
 
+    pub(crate) fn set_rsa2048_keys(
+        &mut self,
+        account_id: AccountId,
+        public_key: PublicKey,
+        rsa2048_keys: RegisterRsa2048KeysAction,
+    ) -> anyhow::Result<()> {
+        self.set(TrieKey::Rsa2048Keys { account_id, public_key }, borsh::to_vec(&rsa2048_keys)?)
+    }
+
+    pub(crate) fn delete_rsa2048_keys(
+        &mut self,
+        account_id: AccountId,
+        public_key: PublicKey,
+    ) -> anyhow::Result<()> {
+        self.remove(TrieKey::Rsa2048Keys { account_id, public_key })
+    }
+
     pub(crate) fn set_data(
         &mut self,
         account_id: AccountId,
@@ -276,6 +276,7 @@ fn main() -> Result<()> {
                 sync_mode: unc_indexer::SyncModeEnum::FromInterruption,
                 await_for_node_synced: unc_indexer::AwaitForNodeSyncedEnum::WaitForFullSync,
                 validate_genesis: true,
+                ..unc_indexer::IndexerConfig::default()
             };
             let system = actix::System::new();
             system.block_on(async move {
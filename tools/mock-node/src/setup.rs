@@ -37,7 +37,10 @@ fn setup_runtime(
             .get_hot_store()
     };
     let epoch_manager = EpochManager::new_arc_handle(store.clone(), &config.genesis.config);
-    let shard_tracker = ShardTracker::new(epoch_manager.clone());
+    let shard_tracker = ShardTracker::new(
+        config.client_config.tracked_shards_config.clone(),
+        epoch_manager.clone(),
+    );
     let runtime = NightshadeRuntime::from_config(home_dir, store, config, epoch_manager.clone());
     (epoch_manager, shard_tracker, runtime)
 }
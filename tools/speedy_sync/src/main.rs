@@ -229,7 +229,10 @@ fn load_snapshot(load_cmd: LoadCmd) {
         .get_hot_store();
     let chain_genesis = ChainGenesis::new(&config.genesis);
     let epoch_manager = EpochManager::new_arc_handle(store.clone(), &config.genesis.config);
-    let shard_tracker = ShardTracker::new(epoch_manager.clone());
+    let shard_tracker = ShardTracker::new(
+        config.client_config.tracked_shards_config.clone(),
+        epoch_manager.clone(),
+    );
     let runtime =
         NightshadeRuntime::from_config(home_dir, store.clone(), &config, epoch_manager.clone());
     // This will initialize the database (add genesis block etc)
@@ -11,6 +11,7 @@ use tokio::time::timeout;
 use unc_client::sync::external::{create_bucket_readonly, ExternalConnection};
 use unc_client::sync::external::{
     external_storage_location, external_storage_location_directory, get_num_parts_from_filename,
+    is_part_filename,
 };
 use unc_jsonrpc::client::{new_client, JsonRpcClient};
 use unc_primitives::hash::CryptoHash;
@@ -37,6 +38,9 @@ pub struct StatePartsDumpCheckCommand {
     // the s3 region to use when retrieving state parts from S3
     #[clap(long)]
     s3_region: Option<String>,
+    // the endpoint of an S3-compatible storage, such as MinIO, to use instead of AWS S3
+    #[clap(long)]
+    s3_endpoint: Option<String>,
     // the gcs bucket to use when retrieving state parts from GCP
     #[clap(long)]
     gcs_bucket: Option<String>,
@@ -82,6 +86,7 @@ impl StatePartsDumpCheckCommand {
             self.root_dir.clone(),
             self.s3_bucket.clone(),
             self.s3_region.clone(),
+            self.s3_endpoint.clone(),
             self.gcs_bucket.clone(),
         )
     }
@@ -94,14 +99,15 @@ impl StatePartsDumpCheckSubCommand {
         root_dir: Option<PathBuf>,
         s3_bucket: Option<String>,
         s3_region: Option<String>,
+        s3_endpoint: Option<String>,
         gcs_bucket: Option<String>,
     ) -> anyhow::Result<()> {
         match self {
             StatePartsDumpCheckSubCommand::SingleCheck(cmd) => {
-                cmd.run(chain_id, root_dir, s3_bucket, s3_region, gcs_bucket)
+                cmd.run(chain_id, root_dir, s3_bucket, s3_region, s3_endpoint, gcs_bucket)
             }
             StatePartsDumpCheckSubCommand::LoopCheck(cmd) => {
-                cmd.run(chain_id, root_dir, s3_bucket, s3_region, gcs_bucket)
+                cmd.run(chain_id, root_dir, s3_bucket, s3_region, s3_endpoint, gcs_bucket)
             }
         }
     }
@@ -114,6 +120,7 @@ impl SingleCheckCommand {
         root_dir: Option<PathBuf>,
         s3_bucket: Option<String>,
         s3_region: Option<String>,
+        s3_endpoint: Option<String>,
         gcs_bucket: Option<String>,
     ) -> anyhow::Result<()> {
         let sys = actix::System::new();
@@ -127,6 +134,7 @@ impl SingleCheckCommand {
                 root_dir,
                 s3_bucket,
                 s3_region,
+                s3_endpoint,
                 gcs_bucket,
             )
             .await;
@@ -146,6 +154,7 @@ impl LoopCheckCommand {
         root_dir: Option<PathBuf>,
         s3_bucket: Option<String>,
         s3_region: Option<String>,
+        s3_endpoint: Option<String>,
         gcs_bucket: Option<String>,
     ) -> anyhow::Result<()> {
         let rpc_server_addr = match &self.rpc_server_addr {
@@ -168,6 +177,7 @@ impl LoopCheckCommand {
             root_dir,
             s3_bucket,
             s3_region,
+            s3_endpoint,
             gcs_bucket,
             &rpc_client,
             &self.prometheus_addr,
@@ -208,13 +218,15 @@ fn create_external_connection(
     root_dir: Option<PathBuf>,
     bucket: Option<String>,
     region: Option<String>,
+    endpoint: Option<String>,
     gcs_bucket: Option<String>,
 ) -> ExternalConnection {
     if let Some(root_dir) = root_dir {
         ExternalConnection::Filesystem { root_dir }
     } else if let (Some(bucket), Some(region)) = (bucket, region) {
-        let bucket = create_bucket_readonly(&bucket, &region, Duration::from_secs(5))
-            .expect("Failed to create an S3 bucket");
+        let bucket =
+            create_bucket_readonly(&bucket, &region, endpoint.as_deref(), Duration::from_secs(5))
+                .expect("Failed to create an S3 bucket");
         ExternalConnection::S3 { bucket: Arc::new(bucket) }
     } else if let Some(bucket) = gcs_bucket {
         ExternalConnection::GCS {
@@ -255,6 +267,7 @@ fn run_loop_all_shards(
     root_dir: Option<PathBuf>,
     s3_bucket: Option<String>,
     s3_region: Option<String>,
+    s3_endpoint: Option<String>,
     gcs_bucket: Option<String>,
     rpc_client: &JsonRpcClient,
     prometheus_addr: &str,
@@ -330,6 +343,7 @@ fn run_loop_all_shards(
             let root_dir = root_dir.clone();
             let s3_bucket = s3_bucket.clone();
             let s3_region = s3_region.clone();
+            let s3_endpoint = s3_endpoint.clone();
             let gcs_bucket = gcs_bucket.clone();
             last_check_status_vec[shard_id] = sys.block_on(async move {
                 if !is_prometheus_server_up {
@@ -356,6 +370,7 @@ fn run_loop_all_shards(
                     root_dir,
                     s3_bucket,
                     s3_region,
+                    s3_endpoint,
                     gcs_bucket,
                 )
                 .await
@@ -384,6 +399,7 @@ async fn run_single_check_with_3_retries(
     root_dir: Option<PathBuf>,
     s3_bucket: Option<String>,
     s3_region: Option<String>,
+    s3_endpoint: Option<String>,
     gcs_bucket: Option<String>,
 ) -> anyhow::Result<StatePartsDumpCheckStatus> {
     let mut retries = 0;
@@ -393,6 +409,7 @@ async fn run_single_check_with_3_retries(
         let root_dir = root_dir.clone();
         let s3_bucket = s3_bucket.clone();
         let s3_region = s3_region.clone();
+        let s3_endpoint = s3_endpoint.clone();
         let gcs_bucket = gcs_bucket.clone();
         let epoch_id = epoch_id.clone();
         res = run_single_check(
@@ -404,6 +421,7 @@ async fn run_single_check_with_3_retries(
             root_dir,
             s3_bucket,
             s3_region,
+            s3_endpoint,
             gcs_bucket,
         )
         .await;
@@ -440,6 +458,7 @@ async fn run_single_check(
     root_dir: Option<PathBuf>,
     s3_bucket: Option<String>,
     s3_region: Option<String>,
+    s3_endpoint: Option<String>,
     gcs_bucket: Option<String>,
 ) -> anyhow::Result<StatePartsDumpCheckStatus> {
     tracing::info!(
@@ -459,13 +478,19 @@ async fn run_single_check(
         root_dir.clone(),
         s3_bucket.clone(),
         s3_region.clone(),
+        s3_endpoint.clone(),
         gcs_bucket.clone(),
     );
 
     let directory_path =
         external_storage_location_directory(&chain_id, &epoch_id, epoch_height, shard_id);
     tracing::info!(directory_path, "the storage location for the state parts being checked:");
-    let part_file_names = external.list_state_parts(shard_id, &directory_path).await?;
+    let part_file_names: Vec<_> = external
+        .list_state_parts(shard_id, &directory_path)
+        .await?
+        .into_iter()
+        .filter(|file_name| is_part_filename(file_name))
+        .collect();
     if part_file_names.is_empty() {
         return Ok(StatePartsDumpCheckStatus::WaitingForParts { epoch_height: epoch_height });
     }
@@ -599,6 +599,9 @@ pub struct StatePartsCmd {
     /// Store state parts in an S3 bucket.
     #[clap(long)]
     s3_region: Option<String>,
+    /// Endpoint of an S3-compatible storage, such as MinIO, to use instead of AWS S3.
+    #[clap(long)]
+    s3_endpoint: Option<String>,
     /// Store state parts in an GCS bucket.
     #[clap(long)]
     gcs_bucket: Option<String>,
@@ -614,6 +617,7 @@ impl StatePartsCmd {
             self.root_dir,
             self.s3_bucket,
             self.s3_region,
+            self.s3_endpoint,
             self.gcs_bucket,
             home_dir,
             unc_config,
@@ -9,7 +9,8 @@ use std::time::{Duration, Instant};
 use unc_chain::{Chain, ChainGenesis, ChainStoreAccess, DoomslugThresholdMode};
 use unc_client::sync::external::{
     create_bucket_readonly, create_bucket_readwrite, external_storage_location,
-    external_storage_location_directory, get_num_parts_from_filename, ExternalConnection,
+    external_storage_location_directory, get_num_parts_from_filename, is_part_filename,
+    ExternalConnection,
 };
 use unc_client::sync::state::StateSync;
 use unc_epoch_manager::shard_tracker::ShardTracker;
@@ -91,13 +92,17 @@ impl StatePartsSubCommand {
         root_dir: Option<PathBuf>,
         s3_bucket: Option<String>,
         s3_region: Option<String>,
+        s3_endpoint: Option<String>,
         gcs_bucket: Option<String>,
         home_dir: &Path,
         unc_config: UncConfig,
         store: Store,
     ) {
         let epoch_manager = EpochManager::new_arc_handle(store.clone(), &unc_config.genesis.config);
-        let shard_tracker = ShardTracker::new(epoch_manager.clone());
+        let shard_tracker = ShardTracker::new(
+            unc_config.client_config.tracked_shards_config.clone(),
+            epoch_manager.clone(),
+        );
         let runtime = NightshadeRuntime::from_config(
             home_dir,
             store.clone(),
@@ -129,6 +134,7 @@ impl StatePartsSubCommand {
                         root_dir,
                         s3_bucket,
                         s3_region,
+                        s3_endpoint,
                         gcs_bucket,
                         None,
                         Mode::Readonly,
@@ -157,6 +163,7 @@ impl StatePartsSubCommand {
                         root_dir,
                         s3_bucket,
                         s3_region,
+                        s3_endpoint,
                         gcs_bucket,
                         credentials_file,
                         Mode::Readwrite,
@@ -195,6 +202,7 @@ fn create_external_connection(
     root_dir: Option<PathBuf>,
     bucket: Option<String>,
     region: Option<String>,
+    endpoint: Option<String>,
     gcs_bucket: Option<String>,
     credentials_file: Option<PathBuf>,
     mode: Mode,
@@ -203,10 +211,19 @@ fn create_external_connection(
         ExternalConnection::Filesystem { root_dir }
     } else if let (Some(bucket), Some(region)) = (bucket, region) {
         let bucket = match mode {
-            Mode::Readonly => create_bucket_readonly(&bucket, &region, Duration::from_secs(5)),
-            Mode::Readwrite => {
-                create_bucket_readwrite(&bucket, &region, Duration::from_secs(5), credentials_file)
-            }
+            Mode::Readonly => create_bucket_readonly(
+                &bucket,
+                &region,
+                endpoint.as_deref(),
+                Duration::from_secs(5),
+            ),
+            Mode::Readwrite => create_bucket_readwrite(
+                &bucket,
+                &region,
+                endpoint.as_deref(),
+                Duration::from_secs(5),
+                credentials_file,
+            ),
         }
         .expect("Failed to create an S3 bucket");
         ExternalConnection::S3 { bucket: Arc::new(bucket) }
@@ -336,7 +353,13 @@ async fn load_state_parts(
 
     let directory_path =
         external_storage_location_directory(chain_id, &epoch_id, epoch_height, shard_id);
-    let part_file_names = external.list_state_parts(shard_id, &directory_path).await.unwrap();
+    let part_file_names: Vec<_> = external
+        .list_state_parts(shard_id, &directory_path)
+        .await
+        .unwrap()
+        .into_iter()
+        .filter(|file_name| is_part_filename(file_name))
+        .collect();
     assert!(!part_file_names.is_empty());
     let num_parts = part_file_names.len() as u64;
     assert_eq!(Some(num_parts), get_num_parts_from_filename(&part_file_names[0]));
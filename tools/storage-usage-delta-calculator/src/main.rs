@@ -21,8 +21,7 @@ async fn main() -> anyhow::Result<()> {
 
     let config_store = RuntimeConfigStore::new(None);
     let config = config_store.get_config(PROTOCOL_VERSION);
-    let storage_usage_config = &config.fees.storage_usage_config;
-    let storage_usage = compute_genesis_storage_usage(&genesis, storage_usage_config);
+    let storage_usage = compute_genesis_storage_usage(&genesis, config);
     debug!(target: "storage-calculator", "Storage usage calculated");
 
     let mut result = Vec::new();